@@ -0,0 +1,151 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::SystemTime;
+
+use tracing::error;
+
+use sierra_analyzer_lib::scarb::contract_classes;
+
+/// Manifest file of the Scarb packages
+const MANIFEST_FILE: &str = "Scarb.toml";
+
+/// Scarb extension analyzing the contracts of a package: `scarb analyze [sierra-decompiler flags]`
+/// Scarb runs the `scarb-<name>` executables of the PATH as its `<name>` subcommand, with the package
+/// manifest & the target directory in the environment. The package is built if its contract classes
+/// are missing or older than its sources, then sierra-decompiler analyzes all of them.
+fn main() {
+    tracing_subscriber::fmt().with_target(false).init();
+
+    // The subcommand name is skipped if Scarb passes it
+    let mut arguments: Vec<OsString> = env::args_os().skip(1).collect();
+    if arguments
+        .first()
+        .is_some_and(|argument| argument == "analyze")
+    {
+        arguments.remove(0);
+    }
+
+    let Some(project_root) = project_root() else {
+        error!(
+            "Could not find {} in the current directory or its parents",
+            MANIFEST_FILE
+        );
+        process::exit(1);
+    };
+    let target_dir = env::var_os("SCARB_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project_root.join("target"));
+
+    if needs_build(&project_root, &target_dir) {
+        build(&project_root);
+    }
+
+    let status = Command::new(decompiler_path())
+        .arg("--scarb")
+        .arg("--all-contracts")
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args(&arguments)
+        .current_dir(&project_root)
+        .status();
+    match status {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            error!("Failed to run sierra-decompiler: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns the root directory of the package, given by Scarb or the first parent directory with a manifest
+fn project_root() -> Option<PathBuf> {
+    if let Some(manifest_path) = env::var_os("SCARB_MANIFEST_PATH") {
+        return PathBuf::from(manifest_path).parent().map(Path::to_path_buf);
+    }
+
+    let current_dir = env::current_dir().ok()?;
+    current_dir
+        .ancestors()
+        .find(|directory| directory.join(MANIFEST_FILE).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Returns true if the dev profile has no contract class or if a source is newer than the oldest one
+fn needs_build(project_root: &Path, target_dir: &Path) -> bool {
+    let Ok(contract_class_files) = contract_classes(target_dir) else {
+        return true;
+    };
+    let oldest_class = contract_class_files
+        .iter()
+        .filter_map(|path| {
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .min();
+
+    match oldest_class {
+        Some(oldest_class) => newest_source(project_root, target_dir)
+            .is_some_and(|newest_source| newest_source > oldest_class),
+        None => true,
+    }
+}
+
+/// Returns the modification time of the newest manifest or Cairo file of a directory & its sub-directories
+/// The target directory & the hidden directories are skipped
+fn newest_source(directory: &Path, target_dir: &Path) -> Option<SystemTime> {
+    let mut newest = None;
+    for entry in fs::read_dir(directory).ok()?.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        let modified = if path.is_dir() {
+            if name.starts_with('.') || path == target_dir || name == "target" {
+                continue;
+            }
+            newest_source(&path, target_dir)
+        } else if name == MANIFEST_FILE || name.ends_with(".cairo") {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        } else {
+            None
+        };
+        newest = newest.max(modified);
+    }
+    newest
+}
+
+/// Builds the package with the dev profile, exits if the build fails
+fn build(project_root: &Path) {
+    // Scarb gives its own path to the extensions
+    let scarb = env::var_os("SCARB").unwrap_or_else(|| OsString::from("scarb"));
+    let status = Command::new(scarb)
+        .arg("build")
+        .env_remove("SCARB_PROFILE")
+        .current_dir(project_root)
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            error!("Failed to run scarb build: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns the path of sierra-decompiler, installed next to this executable or in the PATH
+fn decompiler_path() -> PathBuf {
+    let executable = format!("sierra-decompiler{}", env::consts::EXE_SUFFIX);
+    env::current_exe()
+        .ok()
+        .map(|current_exe| current_exe.with_file_name(&executable))
+        .filter(|path| path.is_file())
+        .unwrap_or_else(|| PathBuf::from(executable))
+}
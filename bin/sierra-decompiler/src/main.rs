@@ -1,31 +1,91 @@
+use std::collections::HashMap;
 use std::fs;
-use std::fs::File;
-use std::io::Read;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
+use num_bigint::BigUint;
+use serde::Deserialize;
 use serde_json;
 use tokio;
+use tracing::{error, info_span, warn, Level};
+use tracing_subscriber::fmt::format::FmtSpan;
 
-use cairo_lang_starknet_classes::contract_class::ContractClass;
+use sierra_analyzer_lib::analysis::access_control::AccessControlMatrix;
+use sierra_analyzer_lib::analysis::context::AnalysisContext;
+use sierra_analyzer_lib::analysis::entry_points::EntryPointsTable;
+use sierra_analyzer_lib::analysis::invariants::{check_invariants, load_invariants};
+use sierra_analyzer_lib::analysis::messaging::MessagingReport;
+use sierra_analyzer_lib::analysis::search::find_libfunc_calls;
+use sierra_analyzer_lib::analysis::storage_access::StorageAccessMatrix;
+use sierra_analyzer_lib::analysis::storage_layout::StorageLayout;
+use sierra_analyzer_lib::cache::{program_hash, AnalysisCache, DetectorResults};
+use sierra_analyzer_lib::class_hash::{verify_class_hash, ClassHashVerification};
+use sierra_analyzer_lib::corpus::{corpus_entries, diff_output, read_snapshot};
+use sierra_analyzer_lib::decompiler::cfg::CfgNodeDetail;
 use sierra_analyzer_lib::decompiler::decompiler::Decompiler;
-use sierra_analyzer_lib::detectors::detector::DetectorType;
+use sierra_analyzer_lib::decompiler::formatting::{
+    load_formatting_options, FormattingOptions, IndentStyle,
+};
+use sierra_analyzer_lib::decompiler::validation::validate_program;
+use sierra_analyzer_lib::detectors::detector::{
+    findings_to_json, Detector, DetectorType, Finding, FindingReport,
+};
+use sierra_analyzer_lib::detectors::findings_diff::{read_findings, FindingsDiff};
 use sierra_analyzer_lib::detectors::get_detectors;
+use sierra_analyzer_lib::detectors::runner::{run_detectors, DetectorOutcome, RunOptions};
+use sierra_analyzer_lib::glob::glob_files;
+use sierra_analyzer_lib::graph::cross_contract::process_cross_contract_callgraph;
+use sierra_analyzer_lib::graph::cross_contract::process_cross_contract_callgraph_json;
+use sierra_analyzer_lib::graph::cross_contract::CrossContract;
 use sierra_analyzer_lib::graph::graph::save_svg_graph_to_file;
+use sierra_analyzer_lib::local_provider::LocalProvider;
+#[cfg(feature = "z3")]
+use sierra_analyzer_lib::parse_element_name;
 use sierra_analyzer_lib::provider::NetworkConfig;
-use sierra_analyzer_lib::provider::RpcClient;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::provider::{
+    ApiKey, ApiKeyLocation, RpcClient, RpcProvider, API_KEY_PLACEHOLDER,
+};
+use sierra_analyzer_lib::scarb;
+use sierra_analyzer_lib::sierra_program::{ProgramKind, SierraProgram};
+#[cfg(feature = "concolic")]
+use sierra_analyzer_lib::sym_exec::concolic::{concolic_execution, PathValidation};
+#[cfg(feature = "z3")]
+use sierra_analyzer_lib::sym_exec::coverage::{coverage_map, BlockCoverage};
+use sierra_analyzer_lib::sym_exec::hashes::HashModel;
+#[cfg(feature = "z3")]
+use sierra_analyzer_lib::sym_exec::prove::{prove_panics, PanicReachability};
+#[cfg(feature = "z3")]
+use sierra_analyzer_lib::sym_exec::smtlib::path_constraints;
+#[cfg(feature = "z3")]
+use sierra_analyzer_lib::sym_exec::solver::SolverResult;
+#[cfg(feature = "z3")]
+use sierra_analyzer_lib::sym_exec::z3_solver::{
+    set_default_options, z3_tactics, z3_version, Z3Options,
+};
+use sierra_analyzer_lib::version::{ANALYZER_VERSION, SUPPORTED_SIERRA_VERSION};
 
 /// Decompile a Sierra program
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    /// Sierra program file
+    /// Sierra program file ("-" to read it from stdin), repeat the flag to analyze several files
     #[clap(short = 'f', long)]
-    sierra_file: Option<PathBuf>,
+    sierra_file: Vec<PathBuf>,
 
-    /// Function name to only get one function for CFG & Callgraph
+    /// Analyze the files matching a glob pattern (e.g. 'contracts/**/*.contract_class.json') or the programs of a directory
+    #[clap(long)]
+    sierra_glob: Option<String>,
+
+    /// Read the Sierra program or the contract class from stdin
+    #[clap(long)]
+    stdin: bool,
+
+    /// Function name to only get one function for CFG & Callgraph, or to only report its detectors findings
     #[clap(long)]
     function: Option<String>,
 
@@ -33,6 +93,50 @@ struct Args {
     #[clap(short, long, default_value_t = false)]
     no_color: bool,
 
+    /// TOML config file of the default options (network, RPC URL, output directories & colors), "sierra-analyzer.toml" by default
+    #[clap(long)]
+    config: Option<PathBuf>,
+
+    /// JSON config file of the output formatting (indent_style, indent_width, max_line_length & function_comments)
+    #[clap(long)]
+    format_config: Option<PathBuf>,
+
+    /// Indent the output using the given number of spaces instead of tabs
+    #[clap(long)]
+    indent_spaces: Option<usize>,
+
+    /// Maximum line length, the arguments of the longer invocations are wrapped
+    #[clap(long)]
+    max_line_length: Option<usize>,
+
+    /// Do not print the function index comments
+    #[clap(long)]
+    no_function_comments: bool,
+
+    /// Merge the entry point wrappers with the function they call in the output
+    #[clap(long)]
+    inline_wrappers: bool,
+
+    /// Summarize the calldata deserialization of the entry point wrappers using the ABI
+    #[clap(long)]
+    summarize_calldata: bool,
+
+    /// Print the prototypes of the entry points using the input & output types of the ABI
+    #[clap(long)]
+    abi_types: bool,
+
+    /// Annotate the function prototypes with their panic safety (`nopanic` or their panic paths)
+    #[clap(long)]
+    panic_annotations: bool,
+
+    /// Replace the gas checks & their out-of-gas branch by a comment
+    #[clap(long)]
+    summarize_gas_checks: bool,
+
+    /// Render the if blocks ending with a return as early returns, without nesting the else blocks
+    #[clap(long)]
+    early_returns: bool,
+
     /// Generate a CFG (Control Flow Graph) instead of normal output
     #[clap(long, default_value_t = false)]
     cfg: bool,
@@ -41,6 +145,14 @@ struct Args {
     #[clap(long, default_value = "./output_cfg")]
     cfg_output: PathBuf,
 
+    /// CFG output format (svg, json & graphml are supported)
+    #[clap(long, default_value = "svg")]
+    cfg_format: String,
+
+    /// Content of the CFG nodes in the SVG output
+    #[clap(long, value_enum, default_value_t = CfgDetail::Raw)]
+    cfg_detail: CfgDetail,
+
     /// Generate a Call Graph instead of normal output
     #[clap(long, default_value_t = false)]
     callgraph: bool,
@@ -49,10 +161,38 @@ struct Args {
     #[clap(long, default_value = "./output_callgraph")]
     callgraph_output: PathBuf,
 
+    /// Call Graph output format (svg, json & graphml are supported)
+    #[clap(long, default_value = "svg")]
+    callgraph_format: String,
+
+    /// Group the functions of the Call Graph SVG output in a cluster per module path
+    #[clap(long, default_value_t = false)]
+    callgraph_clusters: bool,
+
+    /// Only keep the Call Graph nodes reachable from the given roots (only "external" is supported)
+    #[clap(long)]
+    callgraph_roots: Option<String>,
+
+    /// Only keep the direct & indirect callers of the given function in the Call Graph
+    #[clap(long, conflicts_with = "callgraph_roots")]
+    callers: Option<String>,
+
+    /// Other contracts files to link in a cross-contract Call Graph (comma separated)
+    #[clap(long, use_value_delimiter = true)]
+    cross_contract_files: Vec<PathBuf>,
+
     /// Enable verbose decompiler output
     #[clap(short, long, default_value_t = false)]
     verbose: bool,
 
+    /// Maximum level of the logs written to stderr
+    #[clap(long, value_enum, default_value_t = LogLevel::Warn)]
+    log_level: LogLevel,
+
+    /// Logs format
+    #[clap(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
     /// Run the detectors
     #[clap(short = 'd', long)]
     detectors: bool,
@@ -61,26 +201,335 @@ struct Args {
     #[clap(long, use_value_delimiter = true)]
     detector_names: Vec<String>,
 
+    /// Tags of the detectors to run, the detectors having all of them are selected (e.g. fast,security)
+    #[clap(long, use_value_delimiter = true)]
+    detectors_tags: Vec<String>,
+
+    /// Detectors output format, the JSON output contains the located findings with their offsets
+    #[clap(long, value_enum, default_value_t = DetectorsFormat::Text)]
+    detectors_format: DetectorsFormat,
+
+    /// Only print the reports of the detectors having findings, without the decompiled code
+    #[clap(short, long)]
+    quiet: bool,
+
+    /// Only print the counts of the detectors findings by type, one line per program
+    #[clap(long)]
+    summary: bool,
+
+    /// Maximum run time of each detector in seconds, the detectors that don't finish in time are reported
+    #[clap(long)]
+    detector_timeout: Option<u64>,
+
+    /// Check if the panics (assert-like patterns) of the given function are reachable & print a witness input
+    #[clap(long)]
+    prove: Option<String>,
+
+    /// Print the basic blocks of the given function covered by the generated inputs & the constraints of the uncovered ones
+    #[clap(long)]
+    coverage: Option<String>,
+
+    /// Run the paths of the given function with cairo-vm using the generated inputs, to check they are followed
+    #[clap(long)]
+    concolic: Option<String>,
+
+    /// Print the SMT-LIB2 constraints of each path of the given function & the solver verdict
+    #[clap(long)]
+    dump_constraints: Option<String>,
+
+    /// Modeling of the Pedersen & Poseidon hashes by the solver for --prove, --coverage, --concolic & --dump-constraints
+    #[clap(long, value_enum, default_value_t = HashModeling::Unconstrained)]
+    hash_model: HashModeling,
+
+    /// Maximum time of each solver check in milliseconds, the paths reaching it are reported as unknown
+    #[clap(long)]
+    solver_timeout: Option<u32>,
+
+    /// Seed of the solver heuristics, changing it changes the generated inputs
+    #[clap(long)]
+    solver_seed: Option<u32>,
+
+    /// Z3 tactic the solver is created from (e.g. qfnia)
+    #[clap(long)]
+    solver_tactic: Option<String>,
+
+    /// Check the invariants declared in a JSON file (e.g. "function X never writes storage slot Y"),
+    /// the file of the config is used when none is given
+    #[clap(long, num_args = 0..=1)]
+    invariants: Option<Option<PathBuf>>,
+
+    /// Print the matrix of the entry points vs. the access-control checks they perform (owner, role, none)
+    #[clap(long)]
+    access_control: bool,
+
+    /// Access-control matrix output format
+    #[clap(long, value_enum, default_value_t = MatrixFormat::Text)]
+    access_control_format: MatrixFormat,
+
+    /// Print the entry points (external, view, constructor, l1_handler) with their selectors, parameters & state mutability
+    #[clap(long)]
+    entrypoints: bool,
+
+    /// Entry points table output format
+    #[clap(long, value_enum, default_value_t = MatrixFormat::Text)]
+    entrypoints_format: MatrixFormat,
+
+    /// List the functions invoking a libfunc matching a regex (e.g. "replace_class|deploy_syscall") with the statements offsets
+    #[clap(long)]
+    find_calls: Option<String>,
+
+    /// Print the storage variables with their types, base addresses, readers & writers, and the base addresses collisions
+    #[clap(long)]
+    storage_layout: bool,
+
+    /// Storage layout output format
+    #[clap(long, value_enum, default_value_t = MatrixFormat::Text)]
+    storage_layout_format: MatrixFormat,
+
+    /// Print the matrix of the entry points vs. the storage variables they read & write, through their callees
+    #[clap(long)]
+    storage_matrix: bool,
+
+    /// Storage access matrix output format
+    #[clap(long, value_enum, default_value_t = StorageMatrixFormat::Text)]
+    storage_matrix_format: StorageMatrixFormat,
+
+    /// Print the L1 messaging surface: the L1 handlers & the messages sent to L1 with the origins of their payloads
+    #[clap(long)]
+    messaging: bool,
+
+    /// Print the L1 messaging report in JSON
+    #[clap(long)]
+    messaging_json: bool,
+
+    /// Check that the decompiled statements are equivalent to the program (same libfuncs & branches targets)
+    #[clap(long)]
+    validate: bool,
+
     /// Remote contract class address
     #[clap(long, default_value = "")]
     remote: String,
 
+    /// Run the detectors on all the classes declared by the given DECLARE transactions (comma separated hashes)
+    #[clap(long, use_value_delimiter = true)]
+    declare_transactions: Vec<String>,
+
+    /// Read the remote classes from a local directory dump or Pathfinder database instead of the RPC node
+    #[clap(long)]
+    class_source: Option<PathBuf>,
+
+    /// Maximum number of concurrent RPC requests
+    #[clap(long, default_value_t = 4)]
+    rpc_concurrency: usize,
+
+    /// Minimum delay between two RPC requests in milliseconds
+    #[clap(long, default_value_t = 0)]
+    rpc_interval_ms: u64,
+
+    /// Save the fetched classes in a directory, an interrupted batch resumes from the saved classes
+    #[clap(long)]
+    fetch_cache: Option<PathBuf>,
+
+    /// Save the decompiled output & the detectors results in a directory, the next runs on the same program reuse them
+    #[clap(long)]
+    analysis_cache: Option<PathBuf>,
+
     /// Network type (Mainnet & Sepolia are supported)
     #[clap(long, default_value = "mainnet")]
     network: String,
 
+    /// RPC provider requiring an API key (blast, infura & alchemy are supported)
+    #[clap(long)]
+    rpc_provider: Option<String>,
+
+    /// Custom RPC node URL, "{api_key}" is replaced by the API key
+    #[clap(long)]
+    rpc_url: Option<String>,
+
+    /// API key of the RPC provider
+    #[clap(long, env = "SIERRA_ANALYZER_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Send the API key of the custom RPC node in the given HTTP header
+    #[clap(long)]
+    api_key_header: Option<String>,
+
+    /// Send the API key of the custom RPC node in the given URL parameter
+    #[clap(long)]
+    api_key_param: Option<String>,
+
+    /// Check that the class hash of the contract class file matches the class of a deployed contract address
+    #[clap(long)]
+    verify_class_hash: Option<String>,
+
     /// Run sierra-analyzer in a repo that uses Scarb
     #[clap(long)]
     scarb: bool,
 
-    /// List all available detector names
+    /// With --scarb, analyze all the contract classes of the target directory, each one in its own section
+    #[clap(long)]
+    all_contracts: bool,
+
+    /// Scarb target directory, the contract classes are read from its dev profile directory
+    #[clap(long, default_value = "./target")]
+    target_dir: PathBuf,
+
+    /// List all available detectors with their severity, tags & required inputs
     #[clap(long)]
     detector_help: bool,
+
+    /// Print the versions of the Cairo & Sierra support, of Z3 and the detectors the binary was built with
+    #[clap(long)]
+    version_info: bool,
+
+    /// Decompile all the examples of a directory & report the differences with the snapshots (dev command)
+    #[clap(long)]
+    corpus: Option<PathBuf>,
+
+    /// Directory of the corpus snapshots
+    #[clap(long, default_value = "./lib/tests/snapshots")]
+    corpus_snapshots: PathBuf,
+
+    /// Compare two JSON findings files (--detectors-format json) & print the fixed, new & unchanged findings, exits with an error code if there are new findings
+    #[clap(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    compare_findings: Vec<PathBuf>,
+}
+
+/// Content of the CFG nodes
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum CfgDetail {
+    /// Raw Sierra statements
+    Raw,
+    /// Decompiled statements
+    Decompiled,
+    /// Block names & statements counts
+    Summary,
+}
+
+impl From<CfgDetail> for CfgNodeDetail {
+    fn from(detail: CfgDetail) -> Self {
+        match detail {
+            CfgDetail::Raw => CfgNodeDetail::Raw,
+            CfgDetail::Decompiled => CfgNodeDetail::Decompiled,
+            CfgDetail::Summary => CfgNodeDetail::Summary,
+        }
+    }
+}
+
+/// Modeling of the hash functions by the solver
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum HashModeling {
+    /// The hashes results are unconstrained
+    Unconstrained,
+    /// The hashes are uninterpreted functions, equal digests have equal inputs
+    Uninterpreted,
+    /// The hashes of constants are evaluated, the other ones are uninterpreted functions
+    Concrete,
+}
+
+impl From<HashModeling> for HashModel {
+    fn from(modeling: HashModeling) -> Self {
+        match modeling {
+            HashModeling::Unconstrained => HashModel::Unconstrained,
+            HashModeling::Uninterpreted => HashModel::Uninterpreted,
+            HashModeling::Concrete => HashModel::Concrete,
+        }
+    }
+}
+
+/// Format of the access-control matrix, the entry points & the storage layout tables
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum MatrixFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+/// Format of the storage access matrix
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum StorageMatrixFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+/// Format of the detectors output
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum DetectorsFormat {
+    Text,
+    Json,
+    /// Text reports of the detectors having findings, set by --quiet
+    #[value(skip)]
+    Findings,
+    /// Counts of the findings by detector type, set by --summary
+    #[value(skip)]
+    Summary,
+}
+
+/// Name of the config file read from the current directory when --config is not given
+const CONFIG_FILE: &str = "sierra-analyzer.toml";
+
+/// Default options read from the config file, the command-line flags take precedence
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    /// Network type (Mainnet & Sepolia are supported)
+    network: Option<String>,
+    /// RPC provider requiring an API key
+    rpc_provider: Option<String>,
+    /// Custom RPC node URL
+    rpc_url: Option<String>,
+    /// Output directory for the CFG files
+    cfg_output: Option<PathBuf>,
+    /// Output directory for the Call Graph files
+    callgraph_output: Option<PathBuf>,
+    /// Use a colored output
+    color: Option<bool>,
+    /// Maximum time of each solver check in milliseconds
+    solver_timeout: Option<u32>,
+    /// Seed of the solver heuristics
+    solver_seed: Option<u32>,
+    /// Z3 tactic the solver is created from
+    solver_tactic: Option<String>,
+    /// Invariants file checked by --invariants
+    invariants: Option<PathBuf>,
+}
+
+/// Format of the logs written to stderr
+#[derive(ValueEnum, Clone, Debug)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+/// Maximum level of the logs written to stderr
+#[derive(ValueEnum, Clone, Debug)]
+enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
 }
 
 #[tokio::main]
 async fn main() {
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    init_logging(&args.log_level, &args.log_format);
+
+    // The options that are not given on the command line are read from the config file
+    match load_config(args.config.as_deref()) {
+        Ok(config) => apply_config(&mut args, &matches, config),
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    }
+    if let Err(e) = configure_solver(&args) {
+        error!("{}", e);
+        return;
+    }
 
     // Handle the --detector-help flag
     if args.detector_help {
@@ -88,9 +537,73 @@ async fn main() {
         return;
     }
 
-    // Ensure either remote, Sierra file, or scarb is provided
-    if args.remote.is_empty() && args.sierra_file.is_none() && !args.scarb {
-        eprintln!("Error: Either remote, Sierra file, or --scarb flag must be provided");
+    // Handle the --version-info flag
+    if args.version_info {
+        print_version_info();
+        return;
+    }
+
+    // Handle the --corpus flag
+    if let Some(ref corpus) = args.corpus {
+        handle_corpus(corpus, &args.corpus_snapshots);
+        return;
+    }
+
+    // Handle the --compare-findings flag
+    if let [old, new] = args.compare_findings.as_slice() {
+        handle_compare_findings(old, new, &args.detectors_format);
+        return;
+    }
+
+    // Handle the --verify-class-hash flag
+    if let Some(ref contract_address) = args.verify_class_hash {
+        handle_verify_class_hash(&args, contract_address).await;
+        return;
+    }
+
+    // Handle the --declare-transactions flag
+    if !args.declare_transactions.is_empty() {
+        handle_declared_classes(&args).await;
+        return;
+    }
+
+    // Ensure either remote, Sierra file, stdin, or scarb is provided
+    if args.remote.is_empty()
+        && args.sierra_file.is_empty()
+        && args.sierra_glob.is_none()
+        && !args.stdin
+        && !args.scarb
+    {
+        error!(
+            "Either remote, Sierra file, Sierra glob, --stdin, or --scarb flag must be provided"
+        );
+        return;
+    }
+
+    // Handle the --all-contracts flag, each contract class built by Scarb is analyzed separately
+    if args.scarb && args.all_contracts {
+        match scarb_contract_classes(&args.target_dir) {
+            Ok(contract_class_files) => handle_sierra_files(&args, &contract_class_files),
+            Err(e) => error!("{}", e),
+        }
+        return;
+    }
+
+    // Each file is analyzed separately when several Sierra files or a glob pattern are given
+    if args.sierra_glob.is_some()
+        || (args.remote.is_empty() && !args.scarb && args.sierra_file.len() > 1)
+    {
+        let mut sierra_files = args.sierra_file.clone();
+        if let Some(ref sierra_glob) = args.sierra_glob {
+            match glob_files(sierra_glob) {
+                Ok(files) => sierra_files.extend(files),
+                Err(e) => {
+                    error!("Error reading the files matching '{}': {}", sierra_glob, e);
+                    return;
+                }
+            }
+        }
+        handle_sierra_files(&args, &sierra_files);
         return;
     }
 
@@ -98,36 +611,281 @@ async fn main() {
     let program = match load_program(&args).await {
         Ok(program) => program,
         Err(e) => {
-            eprintln!("Error loading program: {}", e);
+            error!("Error loading program: {}", e);
             return;
         }
     };
 
+    analyze_program(&args, &program, &get_file_stem(&args));
+}
+
+/// Analysis status of a file, a row of the files summary table
+struct FileSummary {
+    file: String,
+    program_hash: String,
+    functions: Option<usize>,
+    status: String,
+}
+
+/// Analyze each of the Sierra files, their results are printed under a header
+/// The files containing the same program as a previous file are skipped, a summary table ends the output
+fn handle_sierra_files(args: &Args, sierra_files: &[PathBuf]) {
+    let mut summary = Vec::new();
+    // First file of each analyzed program, by program hash
+    let mut analyzed: HashMap<String, &Path> = HashMap::new();
+
+    for sierra_file in sierra_files {
+        let file = sierra_file.display().to_string();
+        let program = if sierra_file == Path::new("-") {
+            load_program_from_stdin()
+        } else {
+            load_program_from_path(sierra_file)
+        };
+        let program = match program {
+            Ok(program) => program,
+            Err(e) => {
+                error!("Error loading file {}: {}", file, e);
+                summary.push(FileSummary {
+                    file,
+                    program_hash: String::new(),
+                    functions: None,
+                    status: "error".to_string(),
+                });
+                continue;
+            }
+        };
+
+        let program_hash = program_hash(&program);
+        let functions = Some(program.program().funcs.len());
+        if let Some(first_file) = analyzed.get(&program_hash) {
+            summary.push(FileSummary {
+                file,
+                program_hash,
+                functions,
+                status: format!("duplicate of {}", first_file.display()),
+            });
+            continue;
+        }
+        analyzed.insert(program_hash.clone(), sierra_file.as_path());
+
+        print_program_header("File", &file, args);
+        analyze_program(args, &program, &file_stem(sierra_file));
+        print_program_footer(args);
+
+        summary.push(FileSummary {
+            file,
+            program_hash,
+            functions,
+            status: "analyzed".to_string(),
+        });
+    }
+
+    print_files_summary(&summary);
+}
+
+/// Print the header of a program analyzed in a batch, the summary line follows it on the same line
+fn print_program_header(kind: &str, name: &str, args: &Args) {
+    if args.summary {
+        print!("{} {}: ", kind, name);
+    } else {
+        println!("{} {}\n", kind, name);
+    }
+}
+
+/// Print the separation between two programs analyzed in a batch
+fn print_program_footer(args: &Args) {
+    if !args.summary {
+        println!();
+    }
+}
+
+/// Print the summary table of the analyzed files, the program hashes are truncated
+fn print_files_summary(summary: &[FileSummary]) {
+    let width = summary
+        .iter()
+        .map(|row| row.file.len())
+        .chain(std::iter::once("File".len()))
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:<width$}  {:<14}  {:>9}  Status",
+        "File", "Program hash", "Functions"
+    );
+    for row in summary {
+        let functions = row
+            .functions
+            .map(|functions| functions.to_string())
+            .unwrap_or_default();
+        let line = format!(
+            "{:<width$}  {:<14.14}  {:>9}  {}",
+            row.file, row.program_hash, functions, row.status
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+/// Run the analysis selected by the command-line flags on a program, the decompiler by default
+/// The file stem names the CFG & callgraph output files
+fn analyze_program(args: &Args, program: &SierraProgram, file_stem: &str) {
+    // Handle the --validate flag
+    if args.validate {
+        handle_validate(program);
+        return;
+    }
+
+    let mut cache = open_analysis_cache(args, program);
+
+    // Detectors, they analyze the program without decompiling it
+    // The detectors findings are highlighted in the graphs by the CFG & callgraph outputs
+    let other_outputs = args.cfg
+        || args.callgraph
+        || args.prove.is_some()
+        || args.coverage.is_some()
+        || args.concolic.is_some()
+        || args.dump_constraints.is_some()
+        || args.invariants.is_some()
+        || args.access_control
+        || args.entrypoints
+        || args.find_calls.is_some()
+        || args.storage_layout
+        || args.storage_matrix
+        || args.messaging;
+    if (args.detectors || args.quiet || args.summary) && !other_outputs {
+        handle_detectors(
+            program,
+            &args.detector_names,
+            &args.detectors_tags,
+            args.function.as_deref(),
+            detectors_format(args),
+            cache.as_mut(),
+            args.detector_timeout.map(Duration::from_secs),
+        );
+        return;
+    }
+
     // Determine if colored output is needed
     let colored_output = !args.no_color ^ (args.cfg | args.callgraph);
+    let formatting = match formatting_options(args) {
+        Ok(formatting) => formatting,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    // The decompiled output depends on the formatting options, the colors & the verbosity
+    let decompiled_key = format!(
+        "{:?} color={} verbose={}",
+        formatting, colored_output, args.verbose
+    );
+    if !other_outputs {
+        if let Some(decompiled_code) = cache
+            .as_ref()
+            .and_then(|cache| cache.decompiled(&decompiled_key))
+        {
+            println!("{}", decompiled_code);
+            return;
+        }
+    }
+
     let mut decompiler = program.decompiler(args.verbose);
-    let decompiled_code = decompiler.decompile(colored_output);
+    decompiler.set_formatting(formatting);
+    let decompiled_code = match decompiler.try_decompile(colored_output) {
+        Ok(decompiled_code) => decompiled_code,
+        Err(e) => {
+            error!("Error decompiling program: {}", e);
+            return;
+        }
+    };
+    if let Some(cache) = cache.as_mut() {
+        cache.set_decompiled(&decompiled_key, decompiled_code.clone());
+        if let Err(e) = cache.save() {
+            warn!("Failed to save the analysis cache: {}", e);
+        }
+    }
+
+    // The detectors findings are highlighted in the graphs
+    // The detectors analyze the whole program, the function name only scopes their findings
+    if args.detectors && (args.cfg || args.callgraph) {
+        let findings = collect_findings(
+            &decompiler,
+            &args.detector_names,
+            &args.detectors_tags,
+            args.function.as_deref(),
+        );
+        decompiler.set_findings(findings);
+    }
 
     // Filter functions if a specific function name is given
     if let Some(ref function_name) = args.function {
         decompiler.filter_functions(function_name);
     }
 
-    // Determine the file stem based on the remote address or the Sierra file
-    let file_stem = get_file_stem(&args);
-
     // Handle different output options
     // CFG
     if args.cfg {
-        handle_cfg(&args, &mut decompiler, &file_stem);
+        handle_cfg(args, &mut decompiler, file_stem);
+    }
+    // Cross-contract callgraph
+    else if args.callgraph && !args.cross_contract_files.is_empty() {
+        handle_cross_contract_callgraph(args, &decompiler, file_stem);
     }
     // Callgraph
     else if args.callgraph {
-        handle_callgraph(&args, &mut decompiler, &file_stem);
+        handle_callgraph(args, &mut decompiler, file_stem);
+    }
+    // Panics reachability
+    else if let Some(ref function_name) = args.prove {
+        handle_prove(&mut decompiler, function_name, args.hash_model.into());
+    }
+    // Coverage of the generated inputs
+    else if let Some(ref function_name) = args.coverage {
+        handle_coverage(&mut decompiler, function_name, args.hash_model.into());
+    }
+    // Concolic execution
+    else if let Some(ref function_name) = args.concolic {
+        handle_concolic(
+            program,
+            &mut decompiler,
+            function_name,
+            args.hash_model.into(),
+        );
+    }
+    // Constraints of the paths
+    else if let Some(ref function_name) = args.dump_constraints {
+        handle_dump_constraints(&mut decompiler, function_name, args.hash_model.into());
+    }
+    // Invariants
+    else if let Some(ref invariants_file) = args.invariants {
+        match invariants_file {
+            Some(invariants_file) => handle_invariants(&mut decompiler, invariants_file),
+            None => error!("No invariants file given with --invariants or in the config file"),
+        }
+    }
+    // Access-control matrix
+    else if args.access_control {
+        handle_access_control(&mut decompiler, args.access_control_format);
+    }
+    // Entry points table
+    else if args.entrypoints {
+        handle_entry_points(&decompiler, args.entrypoints_format);
+    }
+    // Libfunc calls search
+    else if let Some(ref pattern) = args.find_calls {
+        handle_find_calls(&decompiler, pattern);
+    }
+    // Storage layout
+    else if args.storage_layout {
+        handle_storage_layout(&decompiler, args.storage_layout_format);
+    }
+    // Storage access matrix
+    else if args.storage_matrix {
+        handle_storage_matrix(&decompiler, args.storage_matrix_format);
     }
-    // Detectors
-    else if args.detectors {
-        handle_detectors(&mut decompiler, args.detector_names);
+    // L1 messaging report
+    else if args.messaging {
+        handle_messaging(&mut decompiler, args.messaging_json);
     }
     // Decompiler (default)
     else {
@@ -135,10 +893,32 @@ async fn main() {
     }
 }
 
+/// Initialize the logs written to stderr up to the given level
+/// The spans durations (decompilation, RPC calls, detectors...) are logged when they close
+fn init_logging(log_level: &LogLevel, log_format: &LogFormat) {
+    let level = match log_level {
+        LogLevel::Error => Level::ERROR,
+        LogLevel::Warn => Level::WARN,
+        LogLevel::Info => Level::INFO,
+        LogLevel::Debug => Level::DEBUG,
+        LogLevel::Trace => Level::TRACE,
+    };
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr);
+
+    match log_format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
+
 /// Load the Sierra program from either a remote source, a local file, or scarb
 async fn load_program(args: &Args) -> Result<SierraProgram, String> {
     if args.scarb {
-        load_scarb_program().await
+        load_scarb_program(&args.target_dir).await
     } else if !args.remote.is_empty() {
         load_remote_program(args).await
     } else {
@@ -146,171 +926,573 @@ async fn load_program(args: &Args) -> Result<SierraProgram, String> {
     }
 }
 
-/// Load the Sierra program from a remote source
-async fn load_remote_program(args: &Args) -> Result<SierraProgram, String> {
-    let client = match args.network.as_str() {
-        "mainnet" => RpcClient::new(NetworkConfig::MAINNET_API_URL),
-        "sepolia" => RpcClient::new(NetworkConfig::SEPOLIA_API_URL),
-        _ => {
+/// Load the config file, the default one is optional but the one given with --config must exist
+fn load_config(config_file: Option<&Path>) -> Result<Config, String> {
+    let path = config_file.unwrap_or(Path::new(CONFIG_FILE));
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) if config_file.is_none() => return Ok(Config::default()),
+        Err(e) => {
             return Err(format!(
-                "Error: Unsupported network type '{}'",
-                args.network
+                "Failed to read the config file '{}': {}",
+                path.display(),
+                e
             ))
         }
     };
 
-    match client.get_class(&args.remote).await {
-        Ok(response) => {
-            let content = response.to_json();
-            let program_string = serde_json::from_str::<ContractClass>(&content)
-                .ok()
-                .and_then(|prog| prog.extract_sierra_program().ok())
-                .map_or_else(|| content.clone(), |prog_sierra| prog_sierra.to_string());
-            Ok(SierraProgram::new(program_string))
-        }
-        Err(e) => Err(format!("Error calling RPC: {}", e)),
-    }
+    toml::from_str(&content).map_err(|e| format!("Invalid config file '{}': {}", path.display(), e))
 }
 
-/// Load the Sierra program from a local file
-fn load_local_program(args: &Args) -> Result<SierraProgram, String> {
-    let sierra_file = args.sierra_file.as_ref().unwrap();
-
-    // Open the file
-    let mut file = File::open(sierra_file).map_err(|e| format!("Failed to open file: {}", e))?;
+/// Set the options that are not given on the command line (or in the environment) from the config
+fn apply_config(args: &mut Args, matches: &ArgMatches, config: Config) {
+    let is_unset = |id: &str| {
+        !matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+    };
 
-    // Read the file content into a string
-    let mut content = String::new();
-    file.read_to_string(&mut content)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+    if let Some(network) = config.network.filter(|_| is_unset("network")) {
+        args.network = network;
+    }
+    // The RPC node given on the command line replaces both the config provider & URL
+    if is_unset("rpc_provider") && is_unset("rpc_url") {
+        args.rpc_provider = config.rpc_provider;
+        args.rpc_url = config.rpc_url;
+    }
+    if let Some(cfg_output) = config.cfg_output.filter(|_| is_unset("cfg_output")) {
+        args.cfg_output = cfg_output;
+    }
+    if let Some(callgraph_output) = config
+        .callgraph_output
+        .filter(|_| is_unset("callgraph_output"))
+    {
+        args.callgraph_output = callgraph_output;
+    }
+    if let Some(color) = config.color.filter(|_| is_unset("no_color")) {
+        args.no_color = !color;
+    }
+    if let Some(solver_timeout) = config.solver_timeout.filter(|_| is_unset("solver_timeout")) {
+        args.solver_timeout = Some(solver_timeout);
+    }
+    if let Some(solver_seed) = config.solver_seed.filter(|_| is_unset("solver_seed")) {
+        args.solver_seed = Some(solver_seed);
+    }
+    if let Some(solver_tactic) = config.solver_tactic.filter(|_| is_unset("solver_tactic")) {
+        args.solver_tactic = Some(solver_tactic);
+    }
+    // --invariants without a file checks the invariants file of the config
+    if let Some(invariants @ None) = &mut args.invariants {
+        *invariants = config.invariants;
+    }
+}
 
-    // Deserialize the JSON content into a ContractClass
-    let contract_class: Result<ContractClass, _> = serde_json::from_str(&content);
+/// Set the options of the solver used by the symbolic execution analyses
+#[cfg(feature = "z3")]
+fn configure_solver(args: &Args) -> Result<(), String> {
+    if let Some(ref tactic) = args.solver_tactic {
+        if !z3_tactics().contains(tactic) {
+            return Err(format!("Unknown Z3 tactic '{}'", tactic));
+        }
+    }
 
-    let program_string = match contract_class {
-        Ok(ref prog) => {
-            // Extract the Sierra program from the ContractClass
-            match prog.extract_sierra_program() {
-                Ok(prog_sierra) => prog_sierra.to_string(),
-                Err(e) => {
-                    eprintln!("Error extracting Sierra program: {}", e);
-                    content.clone()
-                }
+    // The options that are not given keep their default value
+    let defaults = Z3Options::default();
+    set_default_options(Z3Options {
+        timeout: args.solver_timeout.or(defaults.timeout),
+        random_seed: args.solver_seed.or(defaults.random_seed),
+        tactic: args.solver_tactic.clone(),
+    });
+    Ok(())
+}
+
+/// The solver options are ignored without a solver backend
+#[cfg(not(feature = "z3"))]
+fn configure_solver(_args: &Args) -> Result<(), String> {
+    Ok(())
+}
+
+/// Returns the detectors output format, --summary & --quiet take precedence over --detectors-format
+fn detectors_format(args: &Args) -> DetectorsFormat {
+    match args.detectors_format {
+        _ if args.summary => DetectorsFormat::Summary,
+        DetectorsFormat::Text if args.quiet => DetectorsFormat::Findings,
+        format => format,
+    }
+}
+
+/// Load the formatting options from the config file, the command-line flags take precedence
+fn formatting_options(args: &Args) -> Result<FormattingOptions, String> {
+    let mut formatting = match args.format_config {
+        Some(ref format_config) => load_formatting_options(format_config)
+            .map_err(|e| format!("Error loading the format config: {}", e))?,
+        None => FormattingOptions::default(),
+    };
+
+    if let Some(indent_spaces) = args.indent_spaces {
+        formatting.indent_style = IndentStyle::Spaces;
+        formatting.indent_width = indent_spaces;
+    }
+    if args.max_line_length.is_some() {
+        formatting.max_line_length = args.max_line_length;
+    }
+    if args.no_function_comments {
+        formatting.function_comments = false;
+    }
+    if args.inline_wrappers {
+        formatting.inline_wrappers = true;
+    }
+    if args.summarize_calldata {
+        formatting.summarize_calldata = true;
+    }
+    if args.abi_types {
+        formatting.abi_types = true;
+    }
+    if args.panic_annotations {
+        formatting.panic_annotations = true;
+    }
+    if args.summarize_gas_checks {
+        formatting.summarize_gas_checks = true;
+    }
+    if args.early_returns {
+        formatting.early_returns = true;
+    }
+
+    Ok(formatting)
+}
+
+/// Create the RPC client of the selected network
+/// The requests are rate limited using the --rpc-concurrency & --rpc-interval-ms options
+fn rpc_client(args: &Args) -> Result<RpcClient, String> {
+    let node_address = if let Some(ref rpc_url) = args.rpc_url {
+        rpc_url.as_str()
+    } else if let Some(ref rpc_provider) = args.rpc_provider {
+        let provider = RpcProvider::from_name(rpc_provider)
+            .ok_or_else(|| format!("Error: Unsupported RPC provider '{}'", rpc_provider))?;
+        provider.url(&args.network).ok_or_else(|| {
+            format!(
+                "Error: Unsupported network type '{}' for {:?}",
+                args.network, provider
+            )
+        })?
+    } else {
+        match args.network.as_str() {
+            "mainnet" => NetworkConfig::MAINNET_API_URL,
+            "sepolia" => NetworkConfig::SEPOLIA_API_URL,
+            _ => {
+                return Err(format!(
+                    "Error: Unsupported network type '{}'",
+                    args.network
+                ))
             }
         }
-        Err(ref _e) => content.clone(),
     };
 
-    // Initialize a new SierraProgram with the deserialized Sierra program content
-    let mut program = SierraProgram::new(program_string);
+    let mut client = RpcClient::new(node_address)
+        .with_concurrency(args.rpc_concurrency)
+        .with_request_interval(Duration::from_millis(args.rpc_interval_ms));
+
+    // Inject the API key in the URL path, a URL parameter or a header
+    let location = if let Some(ref header) = args.api_key_header {
+        Some(ApiKeyLocation::Header(header.clone()))
+    } else if let Some(ref param) = args.api_key_param {
+        Some(ApiKeyLocation::UrlParameter(param.clone()))
+    } else if node_address.contains(API_KEY_PLACEHOLDER) {
+        Some(ApiKeyLocation::UrlPath)
+    } else {
+        None
+    };
 
-    // Set the program ABI if deserialization was successful
-    if let Ok(ref contract_class) = contract_class {
-        let abi = contract_class.abi.clone();
-        program.set_abi(abi.unwrap());
+    match (args.api_key.clone(), location) {
+        (Some(api_key), Some(location)) => {
+            client = client.with_api_key(ApiKey::new(api_key, location));
+        }
+        (None, Some(ApiKeyLocation::UrlPath)) => {
+            return Err(
+                "Error: The RPC node requires an API key (--api-key or SIERRA_ANALYZER_API_KEY)"
+                    .to_string(),
+            )
+        }
+        (Some(_), None) => warn!(
+            "The API key is ignored, use --api-key-header or --api-key-param with a custom RPC URL"
+        ),
+        _ => {}
     }
 
-    Ok(program)
+    Ok(client)
 }
 
-/// Load the Sierra program from the /target directory
-async fn load_scarb_program() -> Result<SierraProgram, String> {
-    let target_dir = Path::new("./target/dev/");
+/// Load the Sierra program from a remote source
+async fn load_remote_program(args: &Args) -> Result<SierraProgram, String> {
+    let client = rpc_client(args)?;
+    fetch_class(args, &client, &args.remote).await
+}
 
-    // Read the directory contents
-    let entries =
-        fs::read_dir(target_dir).map_err(|e| format!("Failed to read directory: {}", e))?;
+/// Fetch a class using the local provider if given, the RPC node otherwise
+async fn fetch_class(
+    args: &Args,
+    client: &RpcClient<'_>,
+    class_hash: &str,
+) -> Result<SierraProgram, String> {
+    let response = match args.class_source {
+        Some(ref class_source) => LocalProvider::from_path(class_source)
+            .and_then(|provider| provider.get_class(class_hash)),
+        None => client.get_class(class_hash).await,
+    }
+    .map_err(|e| e.to_string())?;
 
-    // Find the file that ends with "contract_class.json"
-    let contract_class_file = entries
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            let path = entry.path();
-            if path.is_file()
-                && path
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .map_or(false, |name| name.ends_with("contract_class.json"))
-            {
-                Some(path)
-            } else {
-                None
-            }
-        })
-        .next();
+    let content = response.to_json().map_err(|e| e.to_string())?;
+    load_program_from_content(content)
+}
 
-    // Check if the file was found
-    let contract_class_file = if let Some(file) = contract_class_file {
-        file
+/// Load the Sierra program from a local file or from stdin
+fn load_local_program(args: &Args) -> Result<SierraProgram, String> {
+    if is_stdin_input(args) {
+        load_program_from_stdin()
     } else {
-        eprintln!("You need to run scarb build before running the sierra-analyzer");
-        exit(1);
-    };
+        load_program_from_path(&args.sierra_file[0])
+    }
+}
 
-    // Open the file
-    let mut file =
-        File::open(&contract_class_file).map_err(|e| format!("Failed to open file: {}", e))?;
+/// Returns true if the program is read from stdin (--stdin or -f -)
+fn is_stdin_input(args: &Args) -> bool {
+    args.stdin
+        || args
+            .sierra_file
+            .first()
+            .map_or(false, |sierra_file| sierra_file == Path::new("-"))
+}
 
-    // Read the file content into a string
+/// Load a Sierra program or a contract class from stdin
+fn load_program_from_stdin() -> Result<SierraProgram, String> {
     let mut content = String::new();
-    file.read_to_string(&mut content)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+    io::stdin()
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read stdin: {}", e))?;
 
-    // Deserialize the JSON content into a ContractClass
-    let contract_class: Result<ContractClass, _> = serde_json::from_str(&content);
+    load_program_from_content(content)
+}
 
-    let program_string = match contract_class {
-        Ok(ref prog) => {
-            // Extract the Sierra program from the ContractClass
-            match prog.extract_sierra_program() {
-                Ok(prog_sierra) => prog_sierra.to_string(),
-                Err(e) => {
-                    eprintln!("Error extracting Sierra program: {}", e);
-                    content.clone()
+/// Load a Sierra program or a contract class from a file path
+fn load_program_from_path(sierra_file: &Path) -> Result<SierraProgram, String> {
+    SierraProgram::from_file(sierra_file).map_err(|e| e.to_string())
+}
+
+/// Load a Sierra program or a contract class from its content
+fn load_program_from_content(content: String) -> Result<SierraProgram, String> {
+    SierraProgram::from_content(content).map_err(|e| e.to_string())
+}
+
+/// Returns the contract classes built by Scarb in the dev profile directory of the target directory, sorted by name
+/// Exits with an error message if there is none
+fn scarb_contract_classes(target_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let contract_class_files = scarb::contract_classes(target_dir).map_err(|e| {
+        format!(
+            "Failed to read directory {}: {}",
+            target_dir.join("dev").display(),
+            e
+        )
+    })?;
+
+    // Check if a file was found
+    if contract_class_files.is_empty() {
+        error!("You need to run scarb build before running the sierra-analyzer");
+        exit(1);
+    }
+
+    Ok(contract_class_files)
+}
+
+/// Load the Sierra program of the first contract class of the target directory
+async fn load_scarb_program(target_dir: &Path) -> Result<SierraProgram, String> {
+    let contract_class_file = scarb_contract_classes(target_dir)?.remove(0);
+    load_program_from_path(&contract_class_file)
+}
+
+/// Run the detectors on all the classes declared by a list of transactions and print a combined report
+async fn handle_declared_classes(args: &Args) {
+    let client = match rpc_client(args) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    // Collect the declared classes hashes
+    let mut class_hashes: Vec<String> = Vec::new();
+    let declared_class_hashes = client
+        .get_declared_class_hashes(&args.declare_transactions)
+        .await;
+    for (transaction_hash, declared_class_hash) in
+        args.declare_transactions.iter().zip(declared_class_hashes)
+    {
+        match declared_class_hash {
+            Ok(Some(class_hash)) => {
+                if !class_hashes.contains(&class_hash) {
+                    class_hashes.push(class_hash);
+                }
+            }
+            Ok(None) => warn!("{} is not a DECLARE transaction", transaction_hash),
+            Err(e) => error!("Error fetching transaction {}: {}", transaction_hash, e),
+        }
+    }
+
+    // Fetch the classes, concurrently when they are read from the RPC node
+    let programs: Vec<Result<SierraProgram, String>> = match args.class_source {
+        Some(_) => {
+            let mut programs = Vec::new();
+            for class_hash in &class_hashes {
+                programs.push(fetch_class(args, &client, class_hash).await);
+            }
+            programs
+        }
+        None => {
+            if let Some(ref fetch_cache) = args.fetch_cache {
+                if let Err(e) = fs::create_dir_all(fetch_cache) {
+                    error!("Failed to create the fetch cache directory: {}", e);
+                    return;
                 }
             }
+
+            client
+                .get_classes(&class_hashes, args.fetch_cache.as_deref())
+                .await
+                .into_iter()
+                .map(|response| {
+                    let content = response
+                        .and_then(|response| response.to_json())
+                        .map_err(|e| e.to_string())?;
+                    load_program_from_content(content)
+                })
+                .collect()
+        }
+    };
+
+    // Analyze each class
+    for (class_hash, program) in class_hashes.iter().zip(programs) {
+        let program = match program {
+            Ok(program) => program,
+            Err(e) => {
+                error!("Error loading class {}: {}", class_hash, e);
+                continue;
+            }
+        };
+
+        print_program_header("Class", class_hash, args);
+        let mut cache = open_analysis_cache(args, &program);
+        handle_detectors(
+            &program,
+            &args.detector_names,
+            &args.detectors_tags,
+            args.function.as_deref(),
+            detectors_format(args),
+            cache.as_mut(),
+            args.detector_timeout.map(Duration::from_secs),
+        );
+        print_program_footer(args);
+    }
+}
+
+/// Re-parse the verbose decompiler output & print the differences with the program
+/// Exits with an error code if the output is not equivalent to the program
+fn handle_validate(program: &SierraProgram) {
+    let report = match validate_program(program) {
+        Ok(report) => report,
+        Err(e) => {
+            error!("Error validating program: {}", e);
+            exit(1);
+        }
+    };
+
+    for mismatch in &report.mismatches {
+        println!("{}", mismatch);
+    }
+    println!(
+        "{} functions & {} statements validated, {} mismatches",
+        report.functions,
+        report.statements,
+        report.mismatches.len()
+    );
+
+    if !report.is_valid() {
+        exit(1);
+    }
+}
+
+/// Decompile a corpus of examples & print the differences with the snapshots
+/// Exits with an error code if an output changed
+fn handle_corpus(corpus: &Path, snapshots_directory: &Path) {
+    let entries = match corpus_entries(corpus) {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!("Error reading the corpus: {}", e);
+            exit(1);
         }
-        Err(ref _e) => content.clone(),
     };
 
-    // Initialize a new SierraProgram with the deserialized Sierra program content
-    let mut program = SierraProgram::new(program_string);
+    let (mut unchanged, mut changed, mut new) = (0, 0, 0);
+    for entry in &entries {
+        let output = entry.snapshot_output();
+        match read_snapshot(&entry.snapshot_path(snapshots_directory)) {
+            None => {
+                println!("New {}", entry.name);
+                new += 1;
+            }
+            Some(snapshot) => match diff_output(&snapshot, &output) {
+                Some(diff) => {
+                    println!("Changed {}\n{}", entry.name, diff);
+                    changed += 1;
+                }
+                None => unchanged += 1,
+            },
+        }
+    }
 
-    // Set the program ABI if deserialization was successful
-    if let Ok(ref contract_class) = contract_class {
-        let abi = contract_class.abi.clone();
-        program.set_abi(abi.unwrap());
+    println!(
+        "\n{} unchanged, {} changed, {} new",
+        unchanged, changed, new
+    );
+    if changed > 0 {
+        exit(1);
     }
+}
 
-    Ok(program)
+/// Print the differences between the findings of two audit iterations
+/// Exits with an error code if new findings are reported
+fn handle_compare_findings(old: &Path, new: &Path, format: &DetectorsFormat) {
+    let mut reports = Vec::new();
+    for path in [old, new] {
+        match read_findings(path) {
+            Ok(findings) => reports.push(findings),
+            Err(e) => {
+                error!("Error reading the findings of {}: {}", path.display(), e);
+                exit(1);
+            }
+        }
+    }
+
+    let diff = FindingsDiff::new(&reports[0], &reports[1]);
+    match format {
+        DetectorsFormat::Json => println!("{}", diff.to_json()),
+        _ => print!("{}", diff.to_text()),
+    }
+    if diff.has_new() {
+        exit(1);
+    }
+}
+
+/// Compare the class hash of a local contract class with the class hash of a deployed contract
+async fn handle_verify_class_hash(args: &Args, contract_address: &str) {
+    match verify_local_class_hash(args, contract_address).await {
+        Ok(verification) if verification.is_match() => {
+            println!("Class hash match : {:#x}", verification.local_class_hash);
+        }
+        Ok(verification) => {
+            println!(
+                "Class hash mismatch : local {:#x}, on-chain {:#x}",
+                verification.local_class_hash, verification.onchain_class_hash
+            );
+            exit(1);
+        }
+        Err(e) => {
+            error!("Error verifying the class hash: {}", e);
+            exit(1);
+        }
+    }
+}
+
+/// Compute the class hash of the local contract class & fetch the class hash of the deployed contract
+async fn verify_local_class_hash(
+    args: &Args,
+    contract_address: &str,
+) -> Result<ClassHashVerification, String> {
+    // Read the local contract class
+    let mut content = String::new();
+    if is_stdin_input(args) {
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+    } else if let Some(sierra_file) = args.sierra_file.first() {
+        content =
+            fs::read_to_string(sierra_file).map_err(|e| format!("Failed to read file: {}", e))?;
+    } else {
+        return Err("A contract class file must be provided".to_string());
+    }
+
+    // Fetch the class hash of the deployed contract
+    let client = rpc_client(args)?;
+    let onchain_class_hash = client
+        .get_class_hash_at(contract_address)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    verify_class_hash(&content, &onchain_class_hash).map_err(|e| e.to_string())
 }
 
 /// Get the file stem based on the remote address or the Sierra file
 fn get_file_stem(args: &Args) -> String {
     if !args.remote.is_empty() {
-        args.remote.clone()
+        sanitize_file_stem(&args.remote)
     } else if args.scarb {
-        // TODO : modify with the program name
+        // The contract name of the analyzed Scarb contract class
+        scarb_contract_classes(&args.target_dir)
+            .ok()
+            .and_then(|contract_class_files| {
+                contract_class_files.first().map(|file| file_stem(file))
+            })
+            .unwrap_or_else(|| "sierra_program".to_string())
+    } else if is_stdin_input(args) {
         "sierra_program".to_string()
     } else {
-        args.sierra_file
-            .as_ref()
-            .unwrap()
+        file_stem(&args.sierra_file[0])
+    }
+}
+
+/// Get the file stem of a Sierra file, it names the CFG & callgraph output files
+/// The contract classes are named by their contract (e.g. `package_Counter.contract_class.json`)
+fn file_stem(sierra_file: &Path) -> String {
+    let file_name = sierra_file
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    let stem = match file_name.strip_suffix(".contract_class.json") {
+        Some(contract_name) => contract_name.to_string(),
+        None => sierra_file
             .file_stem()
             .unwrap_or_default()
             .to_string_lossy()
-            .to_string()
+            .to_string(),
+    };
+    sanitize_file_stem(&stem)
+}
+
+/// Replace the characters that are not allowed in the file names of some platforms by `_`
+/// The leading & trailing dots are removed, an empty stem is replaced by "sierra_program"
+fn sanitize_file_stem(stem: &str) -> String {
+    let sanitized: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    match sanitized.trim_matches('.') {
+        "" => "sierra_program".to_string(),
+        stem => stem.to_string(),
     }
 }
 
 /// Handle the generation and saving of the CFG (Control Flow Graph)
 fn handle_cfg(args: &Args, decompiler: &mut Decompiler, file_stem: &str) {
-    let svg_filename = format!("{}_cfg.svg", file_stem);
-    let full_path = args.cfg_output.join(svg_filename);
-
     // Create the output directory if it doesn't exist
     if let Err(e) = fs::create_dir_all(&args.cfg_output) {
-        eprintln!(
+        error!(
             "Failed to create directory '{}': {}",
             args.cfg_output.display(),
             e
@@ -318,20 +1500,115 @@ fn handle_cfg(args: &Args, decompiler: &mut Decompiler, file_stem: &str) {
         return;
     }
 
-    // Generate CFG and save to SVG
-    let cfg_graph = decompiler.generate_cfg();
-    save_svg_graph_to_file(full_path.to_str().unwrap(), cfg_graph)
-        .expect("Failed to save CFG to SVG");
+    match args.cfg_format.as_str() {
+        "svg" => {
+            let svg_filename = format!("{}_cfg.svg", file_stem);
+            let full_path = args.cfg_output.join(svg_filename);
+
+            // Generate CFG and save to SVG
+            let cfg_graph = decompiler.generate_cfg_with_detail(args.cfg_detail.into());
+            save_svg_graph_to_file(full_path.to_str().unwrap(), cfg_graph)
+                .expect("Failed to save CFG to SVG");
+        }
+        "json" => {
+            let json_filename = format!("{}_cfg.json", file_stem);
+            let full_path = args.cfg_output.join(json_filename);
+
+            // Generate CFG and save to JSON
+            let cfg_json = decompiler.generate_cfg_json();
+            fs::write(&full_path, cfg_json).expect("Failed to save CFG to JSON");
+        }
+        "graphml" => {
+            let graphml_filename = format!("{}_cfg.graphml", file_stem);
+            let full_path = args.cfg_output.join(graphml_filename);
+
+            // Generate CFG and save to GraphML
+            let cfg_graphml = decompiler.generate_cfg_graphml();
+            fs::write(&full_path, cfg_graphml).expect("Failed to save CFG to GraphML");
+        }
+        _ => {
+            error!("Unsupported CFG format '{}'", args.cfg_format);
+        }
+    }
 }
 
 /// Handle the generation and saving of the Call Graph
 fn handle_callgraph(args: &Args, decompiler: &mut Decompiler, file_stem: &str) {
-    let svg_filename = format!("{}_callgraph.svg", file_stem);
-    let full_path = args.callgraph_output.join(svg_filename);
+    // Create the output directory if it doesn't exist
+    if let Err(e) = fs::create_dir_all(&args.callgraph_output) {
+        error!(
+            "Failed to create directory '{}': {}",
+            args.callgraph_output.display(),
+            e
+        );
+        return;
+    }
+
+    // Filter the Call Graph nodes
+    let filter = if let Some(ref roots) = args.callgraph_roots {
+        if roots != "external" {
+            error!("Unsupported callgraph roots '{}'", roots);
+            return;
+        }
+        Some(decompiler.reachable_from_entry_points())
+    } else if let Some(ref function_name) = args.callers {
+        let callers = decompiler.callers(function_name);
+        if callers.is_empty() {
+            error!("Function '{}' not found in the callgraph", function_name);
+            return;
+        }
+        Some(callers)
+    } else {
+        None
+    };
+
+    match args.callgraph_format.as_str() {
+        "svg" => {
+            let svg_filename = format!("{}_callgraph.svg", file_stem);
+            let full_path = args.callgraph_output.join(svg_filename);
+
+            // Generate Callgraph and save to SVG
+            decompiler.set_callgraph_clusters(args.callgraph_clusters);
+            let callgraph_graph = match filter {
+                Some(ref filter) => decompiler.generate_filtered_callgraph(filter),
+                None => decompiler.generate_callgraph(),
+            };
+            save_svg_graph_to_file(full_path.to_str().unwrap(), callgraph_graph)
+                .expect("Failed to save Callgraph to SVG");
+        }
+        "json" => {
+            let json_filename = format!("{}_callgraph.json", file_stem);
+            let full_path = args.callgraph_output.join(json_filename);
+
+            // Generate Callgraph and save to JSON
+            let callgraph_json = match filter {
+                Some(ref filter) => decompiler.generate_filtered_callgraph_json(filter),
+                None => decompiler.generate_callgraph_json(),
+            };
+            fs::write(&full_path, callgraph_json).expect("Failed to save Callgraph to JSON");
+        }
+        "graphml" => {
+            let graphml_filename = format!("{}_callgraph.graphml", file_stem);
+            let full_path = args.callgraph_output.join(graphml_filename);
+
+            // Generate Callgraph and save to GraphML
+            let callgraph_graphml = match filter {
+                Some(ref filter) => decompiler.generate_filtered_callgraph_graphml(filter),
+                None => decompiler.generate_callgraph_graphml(),
+            };
+            fs::write(&full_path, callgraph_graphml).expect("Failed to save Callgraph to GraphML");
+        }
+        _ => {
+            error!("Unsupported callgraph format '{}'", args.callgraph_format);
+        }
+    }
+}
 
+/// Handle the generation and saving of the cross-contract Call Graph
+fn handle_cross_contract_callgraph(args: &Args, decompiler: &Decompiler, file_stem: &str) {
     // Create the output directory if it doesn't exist
     if let Err(e) = fs::create_dir_all(&args.callgraph_output) {
-        eprintln!(
+        error!(
             "Failed to create directory '{}': {}",
             args.callgraph_output.display(),
             e
@@ -339,31 +1616,365 @@ fn handle_callgraph(args: &Args, decompiler: &mut Decompiler, file_stem: &str) {
         return;
     }
 
-    // Generate Callgraph and save to SVG
-    let callgraph_graph = decompiler.generate_callgraph();
-    save_svg_graph_to_file(full_path.to_str().unwrap(), callgraph_graph)
-        .expect("Failed to save Callgraph to SVG");
+    // Load the other contracts of the batch
+    let mut programs = Vec::new();
+    for path in &args.cross_contract_files {
+        match load_program_from_path(path) {
+            Ok(program) => programs.push((path, program)),
+            Err(e) => {
+                error!("Error loading program '{}': {}", path.display(), e);
+                return;
+            }
+        }
+    }
+
+    // Decompile them to set their functions types
+    let mut decompilers = Vec::new();
+    for (path, program) in &programs {
+        let mut other_decompiler = program.decompiler(args.verbose);
+        if let Err(e) = other_decompiler.try_decompile(false) {
+            error!("Error decompiling program '{}': {}", path.display(), e);
+            return;
+        }
+        decompilers.push((path, other_decompiler));
+    }
+
+    let mut contracts = vec![CrossContract::new(
+        file_stem,
+        parse_class_hash(file_stem),
+        &decompiler.functions,
+    )];
+    for (path, other_decompiler) in &decompilers {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        contracts.push(CrossContract::new(
+            &stem,
+            parse_class_hash(&stem),
+            &other_decompiler.functions,
+        ));
+    }
+
+    match args.callgraph_format.as_str() {
+        "svg" => {
+            let svg_filename = format!("{}_cross_contract_callgraph.svg", file_stem);
+            let full_path = args.callgraph_output.join(svg_filename);
+
+            // Generate the cross-contract Callgraph and save to SVG
+            let callgraph_graph = process_cross_contract_callgraph(&contracts);
+            save_svg_graph_to_file(full_path.to_str().unwrap(), callgraph_graph)
+                .expect("Failed to save Callgraph to SVG");
+        }
+        "json" => {
+            let json_filename = format!("{}_cross_contract_callgraph.json", file_stem);
+            let full_path = args.callgraph_output.join(json_filename);
+
+            // Generate the cross-contract Callgraph and save to JSON
+            let callgraph_json = process_cross_contract_callgraph_json(&contracts);
+            fs::write(&full_path, callgraph_json).expect("Failed to save Callgraph to JSON");
+        }
+        _ => {
+            error!("Unsupported callgraph format '{}'", args.callgraph_format);
+        }
+    }
 }
 
-/// Handle the running of detectors and printing their results
-fn handle_detectors(decompiler: &mut Decompiler, detector_names: Vec<String>) {
+/// Parse a class hash from a file stem or a remote address (e.g. 0x1234...)
+fn parse_class_hash(value: &str) -> Option<BigUint> {
+    value
+        .strip_prefix("0x")
+        .and_then(|hex| BigUint::parse_bytes(hex.as_bytes(), 16))
+}
+
+/// Returns the detectors selected by their names & having all the given tags
+/// The TESTING detectors are skipped if no specific detector names nor tags are provided
+fn selected_detectors(
+    detector_names: &[String],
+    detector_tags: &[String],
+) -> Vec<Box<dyn Detector>> {
     let mut detectors = get_detectors();
-    let mut output = String::new();
+    detectors.retain(|detector| {
+        let tags = detector.tags();
+        let named =
+            detector_names.is_empty() || detector_names.contains(&detector.id().to_string());
+        let tagged = detector_tags.iter().all(|tag| tags.contains(&tag.as_str()));
+        if detector_names.is_empty() && detector_tags.is_empty() {
+            detector.detector_type() != DetectorType::TESTING
+        } else {
+            named && tagged
+        }
+    });
+    detectors
+}
 
-    // Run the specified detectors
-    for detector in detectors.iter_mut() {
-        // Skip TESTING detectors if no specific detector names are provided
-        if detector_names.is_empty() && detector.detector_type() == DetectorType::TESTING {
-            continue;
+/// Collect the findings of the selected detectors, they are highlighted in the graphs
+fn collect_findings(
+    decompiler: &Decompiler,
+    detector_names: &[String],
+    detector_tags: &[String],
+    function_filter: Option<&str>,
+) -> Vec<Finding> {
+    let context = detectors_context(decompiler, function_filter);
+    let missing_abi = context.kind() == ProgramKind::RawSierra;
+    let missing_registry = context.registry().is_none();
+
+    selected_detectors(detector_names, detector_tags)
+        .iter_mut()
+        .filter(|detector| {
+            !(missing_abi && detector.requires_abi())
+                && !(missing_registry && detector.requires_registry())
+        })
+        .flat_map(|detector| {
+            let _span = info_span!("detector", id = detector.id()).entered();
+            detector.findings(&context)
+        })
+        .collect()
+}
+
+/// Open the cache of the analysis results of a program, if a cache directory is given
+fn open_analysis_cache(args: &Args, program: &SierraProgram) -> Option<AnalysisCache> {
+    let directory = args.analysis_cache.as_ref()?;
+    match AnalysisCache::open(directory, program, &analysis_options(args)) {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            warn!("Failed to open the analysis cache: {}", e);
+            None
         }
+    }
+}
 
-        // Skip detectors not in the provided names if names are provided
-        if !detector_names.is_empty() && !detector_names.contains(&detector.id().to_string()) {
-            continue;
+/// Returns the description of the options the cached results depend on
+/// The config & invariants files are identified by their content
+fn analysis_options(args: &Args) -> String {
+    let config_file = args.config.as_deref().unwrap_or(Path::new(CONFIG_FILE));
+    let file_content = |path: &Path| fs::read_to_string(path).unwrap_or_default();
+
+    format!(
+        "config={:?};invariants={:?};hash_model={:?};solver_timeout={:?};solver_seed={:?};solver_tactic={:?}",
+        file_content(config_file),
+        args.invariants.clone().flatten().as_deref().map(file_content),
+        args.hash_model,
+        args.solver_timeout,
+        args.solver_seed,
+        args.solver_tactic,
+    )
+}
+
+/// Returns the analysis context of the detectors, restricted to the functions matching the filter
+fn detectors_context<'a>(
+    decompiler: &Decompiler<'a>,
+    function_filter: Option<&str>,
+) -> AnalysisContext<'a> {
+    let context = decompiler.analysis_context();
+    match function_filter {
+        Some(function_name) => context.with_function_filter(function_name),
+        None => context,
+    }
+}
+
+/// Handle the running of detectors and printing their results
+/// The results of the detectors already run on the program are read from the cache
+/// The detectors still running after their timeout are reported, the other results are printed
+/// without waiting for them
+fn handle_detectors(
+    program: &SierraProgram,
+    detector_names: &[String],
+    detector_tags: &[String],
+    function_filter: Option<&str>,
+    format: DetectorsFormat,
+    mut cache: Option<&mut AnalysisCache>,
+    timeout: Option<Duration>,
+) {
+    let mut detectors = selected_detectors(detector_names, detector_tags);
+
+    // Skip the detectors that need the ABI of a contract class or the program registry
+    let missing_abi = program.kind() == ProgramKind::RawSierra;
+    let missing_registry = program.registry().is_none();
+    let mut skipped_detectors = Vec::new();
+    detectors.retain(|detector| {
+        let skipped = (missing_abi && detector.requires_abi())
+            || (missing_registry && detector.requires_registry());
+        if skipped {
+            skipped_detectors.push(detector.id());
         }
+        !skipped
+    });
+    if !skipped_detectors.is_empty() {
+        let reason = match (missing_abi, missing_registry) {
+            (true, true) => "missing ABI & legacy Sierra program",
+            (true, false) => "missing ABI",
+            _ => "legacy Sierra program",
+        };
+        warn!(
+            "Skipped detectors ({}): {}",
+            reason,
+            skipped_detectors.join(", ")
+        );
+    }
+
+    // Detectors ids & headers, the detectors are moved to their threads
+    let ids: Vec<&'static str> = detectors.iter().map(|detector| detector.id()).collect();
+    let headers: Vec<(String, &'static str)> = detectors
+        .iter()
+        .map(|detector| {
+            (
+                detector.detector_type().as_str().to_string(),
+                detector.name(),
+            )
+        })
+        .collect();
+
+    let mut results: Vec<Option<DetectorResults>> = ids
+        .iter()
+        .map(|id| {
+            cache
+                .as_ref()
+                .and_then(|cache| cache.detector_results(id, function_filter))
+                .cloned()
+        })
+        .collect();
+    let cached: Vec<bool> = results.iter().map(Option::is_some).collect();
+    if cached.iter().all(|cached| *cached) {
+        print_detectors_results(&headers, &results, format);
+        return;
+    }
+
+    // Both outputs are computed when caching, to switch between the output formats
+    let caching = cache.is_some();
+    let with_report =
+        caching || matches!(format, DetectorsFormat::Text | DetectorsFormat::Findings);
+    let with_findings = caching || !matches!(format, DetectorsFormat::Text);
+
+    let running = cached.iter().filter(|cached| !**cached).count();
+    let progress_bar = ProgressBar::new(running as u64);
+    progress_bar.set_style(
+        ProgressStyle::with_template("{spinner} [{elapsed_precise}] {bar:40} {pos}/{len} {msg}")
+            .unwrap(),
+    );
+    progress_bar.enable_steady_tick(Duration::from_millis(100));
+
+    // The cached detectors are not run again, the detectors that time out keep running in the
+    // background until the process exits
+    let (running_indexes, running_detectors): (Vec<usize>, Vec<_>) = detectors
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !cached[*index])
+        .unzip();
+    let options = RunOptions {
+        function_filter: function_filter.map(str::to_string),
+        with_report,
+        with_findings,
+        timeout,
+    };
+    let outcomes = match run_detectors(program, running_detectors, options, |index, _| {
+        progress_bar.set_message(headers[running_indexes[index]].1);
+        progress_bar.inc(1);
+    }) {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            progress_bar.finish_and_clear();
+            error!("Error analyzing program: {}", e);
+            return;
+        }
+    };
+
+    let mut timed_out = vec![false; ids.len()];
+    let mut failed = vec![false; ids.len()];
+    for (index, outcome) in running_indexes.into_iter().zip(outcomes) {
+        match outcome {
+            DetectorOutcome::Finished(result) => results[index] = Some(result),
+            DetectorOutcome::TimedOut => timed_out[index] = true,
+            DetectorOutcome::Failed => failed[index] = true,
+        }
+    }
+    progress_bar.finish_and_clear();
+
+    // Cache the results of the detectors that finished
+    if let Some(cache) = cache.as_mut() {
+        for (index, id) in ids.iter().enumerate() {
+            if let (Some(result), false) = (&results[index], cached[index]) {
+                cache.set_detector_results(id, function_filter, result.clone());
+            }
+        }
+        if let Err(e) = cache.save() {
+            warn!("Failed to save the analysis cache: {}", e);
+        }
+    }
 
-        let result = detector.detect(decompiler);
-        if !result.trim().is_empty() {
+    print_detectors_results(&headers, &results, format);
+
+    // Summary of the detectors that timed out
+    let timed_out: Vec<&str> = headers
+        .iter()
+        .zip(&timed_out)
+        .filter(|(_, timed_out)| **timed_out)
+        .map(|((_, detector_name), _)| *detector_name)
+        .collect();
+    if !timed_out.is_empty() {
+        warn!("Timed out detectors: {}", timed_out.join(", "));
+    }
+
+    // Summary of the detectors that panicked
+    let failed: Vec<&str> = headers
+        .iter()
+        .zip(&failed)
+        .filter(|(_, failed)| **failed)
+        .map(|((_, detector_name), _)| *detector_name)
+        .collect();
+    if !failed.is_empty() {
+        warn!("Failed detectors: {}", failed.join(", "));
+    }
+}
+
+/// Print the results of the detectors, the ones that timed out are skipped
+fn print_detectors_results(
+    headers: &[(String, &str)],
+    results: &[Option<DetectorResults>],
+    format: DetectorsFormat,
+) {
+    // The findings of all the detectors are printed in a single JSON array
+    if let DetectorsFormat::Json = format {
+        let reports: Vec<FindingReport> = results
+            .iter()
+            .flatten()
+            .flat_map(|result| result.findings.clone())
+            .collect();
+        println!("{}", findings_to_json(&reports));
+        return;
+    }
+
+    // The findings are counted by detector type on a single line
+    if let DetectorsFormat::Summary = format {
+        let counts: Vec<String> = [
+            DetectorType::SECURITY,
+            DetectorType::CORRECTNESS,
+            DetectorType::OPTIMIZATION,
+            DetectorType::INFORMATIONAL,
+            DetectorType::TESTING,
+        ]
+        .iter()
+        .map(|detector_type| {
+            let count = results
+                .iter()
+                .flatten()
+                .flat_map(|result| &result.findings)
+                .filter(|report| report.detector_type == detector_type.tag())
+                .count();
+            format!("{} {}", count, detector_type.tag())
+        })
+        .collect();
+        println!("{}", counts.join(", "));
+        return;
+    }
+
+    let mut output = String::new();
+    for ((detector_type, detector_name), result) in headers.iter().zip(results) {
+        let Some(result) = result else {
+            continue;
+        };
+        // The quiet output skips the detectors only printing a report & the ones without findings
+        if matches!(format, DetectorsFormat::Findings) && result.findings.is_empty() {
+            continue;
+        }
+        if !result.report.trim().is_empty() {
             // Each detector output is formatted like
             //
             // [Detector category] Detector name
@@ -371,9 +1982,10 @@ fn handle_detectors(decompiler: &mut Decompiler, detector_names: Vec<String>) {
             //      - ...
             output.push_str(&format!(
                 "[{}] {}\n{}\n\n",
-                detector.detector_type().as_str(),
-                detector.name(),
+                detector_type,
+                detector_name,
                 result
+                    .report
                     .lines()
                     .map(|line| format!("\t- {}", line))
                     .collect::<Vec<String>>()
@@ -388,7 +2000,370 @@ fn handle_detectors(decompiler: &mut Decompiler, detector_names: Vec<String>) {
     }
 }
 
-/// Print all available detector names with their types and descriptions
+/// Check the invariants declared in a file and print the violated ones
+fn handle_invariants(decompiler: &mut Decompiler, invariants_file: &Path) {
+    let invariants = match fs::read_to_string(invariants_file)
+        .map_err(|e| format!("Failed to read invariants file: {}", e))
+        .and_then(|content| load_invariants(&content))
+    {
+        Ok(invariants) => invariants,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    // The paths are explored over the CFGs
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let violations = check_invariants(&decompiler.functions, &invariants);
+    if violations.is_empty() {
+        println!("All the {} invariants hold", invariants.len());
+        return;
+    }
+
+    // The violations are formatted like the detectors results
+    println!("[Security] Invariants");
+    for violation in violations {
+        println!("\t- {}", violation);
+    }
+}
+
+/// Print the matrix of the entry points vs. the access-control checks they perform
+fn handle_access_control(decompiler: &mut Decompiler, format: MatrixFormat) {
+    // The checks are found in the SSA form of the functions
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let matrix = AccessControlMatrix::new(&decompiler.functions);
+    match format {
+        MatrixFormat::Text => print!("{}", matrix.to_text()),
+        MatrixFormat::Markdown => print!("{}", matrix.to_markdown()),
+        MatrixFormat::Json => println!("{}", matrix.to_json()),
+    }
+}
+
+/// Print the table of the entry points with their selectors, parameters & state mutability
+fn handle_entry_points(decompiler: &Decompiler, format: MatrixFormat) {
+    let table = EntryPointsTable::new(&decompiler.functions, decompiler.abi.as_ref());
+    match format {
+        MatrixFormat::Text => print!("{}", table.to_text()),
+        MatrixFormat::Markdown => print!("{}", table.to_markdown()),
+        MatrixFormat::Json => println!("{}", table.to_json()),
+    }
+}
+
+/// Print the functions invoking a libfunc matching a regex, with the offsets of the invocations
+fn handle_find_calls(decompiler: &Decompiler, pattern: &str) {
+    let function_calls = match find_libfunc_calls(
+        &decompiler.functions,
+        pattern,
+        &decompiler.declared_libfuncs_names,
+    ) {
+        Ok(function_calls) => function_calls,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    if function_calls.is_empty() {
+        println!("No function invokes a libfunc matching {}", pattern);
+        return;
+    }
+
+    for function_call in function_calls {
+        println!("{}", function_call.function);
+        for call in function_call.calls {
+            println!("\t- {}: {}", call.offset, call.libfunc);
+        }
+    }
+}
+
+/// Print the storage variables of the program & the collisions of their base addresses
+fn handle_storage_layout(decompiler: &Decompiler, format: MatrixFormat) {
+    let layout = StorageLayout::new(
+        &decompiler.functions,
+        &decompiler.declared_libfuncs_names,
+        &decompiler.declared_types_names,
+    );
+    match format {
+        MatrixFormat::Text => print!("{}", layout.to_text()),
+        MatrixFormat::Markdown => print!("{}", layout.to_markdown()),
+        MatrixFormat::Json => println!("{}", layout.to_json()),
+    }
+}
+
+/// Print the matrix of the entry points vs. the storage variables they read & write
+fn handle_storage_matrix(decompiler: &Decompiler, format: StorageMatrixFormat) {
+    let layout = StorageLayout::new(
+        &decompiler.functions,
+        &decompiler.declared_libfuncs_names,
+        &decompiler.declared_types_names,
+    );
+    let matrix = StorageAccessMatrix::new(&decompiler.functions, &layout);
+    match format {
+        StorageMatrixFormat::Text => print!("{}", matrix.to_text()),
+        StorageMatrixFormat::Csv => print!("{}", matrix.to_csv()),
+        StorageMatrixFormat::Json => println!("{}", matrix.to_json()),
+    }
+}
+
+/// Print the L1 handlers & the messages sent to L1 with the origins of their payloads
+fn handle_messaging(decompiler: &mut Decompiler, json: bool) {
+    // The origins are tracked in the SSA form of the functions
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let report = MessagingReport::new(&decompiler.functions, decompiler.abi.as_ref());
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_text());
+    }
+}
+
+/// Check the reachability of the panics of a function and print the witnesses inputs
+#[cfg(feature = "z3")]
+fn handle_prove(decompiler: &mut Decompiler, function_name: &str, hash_model: HashModel) {
+    let Some(function_index) = decompiler
+        .functions
+        .iter()
+        .position(|function| parse_element_name!(function.function.id) == function_name)
+    else {
+        error!("Function '{}' not found", function_name);
+        return;
+    };
+
+    // Generate all the CFGs so the called functions can be inlined
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let reports = prove_panics(
+        &decompiler.functions,
+        function_index,
+        &decompiler.declared_libfuncs_names,
+        hash_model,
+    );
+    if reports.is_empty() {
+        println!("No panic found in {}", function_name);
+        return;
+    }
+
+    for report in reports {
+        match report.reachability {
+            PanicReachability::Reachable(witness) => {
+                println!("Panic at statement {} : reachable", report.offset);
+                let arguments: Vec<String> = witness
+                    .arguments
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect();
+                println!("\t- {}", arguments.join(", "));
+                for (address, value) in witness.storage {
+                    println!("\t- storage[0x{:x}]: {}", address, value);
+                }
+            }
+            PanicReachability::Unreachable => {
+                println!("Panic at statement {} : unreachable", report.offset)
+            }
+            PanicReachability::Unknown => {
+                println!("Panic at statement {} : unknown", report.offset)
+            }
+        }
+    }
+}
+
+/// The panics reachability requires a solver backend
+#[cfg(not(feature = "z3"))]
+fn handle_prove(_decompiler: &mut Decompiler, _function_name: &str, _hash_model: HashModel) {
+    error!("--prove requires the z3 feature");
+}
+
+/// Print the basic blocks of a function covered by the generated inputs
+/// The uncovered blocks are printed with the constraints of the unsatisfiable path reaching them
+#[cfg(feature = "z3")]
+fn handle_coverage(decompiler: &mut Decompiler, function_name: &str, hash_model: HashModel) {
+    let Some(function_index) = decompiler
+        .functions
+        .iter()
+        .position(|function| parse_element_name!(function.function.id) == function_name)
+    else {
+        error!("Function '{}' not found", function_name);
+        return;
+    };
+
+    // Generate all the CFGs so the loops can be unrolled
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let reports = coverage_map(
+        &decompiler.functions,
+        function_index,
+        &decompiler.declared_libfuncs_names,
+        hash_model,
+    );
+    let covered = reports
+        .iter()
+        .filter(|report| report.coverage == BlockCoverage::Covered)
+        .count();
+
+    for report in &reports {
+        match &report.coverage {
+            BlockCoverage::Covered => println!("Block {} : covered", report.offset),
+            BlockCoverage::Unsatisfiable(constraints) => {
+                println!("Block {} : uncovered (unsatisfiable path)", report.offset);
+                for constraint in constraints {
+                    println!("\t- {}", constraint);
+                }
+            }
+            BlockCoverage::Unknown => println!("Block {} : unknown", report.offset),
+            BlockCoverage::Unexplored => {
+                println!("Block {} : uncovered (not explored)", report.offset)
+            }
+        }
+    }
+    println!("\n{}/{} blocks covered", covered, reports.len());
+}
+
+/// The coverage map requires a solver backend
+#[cfg(not(feature = "z3"))]
+fn handle_coverage(_decompiler: &mut Decompiler, _function_name: &str, _hash_model: HashModel) {
+    error!("--coverage requires the z3 feature");
+}
+
+/// Print the SMT-LIB2 constraints of each path of a function & their satisfiability
+#[cfg(feature = "z3")]
+fn handle_dump_constraints(
+    decompiler: &mut Decompiler,
+    function_name: &str,
+    hash_model: HashModel,
+) {
+    let Some(function_index) = decompiler
+        .functions
+        .iter()
+        .position(|function| parse_element_name!(function.function.id) == function_name)
+    else {
+        error!("Function '{}' not found", function_name);
+        return;
+    };
+
+    // Generate all the CFGs so the loops can be unrolled
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let paths = path_constraints(
+        &decompiler.functions,
+        function_index,
+        &decompiler.declared_libfuncs_names,
+        hash_model,
+    );
+    for (index, path) in paths.iter().enumerate() {
+        let verdict = match path.result {
+            SolverResult::Sat => "sat",
+            SolverResult::Unsat => "unsat",
+            SolverResult::Unknown => "unknown",
+        };
+        println!("; Path {} : {} ({:.2?})", index, verdict, path.solving_time);
+        println!("{}", path.to_smtlib2());
+    }
+}
+
+/// The constraints verdicts require a solver backend
+#[cfg(not(feature = "z3"))]
+fn handle_dump_constraints(
+    _decompiler: &mut Decompiler,
+    _function_name: &str,
+    _hash_model: HashModel,
+) {
+    error!("--dump-constraints requires the z3 feature");
+}
+
+/// Run the paths of a function with cairo-vm & print the inputs of each path with its validation
+#[cfg(feature = "concolic")]
+fn handle_concolic(
+    program: &SierraProgram,
+    decompiler: &mut Decompiler,
+    function_name: &str,
+    hash_model: HashModel,
+) {
+    let Some(function_index) = decompiler
+        .functions
+        .iter()
+        .position(|function| parse_element_name!(function.function.id) == function_name)
+    else {
+        error!("Function '{}' not found", function_name);
+        return;
+    };
+
+    // Generate all the CFGs so the loops can be unrolled
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let reports = match concolic_execution(
+        program.program(),
+        &decompiler.functions,
+        function_index,
+        &decompiler.declared_libfuncs_names,
+        hash_model,
+    ) {
+        Ok(reports) => reports,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    for (index, report) in reports.iter().enumerate() {
+        let validation = match report.validation {
+            PathValidation::Validated => "validated".to_string(),
+            PathValidation::Diverged(offset) => format!("diverged at statement {}", offset),
+            PathValidation::Infeasible => "infeasible".to_string(),
+            PathValidation::Unknown => "unknown".to_string(),
+        };
+        let panic = if report.panicked { " (panic)" } else { "" };
+        println!("Path {} : {}{}", index, validation, panic);
+
+        if !report.arguments.is_empty() {
+            let arguments: Vec<String> = report
+                .arguments
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            println!("\t- {}", arguments.join(", "));
+        }
+        if report.concretized_hashes > 0 {
+            println!("\t- {} concretized hashes", report.concretized_hashes);
+        }
+    }
+
+    let validated = reports
+        .iter()
+        .filter(|report| report.validation == PathValidation::Validated)
+        .count();
+    println!("\n{}/{} paths validated", validated, reports.len());
+}
+
+/// The concolic execution requires the cairo-vm runner
+#[cfg(not(feature = "concolic"))]
+fn handle_concolic(
+    _program: &SierraProgram,
+    _decompiler: &mut Decompiler,
+    _function_name: &str,
+    _hash_model: HashModel,
+) {
+    error!("--concolic requires the concolic feature");
+}
+
+/// Print all available detector names with their types, descriptions, tags & required inputs
 fn print_available_detectors() {
     let detectors = get_detectors();
     println!("Available detectors:");
@@ -399,5 +2374,75 @@ fn print_available_detectors() {
             detector.id(),
             detector.description()
         );
+
+        // The detectors requiring an input are skipped when it's missing
+        let mut requires = Vec::new();
+        if detector.requires_abi() {
+            requires.push("ABI");
+        }
+        if detector.requires_registry() {
+            requires.push("program registry");
+        }
+        if detector.uses_symbolic_execution() {
+            requires.push("symbolic execution (slow)");
+        }
+        let requires = if requires.is_empty() {
+            "-".to_string()
+        } else {
+            requires.join(", ")
+        };
+        println!(
+            "    tags: {} | requires: {}",
+            detector.tags().join(", "),
+            requires
+        );
+    }
+}
+
+/// Print the build metadata of the binary, to reproduce the results of an analysis
+/// The dependencies versions are read from Cargo.lock by the build script
+fn print_version_info() {
+    println!("sierra-analyzer {}", ANALYZER_VERSION);
+    println!("cairo-lang-sierra: {}", env!("CAIRO_LANG_SIERRA_VERSION"));
+    println!(
+        "Sierra version: {} (the older programs are analyzed without the program registry)",
+        SUPPORTED_SIERRA_VERSION
+    );
+
+    #[cfg(feature = "z3")]
+    println!(
+        "Z3: {} (z3 crate {})",
+        z3_version(),
+        env!("Z3_CRATE_VERSION")
+    );
+    #[cfg(not(feature = "z3"))]
+    println!("Z3: disabled (built without the z3 feature)");
+
+    let features: Vec<&str> = [
+        ("z3", cfg!(feature = "z3")),
+        ("pathfinder", cfg!(feature = "pathfinder")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(feature, _)| feature)
+    .collect();
+    println!(
+        "Features: {}",
+        if features.is_empty() {
+            "-".to_string()
+        } else {
+            features.join(", ")
+        }
+    );
+    println!(
+        "Build: {} ({})",
+        env!("BUILD_TARGET"),
+        env!("BUILD_PROFILE")
+    );
+
+    let detectors = get_detectors();
+    println!("Detectors ({}):", detectors.len());
+    for detector in detectors {
+        println!("- {} [{}]", detector.id(), detector.detector_type().tag());
     }
 }
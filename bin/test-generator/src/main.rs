@@ -1,13 +1,22 @@
-use std::collections::HashSet;
 use std::env;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process;
-use std::str::FromStr;
+use std::sync::Arc;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use tracing::error;
 
+use sierra_analyzer_lib::analysis::entry_points::EntryPointsTable;
+use sierra_analyzer_lib::parse_element_name;
 use sierra_analyzer_lib::sierra_program::SierraProgram;
-use sierra_analyzer_lib::sym_exec::sym_exec::generate_test_cases_for_function;
+use sierra_analyzer_lib::sym_exec::snforge::{
+    abi_constructor_parameters, abi_interface_functions, snforge_tests, EntryPointTestCases,
+    SnforgeContract, TestCase,
+};
+use sierra_analyzer_lib::sym_exec::sym_exec::{
+    generate_test_cases_for_function, get_integers_inputs,
+};
 
 /// Struct representing the fuzzing data
 #[derive(Serialize)]
@@ -35,52 +44,16 @@ struct ValueData {
 fn print_function_names(decompiler: &sierra_analyzer_lib::decompiler::decompiler::Decompiler) {
     println!("Available functions:");
     for function in &decompiler.functions {
-        if let Some(prototype) = &function.prototype {
-            let function_name = extract_function_name(prototype);
+        if let Some(function_name) = function.prototype_name() {
             println!("\t- {}", function_name);
         }
     }
 }
 
-/// Extracts the function name from the prototype string
-fn extract_function_name(prototype: &str) -> String {
-    let stripped_prototype = &prototype[5..];
-    if let Some(first_space_index) = stripped_prototype.find('(') {
-        return stripped_prototype[..first_space_index].trim().to_string();
-    }
-    String::new()
-}
-
-/// Parses the result of generate_test_cases_for_function and returns a vector of vectors of integer inputs
-fn get_integers_inputs(test_cases: &str) -> Vec<Vec<i64>> {
-    let unique_results: HashSet<String> = test_cases.lines().map(|line| line.to_string()).collect();
-    unique_results
-        .iter()
-        .map(|line| parse_line_inputs(line))
-        .collect()
-}
-
-/// Parses a single line of test cases and returns a vector of integer inputs
-fn parse_line_inputs(line: &str) -> Vec<i64> {
-    let parts: Vec<&str> = line.split(", ").collect();
-    parts
-        .iter()
-        .filter_map(|part| {
-            let key_value: Vec<&str> = part.split(": ").collect();
-            if key_value.len() == 2 {
-                if let Ok(value) = i64::from_str(key_value[1]) {
-                    return Some(value);
-                }
-            }
-            None
-        })
-        .collect()
-}
-
 /// Generates the fuzzing data for a given function
 fn generate_fuzzing_data(
     function: &mut sierra_analyzer_lib::decompiler::function::Function,
-    declared_libfuncs_names: Vec<String>,
+    declared_libfuncs_names: &[Arc<str>],
     workspace: &str,
     path: &str,
     name: &str,
@@ -100,6 +73,123 @@ fn generate_fuzzing_data(
     }
 }
 
+/// Contracts of a Scarb package, listed in its `<package>.starknet_artifacts.json` file
+#[derive(Deserialize)]
+struct StarknetArtifacts {
+    contracts: Vec<StarknetArtifact>,
+}
+
+/// A contract of the Scarb artifacts file
+#[derive(Deserialize)]
+struct StarknetArtifact {
+    contract_name: String,
+    artifacts: ContractArtifacts,
+}
+
+/// Files of a contract, in the directory of the artifacts file
+#[derive(Deserialize)]
+struct ContractArtifacts {
+    sierra: String,
+}
+
+/// Generates the snforge tests of the contracts of a Scarb project in `tests/generated_symbolic.cairo`
+/// The contracts are read from the dev profile artifacts, returns the path of the tests file
+fn generate_snforge_tests(project_dir: &Path) -> Result<PathBuf, String> {
+    let dev_dir = project_dir.join("target").join("dev");
+    let entries = fs::read_dir(&dev_dir).map_err(|e| {
+        format!(
+            "Error reading {}: {}, run scarb build first",
+            dev_dir.display(),
+            e
+        )
+    })?;
+    let mut artifacts_files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.to_string_lossy().ends_with(".starknet_artifacts.json"))
+        .collect();
+    artifacts_files.sort();
+    if artifacts_files.is_empty() {
+        return Err(format!(
+            "No Starknet artifacts in {}, enable the starknet-contract target & run scarb build",
+            dev_dir.display()
+        ));
+    }
+
+    let mut contracts = Vec::new();
+    for artifacts_file in &artifacts_files {
+        let content = fs::read_to_string(artifacts_file)
+            .map_err(|e| format!("Error reading {}: {}", artifacts_file.display(), e))?;
+        let artifacts: StarknetArtifacts = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid artifacts file {}: {}", artifacts_file.display(), e))?;
+
+        for artifact in artifacts.contracts {
+            contracts.push(snforge_contract(
+                &dev_dir.join(&artifact.artifacts.sierra),
+                artifact.contract_name,
+            )?);
+        }
+    }
+
+    let tests_dir = project_dir.join("tests");
+    fs::create_dir_all(&tests_dir)
+        .map_err(|e| format!("Error creating {}: {}", tests_dir.display(), e))?;
+    let tests_file = tests_dir.join("generated_symbolic.cairo");
+    fs::write(&tests_file, snforge_tests(&contracts))
+        .map_err(|e| format!("Error writing {}: {}", tests_file.display(), e))?;
+
+    Ok(tests_file)
+}
+
+/// Generates the test cases of the entry points declared by the interfaces of a contract class
+/// The entry points inlined in their wrapper have no implementation to execute & are skipped
+fn snforge_contract(contract_class_file: &Path, name: String) -> Result<SnforgeContract, String> {
+    let program = SierraProgram::from_file(contract_class_file)
+        .map_err(|e| format!("Error loading {}: {}", contract_class_file.display(), e))?;
+    let abi = program
+        .abi
+        .clone()
+        .ok_or_else(|| format!("The contract class of {} has no ABI", name))?;
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let entry_points_table = EntryPointsTable::new(&decompiler.functions, Some(&abi));
+    let declared_libfuncs_names = decompiler.declared_libfuncs_names.clone();
+
+    let mut entry_points = Vec::new();
+    for (interface, entry_point, parameters) in abi_interface_functions(&abi) {
+        let Some(implementation) = entry_points_table
+            .entries
+            .iter()
+            .find(|entry| entry.name == entry_point)
+            .and_then(|entry| entry.function.clone())
+        else {
+            continue;
+        };
+        let Some(function) = decompiler
+            .functions
+            .iter_mut()
+            .find(|function| parse_element_name!(function.function.id) == implementation)
+        else {
+            continue;
+        };
+
+        let test_cases = generate_test_cases_for_function(function, &declared_libfuncs_names);
+        entry_points.push(EntryPointTestCases {
+            interface,
+            entry_point,
+            parameters,
+            test_cases: TestCase::parse_all(&test_cases),
+        });
+    }
+
+    Ok(SnforgeContract {
+        name,
+        constructor_parameters: abi_constructor_parameters(&abi),
+        entry_points,
+    })
+}
+
 /// Converts integer inputs to the desired JSON format
 fn convert_integer_inputs_to_values(integer_inputs: Vec<Vec<i64>>) -> Vec<Vec<Value>> {
     integer_inputs
@@ -117,27 +207,42 @@ fn convert_integer_inputs_to_values(integer_inputs: Vec<Vec<i64>>) -> Vec<Vec<Va
 
 /// Main function to handle command-line arguments and generate fuzzing data
 fn main() {
+    // The logs are written to stderr, the fuzzing data to stdout
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        eprintln!("Error: Please provide a file path as an argument.");
+        error!("Please provide a file path as an argument.");
         process::exit(1);
     }
 
+    // Generate the snforge tests of a Scarb project, in the current directory by default
+    if args[1] == "--snforge" {
+        let project_dir = Path::new(args.get(2).map_or(".", String::as_str));
+        match generate_snforge_tests(project_dir) {
+            Ok(tests_file) => println!("Tests written to {}", tests_file.display()),
+            Err(e) => {
+                error!("{}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     let file_path = &args[1];
 
-    // Read the content of the Sierra program file
-    let content = match fs::read_to_string(file_path) {
-        Ok(content) => content,
+    // Load the Sierra program or the contract class
+    let program = match SierraProgram::from_file(Path::new(file_path)) {
+        Ok(program) => program,
         Err(e) => {
-            eprintln!("Error reading file {}: {}", file_path, e);
+            error!("Error loading file {}: {}", file_path, e);
             process::exit(1);
         }
     };
 
-    // Initialize a new SierraProgram with the content of the .sierra file
-    let program = SierraProgram::new(content);
-
     // Disable verbose output for the decompiler
     let verbose_output = false;
 
@@ -157,29 +262,26 @@ fn main() {
         let mut found = false;
 
         for function in &mut decompiler.functions {
-            if let Some(prototype) = &function.prototype {
-                let name = extract_function_name(prototype);
-                if name == *function_name {
-                    let fuzzing_data = generate_fuzzing_data(
-                        function,
-                        decompiler.declared_libfuncs_names.clone(),
-                        "fuzzer_workspace",
-                        "input_file",
-                        "Fuzz_one",
-                    );
-
-                    // Serialize the data to JSON and print it
-                    let json_output = serde_json::to_string_pretty(&fuzzing_data).unwrap();
-                    println!("{}", json_output);
-
-                    found = true;
-                    break;
-                }
+            if function.prototype_name() == Some(function_name.as_str()) {
+                let fuzzing_data = generate_fuzzing_data(
+                    function,
+                    &decompiler.declared_libfuncs_names,
+                    "fuzzer_workspace",
+                    "input_file",
+                    "Fuzz_one",
+                );
+
+                // Serialize the data to JSON and print it
+                let json_output = serde_json::to_string_pretty(&fuzzing_data).unwrap();
+                println!("{}", json_output);
+
+                found = true;
+                break;
             }
         }
 
         if !found {
-            eprintln!("Error: Function '{}' not found.", function_name);
+            error!("Function '{}' not found.", function_name);
             process::exit(1);
         }
     }
@@ -0,0 +1,36 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Embeds the versions of the dependencies & the build target in the binary, for `--version-info`
+fn main() {
+    let lockfile = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lockfile.display());
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let lockfile = fs::read_to_string(lockfile).unwrap_or_default();
+    for (package, variable) in [
+        ("cairo-lang-sierra", "CAIRO_LANG_SIERRA_VERSION"),
+        ("z3", "Z3_CRATE_VERSION"),
+    ] {
+        let version = locked_version(&lockfile, package).unwrap_or("unknown");
+        println!("cargo:rustc-env={}={}", variable, version);
+    }
+
+    for (build_variable, variable) in [("TARGET", "BUILD_TARGET"), ("PROFILE", "BUILD_PROFILE")] {
+        let value = env::var(build_variable).unwrap_or_else(|_| "unknown".to_string());
+        println!("cargo:rustc-env={}={}", variable, value);
+    }
+}
+
+/// Returns the version of a package in the content of a Cargo.lock file
+fn locked_version<'a>(lockfile: &'a str, package: &str) -> Option<&'a str> {
+    let name = format!("name = \"{}\"", package);
+    let mut lines = lockfile.lines();
+
+    lines.find(|line| *line == name)?;
+    lines
+        .next()?
+        .strip_prefix("version = \"")?
+        .strip_suffix('"')
+}
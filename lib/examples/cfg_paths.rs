@@ -1,4 +1,4 @@
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::SierraProgram;
 
 fn main() {
     let content = include_str!("../../examples/sierra/fib_match.sierra").to_string();
@@ -18,9 +18,8 @@ fn main() {
 
     // Print the number of paths in the first function
     // It should be 10 in examples::fib_match::fib function
-    decompiler.functions[0].create_cfg();
     println!(
         "Number of possible paths : {:#?}",
-        decompiler.functions[0].cfg.as_ref().unwrap().paths().len()
+        decompiler.functions[0].cfg().paths().len()
     );
 }
@@ -1,4 +1,4 @@
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::SierraProgram;
 
 fn main() {
     let content = include_str!("../../examples/sierra/fib.sierra").to_string();
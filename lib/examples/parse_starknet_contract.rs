@@ -1,6 +1,6 @@
 use sierra_analyzer_lib::provider::NetworkConfig;
 use sierra_analyzer_lib::provider::RpcClient;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::SierraProgram;
 
 use cairo_lang_starknet_classes::contract_class::ContractClass;
 
@@ -13,7 +13,13 @@ async fn main() {
     match client.get_class(contract_class).await {
         Ok(response) => {
             // Convert RpcClient response to JSON content
-            let content = response.to_json();
+            let content = match response.to_json() {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("Error reading the RPC response: {}", e);
+                    return;
+                }
+            };
 
             // Deserialize JSON into a ContractClass
             let program_string = serde_json::from_str::<ContractClass>(&content)
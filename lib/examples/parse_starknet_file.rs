@@ -1,5 +1,5 @@
 use cairo_lang_starknet_classes::contract_class::ContractClass;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::SierraProgram;
 
 fn main() {
     // Read the JSON content from the file
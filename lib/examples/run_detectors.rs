@@ -1,5 +1,4 @@
-use sierra_analyzer_lib::detectors::get_detectors;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::{get_detectors, SierraProgram};
 
 fn main() {
     let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
@@ -15,6 +14,9 @@ fn main() {
     let use_color = true;
     decompiler.decompile(use_color);
 
+    // The detectors run against the read-only analysis context
+    let context = decompiler.analysis_context();
+
     // Get the detectors list
     let mut detectors = get_detectors();
 
@@ -23,7 +25,7 @@ fn main() {
 
     // Run all the detectors
     for detector in detectors.iter_mut() {
-        let result = detector.detect(&mut decompiler);
+        let result = detector.detect(&context);
         if !result.trim().is_empty() {
             // Each detector output is formatted like
             //
@@ -1,6 +1,5 @@
-use sierra_analyzer_lib::detectors::detector::Detector;
 use sierra_analyzer_lib::detectors::strings_detector::StringsDetector;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::{Detector, SierraProgram};
 
 fn main() {
     let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
@@ -20,5 +19,5 @@ fn main() {
     let mut detector = StringsDetector::new();
 
     // Print the detected strings
-    println!("{}", detector.detect(&mut decompiler));
+    println!("{}", detector.detect(&decompiler.analysis_context()));
 }
@@ -1,5 +1,5 @@
-use sierra_analyzer_lib::sierra_program::SierraProgram;
 use sierra_analyzer_lib::sym_exec::sym_exec::generate_test_cases_for_function;
+use sierra_analyzer_lib::SierraProgram;
 
 fn main() {
     // Read the content of the Sierra program file
@@ -28,10 +28,8 @@ fn main() {
     // Generate test cases for the `symbolic::symbolic::symbolic_execution_test` function
     // This should return the input values that maximize code coverage:
     // ["v0: 102", "v1: 117", "v2: 122", "v3: 122"]
-    let test_cases = generate_test_cases_for_function(
-        &mut functions[0],
-        decompiler.declared_libfuncs_names.clone(),
-    );
+    let test_cases =
+        generate_test_cases_for_function(&mut functions[0], &decompiler.declared_libfuncs_names);
 
     // Print the generated test cases
     println!("{}", test_cases);
@@ -0,0 +1,450 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use cairo_lang_sierra::program::GenStatement;
+use serde::Serialize;
+
+use crate::analysis::invariants::{called_function, transitive_callers};
+use crate::analysis::ssa::{PhiNode, SsaForm, SsaStatement, SsaVariable};
+use crate::decompiler::function::{Function, FunctionType, SierraStatement};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::detectors::controlled_library_call_detector::BUILTINS;
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+/// Access-control check performed by an entry point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessCheck {
+    /// The caller address is compared with an address read from the storage
+    Owner,
+    /// A storage value keyed by the caller address (e.g. a role membership) is tested
+    Role,
+    /// No access-control check
+    None,
+}
+
+impl AccessCheck {
+    /// Returns the name of the check, as in the matrix columns
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessCheck::Owner => "owner",
+            AccessCheck::Role => "role",
+            AccessCheck::None => "none",
+        }
+    }
+}
+
+/// A row of the access-control matrix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccessControlEntry {
+    /// ABI name of the entry point
+    pub entry_point: String,
+    /// Name of the compiler wrapper of the entry point
+    pub wrapper: String,
+    /// Functions reachable from the entry point performing an owner check
+    pub owner_checks: Vec<String>,
+    /// Functions reachable from the entry point performing a role check
+    pub role_checks: Vec<String>,
+}
+
+impl AccessControlEntry {
+    /// Returns the checks performed by the entry point, `AccessCheck::None` if there are none
+    pub fn access_checks(&self) -> Vec<AccessCheck> {
+        let mut checks = Vec::new();
+        if !self.owner_checks.is_empty() {
+            checks.push(AccessCheck::Owner);
+        }
+        if !self.role_checks.is_empty() {
+            checks.push(AccessCheck::Role);
+        }
+        if checks.is_empty() {
+            checks.push(AccessCheck::None);
+        }
+        checks
+    }
+}
+
+/// Matrix of the entry points vs. the access-control checks they perform
+/// The checks are found in the functions reachable from the entry points wrappers, using the
+/// flow of the caller address & of the storage values in their SSA form
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AccessControlMatrix {
+    pub entries: Vec<AccessControlEntry>,
+}
+
+impl AccessControlMatrix {
+    /// Builds the matrix of the entry points of the functions
+    pub fn new(functions: &[Function]) -> Self {
+        let callgraph = CallGraph::new(functions);
+        let caller_readers = transitive_callers(&callgraph, functions, LibfuncKind::CallerAddress);
+        let storage_readers = transitive_callers(&callgraph, functions, LibfuncKind::StorageRead);
+
+        // Checks performed by each function, without its callees
+        let checks: HashMap<String, HashSet<AccessCheck>> = functions
+            .iter()
+            .map(|function| {
+                (
+                    parse_element_name!(function.function.id),
+                    function_checks(function, &caller_readers, &storage_readers),
+                )
+            })
+            .collect();
+
+        let mut entries = Vec::new();
+        for function in functions {
+            if !matches!(function.function_type, Some(FunctionType::Wrapper)) {
+                continue;
+            }
+
+            let wrapper = parse_element_name!(function.function.id);
+            let Some(entry_point) = wrapper_entry_point_name(&wrapper) else {
+                continue;
+            };
+
+            let mut reachable: Vec<String> = callgraph
+                .reachable_from(&[wrapper.clone()])
+                .into_iter()
+                .filter(|name| checks.contains_key(name))
+                .collect();
+            reachable.sort();
+
+            let performing = |check: AccessCheck| -> Vec<String> {
+                reachable
+                    .iter()
+                    .filter(|name| checks[*name].contains(&check))
+                    .cloned()
+                    .collect()
+            };
+
+            entries.push(AccessControlEntry {
+                entry_point,
+                owner_checks: performing(AccessCheck::Owner),
+                role_checks: performing(AccessCheck::Role),
+                wrapper,
+            });
+        }
+
+        Self { entries }
+    }
+
+    /// Returns the matrix as a text table, one row per entry point
+    pub fn to_text(&self) -> String {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.entry_point.len())
+            .chain(std::iter::once("Entry point".len()))
+            .max()
+            .unwrap_or_default();
+
+        let mut text = format!("{:<width$}  Owner  Role  None\n", "Entry point");
+        for entry in &self.entries {
+            let marks = matrix_marks(entry, "x");
+            let row = format!(
+                "{:<width$}  {:<5}  {:<4}  {}",
+                entry.entry_point, marks[0], marks[1], marks[2]
+            );
+            text += row.trim_end();
+            text += "\n";
+        }
+
+        text
+    }
+
+    /// Returns the matrix as a Markdown table, one row per entry point
+    pub fn to_markdown(&self) -> String {
+        let mut markdown =
+            "| Entry point | Owner | Role | None |\n|---|:---:|:---:|:---:|\n".to_string();
+        for entry in &self.entries {
+            let marks = matrix_marks(entry, "✓");
+            markdown += &format!(
+                "| `{}` | {} | {} | {} |\n",
+                entry.entry_point, marks[0], marks[1], marks[2]
+            );
+        }
+
+        markdown
+    }
+
+    /// Returns the matrix in JSON, with the functions performing the checks
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.entries).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+impl fmt::Display for AccessControlMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+/// Returns the marks of the owner, role & none columns of an entry point
+fn matrix_marks<'a>(entry: &AccessControlEntry, mark: &'a str) -> [&'a str; 3] {
+    let checks = entry.access_checks();
+    [AccessCheck::Owner, AccessCheck::Role, AccessCheck::None].map(|check| {
+        if checks.contains(&check) {
+            mark
+        } else {
+            ""
+        }
+    })
+}
+
+/// Origins of a value, propagated from the arguments to the results of the statements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Taint {
+    /// Derived from the caller address
+    caller: bool,
+    /// Derived from a storage value
+    storage: bool,
+    /// Derived from a storage value read at an address computed from the caller address
+    keyed: bool,
+}
+
+impl Taint {
+    fn union(self, other: Taint) -> Taint {
+        Taint {
+            caller: self.caller || other.caller,
+            storage: self.storage || other.storage,
+            keyed: self.keyed || other.keyed,
+        }
+    }
+}
+
+/// Returns the access-control checks performed by a function
+/// A check is a branch (zero check, equality or boolean match) on a value derived from both the
+/// caller address & a storage value (owner), or from a storage value keyed by the caller (role)
+fn function_checks(
+    function: &Function,
+    caller_readers: &HashSet<String>,
+    storage_readers: &HashSet<String>,
+) -> HashSet<AccessCheck> {
+    let mut checks = HashSet::new();
+
+    let Ok(ssa) = function.ssa() else {
+        return checks;
+    };
+    let builtins = builtin_variables(function, &ssa);
+    let mut taints: HashMap<SsaVariable, Taint> = HashMap::new();
+
+    // The statements are evaluated until the taints of the loops are stable
+    propagate_taints(
+        function,
+        &ssa,
+        &mut taints,
+        |taints, phi| {
+            let taint = phi
+                .operands
+                .values()
+                .flatten()
+                .fold(Taint::default(), |taint, operand| {
+                    taint.union(taints.get(operand).copied().unwrap_or_default())
+                });
+            update_taint(taints, phi.result, taint)
+        },
+        |taints, statement, ssa_statement| {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                return false;
+            };
+            let libfunc_name = parse_element_name!(invocation.libfunc_id);
+
+            // The builtins (gas, system...) are threaded through the calls, they don't carry values
+            let arguments = ssa_statement
+                .uses
+                .iter()
+                .filter(|variable| !builtins.contains(*variable))
+                .fold(Taint::default(), |taint, variable| {
+                    taint.union(taints.get(variable).copied().unwrap_or_default())
+                });
+
+            let callee = called_function(&libfunc_name);
+            let reads_caller = statement.libfunc_kind == LibfuncKind::CallerAddress
+                || callee
+                    .as_ref()
+                    .is_some_and(|callee| caller_readers.contains(callee));
+            let reads_storage = statement.libfunc_kind == LibfuncKind::StorageRead
+                || callee
+                    .as_ref()
+                    .is_some_and(|callee| storage_readers.contains(callee));
+
+            let mut taint = arguments;
+            taint.caller |= reads_caller;
+            taint.storage |= reads_storage;
+            taint.keyed |= reads_storage && arguments.caller;
+
+            let mut changed = false;
+            for result in ssa_statement.definitions.iter().flatten() {
+                if !builtins.contains(result) {
+                    changed |= update_taint(taints, *result, taint);
+                }
+            }
+            changed
+        },
+    );
+
+    // The branches on the tainted values
+    for statement in function.statements() {
+        if !statement.libfunc_kind.is_condition() {
+            continue;
+        }
+        let Some(ssa_statement) = ssa.statement(statement.offset) else {
+            continue;
+        };
+
+        let taint = ssa_statement
+            .uses
+            .iter()
+            .filter(|variable| !builtins.contains(*variable))
+            .fold(Taint::default(), |taint, variable| {
+                taint.union(taints.get(variable).copied().unwrap_or_default())
+            });
+        if taint.keyed {
+            checks.insert(AccessCheck::Role);
+        } else if taint.caller && taint.storage {
+            checks.insert(AccessCheck::Owner);
+        }
+    }
+
+    checks
+}
+
+/// Merges a taint into the taint of a variable, returns true if it changed
+fn update_taint(
+    taints: &mut HashMap<SsaVariable, Taint>,
+    variable: SsaVariable,
+    taint: Taint,
+) -> bool {
+    let current = taints.get(&variable).copied().unwrap_or_default();
+    let merged = current.union(taint);
+    if merged == current {
+        return false;
+    }
+    taints.insert(variable, merged);
+    true
+}
+
+/// Evaluates the phi functions & the statements of a function until the taints of the loops are
+/// stable, the evaluations update the taints & return true if they changed
+pub(crate) fn propagate_taints<T>(
+    function: &Function,
+    ssa: &SsaForm,
+    taints: &mut T,
+    mut evaluate_phi: impl FnMut(&mut T, &PhiNode) -> bool,
+    mut evaluate_statement: impl FnMut(&mut T, &SierraStatement, &SsaStatement) -> bool,
+) {
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for (_, phi) in ssa.all_phis() {
+            changed |= evaluate_phi(taints, phi);
+        }
+
+        for statement in function.statements() {
+            let Some(ssa_statement) = ssa.statement(statement.offset) else {
+                continue;
+            };
+            changed |= evaluate_statement(taints, statement, ssa_statement);
+        }
+    }
+}
+
+/// Taints the results (except the builtins) of the statements using a tainted variable or for
+/// which `is_source` is true, until the taints of the loops are stable
+pub(crate) fn propagate_tainted_variables(
+    function: &Function,
+    ssa: &SsaForm,
+    builtins: &HashSet<SsaVariable>,
+    tainted: &mut HashSet<SsaVariable>,
+    is_source: impl Fn(&SierraStatement) -> bool,
+) {
+    propagate_taints(
+        function,
+        ssa,
+        tainted,
+        |tainted, phi| {
+            phi.operands
+                .values()
+                .flatten()
+                .any(|operand| tainted.contains(operand))
+                && tainted.insert(phi.result)
+        },
+        |tainted, statement, ssa_statement| {
+            if !is_source(statement)
+                && !ssa_statement
+                    .uses
+                    .iter()
+                    .any(|variable| !builtins.contains(variable) && tainted.contains(variable))
+            {
+                return false;
+            }
+
+            let mut changed = false;
+            for result in ssa_statement.definitions.iter().flatten() {
+                if !builtins.contains(result) {
+                    changed |= tainted.insert(*result);
+                }
+            }
+            changed
+        },
+    );
+}
+
+/// Returns the variables holding a builtin (range check, gas, system...)
+/// The builtins are the first parameters of a function, the libfuncs & the functions taking
+/// builtins return them first in the same order
+pub(crate) fn builtin_variables(function: &Function, ssa: &SsaForm) -> HashSet<SsaVariable> {
+    let mut builtins: HashSet<SsaVariable> = ssa
+        .parameters()
+        .iter()
+        .zip(&function.function.params)
+        .enumerate()
+        .filter(|(index, (_, param))| {
+            let type_name = param
+                .ty
+                .debug_name
+                .as_ref()
+                .map(|name| name.to_string())
+                .or_else(|| {
+                    function
+                        .arguments
+                        .get(*index)
+                        .map(|(_, type_name)| type_name.clone())
+                });
+            type_name.is_some_and(|type_name| BUILTINS.contains(&type_name.as_str()))
+        })
+        .map(|(_, (parameter, _))| *parameter)
+        .collect();
+
+    propagate_taints(
+        function,
+        ssa,
+        &mut builtins,
+        |builtins, phi| {
+            phi.operands
+                .values()
+                .flatten()
+                .any(|operand| builtins.contains(operand))
+                && builtins.insert(phi.result)
+        },
+        |builtins, _, ssa_statement| {
+            let leading_builtins = ssa_statement
+                .uses
+                .iter()
+                .take_while(|variable| builtins.contains(*variable))
+                .count();
+
+            let mut changed = false;
+            for results in &ssa_statement.definitions {
+                for result in results.iter().take(leading_builtins) {
+                    changed |= builtins.insert(*result);
+                }
+            }
+            changed
+        },
+    );
+
+    builtins
+}
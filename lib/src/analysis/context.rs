@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+
+use crate::analysis::session::AnalysisSession;
+use crate::decompiler::decompiler::Decompiler;
+use crate::decompiler::function::Function;
+use crate::error::Result;
+use crate::sierra_program::{ProgramKind, SierraProgram};
+
+/// Read-only context the detectors run against
+/// It gives access to the parsed program and to the shared analysis results,
+/// so the detectors don't need a mutable decompiler and can run concurrently
+#[derive(Clone)]
+pub struct AnalysisContext<'a> {
+    /// The analyzed Sierra program
+    sierra_program: &'a SierraProgram,
+    /// Functions, CFGs & callgraph of the program
+    session: AnalysisSession<'a>,
+    /// Name the reported functions prototypes contain, all the functions are reported if None
+    function_filter: Option<String>,
+}
+
+impl<'a> AnalysisContext<'a> {
+    /// Analyzes a Sierra program and creates its context
+    pub fn new(sierra_program: &'a SierraProgram) -> Result<Self> {
+        Ok(Self {
+            sierra_program,
+            session: AnalysisSession::new(sierra_program)?,
+            function_filter: None,
+        })
+    }
+
+    /// Creates the context of the functions loaded by a decompiler
+    pub fn from_decompiler(decompiler: &Decompiler<'a>) -> Self {
+        Self {
+            sierra_program: decompiler.sierra_program,
+            session: AnalysisSession::from_decompiler(decompiler),
+            function_filter: None,
+        }
+    }
+
+    /// Restricts the detectors reports to the functions whose prototype contains the given name
+    /// The whole program is still analyzed, e.g. the callees of the reported functions
+    pub fn with_function_filter(mut self, function_name: &str) -> Self {
+        self.function_filter = Some(function_name.to_string());
+        self
+    }
+
+    /// Returns a reference to the parsed Sierra program
+    #[inline]
+    pub fn program(&self) -> &Program {
+        self.sierra_program.program()
+    }
+
+    /// Returns a reference to the program registry, if available
+    #[inline]
+    pub fn registry(&self) -> Option<&ProgramRegistry<CoreType, CoreLibfunc>> {
+        self.sierra_program.registry()
+    }
+
+    /// Returns the analysis session of the program
+    #[inline]
+    pub fn session(&self) -> &AnalysisSession<'a> {
+        &self.session
+    }
+
+    /// Returns the kind of the analyzed program
+    #[inline]
+    pub fn kind(&self) -> ProgramKind {
+        self.session.kind()
+    }
+
+    /// Returns the program functions
+    #[inline]
+    pub fn functions(&self) -> &[Function<'a>] {
+        self.session.functions()
+    }
+
+    /// Checks if the detectors report the findings of a function
+    pub fn is_in_scope(&self, function: &Function) -> bool {
+        match &self.function_filter {
+            Some(function_name) => function
+                .prototype
+                .as_ref()
+                .is_some_and(|prototype| prototype.contains(function_name.as_str())),
+            None => true,
+        }
+    }
+
+    /// Returns the functions reported by the detectors
+    pub fn scoped_functions(&self) -> impl Iterator<Item = &Function<'a>> {
+        self.functions()
+            .iter()
+            .filter(|function| self.is_in_scope(function))
+    }
+
+    /// Returns the functions that are defined by the user
+    #[inline]
+    pub fn user_defined_functions(&self) -> impl Iterator<Item = &Function<'a>> {
+        self.session.user_defined_functions()
+    }
+
+    /// Returns the names of all declared types (in order)
+    #[inline]
+    pub fn declared_types_names(&self) -> &[Arc<str>] {
+        self.session.declared_types_names()
+    }
+
+    /// Returns the names of all declared libfuncs (in order)
+    #[inline]
+    pub fn declared_libfuncs_names(&self) -> &[Arc<str>] {
+        self.session.declared_libfuncs_names()
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::decompiler::function::Function;
+
+/// Site where a variable is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Definition {
+    /// The variable is a parameter of the function
+    Parameter,
+    /// The variable is a result of the statement at the given offset
+    Statement(u32),
+}
+
+/// Definitions & uses of a single variable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefUse {
+    /// Definition sites, in the statements order
+    /// A Sierra variable can be redefined (e.g. `store_temp<felt252>([0]) -> ([0])`)
+    pub definitions: Vec<Definition>,
+    /// Offsets of the statements using the variable, in the statements order
+    pub uses: Vec<u32>,
+}
+
+/// Statement-level def-use chains of a function
+#[derive(Debug, Clone, Default)]
+pub struct DataFlow {
+    /// Def-use chains indexed by variable ID
+    variables: BTreeMap<u64, DefUse>,
+}
+
+impl DataFlow {
+    /// Computes the def-use chains of a function
+    pub fn new(function: &Function) -> Self {
+        let mut dataflow = Self::default();
+
+        // The function parameters are defined at the function entry
+        for param in &function.function.params {
+            dataflow.add_definition(param.id.id, Definition::Parameter);
+        }
+
+        for statement in function.statements() {
+            match &statement.statement {
+                GenStatement::Invocation(invocation) => {
+                    // Arguments are used by the invocation
+                    for arg in &invocation.args {
+                        dataflow.add_use(arg.id, statement.offset);
+                    }
+
+                    // Each branch defines its own results
+                    for branch in &invocation.branches {
+                        for result in &branch.results {
+                            dataflow
+                                .add_definition(result.id, Definition::Statement(statement.offset));
+                        }
+                    }
+                }
+                GenStatement::Return(returned_variables) => {
+                    for variable in returned_variables {
+                        dataflow.add_use(variable.id, statement.offset);
+                    }
+                }
+            }
+        }
+
+        dataflow
+    }
+
+    /// Adds a definition site to a variable, each site is only stored once
+    fn add_definition(&mut self, var_id: u64, definition: Definition) {
+        let def_use = self.variables.entry(var_id).or_default();
+        if !def_use.definitions.contains(&definition) {
+            def_use.definitions.push(definition);
+        }
+    }
+
+    /// Adds a use site to a variable, each site is only stored once
+    fn add_use(&mut self, var_id: u64, offset: u32) {
+        let def_use = self.variables.entry(var_id).or_default();
+        if !def_use.uses.contains(&offset) {
+            def_use.uses.push(offset);
+        }
+    }
+
+    /// Returns the def-use chains of a variable
+    #[inline]
+    pub fn get(&self, var_id: u64) -> Option<&DefUse> {
+        self.variables.get(&var_id)
+    }
+
+    /// Returns the definition sites of a variable
+    pub fn definitions(&self, var_id: u64) -> &[Definition] {
+        self.variables
+            .get(&var_id)
+            .map(|def_use| def_use.definitions.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the offsets of the statements using a variable
+    pub fn uses(&self, var_id: u64) -> &[u32] {
+        self.variables
+            .get(&var_id)
+            .map(|def_use| def_use.uses.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the def-use chains of all the variables, ordered by variable ID
+    pub fn variables(&self) -> impl Iterator<Item = (u64, &DefUse)> {
+        self.variables
+            .iter()
+            .map(|(var_id, def_use)| (*var_id, def_use))
+    }
+
+    /// Returns the IDs of the variables that are defined but never used
+    pub fn unused_variables(&self) -> Vec<u64> {
+        self.variables()
+            .filter(|(_, def_use)| !def_use.definitions.is_empty() && def_use.uses.is_empty())
+            .map(|(var_id, _)| var_id)
+            .collect()
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+use cairo_lang_starknet_classes::abi::Contract;
+use serde::Serialize;
+use starknet_core::utils::starknet_keccak;
+
+use crate::analysis::invariants::{called_functions, transitive_callers};
+use crate::decompiler::decompiler::abi_function_inputs;
+use crate::decompiler::function::{Function, FunctionType};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::detectors::controlled_library_call_detector::BUILTINS;
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+/// Kind of an entry point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryPointKind {
+    External,
+    View,
+    Constructor,
+    L1Handler,
+}
+
+impl EntryPointKind {
+    /// Returns the kind of an entry point function given by the ABI, None if it is not an entry point
+    pub fn from_function_type(function_type: &FunctionType) -> Option<Self> {
+        match function_type {
+            FunctionType::External => Some(EntryPointKind::External),
+            FunctionType::View => Some(EntryPointKind::View),
+            FunctionType::Constructor => Some(EntryPointKind::Constructor),
+            FunctionType::L1Handler => Some(EntryPointKind::L1Handler),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the kind, as in the table column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryPointKind::External => "external",
+            EntryPointKind::View => "view",
+            EntryPointKind::Constructor => "constructor",
+            EntryPointKind::L1Handler => "l1_handler",
+        }
+    }
+}
+
+/// A row of the entry points table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntryPoint {
+    /// ABI name of the entry point
+    pub name: String,
+    /// Name of the compiler wrapper of the entry point
+    pub wrapper: String,
+    /// Name of the function implementing the entry point, if it is found
+    pub function: Option<String>,
+    /// Kind of the entry point
+    pub kind: EntryPointKind,
+    /// Selector of the entry point, the starknet keccak of its name
+    pub selector: String,
+    /// Types of the parameters, without the builtins & the contract state
+    pub parameters: Vec<String>,
+    /// True if the entry point writes the storage, directly or through its callees
+    pub writes_storage: bool,
+}
+
+impl EntryPoint {
+    /// Returns the state mutability inferred from the storage writes
+    pub fn mutability(&self) -> &'static str {
+        if self.writes_storage {
+            "mutable"
+        } else {
+            "read-only"
+        }
+    }
+}
+
+/// Table of the external, view, constructor & l1_handler functions of a contract
+/// The entry points are found from their compiler wrappers, listed by entry offset. Their kind is
+/// read from the ABI, the external & view functions are told apart by their storage writes without it.
+/// The parameters are read from the ABI or from the implementation function when it isn't inlined
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EntryPointsTable {
+    pub entries: Vec<EntryPoint>,
+}
+
+impl EntryPointsTable {
+    /// Builds the table of the entry points of the functions, with the ABI of the contract class if any
+    pub fn new(functions: &[Function], abi: Option<&Contract>) -> Self {
+        let callgraph = CallGraph::new(functions);
+        let storage_writers = transitive_callers(&callgraph, functions, LibfuncKind::StorageWrite);
+
+        let mut entries = Vec::new();
+        for wrapper_function in functions {
+            if !matches!(wrapper_function.function_type, Some(FunctionType::Wrapper)) {
+                continue;
+            }
+
+            let wrapper = parse_element_name!(wrapper_function.function.id);
+            let Some(name) = wrapper_entry_point_name(&wrapper) else {
+                continue;
+            };
+
+            // The implementation is the function called by the wrapper with the entry point name
+            let implementation = called_functions(wrapper_function)
+                .iter()
+                .find_map(|callee| {
+                    functions.iter().find(|function| {
+                        parse_element_name!(function.function.id) == *callee
+                            && wrapper_entry_point_name(callee).as_ref() == Some(&name)
+                    })
+                });
+
+            let writes_storage = storage_writers.contains(&wrapper);
+            let kind = implementation
+                .and_then(|function| function.function_type.as_ref())
+                .and_then(EntryPointKind::from_function_type)
+                .unwrap_or_else(|| {
+                    if name == "constructor" || wrapper.contains("::__constructor::") {
+                        EntryPointKind::Constructor
+                    } else if wrapper.contains("::__l1_handler::") {
+                        EntryPointKind::L1Handler
+                    } else if writes_storage {
+                        EntryPointKind::External
+                    } else {
+                        EntryPointKind::View
+                    }
+                });
+
+            let parameters = abi
+                .and_then(|abi| abi_function_inputs(abi, &name))
+                .or_else(|| implementation.map(entry_point_parameters))
+                .unwrap_or_default();
+
+            entries.push(EntryPoint {
+                selector: format!("{:#x}", starknet_keccak(name.as_bytes())),
+                name,
+                kind,
+                parameters,
+                writes_storage,
+                function: implementation.map(|function| parse_element_name!(function.function.id)),
+                wrapper,
+            });
+        }
+
+        Self { entries }
+    }
+
+    /// Returns the table as text, one row per entry point
+    pub fn to_text(&self) -> String {
+        let column_width = |header: &str, value: fn(&EntryPoint) -> usize| {
+            self.entries
+                .iter()
+                .map(value)
+                .chain(std::iter::once(header.len()))
+                .max()
+                .unwrap_or_default()
+        };
+        let name_width = column_width("Entry point", |entry| entry.name.len());
+        let kind_width = column_width("Kind", |entry| entry.kind.as_str().len());
+        let selector_width = column_width("Selector", |entry| entry.selector.len());
+
+        let mut text = format!(
+            "{:<name_width$}  {:<kind_width$}  {:<selector_width$}  {:<9}  Parameters\n",
+            "Entry point", "Kind", "Selector", "State"
+        );
+        for entry in &self.entries {
+            text += &format!(
+                "{:<name_width$}  {:<kind_width$}  {:<selector_width$}  {:<9}  ({})\n",
+                entry.name,
+                entry.kind.as_str(),
+                entry.selector,
+                entry.mutability(),
+                entry.parameters.join(", ")
+            );
+        }
+
+        text
+    }
+
+    /// Returns the table as a Markdown table, one row per entry point
+    pub fn to_markdown(&self) -> String {
+        let mut markdown =
+            "| Entry point | Kind | Selector | State | Parameters |\n|---|---|---|---|---|\n"
+                .to_string();
+        for entry in &self.entries {
+            markdown += &format!(
+                "| `{}` | {} | `{}` | {} | `({})` |\n",
+                entry.name,
+                entry.kind.as_str(),
+                entry.selector,
+                entry.mutability(),
+                entry.parameters.join(", ")
+            );
+        }
+
+        markdown
+    }
+
+    /// Returns the table in JSON, with the functions implementing the entry points
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.entries).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+/// Returns the types of the parameters of an entry point, without the builtins & the contract state
+fn entry_point_parameters(function: &Function) -> Vec<String> {
+    function
+        .arguments
+        .iter()
+        .map(|(_, arg_type)| arg_type.clone())
+        .filter(|arg_type| {
+            !BUILTINS.contains(&arg_type.as_str()) && !arg_type.ends_with("ContractState")
+        })
+        .collect()
+}
+
+impl fmt::Display for EntryPointsTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
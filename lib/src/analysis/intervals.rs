@@ -0,0 +1,502 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use cairo_lang_sierra::program::{BranchTarget, GenStatement, Invocation};
+use num_bigint::BigInt;
+
+use crate::analysis::ssa::{SsaForm, SsaVariable};
+use crate::decompiler::function::Function;
+use crate::decompiler::libfuncs_patterns::{
+    CONST_REGEXES, COPY_REGEXES, DOWNCAST_REGEX, EQUAL_REGEX, FELT252_OPERATION_REGEX,
+    INTEGER_FROM_FELT252_REGEX, INTEGER_LIBFUNC_REGEX, INTEGER_TYPE_REGEX, IS_ZERO_REGEX,
+    LESS_THAN_OR_EQUAL_REGEX, LESS_THAN_REGEX, OVERFLOWING_OPERATION_REGEX,
+};
+use crate::decompiler::utils::felt252_prime;
+use crate::error::Result;
+use crate::parse_element_name_with_fallback;
+
+/// Number of times the interval of a variable can change before it's widened
+/// It bounds the iterations of the analysis of the loops
+const WIDENING_THRESHOLD: usize = 8;
+
+/// Maximum number of passes over the statements
+const MAX_PASSES: usize = 64;
+
+/// Closed interval of integer values
+/// The felt252 values are represented by their signed representative in [-(P - 1)/2, (P - 1)/2]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub min: BigInt,
+    pub max: BigInt,
+}
+
+impl Interval {
+    /// Creates a new interval
+    pub fn new(min: BigInt, max: BigInt) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates an interval containing a single value
+    pub fn constant(value: BigInt) -> Self {
+        Self::new(value.clone(), value)
+    }
+
+    /// Returns the interval of all the felt252 values
+    pub fn felt252() -> Self {
+        let half: BigInt = felt252_prime() / 2;
+        Self::new(-half.clone(), half)
+    }
+
+    /// Returns the range of an integer type (e.g. `u8`, `i64` or `felt252`)
+    pub fn of_type(type_name: &str) -> Option<Self> {
+        if type_name == "felt252" {
+            return Some(Self::felt252());
+        }
+
+        let captures = INTEGER_TYPE_REGEX.captures(type_name)?;
+        let bits = usize::from_str(&captures["bits"]).ok()?;
+        Some(match &captures["sign"] {
+            "u" => Self::new(BigInt::from(0), (BigInt::from(1) << bits) - 1),
+            _ => Self::new(
+                -(BigInt::from(1) << (bits - 1)),
+                (BigInt::from(1) << (bits - 1)) - 1,
+            ),
+        })
+    }
+
+    /// Returns the smallest interval containing both intervals
+    pub fn join(&self, other: &Interval) -> Interval {
+        Interval::new(
+            self.min.clone().min(other.min.clone()),
+            self.max.clone().max(other.max.clone()),
+        )
+    }
+
+    /// Returns the values of both intervals, None if they are disjoint
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let min = self.min.clone().max(other.min.clone());
+        let max = self.max.clone().min(other.max.clone());
+        (min <= max).then(|| Interval::new(min, max))
+    }
+
+    /// Checks if all the values of an interval are in this one
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.min <= other.min && other.max <= self.max
+    }
+
+    /// Applies an arithmetic operator (`add`, `sub` or `mul`) without wrapping around
+    pub fn apply(&self, operator: &str, other: &Interval) -> Option<Interval> {
+        match operator {
+            "add" => Some(Interval::new(
+                &self.min + &other.min,
+                &self.max + &other.max,
+            )),
+            "sub" => Some(Interval::new(
+                &self.min - &other.max,
+                &self.max - &other.min,
+            )),
+            "mul" => {
+                let products = [
+                    &self.min * &other.min,
+                    &self.min * &other.max,
+                    &self.max * &other.min,
+                    &self.max * &other.max,
+                ];
+                let min = products.iter().min()?.clone();
+                let max = products.iter().max()?.clone();
+                Some(Interval::new(min, max))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.min, self.max)
+    }
+}
+
+/// Value-range analysis of the integer variables of a function
+///
+/// It's an abstract interpretation over the SSA form, the intervals come from the constants,
+/// the integer types (their values are range checked) & the comparisons.
+/// The intervals implied by a branch are valid in the blocks dominated by its target block.
+#[derive(Debug, Clone)]
+pub struct IntervalAnalysis {
+    /// SSA form of the function
+    ssa: SsaForm,
+    /// Interval of each SSA variable
+    intervals: HashMap<SsaVariable, Interval>,
+    /// Intervals implied by the branches, indexed by the start offset of their target block
+    refinements: HashMap<u32, Vec<(SsaVariable, Interval)>>,
+    /// Number of changes of the interval of each variable
+    changes: HashMap<SsaVariable, usize>,
+    /// Variables widened to the felt252 range
+    widened: HashSet<SsaVariable>,
+}
+
+impl IntervalAnalysis {
+    /// Computes the intervals of the variables of a function
+    pub fn new(function: &Function, declared_libfuncs_names: &[Arc<str>]) -> Result<Self> {
+        let mut analysis = Self {
+            ssa: function.ssa()?,
+            intervals: HashMap::new(),
+            refinements: HashMap::new(),
+            changes: HashMap::new(),
+            widened: HashSet::new(),
+        };
+
+        // The parameters are in the range of their type
+        let parameters = analysis.ssa.parameters().to_vec();
+        for (index, parameter) in parameters.into_iter().enumerate() {
+            let interval = function
+                .arguments
+                .get(index)
+                .and_then(|(_, type_name)| Interval::of_type(type_name))
+                .unwrap_or_else(Interval::felt252);
+            analysis.intervals.insert(parameter, interval);
+        }
+
+        // Iterate until the fixed point, the widening bounds the number of passes of the loops
+        for _ in 0..MAX_PASSES {
+            let mut changed = analysis.evaluate_phis();
+
+            for statement in function.statements() {
+                if let GenStatement::Invocation(invocation) = &statement.statement {
+                    let libfunc_name = parse_element_name_with_fallback!(
+                        invocation.libfunc_id,
+                        declared_libfuncs_names
+                    );
+                    changed |=
+                        analysis.evaluate_invocation(statement.offset, invocation, &libfunc_name);
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        Ok(analysis)
+    }
+
+    /// Returns the SSA form the analysis is based on
+    #[inline]
+    pub fn ssa(&self) -> &SsaForm {
+        &self.ssa
+    }
+
+    /// Returns the interval of a variable, regardless of the branches taken
+    #[inline]
+    pub fn interval(&self, variable: SsaVariable) -> Option<&Interval> {
+        self.intervals.get(&variable)
+    }
+
+    /// Returns the interval of an argument of a statement, refined by the branches leading to it
+    pub fn argument_interval(&self, offset: u32, argument: usize) -> Option<Interval> {
+        let statement = self.ssa.statement(offset)?;
+        self.value_at(*statement.uses.get(argument)?, statement.block)
+    }
+
+    /// Checks if the arithmetic operation of a statement can't overflow
+    /// The felt252 operations must not wrap around the prime, the unsigned integers operations
+    /// must stay in the range of their type
+    pub fn proves_no_overflow(&self, offset: u32, libfunc_name: &str) -> bool {
+        let Some(statement) = self.ssa.statement(offset) else {
+            return false;
+        };
+        let arguments_count = statement.uses.len();
+        if arguments_count < 2 {
+            return false;
+        }
+
+        // The operands are the last arguments, after the range check
+        let (operator, range) =
+            if let Some(captures) = FELT252_OPERATION_REGEX.captures(libfunc_name) {
+                (captures["operator"].to_string(), Interval::felt252())
+            } else if let Some(captures) = OVERFLOWING_OPERATION_REGEX.captures(libfunc_name) {
+                let Some(range) = Interval::of_type(&format!("u{}", &captures["bits"])) else {
+                    return false;
+                };
+                (captures["operator"].to_string(), range)
+            } else {
+                return false;
+            };
+
+        let lhs = self.argument_interval(offset, arguments_count - 2);
+        let rhs = self.argument_interval(offset, arguments_count - 1);
+        match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => lhs
+                .apply(&operator, &rhs)
+                .is_some_and(|result| range.contains(&result)),
+            _ => false,
+        }
+    }
+
+    /// Returns the interval of a variable in a block
+    /// The refinements of the dominating branch targets are applied
+    fn value_at(&self, variable: SsaVariable, block: u32) -> Option<Interval> {
+        let mut interval = self.intervals.get(&variable)?.clone();
+
+        let mut current_block = Some(block);
+        while let Some(block) = current_block {
+            for (refined_variable, refinement) in self.refinements.get(&block).into_iter().flatten()
+            {
+                if *refined_variable == variable {
+                    interval = interval.intersect(refinement).unwrap_or(interval);
+                }
+            }
+            current_block = self.ssa.immediate_dominator(block);
+        }
+
+        Some(interval)
+    }
+
+    /// Updates the interval of a variable, returns true if it changed
+    fn update(&mut self, variable: SsaVariable, interval: Interval) -> bool {
+        if self.widened.contains(&variable) || self.intervals.get(&variable) == Some(&interval) {
+            return false;
+        }
+
+        let changes = self.changes.entry(variable).or_insert(0);
+        *changes += 1;
+        if *changes > WIDENING_THRESHOLD {
+            self.widened.insert(variable);
+            self.intervals.insert(variable, Interval::felt252());
+        } else {
+            self.intervals.insert(variable, interval);
+        }
+        true
+    }
+
+    /// Joins the incoming intervals of the phi functions
+    fn evaluate_phis(&mut self) -> bool {
+        let mut updates = Vec::new();
+        for (_, phi) in self.ssa.all_phis() {
+            let interval = phi
+                .operands
+                .iter()
+                .filter_map(|(predecessor, operand)| self.value_at((*operand)?, *predecessor))
+                .reduce(|joined, interval| joined.join(&interval));
+            if let Some(interval) = interval {
+                updates.push((phi.result, interval));
+            }
+        }
+
+        let mut changed = false;
+        for (variable, interval) in updates {
+            changed |= self.update(variable, interval);
+        }
+        changed
+    }
+
+    /// Computes the intervals of the results of an invocation & the refinements of its branches
+    fn evaluate_invocation(
+        &mut self,
+        offset: u32,
+        invocation: &Invocation,
+        libfunc_name: &str,
+    ) -> bool {
+        let Some(statement) = self.ssa.statement(offset).cloned() else {
+            return false;
+        };
+        let arguments: Vec<Option<Interval>> = statement
+            .uses
+            .iter()
+            .map(|variable| self.value_at(*variable, statement.block))
+            .collect();
+
+        // Intervals of the results of each branch, None when they are not known yet
+        let mut results: Vec<Vec<Option<Interval>>> = statement
+            .definitions
+            .iter()
+            .map(|definitions| vec![None; definitions.len()])
+            .collect();
+        // Intervals implied by each branch for the arguments
+        let mut refinements: Vec<Vec<(usize, Interval)>> = vec![Vec::new(); results.len()];
+        let mut set_result = |branch: usize, index: usize, interval: Option<Interval>| {
+            if let Some(result) = results
+                .get_mut(branch)
+                .and_then(|branch| branch.get_mut(index))
+            {
+                *result = interval;
+            }
+        };
+        let last_arguments = || {
+            let count = arguments.len();
+            (count >= 2).then(|| (arguments[count - 2].clone(), arguments[count - 1].clone()))
+        };
+
+        let mut handled = true;
+        if let Some(constant) = CONST_REGEXES
+            .iter()
+            .find_map(|regex| regex.captures(libfunc_name))
+            .and_then(|captures| BigInt::from_str(&captures["const"]).ok())
+        {
+            set_result(0, 0, Some(Interval::constant(constant)));
+        } else if let Some(captures) = FELT252_OPERATION_REGEX.captures(libfunc_name) {
+            // The felt252 operations wrap around the prime
+            if let Some((Some(lhs), Some(rhs))) = last_arguments() {
+                let result = lhs
+                    .apply(&captures["operator"], &rhs)
+                    .filter(|result| Interval::felt252().contains(result))
+                    .unwrap_or_else(Interval::felt252);
+                set_result(0, 0, Some(result));
+            }
+        } else if let Some(captures) = OVERFLOWING_OPERATION_REGEX.captures(libfunc_name) {
+            // `u8_overflowing_add(range_check, lhs, rhs) { fallthrough(range_check, result) 42(range_check, wrapped_result) }`
+            let range = Interval::of_type(&format!("u{}", &captures["bits"]));
+            if let (Some(range), Some((Some(lhs), Some(rhs)))) = (range, last_arguments()) {
+                let result = lhs
+                    .apply(&captures["operator"], &rhs)
+                    .and_then(|result| result.intersect(&range))
+                    .unwrap_or_else(|| range.clone());
+                set_result(0, 1, Some(result));
+                set_result(1, 1, Some(range.clone()));
+
+                // The subtraction compares the operands: branch 0 is taken when `lhs >= rhs`,
+                // branch 1 (the overflow) when `lhs < rhs`
+                if &captures["operator"] == "sub" {
+                    let lhs_index = arguments.len() - 2;
+                    let rhs_index = arguments.len() - 1;
+                    let one = BigInt::from(1);
+                    refinements[0]
+                        .push((lhs_index, Interval::new(rhs.min.clone(), range.max.clone())));
+                    refinements[0]
+                        .push((rhs_index, Interval::new(range.min.clone(), lhs.max.clone())));
+                    if refinements.len() > 1 {
+                        refinements[1]
+                            .push((lhs_index, Interval::new(range.min.clone(), &rhs.max - &one)));
+                        refinements[1]
+                            .push((rhs_index, Interval::new(&lhs.min + &one, range.max.clone())));
+                    }
+                }
+            }
+        } else if let Some(captures) = INTEGER_FROM_FELT252_REGEX
+            .captures(libfunc_name)
+            .or_else(|| DOWNCAST_REGEX.captures(libfunc_name))
+        {
+            // The value is range checked, the conversion only succeeds in the type range
+            if let (Some(range), Some(Some(value))) =
+                (Interval::of_type(&captures["type"]), arguments.last())
+            {
+                set_result(0, 1, Some(value.intersect(&range).unwrap_or(range)));
+            }
+        } else if COPY_REGEXES
+            .iter()
+            .any(|regex| regex.is_match(libfunc_name))
+        {
+            // The results are copies of the argument
+            if let Some(Some(value)) = arguments.first() {
+                for index in 0..statement.definitions.first().map_or(0, Vec::len) {
+                    set_result(0, index, Some(value.clone()));
+                }
+            }
+        } else if IS_ZERO_REGEX.is_match(libfunc_name) {
+            // Branch 0 is taken if the operand is zero, branch 1 returns it as a NonZero value
+            refinements[0].push((0, Interval::constant(BigInt::from(0))));
+            if let Some(Some(value)) = arguments.first() {
+                set_result(1, 0, Some(value.clone()));
+            }
+        } else if LESS_THAN_REGEX.is_match(libfunc_name)
+            || LESS_THAN_OR_EQUAL_REGEX.is_match(libfunc_name)
+            || EQUAL_REGEX.is_match(libfunc_name)
+        {
+            // Branch 0 is taken when the comparison is false, branch 1 when it is true
+            handled = false;
+            if let Some((Some(lhs), Some(rhs))) = last_arguments() {
+                let lhs_index = arguments.len() - 2;
+                let rhs_index = arguments.len() - 1;
+                let felt252 = Interval::felt252();
+                let one = BigInt::from(1);
+
+                let (lhs_false, rhs_false, lhs_true, rhs_true) =
+                    if LESS_THAN_REGEX.is_match(libfunc_name) {
+                        (
+                            Some(Interval::new(rhs.min.clone(), felt252.max.clone())),
+                            Some(Interval::new(felt252.min.clone(), lhs.max.clone())),
+                            Interval::new(felt252.min.clone(), &rhs.max - &one),
+                            Interval::new(&lhs.min + &one, felt252.max.clone()),
+                        )
+                    } else if LESS_THAN_OR_EQUAL_REGEX.is_match(libfunc_name) {
+                        (
+                            Some(Interval::new(&rhs.min + &one, felt252.max.clone())),
+                            Some(Interval::new(felt252.min.clone(), &lhs.max - &one)),
+                            Interval::new(felt252.min.clone(), rhs.max.clone()),
+                            Interval::new(lhs.min.clone(), felt252.max.clone()),
+                        )
+                    } else {
+                        (None, None, rhs.clone(), lhs.clone())
+                    };
+
+                if let (Some(lhs_false), Some(rhs_false)) = (lhs_false, rhs_false) {
+                    refinements[0].push((lhs_index, lhs_false));
+                    refinements[0].push((rhs_index, rhs_false));
+                }
+                if refinements.len() > 1 {
+                    refinements[1].push((lhs_index, lhs_true));
+                    refinements[1].push((rhs_index, rhs_true));
+                }
+            }
+        } else {
+            handled = false;
+        }
+
+        // The results of the other libfuncs are in the range of their integer type, if any
+        if !handled {
+            let range = INTEGER_LIBFUNC_REGEX
+                .captures(libfunc_name)
+                .and_then(|captures| Interval::of_type(&captures["type"]))
+                .unwrap_or_else(Interval::felt252);
+            for (branch, definitions) in statement.definitions.iter().enumerate() {
+                for index in 0..definitions.len() {
+                    set_result(branch, index, Some(range.clone()));
+                }
+            }
+        }
+
+        let mut changed = false;
+        for (branch, definitions) in statement.definitions.iter().enumerate() {
+            for (variable, interval) in definitions.iter().zip(&results[branch]) {
+                if let Some(interval) = interval {
+                    changed |= self.update(*variable, interval.clone());
+                }
+            }
+        }
+
+        // The refinements are valid in the target block if it's only reached by the branch
+        for (branch, branch_refinements) in refinements.into_iter().enumerate() {
+            let Some(target) = branch_target_block(offset, invocation, branch) else {
+                continue;
+            };
+            let targets_count = (0..invocation.branches.len())
+                .filter(|other| branch_target_block(offset, invocation, *other) == Some(target))
+                .count();
+            if targets_count != 1 || self.ssa.predecessors(target) != [statement.block] {
+                continue;
+            }
+
+            let block_refinements: Vec<(SsaVariable, Interval)> = branch_refinements
+                .into_iter()
+                .filter_map(|(argument, interval)| Some((*statement.uses.get(argument)?, interval)))
+                .collect();
+            if self.refinements.get(&target) != Some(&block_refinements) {
+                self.refinements.insert(target, block_refinements);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+/// Returns the start offset of the block targeted by a branch of an invocation
+fn branch_target_block(offset: u32, invocation: &Invocation, branch: usize) -> Option<u32> {
+    Some(match &invocation.branches.get(branch)?.target {
+        BranchTarget::Fallthrough => offset + 1,
+        BranchTarget::Statement(statement_idx) => statement_idx.0 as u32,
+    })
+}
@@ -0,0 +1,408 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use cairo_lang_sierra::program::GenStatement;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::analysis::dataflow::Definition;
+use crate::config::SymbolicExecutionConfig;
+use crate::decompiler::function::Function;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::{
+    STORAGE_ADDRESS_FROM_BASE_REGEX, STORAGE_BASE_ADDRESS_CONST_REGEX, USER_DEFINED_FUNCTION_REGEX,
+};
+use crate::graph::callgraph::CallGraph;
+use crate::parse_element_name;
+
+/// A user-declared property of the program
+/// Invariants are loaded from a JSON file, e.g.
+/// `[{"kind": "never_writes_storage", "function": "erc20::ERC20::name", "slot": "0x1234"}]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Invariant {
+    /// The function (and its callees) never writes the storage slot (base address, decimal or 0x hex)
+    NeverWritesStorage { function: String, slot: String },
+    /// All the non-panicking paths of the function read the caller address
+    ChecksCaller { function: String },
+}
+
+impl Invariant {
+    /// Returns the name of the function the invariant is declared on
+    pub fn function(&self) -> &str {
+        match self {
+            Invariant::NeverWritesStorage { function, .. } => function,
+            Invariant::ChecksCaller { function } => function,
+        }
+    }
+}
+
+impl fmt::Display for Invariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Invariant::NeverWritesStorage { function, slot } => {
+                write!(f, "{} never writes storage slot {}", function, slot)
+            }
+            Invariant::ChecksCaller { function } => write!(f, "{} always checks caller", function),
+        }
+    }
+}
+
+/// A violated invariant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvariantViolation {
+    /// The violated invariant
+    pub invariant: Invariant,
+    /// Why the invariant does not hold
+    pub reason: String,
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} : {}", self.invariant, self.reason)
+    }
+}
+
+/// Parses the invariants declared in a JSON file
+pub fn load_invariants(json: &str) -> Result<Vec<Invariant>, String> {
+    let invariants: Vec<Invariant> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid invariants file: {}", e))?;
+
+    // The storage slots must be valid addresses
+    for invariant in &invariants {
+        if let Invariant::NeverWritesStorage { slot, .. } = invariant {
+            if parse_slot(slot).is_none() {
+                return Err(format!("Invalid storage slot: {}", slot));
+            }
+        }
+    }
+
+    Ok(invariants)
+}
+
+/// Parses a storage slot, in decimal or 0x-prefixed hexadecimal
+fn parse_slot(slot: &str) -> Option<BigUint> {
+    match slot.strip_prefix("0x") {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+        None => BigUint::parse_bytes(slot.as_bytes(), 10),
+    }
+}
+
+/// Returns a function name without its generic arguments
+/// e.g. `erc20::IERC20::<erc20::ContractState>::transfer` -> `erc20::IERC20::transfer`
+fn strip_generics(function_name: &str) -> String {
+    let mut stripped = String::new();
+    let mut depth = 0;
+
+    for c in function_name.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped.replace("::::", "::")
+}
+
+/// Verifies the invariants over the functions CFGs & callgraph
+#[instrument(skip_all, fields(invariants = invariants.len()))]
+pub fn check_invariants(
+    functions: &[Function],
+    invariants: &[Invariant],
+) -> Vec<InvariantViolation> {
+    let engine = InvariantsEngine::new(functions);
+
+    invariants
+        .iter()
+        .filter_map(|invariant| {
+            engine.check(invariant).map(|reason| InvariantViolation {
+                invariant: invariant.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Facts about the program shared by the invariants checks
+struct InvariantsEngine<'a> {
+    functions: &'a [Function<'a>],
+    callgraph: CallGraph,
+    /// Functions writing the storage, directly or through their callees
+    storage_writers: HashSet<String>,
+    /// Functions reading the caller address, directly or through their callees
+    caller_readers: HashSet<String>,
+}
+
+impl<'a> InvariantsEngine<'a> {
+    fn new(functions: &'a [Function<'a>]) -> Self {
+        let callgraph = CallGraph::new(functions);
+
+        let storage_writers = transitive_callers(&callgraph, functions, LibfuncKind::StorageWrite);
+        let caller_readers = transitive_callers(&callgraph, functions, LibfuncKind::CallerAddress);
+
+        Self {
+            functions,
+            callgraph,
+            storage_writers,
+            caller_readers,
+        }
+    }
+
+    /// Returns the reason of the violation if the invariant does not hold
+    fn check(&self, invariant: &Invariant) -> Option<String> {
+        let Some(function) = self.function(invariant.function()) else {
+            return Some(format!("function {} not found", invariant.function()));
+        };
+
+        match invariant {
+            Invariant::NeverWritesStorage { slot, .. } => match parse_slot(slot) {
+                Some(slot) => self.check_never_writes_storage(function, &slot),
+                None => Some(format!("invalid storage slot {}", slot)),
+            },
+            Invariant::ChecksCaller { .. } => self.check_caller(function),
+        }
+    }
+
+    /// Returns a function by its name, with or without its generic arguments
+    fn function(&self, name: &str) -> Option<&'a Function<'a>> {
+        self.functions.iter().find(|function| {
+            let function_name = parse_element_name!(function.function.id);
+            function_name == name || strip_generics(&function_name) == name
+        })
+    }
+
+    /// Checks that no function reachable from `function` writes the slot
+    /// A function writes the slot if it uses the slot base address and writes it directly,
+    /// or passes it to a function writing the storage
+    fn check_never_writes_storage(&self, function: &Function, slot: &BigUint) -> Option<String> {
+        let function_name = parse_element_name!(function.function.id);
+        let mut reachable: Vec<String> = self
+            .callgraph
+            .reachable_from(&[function_name])
+            .into_iter()
+            .collect();
+        reachable.sort();
+
+        for name in reachable {
+            let Some(reachable_function) = self.function(&name) else {
+                continue;
+            };
+
+            if !uses_storage_base_address(reachable_function, slot) {
+                continue;
+            }
+
+            // Direct write to the slot
+            if let Some(offset) = direct_storage_write(reachable_function, slot) {
+                return Some(format!("{} writes the slot at statement {}", name, offset));
+            }
+
+            // The slot address is passed to a function writing the storage
+            if let Some(callee) = called_functions(reachable_function)
+                .into_iter()
+                .find(|callee| self.storage_writers.contains(callee))
+            {
+                return Some(format!("{} may write the slot through {}", name, callee));
+            }
+        }
+
+        None
+    }
+
+    /// Checks that all the non-panicking paths of the function read the caller address
+    fn check_caller(&self, function: &Function) -> Option<String> {
+        let cfg = function.cfg();
+
+        for path in cfg.paths_iter(
+            SymbolicExecutionConfig::MAX_PATHS,
+            SymbolicExecutionConfig::MAX_PATH_DEPTH,
+        ) {
+            let libfuncs: Vec<(LibfuncKind, String)> = path
+                .iter()
+                .flat_map(|block| &block.statements)
+                .filter_map(|statement| match &statement.statement {
+                    GenStatement::Invocation(invocation) => Some((
+                        statement.libfunc_kind,
+                        parse_element_name!(invocation.libfunc_id),
+                    )),
+                    _ => None,
+                })
+                .collect();
+
+            // The panicking paths don't need to check the caller
+            if libfuncs.iter().any(|(libfunc_kind, _)| {
+                matches!(
+                    libfunc_kind,
+                    LibfuncKind::Panic | LibfuncKind::PanicResultErr
+                )
+            }) {
+                continue;
+            }
+
+            let reads_caller = libfuncs.iter().any(|(libfunc_kind, libfunc_name)| {
+                *libfunc_kind == LibfuncKind::CallerAddress
+                    || called_function(libfunc_name)
+                        .map_or(false, |callee| self.caller_readers.contains(&callee))
+            });
+
+            if !reads_caller {
+                let start_offset = path.first().map_or(0, |block| block.start_offset);
+                let end_offset = path
+                    .last()
+                    .and_then(|block| block.statements.last())
+                    .map_or(0, |statement| statement.offset);
+                return Some(format!(
+                    "the path from statement {} to statement {} does not read the caller address",
+                    start_offset, end_offset
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the names of the functions invoking a libfunc of a kind, directly or through their callees
+pub(crate) fn transitive_callers(
+    callgraph: &CallGraph,
+    functions: &[Function],
+    libfunc_kind: LibfuncKind,
+) -> HashSet<String> {
+    let mut callers = HashSet::new();
+
+    for function in functions {
+        let invokes_libfunc = function
+            .statements()
+            .iter()
+            .any(|statement| statement.libfunc_kind == libfunc_kind);
+
+        if invokes_libfunc {
+            callers.extend(callgraph.callers(&parse_element_name!(function.function.id)));
+        }
+    }
+
+    callers
+}
+
+/// Returns the name of the user-defined function called by a libfunc, if any
+pub(crate) fn called_function(libfunc_name: &str) -> Option<String> {
+    USER_DEFINED_FUNCTION_REGEX
+        .captures(libfunc_name)
+        .and_then(|captures| captures.name("function_id"))
+        .map(|function_id| function_id.as_str().to_string())
+}
+
+/// Returns the names of the user-defined functions called by a function
+pub(crate) fn called_functions(function: &Function) -> Vec<String> {
+    function
+        .statements()
+        .iter()
+        .filter_map(|statement| match &statement.statement {
+            GenStatement::Invocation(invocation) => {
+                called_function(&parse_element_name!(invocation.libfunc_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the slot of a `storage_base_address_const<slot>` libfunc
+fn storage_base_address_const(libfunc_name: &str) -> Option<BigUint> {
+    STORAGE_BASE_ADDRESS_CONST_REGEX
+        .captures(libfunc_name)
+        .and_then(|captures| captures["const"].parse::<BigUint>().ok())
+}
+
+/// Returns the storage base addresses constants used by a function
+pub(crate) fn storage_base_addresses(function: &Function) -> Vec<BigUint> {
+    function
+        .statements()
+        .iter()
+        .filter_map(|statement| match &statement.statement {
+            GenStatement::Invocation(invocation) => {
+                storage_base_address_const(&parse_element_name!(invocation.libfunc_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns true if the function uses the base address of the slot
+fn uses_storage_base_address(function: &Function, slot: &BigUint) -> bool {
+    function.statements().iter().any(|statement| {
+        if let GenStatement::Invocation(invocation) = &statement.statement {
+            storage_base_address_const(&parse_element_name!(invocation.libfunc_id)).as_ref()
+                == Some(slot)
+        } else {
+            false
+        }
+    })
+}
+
+/// Returns the offset of a storage write to the slot in the function
+/// The writes whose address can't be resolved in the function are considered as writing the slot
+fn direct_storage_write(function: &Function, slot: &BigUint) -> Option<u32> {
+    let dataflow = function.def_use();
+
+    // Resolves the storage base address a variable used at `offset` is computed from
+    let resolve = |var_id: u64, offset: u32| -> Option<BigUint> {
+        let (mut var_id, mut offset) = (var_id, offset);
+
+        // The address is computed by a chain of renamings & address conversions
+        for _ in 0..function.statements().len() {
+            // A variable can be redefined, the last definition before the use is kept
+            let definition_offset = dataflow
+                .definitions(var_id)
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Statement(definition_offset) if *definition_offset < offset => {
+                        Some(*definition_offset)
+                    }
+                    _ => None,
+                })
+                .last()?;
+            let statement = function
+                .statements()
+                .iter()
+                .find(|statement| statement.offset == definition_offset)?;
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                return None;
+            };
+
+            let libfunc_name = parse_element_name!(invocation.libfunc_id);
+            if let Some(slot) = storage_base_address_const(&libfunc_name) {
+                return Some(slot);
+            }
+
+            let is_forwarding = STORAGE_ADDRESS_FROM_BASE_REGEX.is_match(&libfunc_name)
+                || statement.libfunc_kind == LibfuncKind::Dup
+                || statement.libfunc_kind.is_assignment();
+            if !is_forwarding {
+                return None;
+            }
+            var_id = invocation.args.first()?.id;
+            offset = definition_offset;
+        }
+
+        None
+    };
+
+    function.statements().iter().find_map(|statement| {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            return None;
+        };
+        if statement.libfunc_kind != LibfuncKind::StorageWrite {
+            return None;
+        }
+
+        // storage_write_syscall(gas, system, address_domain, address, value)
+        let address = invocation.args.get(3)?;
+        match resolve(address.id, statement.offset) {
+            Some(written_slot) if written_slot != *slot => None,
+            _ => Some(statement.offset),
+        }
+    })
+}
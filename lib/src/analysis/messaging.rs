@@ -0,0 +1,173 @@
+use std::fmt;
+
+use cairo_lang_starknet_classes::abi::Contract;
+use serde::Serialize;
+
+use crate::analysis::entry_points::{EntryPoint, EntryPointKind, EntryPointsTable};
+pub use crate::analysis::origins::Origin;
+use crate::analysis::origins::ProgramOrigins;
+use crate::decompiler::function::{Function, FunctionType};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+/// A message sent to L1 by `send_message_to_l1_syscall`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct L1Message {
+    /// Function invoking the syscall
+    pub function: String,
+    /// Offset of the syscall
+    pub offset: u32,
+    /// Entry points calling the function, directly or through other functions
+    pub entry_points: Vec<String>,
+    /// Origin of the L1 recipient address
+    pub to_address: Origin,
+    /// Origin of the whole payload
+    pub payload: Origin,
+    /// Origins of the payload fields, empty if the payload isn't built by the function
+    pub payload_fields: Vec<Origin>,
+}
+
+impl L1Message {
+    /// Returns the indexes of the user-controlled payload fields
+    pub fn user_controlled_fields(&self) -> Vec<usize> {
+        self.payload_fields
+            .iter()
+            .enumerate()
+            .filter(|(_, origin)| origin.is_user_controlled())
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// L1 messaging surface of a contract, the L1 handlers & the messages sent to L1
+/// The origins of the messages recipients & payloads are tracked through the SSA form of the
+/// functions, from the calldata of the entry points to the arguments of the called functions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MessagingReport {
+    /// Entry points receiving the messages of L1, they are only known from the ABI
+    pub l1_handlers: Vec<EntryPoint>,
+    pub messages: Vec<L1Message>,
+}
+
+impl MessagingReport {
+    /// Builds the messaging report of the functions
+    pub fn new(functions: &[Function], abi: Option<&Contract>) -> Self {
+        let l1_handlers = EntryPointsTable::new(functions, abi)
+            .entries
+            .into_iter()
+            .filter(|entry| entry.kind == EntryPointKind::L1Handler)
+            .collect();
+
+        let callgraph = CallGraph::new(functions);
+        let origins = ProgramOrigins::new(functions, &callgraph);
+
+        let mut messages = Vec::new();
+        for analysis in &origins.functions {
+            let arrays = analysis.arrays_fields();
+
+            for statement in analysis.function.statements() {
+                if statement.libfunc_kind != LibfuncKind::SendMessageToL1 {
+                    continue;
+                }
+
+                // send_message_to_l1_syscall(gas, system, to_address, payload)
+                let arguments = analysis.arguments(statement.offset);
+                let origin = |index: usize| {
+                    arguments
+                        .get(index)
+                        .map(|variable| analysis.origin(variable))
+                        .unwrap_or_default()
+                };
+
+                let mut entry_points: Vec<String> = callgraph
+                    .callers(&analysis.name)
+                    .into_iter()
+                    .filter(|caller| {
+                        functions.iter().any(|function| {
+                            matches!(function.function_type, Some(FunctionType::Wrapper))
+                                && parse_element_name!(function.function.id) == *caller
+                        })
+                    })
+                    .filter_map(|wrapper| wrapper_entry_point_name(&wrapper))
+                    .collect();
+                entry_points.sort();
+
+                messages.push(L1Message {
+                    function: analysis.name.clone(),
+                    offset: statement.offset,
+                    entry_points,
+                    to_address: origin(0),
+                    payload: origin(1),
+                    payload_fields: arguments
+                        .get(1)
+                        .and_then(|payload| arrays.get(payload))
+                        .cloned()
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        Self {
+            l1_handlers,
+            messages,
+        }
+    }
+
+    /// Returns the report as text, the L1 handlers followed by the messages sent to L1
+    pub fn to_text(&self) -> String {
+        let mut text = "L1 handlers\n".to_string();
+        for handler in &self.l1_handlers {
+            text += &format!(
+                "\t- {} ({}) : ({})\n",
+                handler.name,
+                handler.selector,
+                handler.parameters.join(", ")
+            );
+        }
+
+        text += "Messages to L1\n";
+        for message in &self.messages {
+            text += &format!(
+                "\t- {} at {} (entry points: {})\n",
+                message.function,
+                message.offset,
+                message.entry_points.join(", ")
+            );
+            text += &format!("\t\tto_address: {}\n", message.to_address.as_str());
+            if message.payload_fields.is_empty() {
+                text += &format!("\t\tpayload: {}\n", message.payload.as_str());
+            } else {
+                let fields: Vec<String> = message
+                    .payload_fields
+                    .iter()
+                    .map(|field| field.as_str())
+                    .collect();
+                text += &format!("\t\tpayload: [{}]\n", fields.join(", "));
+            }
+
+            let user_controlled = message.user_controlled_fields();
+            if !user_controlled.is_empty() {
+                let indexes: Vec<String> = user_controlled
+                    .iter()
+                    .map(|index| index.to_string())
+                    .collect();
+                text += &format!("\t\tuser-controlled fields: {}\n", indexes.join(", "));
+            }
+        }
+
+        text
+    }
+
+    /// Returns the report in JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+impl fmt::Display for MessagingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
@@ -0,0 +1,13 @@
+pub mod access_control;
+pub mod context;
+pub mod dataflow;
+pub mod entry_points;
+pub mod intervals;
+pub mod invariants;
+pub mod messaging;
+pub mod origins;
+pub mod search;
+pub mod session;
+pub mod ssa;
+pub mod storage_access;
+pub mod storage_layout;
@@ -0,0 +1,357 @@
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_sierra::program::GenStatement;
+use serde::Serialize;
+
+use crate::analysis::access_control::builtin_variables;
+use crate::analysis::invariants::{called_function, transitive_callers};
+use crate::analysis::ssa::{SsaForm, SsaVariable};
+use crate::decompiler::function::{Function, FunctionType, SierraStatement};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::graph::callgraph::CallGraph;
+use crate::parse_element_name;
+
+/// Origins of a value, propagated from the arguments to the results of the statements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct Origin {
+    /// Derived from the calldata of an entry point
+    pub calldata: bool,
+    /// Derived from the arguments of a function that isn't called by the program
+    pub input: bool,
+    /// Derived from a storage value
+    pub storage: bool,
+}
+
+impl Origin {
+    pub(crate) fn union(self, other: Origin) -> Origin {
+        Origin {
+            calldata: self.calldata || other.calldata,
+            input: self.input || other.input,
+            storage: self.storage || other.storage,
+        }
+    }
+
+    /// Returns true if the value can be chosen by the caller
+    pub fn is_user_controlled(&self) -> bool {
+        self.calldata || self.input
+    }
+
+    /// Returns the origins separated by `+`, `constant` for the values without origin
+    pub fn as_str(&self) -> String {
+        let origins: Vec<&str> = [
+            (self.calldata, "calldata"),
+            (self.input, "input"),
+            (self.storage, "storage"),
+        ]
+        .into_iter()
+        .filter_map(|(is_origin, name)| is_origin.then_some(name))
+        .collect();
+
+        if origins.is_empty() {
+            "constant".to_string()
+        } else {
+            origins.join("+")
+        }
+    }
+}
+
+/// Origins of the values of the functions of a program
+/// The parameters of the wrappers are the calldata, the parameters of the functions without
+/// caller are inputs, the other parameters get the origins of the arguments of their calls
+pub(crate) struct ProgramOrigins<'a, 'f> {
+    pub(crate) functions: Vec<FunctionOrigins<'a, 'f>>,
+}
+
+impl<'a, 'f> ProgramOrigins<'a, 'f> {
+    /// Propagates the origins through the functions & their calls
+    /// The functions whose SSA form can't be computed are skipped
+    pub(crate) fn new(functions: &'a [Function<'f>], callgraph: &CallGraph) -> Self {
+        let storage_readers = transitive_callers(callgraph, functions, LibfuncKind::StorageRead);
+        let mut analyses: Vec<FunctionOrigins> =
+            functions.iter().filter_map(FunctionOrigins::new).collect();
+
+        let called: HashSet<String> = functions
+            .iter()
+            .flat_map(|function| {
+                function.statements().iter().filter_map(|statement| {
+                    let GenStatement::Invocation(invocation) = &statement.statement else {
+                        return None;
+                    };
+                    called_function(&parse_element_name!(invocation.libfunc_id))
+                })
+            })
+            .collect();
+        let mut parameters: HashMap<String, Vec<Origin>> = analyses
+            .iter()
+            .map(|analysis| {
+                let origin = match analysis.function.function_type {
+                    Some(FunctionType::Wrapper) => Origin {
+                        calldata: true,
+                        ..Origin::default()
+                    },
+                    _ if !called.contains(&analysis.name) => Origin {
+                        input: true,
+                        ..Origin::default()
+                    },
+                    _ => Origin::default(),
+                };
+                (
+                    analysis.name.clone(),
+                    vec![origin; analysis.value_parameters.len()],
+                )
+            })
+            .collect();
+
+        // The origins of the arguments are propagated through the calls until they are stable
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for analysis in &mut analyses {
+                analysis.variables =
+                    analysis.propagate(&parameters[&analysis.name], &storage_readers);
+
+                for (callee, arguments) in analysis.calls_arguments() {
+                    let Some(callee_parameters) = parameters.get_mut(&callee) else {
+                        continue;
+                    };
+                    for (parameter, argument) in callee_parameters.iter_mut().zip(arguments) {
+                        let merged = parameter.union(argument);
+                        changed |= merged != *parameter;
+                        *parameter = merged;
+                    }
+                }
+            }
+        }
+
+        Self {
+            functions: analyses,
+        }
+    }
+}
+
+/// SSA form of a function & the origins of its values
+pub(crate) struct FunctionOrigins<'a, 'f> {
+    pub(crate) function: &'a Function<'f>,
+    pub(crate) name: String,
+    pub(crate) ssa: SsaForm,
+    builtins: HashSet<SsaVariable>,
+    /// Parameters that aren't builtins
+    value_parameters: Vec<SsaVariable>,
+    /// Origins of the variables, the variables without origin are constants
+    variables: HashMap<SsaVariable, Origin>,
+}
+
+impl<'a, 'f> FunctionOrigins<'a, 'f> {
+    fn new(function: &'a Function<'f>) -> Option<Self> {
+        let ssa = function.ssa().ok()?;
+        let builtins = builtin_variables(function, &ssa);
+        let value_parameters = ssa
+            .parameters()
+            .iter()
+            .filter(|parameter| !builtins.contains(*parameter))
+            .copied()
+            .collect();
+
+        Some(Self {
+            name: parse_element_name!(function.function.id),
+            function,
+            ssa,
+            builtins,
+            value_parameters,
+            variables: HashMap::new(),
+        })
+    }
+
+    /// Returns the origin of a variable
+    pub(crate) fn origin(&self, variable: &SsaVariable) -> Origin {
+        self.variables.get(variable).copied().unwrap_or_default()
+    }
+
+    /// Returns the arguments of a statement that aren't builtins
+    pub(crate) fn arguments(&self, offset: u32) -> Vec<SsaVariable> {
+        self.ssa
+            .statement(offset)
+            .map(|ssa_statement| {
+                ssa_statement
+                    .uses
+                    .iter()
+                    .filter(|variable| !self.builtins.contains(*variable))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the statement defining a variable, None for the parameters & the phis results
+    pub(crate) fn definition(&self, variable: &SsaVariable) -> Option<&'a SierraStatement> {
+        self.function.statements().iter().find(|statement| {
+            self.ssa
+                .statement(statement.offset)
+                .is_some_and(|ssa_statement| {
+                    ssa_statement
+                        .definitions
+                        .iter()
+                        .flatten()
+                        .any(|result| result == variable)
+                })
+        })
+    }
+
+    /// Returns the origins of the variables from the origins of the parameters
+    fn propagate(
+        &self,
+        parameters: &[Origin],
+        storage_readers: &HashSet<String>,
+    ) -> HashMap<SsaVariable, Origin> {
+        let mut origins: HashMap<SsaVariable, Origin> = self
+            .value_parameters
+            .iter()
+            .copied()
+            .zip(parameters.iter().copied())
+            .collect();
+
+        // The statements are evaluated until the origins of the loops are stable
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for (_, phi) in self.ssa.all_phis() {
+                let origin =
+                    phi.operands
+                        .values()
+                        .flatten()
+                        .fold(Origin::default(), |origin, operand| {
+                            origin.union(origins.get(operand).copied().unwrap_or_default())
+                        });
+                changed |= update_origin(&mut origins, phi.result, origin);
+            }
+
+            for statement in self.function.statements() {
+                let GenStatement::Invocation(invocation) = &statement.statement else {
+                    continue;
+                };
+                let Some(ssa_statement) = self.ssa.statement(statement.offset) else {
+                    continue;
+                };
+
+                let mut origin = ssa_statement
+                    .uses
+                    .iter()
+                    .filter(|variable| !self.builtins.contains(*variable))
+                    .fold(Origin::default(), |origin, variable| {
+                        origin.union(origins.get(variable).copied().unwrap_or_default())
+                    });
+                origin.storage |= statement.libfunc_kind == LibfuncKind::StorageRead
+                    || called_function(&parse_element_name!(invocation.libfunc_id))
+                        .is_some_and(|callee| storage_readers.contains(&callee));
+
+                for result in ssa_statement.definitions.iter().flatten() {
+                    if !self.builtins.contains(result) {
+                        changed |= update_origin(&mut origins, *result, origin);
+                    }
+                }
+            }
+        }
+
+        origins
+    }
+
+    /// Returns the origins of the arguments of the user-defined functions calls, without the builtins
+    fn calls_arguments(&self) -> Vec<(String, Vec<Origin>)> {
+        self.function
+            .statements()
+            .iter()
+            .filter_map(|statement| {
+                let GenStatement::Invocation(invocation) = &statement.statement else {
+                    return None;
+                };
+                let callee = called_function(&parse_element_name!(invocation.libfunc_id))?;
+
+                let arguments = self
+                    .arguments(statement.offset)
+                    .iter()
+                    .map(|variable| self.origin(variable))
+                    .collect();
+                Some((callee, arguments))
+            })
+            .collect()
+    }
+
+    /// Returns the origins of the fields of the arrays built by the function
+    /// The arrays are followed from their creation through the appends, the copies & the spans
+    pub(crate) fn arrays_fields(&self) -> HashMap<SsaVariable, Vec<Origin>> {
+        let mut arrays: HashMap<SsaVariable, Vec<Origin>> = HashMap::new();
+
+        for statement in self.function.statements() {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+            let Some(ssa_statement) = self.ssa.statement(statement.offset) else {
+                continue;
+            };
+            let Some(results) = ssa_statement.definitions.first() else {
+                continue;
+            };
+
+            let fields = match statement.libfunc_kind {
+                LibfuncKind::ArrayNew => Some(Vec::new()),
+                LibfuncKind::ArrayAppend => {
+                    let (Some(array), Some(value)) =
+                        (ssa_statement.uses.first(), ssa_statement.uses.get(1))
+                    else {
+                        continue;
+                    };
+                    arrays.get(array).cloned().map(|mut fields| {
+                        fields.push(self.origin(value));
+                        fields
+                    })
+                }
+                LibfuncKind::StoreTemp
+                | LibfuncKind::Dup
+                | LibfuncKind::SnapshotTake
+                | LibfuncKind::Assignment => ssa_statement
+                    .uses
+                    .first()
+                    .and_then(|array| arrays.get(array))
+                    .cloned(),
+                _ if is_span_construction(&parse_element_name!(invocation.libfunc_id)) => {
+                    ssa_statement
+                        .uses
+                        .first()
+                        .and_then(|array| arrays.get(array))
+                        .cloned()
+                }
+                _ => None,
+            };
+
+            if let Some(fields) = fields {
+                for result in results {
+                    arrays.insert(*result, fields.clone());
+                }
+            }
+        }
+
+        arrays
+    }
+}
+
+/// Returns true if a libfunc constructs a span from an array snapshot
+fn is_span_construction(libfunc_name: &str) -> bool {
+    libfunc_name.starts_with("struct_construct<core::array::Span::<")
+}
+
+/// Merges an origin into the origin of a variable, returns true if it changed
+fn update_origin(
+    origins: &mut HashMap<SsaVariable, Origin>,
+    variable: SsaVariable,
+    origin: Origin,
+) -> bool {
+    let current = origins.get(&variable).copied().unwrap_or_default();
+    let merged = current.union(origin);
+    if merged == current {
+        return false;
+    }
+    origins.insert(variable, merged);
+    true
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use cairo_lang_sierra::program::GenStatement;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::decompiler::function::Function;
+use crate::error::Result;
+use crate::{parse_element_name, parse_element_name_with_fallback};
+
+/// An invocation of a libfunc matching a search pattern
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LibfuncCall {
+    /// Offset of the invoking statement
+    pub offset: u32,
+    /// Name of the invoked libfunc
+    pub libfunc: String,
+}
+
+/// The matching libfunc invocations of a function, by statement offset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FunctionCalls {
+    /// Name of the function
+    pub function: String,
+    pub calls: Vec<LibfuncCall>,
+}
+
+/// Returns the functions invoking a libfunc matching a regex (e.g. `replace_class`, `deploy_syscall`)
+/// The functions without matching invocation are not returned
+pub fn find_libfunc_calls(
+    functions: &[Function],
+    pattern: &str,
+    declared_libfuncs_names: &[Arc<str>],
+) -> Result<Vec<FunctionCalls>> {
+    let regex = Regex::new(pattern)?;
+
+    let function_calls = functions
+        .iter()
+        .filter_map(|function| {
+            let calls: Vec<LibfuncCall> = function
+                .statements()
+                .iter()
+                .filter_map(|statement| match &statement.statement {
+                    GenStatement::Invocation(invocation) => Some(LibfuncCall {
+                        offset: statement.offset,
+                        libfunc: parse_element_name_with_fallback!(
+                            invocation.libfunc_id,
+                            declared_libfuncs_names
+                        ),
+                    }),
+                    GenStatement::Return(_) => None,
+                })
+                .filter(|call| regex.is_match(&call.libfunc))
+                .collect();
+
+            (!calls.is_empty()).then(|| FunctionCalls {
+                function: parse_element_name!(function.function.id),
+                calls,
+            })
+        })
+        .collect();
+
+    Ok(function_calls)
+}
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cairo_lang_starknet_classes::abi::Contract;
+use tracing::instrument;
+
+use crate::decompiler::decompiler::Decompiler;
+use crate::decompiler::function::{Function, FunctionType};
+use crate::decompiler::names::NamesTable;
+use crate::error::Result;
+use crate::graph::callgraph::CallGraph;
+use crate::parse_element_name;
+use crate::sierra_program::{ProgramKind, SierraProgram};
+
+/// Immutable analysis results of a Sierra program
+/// The functions (with their CFGs) and the callgraph are computed once and shared using `Arc`,
+/// cloning a session is cheap and a session can be used from several threads at the same time
+/// e.g. to run detectors concurrently
+#[derive(Debug, Clone)]
+pub struct AnalysisSession<'a> {
+    /// Program functions, with their statements, types & CFGs
+    functions: Arc<[Function<'a>]>,
+    /// Callgraph of the program functions
+    callgraph: Arc<CallGraph>,
+    /// Names of all declared types (in order)
+    declared_types_names: NamesTable,
+    /// Names of all declared libfuncs (in order)
+    declared_libfuncs_names: NamesTable,
+    /// ABI of the contract
+    abi: Option<Arc<Contract>>,
+    /// Kind of the analyzed program
+    kind: ProgramKind,
+}
+
+impl<'a> AnalysisSession<'a> {
+    /// Analyzes a Sierra program
+    pub fn new(sierra_program: &'a SierraProgram) -> Result<Self> {
+        let mut decompiler = sierra_program.decompiler(false);
+        decompiler.analyze()?;
+
+        Ok(Self::from_decompiler(&decompiler))
+    }
+
+    /// Creates a session from the functions loaded by a decompiler
+    /// The functions filtered out of the decompiler are not part of the session
+    #[instrument(skip_all, fields(functions = decompiler.functions.len()))]
+    pub fn from_decompiler(decompiler: &Decompiler<'a>) -> Self {
+        let mut functions = decompiler.functions.clone();
+        let functions_ref = functions.clone();
+        for function in &mut functions {
+            // The meta informations need the program registry
+            if let Some(registry) = decompiler.registry() {
+                let _ = function.set_meta_informations(&functions_ref, registry);
+            }
+            function.create_cfg();
+        }
+
+        Self {
+            callgraph: Arc::new(CallGraph::new(&functions)),
+            functions: functions.into(),
+            declared_types_names: decompiler.declared_types_names.clone(),
+            declared_libfuncs_names: decompiler.declared_libfuncs_names.clone(),
+            abi: decompiler.abi.clone().map(Arc::new),
+            kind: decompiler.sierra_program.kind(),
+        }
+    }
+
+    /// Returns the program functions
+    #[inline]
+    pub fn functions(&self) -> &[Function<'a>] {
+        &self.functions
+    }
+
+    /// Returns a function using its name
+    pub fn function(&self, name: &str) -> Option<&Function<'a>> {
+        self.functions
+            .iter()
+            .find(|function| parse_element_name!(function.function.id) == name)
+    }
+
+    /// Returns the callgraph of the program
+    #[inline]
+    pub fn callgraph(&self) -> &CallGraph {
+        &self.callgraph
+    }
+
+    /// Returns the names of all declared types (in order)
+    #[inline]
+    pub fn declared_types_names(&self) -> &[Arc<str>] {
+        &self.declared_types_names
+    }
+
+    /// Returns the names of all declared libfuncs (in order)
+    #[inline]
+    pub fn declared_libfuncs_names(&self) -> &[Arc<str>] {
+        &self.declared_libfuncs_names
+    }
+
+    /// Returns the ABI of the contract, if available
+    #[inline]
+    pub fn abi(&self) -> Option<&Contract> {
+        self.abi.as_deref()
+    }
+
+    /// Returns the kind of the analyzed program
+    #[inline]
+    pub fn kind(&self) -> ProgramKind {
+        self.kind
+    }
+
+    /// Returns the functions that are defined by the user
+    /// Constructor - External - View - Private - L1Handler
+    pub fn user_defined_functions(&self) -> impl Iterator<Item = &Function<'a>> {
+        self.functions.iter().filter(|f| {
+            matches!(
+                f.function_type,
+                Some(
+                    FunctionType::Constructor
+                        | FunctionType::External
+                        | FunctionType::View
+                        | FunctionType::Private
+                        | FunctionType::L1Handler
+                        | FunctionType::Loop
+                )
+            )
+        })
+    }
+
+    /// Returns the entry points of the program
+    /// Wrappers, External, View, Constructor & L1Handler functions
+    pub fn entry_points(&self) -> impl Iterator<Item = &Function<'a>> {
+        self.functions.iter().filter(|f| {
+            matches!(
+                f.function_type,
+                Some(
+                    FunctionType::Wrapper
+                        | FunctionType::External
+                        | FunctionType::View
+                        | FunctionType::Constructor
+                        | FunctionType::L1Handler
+                )
+            )
+        })
+    }
+
+    /// Returns the names of the callgraph nodes reachable from the program entry points
+    pub fn reachable_from_entry_points(&self) -> HashSet<String> {
+        let roots: Vec<String> = self
+            .entry_points()
+            .map(|function| parse_element_name!(function.function.id))
+            .collect();
+        self.callgraph.reachable_from(&roots)
+    }
+
+    /// Returns the names of all the direct & indirect callers of a function (function included)
+    #[inline]
+    pub fn callers(&self, function_name: &str) -> HashSet<String> {
+        self.callgraph.callers(function_name)
+    }
+}
@@ -0,0 +1,535 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use cairo_lang_sierra::program::{BranchTarget, GenStatement};
+use petgraph::algo::dominators::simple_fast;
+use petgraph::graph::{DiGraph, NodeIndex};
+
+use crate::decompiler::function::{Function, SierraStatement};
+use crate::error::Result;
+
+/// Version of a Sierra variable, each definition of a variable creates a new version
+/// The version 0 is the value of the variable at the function entry (e.g. a parameter)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SsaVariable {
+    pub id: u64,
+    pub version: u32,
+}
+
+impl fmt::Display for SsaVariable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}_{}", self.id, self.version)
+    }
+}
+
+/// Phi function merging the versions of a variable at the start of a basic block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhiNode {
+    /// Version defined by the phi function
+    pub result: SsaVariable,
+    /// Incoming version of each predecessor, indexed by the predecessor start offset
+    /// The version is None when the variable is not defined on the paths of the predecessor
+    pub operands: BTreeMap<u32, Option<SsaVariable>>,
+}
+
+/// Versioned variables of a statement
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SsaStatement {
+    /// Start offset of the basic block of the statement
+    pub block: u32,
+    /// Versions of the arguments (or of the returned variables)
+    pub uses: Vec<SsaVariable>,
+    /// Versions of the results of each branch of an invocation
+    pub definitions: Vec<Vec<SsaVariable>>,
+}
+
+/// Static single assignment (SSA) form of a function
+///
+/// The basic blocks are the ones delimited by the CFG, the phi functions are inserted at the
+/// dominance frontiers of the definitions where the variable is live (pruned SSA).
+/// The results of a branch are defined at the start of its target block when the block is only
+/// reached by this branch, so each branch of a match has its own versions.
+#[derive(Debug, Clone, Default)]
+pub struct SsaForm {
+    /// Versions of the function parameters
+    parameters: Vec<SsaVariable>,
+    /// Predecessors of each reachable basic block, indexed by start offset
+    predecessors: BTreeMap<u32, Vec<u32>>,
+    /// Immediate dominator of each reachable basic block, indexed by start offset
+    immediate_dominators: BTreeMap<u32, u32>,
+    /// Dominance frontier of each reachable basic block, indexed by start offset
+    dominance_frontiers: BTreeMap<u32, BTreeSet<u32>>,
+    /// Phi functions at the start of the basic blocks, indexed by start offset
+    phis: BTreeMap<u32, Vec<PhiNode>>,
+    /// Versioned variables of the reachable statements, indexed by offset
+    statements: BTreeMap<u32, SsaStatement>,
+}
+
+/// Renaming state, the stacks hold the current versions of each variable
+#[derive(Default)]
+struct Renamer {
+    counters: HashMap<u64, u32>,
+    stacks: HashMap<u64, Vec<u32>>,
+}
+
+impl Renamer {
+    /// Creates a new version of a variable & makes it the current one
+    fn define(&mut self, id: u64) -> SsaVariable {
+        let version = self.counters.entry(id).or_insert(0);
+        *version += 1;
+        self.stacks.entry(id).or_default().push(*version);
+
+        SsaVariable {
+            id,
+            version: *version,
+        }
+    }
+
+    /// Returns the current version of a variable
+    fn current(&self, id: u64) -> Option<SsaVariable> {
+        let version = *self.stacks.get(&id)?.last()?;
+        Some(SsaVariable { id, version })
+    }
+
+    /// Restores the versions that were current before a definition
+    fn undefine(&mut self, id: u64) {
+        if let Some(stack) = self.stacks.get_mut(&id) {
+            stack.pop();
+        }
+    }
+}
+
+/// Step of the dominator tree traversal
+enum Visit {
+    Enter(usize),
+    Exit(Vec<u64>),
+}
+
+impl SsaForm {
+    /// Computes the SSA form of a function
+    pub fn new(function: &Function) -> Result<Self> {
+        let cfg = function.cfg();
+
+        let mut form = Self::default();
+        let mut renamer = Renamer::default();
+
+        // The function parameters are defined at the function entry
+        for param in &function.function.params {
+            renamer.stacks.entry(param.id.id).or_default().push(0);
+            form.parameters.push(SsaVariable {
+                id: param.id.id,
+                version: 0,
+            });
+        }
+
+        // Split the statements using the CFG delimitations
+        let starts: HashSet<u32> = cfg.get_basic_blocks_delimitations().into_iter().collect();
+        let mut blocks: Vec<Vec<&SierraStatement>> = Vec::new();
+        for statement in function.statements() {
+            if blocks.is_empty() || starts.contains(&statement.offset) {
+                blocks.push(Vec::new());
+            }
+            if let Some(block) = blocks.last_mut() {
+                block.push(statement);
+            }
+        }
+        if blocks.is_empty() {
+            return Ok(form);
+        }
+
+        let blocks_indexes: HashMap<u32, usize> = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (block[0].offset, index))
+            .collect();
+        let start_offset = |index: usize| blocks[index][0].offset;
+
+        // Successors of each block with the branch of the last statement leading to them
+        // The branch is only set for the invocations with several branches
+        let successors: Vec<Vec<(usize, Option<usize>)>> = blocks
+            .iter()
+            .map(|block| block_successors(block, &blocks_indexes))
+            .collect();
+
+        let mut graph = DiGraph::<(), ()>::new();
+        for _ in &blocks {
+            graph.add_node(());
+        }
+        for (source, block_successors) in successors.iter().enumerate() {
+            for (destination, _) in block_successors {
+                graph.update_edge(NodeIndex::new(source), NodeIndex::new(*destination), ());
+            }
+        }
+
+        // Dominator tree rooted at the function entry, the unreachable blocks are ignored
+        let dominators = simple_fast(&graph, NodeIndex::new(0));
+        let immediate_dominator = |index: usize| {
+            dominators
+                .immediate_dominator(NodeIndex::new(index))
+                .map(|dominator| dominator.index())
+        };
+        let reachable: Vec<bool> = (0..blocks.len())
+            .map(|index| index == 0 || immediate_dominator(index).is_some())
+            .collect();
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); blocks.len()];
+        for (source, block_successors) in successors.iter().enumerate() {
+            for (destination, _) in block_successors {
+                if reachable[source] && !predecessors[*destination].contains(&source) {
+                    predecessors[*destination].push(source);
+                }
+            }
+        }
+
+        for (index, block_predecessors) in predecessors.iter().enumerate() {
+            if reachable[index] {
+                form.predecessors.insert(
+                    start_offset(index),
+                    block_predecessors
+                        .iter()
+                        .map(|predecessor| start_offset(*predecessor))
+                        .collect(),
+                );
+            }
+        }
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); blocks.len()];
+        for index in 1..blocks.len() {
+            if let Some(dominator) = immediate_dominator(index) {
+                children[dominator].push(index);
+                form.immediate_dominators
+                    .insert(start_offset(index), start_offset(dominator));
+            }
+        }
+
+        // Dominance frontiers (Cooper, Harvey & Kennedy)
+        let mut frontiers: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); blocks.len()];
+        for (index, block_predecessors) in predecessors.iter().enumerate() {
+            if block_predecessors.len() < 2 {
+                continue;
+            }
+            for predecessor in block_predecessors {
+                let mut runner = Some(*predecessor);
+                while let Some(current) = runner {
+                    if Some(current) == immediate_dominator(index) {
+                        break;
+                    }
+                    frontiers[current].insert(index);
+                    runner = immediate_dominator(current);
+                }
+            }
+        }
+        for (index, frontier) in frontiers.iter().enumerate() {
+            if reachable[index] {
+                form.dominance_frontiers.insert(
+                    start_offset(index),
+                    frontier.iter().map(|block| start_offset(*block)).collect(),
+                );
+            }
+        }
+
+        // The results of a branch are defined in its target block if it's only reached by it
+        let mut entry_definitions: HashMap<usize, (u32, usize, Vec<u64>)> = HashMap::new();
+        let mut deferred_branches: HashSet<(u32, usize)> = HashSet::new();
+        for (source, block_successors) in successors.iter().enumerate() {
+            for (destination, branch) in block_successors {
+                let Some(branch) = branch else {
+                    continue;
+                };
+                let targets = block_successors
+                    .iter()
+                    .filter(|(other, _)| other == destination)
+                    .count();
+                if targets != 1 || predecessors[*destination] != [source] {
+                    continue;
+                }
+
+                let Some(statement) = blocks[source].last() else {
+                    continue;
+                };
+                if let GenStatement::Invocation(invocation) = &statement.statement {
+                    let results = invocation.branches[*branch]
+                        .results
+                        .iter()
+                        .map(|result| result.id)
+                        .collect();
+                    entry_definitions.insert(*destination, (statement.offset, *branch, results));
+                    deferred_branches.insert((statement.offset, *branch));
+                }
+            }
+        }
+
+        // Upward exposed uses & definitions of each block
+        let mut exposed_uses: Vec<HashSet<u64>> = vec![HashSet::new(); blocks.len()];
+        let mut definitions: Vec<HashSet<u64>> = vec![HashSet::new(); blocks.len()];
+        for (index, block) in blocks.iter().enumerate() {
+            if let Some((_, _, results)) = entry_definitions.get(&index) {
+                definitions[index].extend(results);
+            }
+            for statement in block {
+                for used in statement_uses(statement) {
+                    if !definitions[index].contains(&used) {
+                        exposed_uses[index].insert(used);
+                    }
+                }
+                for (branch, results) in statement_definitions(statement) {
+                    if !deferred_branches.contains(&(statement.offset, branch)) {
+                        definitions[index].extend(results);
+                    }
+                }
+            }
+        }
+
+        // Live variables at the start of each block, iterated until the fixed point
+        let mut live_in: Vec<HashSet<u64>> = vec![HashSet::new(); blocks.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for index in (0..blocks.len()).rev() {
+                let mut live: HashSet<u64> = successors[index]
+                    .iter()
+                    .flat_map(|(successor, _)| live_in[*successor].iter().copied())
+                    .filter(|variable| !definitions[index].contains(variable))
+                    .collect();
+                live.extend(&exposed_uses[index]);
+                if live != live_in[index] {
+                    live_in[index] = live;
+                    changed = true;
+                }
+            }
+        }
+
+        // Insert the phi functions at the iterated dominance frontiers of the definitions
+        let mut definition_blocks: BTreeMap<u64, BTreeSet<usize>> = BTreeMap::new();
+        for param in &function.function.params {
+            definition_blocks.entry(param.id.id).or_default().insert(0);
+        }
+        for (index, block_definitions) in definitions.iter().enumerate() {
+            for variable in block_definitions {
+                definition_blocks
+                    .entry(*variable)
+                    .or_default()
+                    .insert(index);
+            }
+        }
+        let mut phis: Vec<Vec<PhiNode>> = vec![Vec::new(); blocks.len()];
+        for (variable, blocks_defining) in &definition_blocks {
+            let mut worklist: Vec<usize> = blocks_defining.iter().copied().collect();
+            let mut phi_blocks = HashSet::new();
+            while let Some(index) = worklist.pop() {
+                for frontier in &frontiers[index] {
+                    if !live_in[*frontier].contains(variable) || !phi_blocks.insert(*frontier) {
+                        continue;
+                    }
+                    phis[*frontier].push(PhiNode {
+                        result: SsaVariable {
+                            id: *variable,
+                            version: 0,
+                        },
+                        operands: BTreeMap::new(),
+                    });
+                    if !blocks_defining.contains(frontier) {
+                        worklist.push(*frontier);
+                    }
+                }
+            }
+        }
+
+        // Rename the variables in a preorder traversal of the dominator tree
+        let mut worklist = vec![Visit::Enter(0)];
+        while let Some(visit) = worklist.pop() {
+            let index = match visit {
+                Visit::Enter(index) => index,
+                Visit::Exit(defined) => {
+                    for variable in defined {
+                        renamer.undefine(variable);
+                    }
+                    continue;
+                }
+            };
+            let mut defined = Vec::new();
+
+            for phi in &mut phis[index] {
+                phi.result = renamer.define(phi.result.id);
+                defined.push(phi.result.id);
+            }
+
+            if let Some((offset, branch, results)) = entry_definitions.get(&index) {
+                let versions = results
+                    .iter()
+                    .map(|result| renamer.define(*result))
+                    .collect();
+                defined.extend(results);
+                let statement = form.statements.entry(*offset).or_default();
+                if statement.definitions.len() <= *branch {
+                    statement.definitions.resize(*branch + 1, Vec::new());
+                }
+                statement.definitions[*branch] = versions;
+            }
+
+            for statement in &blocks[index] {
+                let uses = statement_uses(statement)
+                    .into_iter()
+                    .map(|used| {
+                        renamer.current(used).unwrap_or(SsaVariable {
+                            id: used,
+                            version: 0,
+                        })
+                    })
+                    .collect();
+
+                let branches_results = statement_definitions(statement);
+                let ssa_statement = form.statements.entry(statement.offset).or_default();
+                ssa_statement.block = start_offset(index);
+                ssa_statement.uses = uses;
+                if ssa_statement.definitions.len() < branches_results.len() {
+                    ssa_statement
+                        .definitions
+                        .resize(branches_results.len(), Vec::new());
+                }
+
+                for (branch, results) in branches_results {
+                    if deferred_branches.contains(&(statement.offset, branch)) {
+                        continue;
+                    }
+                    ssa_statement.definitions[branch] = results
+                        .iter()
+                        .map(|result| renamer.define(*result))
+                        .collect();
+                    defined.extend(results);
+                }
+            }
+
+            // Incoming versions of the successors phi functions
+            for (successor, _) in &successors[index] {
+                for phi in &mut phis[*successor] {
+                    phi.operands
+                        .insert(start_offset(index), renamer.current(phi.result.id));
+                }
+            }
+
+            worklist.push(Visit::Exit(defined));
+            for child in children[index].iter().rev() {
+                worklist.push(Visit::Enter(*child));
+            }
+        }
+
+        for (index, block_phis) in phis.into_iter().enumerate() {
+            if reachable[index] && !block_phis.is_empty() {
+                form.phis.insert(start_offset(index), block_phis);
+            }
+        }
+
+        Ok(form)
+    }
+
+    /// Returns the versions of the function parameters
+    #[inline]
+    pub fn parameters(&self) -> &[SsaVariable] {
+        &self.parameters
+    }
+
+    /// Returns the phi functions at the start of a basic block
+    pub fn phis(&self, block_offset: u32) -> &[PhiNode] {
+        self.phis
+            .get(&block_offset)
+            .map(|phis| phis.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the phi functions of all the basic blocks, ordered by start offset
+    pub fn all_phis(&self) -> impl Iterator<Item = (u32, &PhiNode)> {
+        self.phis
+            .iter()
+            .flat_map(|(offset, phis)| phis.iter().map(move |phi| (*offset, phi)))
+    }
+
+    /// Returns the versioned variables of a statement, None if it's unreachable
+    #[inline]
+    pub fn statement(&self, offset: u32) -> Option<&SsaStatement> {
+        self.statements.get(&offset)
+    }
+
+    /// Returns the start offsets of the predecessors of a basic block
+    pub fn predecessors(&self, block_offset: u32) -> &[u32] {
+        self.predecessors
+            .get(&block_offset)
+            .map(|predecessors| predecessors.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the start offset of the immediate dominator of a basic block
+    /// None for the function entry & the unreachable blocks
+    #[inline]
+    pub fn immediate_dominator(&self, block_offset: u32) -> Option<u32> {
+        self.immediate_dominators.get(&block_offset).copied()
+    }
+
+    /// Returns the start offsets of the dominance frontier of a basic block
+    pub fn dominance_frontier(&self, block_offset: u32) -> Vec<u32> {
+        self.dominance_frontiers
+            .get(&block_offset)
+            .map(|frontier| frontier.iter().copied().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the successors of a block with the branch leading to them
+fn block_successors(
+    block: &[&SierraStatement],
+    blocks_indexes: &HashMap<u32, usize>,
+) -> Vec<(usize, Option<usize>)> {
+    let Some(last_statement) = block.last() else {
+        return Vec::new();
+    };
+
+    match &last_statement.statement {
+        GenStatement::Return(_) => Vec::new(),
+        GenStatement::Invocation(invocation) => {
+            let branching = invocation.branches.len() > 1;
+            invocation
+                .branches
+                .iter()
+                .enumerate()
+                .filter_map(|(branch, branch_info)| {
+                    let target = match &branch_info.target {
+                        BranchTarget::Fallthrough => last_statement.offset + 1,
+                        BranchTarget::Statement(statement_idx) => statement_idx.0 as u32,
+                    };
+                    blocks_indexes
+                        .get(&target)
+                        .map(|index| (*index, branching.then_some(branch)))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Returns the IDs of the variables used by a statement
+fn statement_uses(statement: &SierraStatement) -> Vec<u64> {
+    match &statement.statement {
+        GenStatement::Invocation(invocation) => invocation.args.iter().map(|arg| arg.id).collect(),
+        GenStatement::Return(returned_variables) => returned_variables
+            .iter()
+            .map(|variable| variable.id)
+            .collect(),
+    }
+}
+
+/// Returns the IDs of the variables defined by each branch of a statement
+fn statement_definitions(statement: &SierraStatement) -> Vec<(usize, Vec<u64>)> {
+    match &statement.statement {
+        GenStatement::Invocation(invocation) => invocation
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(branch, branch_info)| {
+                (
+                    branch,
+                    branch_info.results.iter().map(|result| result.id).collect(),
+                )
+            })
+            .collect(),
+        GenStatement::Return(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::analysis::storage_layout::{StorageLayout, StorageVariable};
+use crate::decompiler::function::{Function, FunctionType};
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+/// Access of an entry point to a storage variable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageAccess {
+    #[default]
+    None,
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl StorageAccess {
+    /// Returns the access from the read & write flags
+    pub fn new(reads: bool, writes: bool) -> Self {
+        match (reads, writes) {
+            (false, false) => StorageAccess::None,
+            (true, false) => StorageAccess::Read,
+            (false, true) => StorageAccess::Write,
+            (true, true) => StorageAccess::ReadWrite,
+        }
+    }
+
+    /// Returns the mark of the access in the matrix cells
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageAccess::None => "",
+            StorageAccess::Read => "R",
+            StorageAccess::Write => "W",
+            StorageAccess::ReadWrite => "RW",
+        }
+    }
+
+    /// Returns true if the variable can be written
+    pub fn writes(&self) -> bool {
+        matches!(self, StorageAccess::Write | StorageAccess::ReadWrite)
+    }
+}
+
+/// A row of the storage access matrix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageAccessEntry {
+    /// ABI name of the entry point
+    pub entry_point: String,
+    /// Name of the compiler wrapper of the entry point
+    pub wrapper: String,
+    /// Access to each variable, in the order of the matrix variables
+    pub accesses: Vec<StorageAccess>,
+}
+
+/// Matrix of the entry points vs. the storage variables they read & write
+/// The accesses of the functions reachable from the entry points wrappers are included, the
+/// variables are the ones of the storage layout
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StorageAccessMatrix {
+    /// Columns names, the variables names or the base addresses of the unknown variables
+    pub variables: Vec<String>,
+    pub entries: Vec<StorageAccessEntry>,
+}
+
+impl StorageAccessMatrix {
+    /// Builds the matrix of the entry points of the functions & of the storage layout variables
+    pub fn new(functions: &[Function], layout: &StorageLayout) -> Self {
+        let callgraph = CallGraph::new(functions);
+
+        let mut entries = Vec::new();
+        for function in functions {
+            if !matches!(function.function_type, Some(FunctionType::Wrapper)) {
+                continue;
+            }
+
+            let wrapper = parse_element_name!(function.function.id);
+            let Some(entry_point) = wrapper_entry_point_name(&wrapper) else {
+                continue;
+            };
+
+            let reachable = callgraph.reachable_from(&[wrapper.clone()]);
+            let accesses = layout
+                .variables
+                .iter()
+                .map(|variable| {
+                    let accessing = |functions: &[String]| {
+                        functions.iter().any(|name| reachable.contains(name))
+                    };
+                    StorageAccess::new(accessing(&variable.readers), accessing(&variable.writers))
+                })
+                .collect();
+
+            entries.push(StorageAccessEntry {
+                entry_point,
+                wrapper,
+                accesses,
+            });
+        }
+
+        Self {
+            variables: layout.variables.iter().map(variable_column).collect(),
+            entries,
+        }
+    }
+
+    /// Returns the access of an entry point to a variable, None if one of them isn't in the matrix
+    pub fn access(&self, entry_point: &str, variable: &str) -> Option<StorageAccess> {
+        let column = self.variables.iter().position(|name| name == variable)?;
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.entry_point == entry_point)?;
+
+        entry.accesses.get(column).copied()
+    }
+
+    /// Returns the entry points that can write a variable
+    pub fn writers(&self, variable: &str) -> Vec<&str> {
+        let Some(column) = self.variables.iter().position(|name| name == variable) else {
+            return Vec::new();
+        };
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.accesses[column].writes())
+            .map(|entry| entry.entry_point.as_str())
+            .collect()
+    }
+
+    /// Returns the matrix as a text table, one row per entry point & one column per variable
+    pub fn to_text(&self) -> String {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.entry_point.len())
+            .chain(std::iter::once("Entry point".len()))
+            .max()
+            .unwrap_or_default();
+        let widths: Vec<usize> = self
+            .variables
+            .iter()
+            .map(|variable| variable.len().max(2))
+            .collect();
+
+        let mut header = format!("{:<width$}", "Entry point");
+        for (variable, variable_width) in self.variables.iter().zip(&widths) {
+            header += &format!("  {:<variable_width$}", variable);
+        }
+        let mut text = header.trim_end().to_string() + "\n";
+
+        for entry in &self.entries {
+            let mut row = format!("{:<width$}", entry.entry_point);
+            for (access, variable_width) in entry.accesses.iter().zip(&widths) {
+                row += &format!("  {:<variable_width$}", access.as_str());
+            }
+            text += row.trim_end();
+            text += "\n";
+        }
+
+        text
+    }
+
+    /// Returns the matrix in CSV, with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = std::iter::once::<&str>("entry_point")
+            .chain(self.variables.iter().map(String::as_str))
+            .map(csv_field)
+            .collect::<Vec<_>>()
+            .join(",")
+            + "\n";
+
+        for entry in &self.entries {
+            csv += &std::iter::once(entry.entry_point.as_str())
+                .chain(entry.accesses.iter().map(|access| access.as_str()))
+                .map(csv_field)
+                .collect::<Vec<_>>()
+                .join(",");
+            csv += "\n";
+        }
+
+        csv
+    }
+
+    /// Returns the matrix in JSON, with the wrappers of the entry points
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+impl fmt::Display for StorageAccessMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+/// Returns the column name of a variable, its base address if its name is unknown
+/// The variables declared with the same name by several modules share a column name
+fn variable_column(variable: &StorageVariable) -> String {
+    match &variable.name {
+        Some(name) => name.clone(),
+        None => format!("{:#x}", variable.base_address),
+    }
+}
+
+/// Returns a CSV field, quoted if it contains a separator or a quote
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+use cairo_lang_sierra::program::GenStatement;
+use lazy_static::lazy_static;
+use num_bigint::BigUint;
+use regex::Regex;
+use serde::{Serialize, Serializer};
+use starknet_core::utils::starknet_keccak;
+
+use crate::decompiler::decompiler::shorten_type_name;
+use crate::decompiler::function::Function;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::CONST_REGEXES;
+use crate::parse_element_name;
+
+lazy_static! {
+    /// Member state of a storage variable, the name is the last segment of the path
+    /// e.g. `erc20::erc20::__member_module_balances::ComponentMemberState` or `erc20::erc20::balances::ComponentMemberState`
+    static ref MEMBER_STATE_REGEX: Regex =
+        Regex::new(r"(?P<path>[\w:]+)::(Contract|Component)MemberState\b").unwrap();
+
+    /// Storage variables accessors, their generic arguments are the member state, the key type
+    /// for the maps & the value type
+    static ref MEMBER_ACCESS_REGEX: Regex =
+        Regex::new(r"core::starknet::storage::Storage(?P<map>Map)?MemberAccessImpl::<").unwrap();
+}
+
+/// Kind of a storage variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageKind {
+    /// A value stored from the base address
+    Value,
+    /// A map whose entries are stored at the hash of the base address & the key
+    Map,
+}
+
+impl StorageKind {
+    /// Returns the name of the kind, as in the table column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageKind::Value => "value",
+            StorageKind::Map => "map",
+        }
+    }
+}
+
+/// A storage variable of the layout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageVariable {
+    /// Name of the variable, None for the base addresses constants without a declared variable
+    pub name: Option<String>,
+    /// Path of the contract or component module declaring the variable
+    pub module: Option<String>,
+    /// Base address of the variable, the starknet keccak of its name
+    #[serde(serialize_with = "serialize_address")]
+    pub base_address: BigUint,
+    /// Kind of the variable, the constants without a declared variable are values
+    pub kind: StorageKind,
+    /// Type of the keys of a map
+    pub key_type: Option<String>,
+    /// Type of the stored values
+    pub value_type: Option<String>,
+    /// Functions reading the storage from the base address
+    pub readers: Vec<String>,
+    /// Functions writing the storage from the base address
+    pub writers: Vec<String>,
+}
+
+impl StorageVariable {
+    /// Returns the name of the variable, `<unknown>` for the recovered constants
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("<unknown>")
+    }
+
+    /// Returns the type of the variable, e.g. `Map<ContractAddress, u256>`
+    pub fn type_name(&self) -> String {
+        let value_type = self.value_type.as_deref().unwrap_or("?");
+        match self.kind {
+            StorageKind::Value => value_type.to_string(),
+            StorageKind::Map => format!(
+                "Map<{}, {}>",
+                self.key_type.as_deref().unwrap_or("?"),
+                value_type
+            ),
+        }
+    }
+}
+
+/// Variables stored from the same base address
+/// The values are overwritten by each other & the entries of the maps are hashed from the same prefix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageCollision {
+    #[serde(serialize_with = "serialize_address")]
+    pub base_address: BigUint,
+    /// Qualified names of the colliding variables
+    pub variables: Vec<String>,
+    /// True if a colliding variable is a map
+    pub has_map: bool,
+}
+
+impl fmt::Display for StorageCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} have the same base address {:#x}",
+            self.variables.join(" & "),
+            self.base_address
+        )?;
+        if self.has_map {
+            write!(f, ", the map entries are hashed from the same prefix")?;
+        }
+        Ok(())
+    }
+}
+
+/// Storage layout of a contract
+/// The variables are declared by their member states in the program debug names, the base
+/// addresses constants used by the functions without a declared variable are also listed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StorageLayout {
+    /// Variables sorted by name, then the recovered constants by base address
+    pub variables: Vec<StorageVariable>,
+    pub collisions: Vec<StorageCollision>,
+}
+
+impl StorageLayout {
+    /// Builds the storage layout of the functions with the declared libfuncs & types names
+    pub fn new(
+        functions: &[Function],
+        declared_libfuncs_names: &[Arc<str>],
+        declared_types_names: &[Arc<str>],
+    ) -> Self {
+        let names: Vec<String> = declared_types_names
+            .iter()
+            .chain(declared_libfuncs_names)
+            .map(|name| name.to_string())
+            .chain(
+                functions
+                    .iter()
+                    .map(|function| parse_element_name!(function.function.id)),
+            )
+            .collect();
+
+        // Declared variables, indexed by module & name
+        let mut variables: BTreeMap<(String, String), StorageVariable> = BTreeMap::new();
+        for name in &names {
+            for captures in MEMBER_STATE_REGEX.captures_iter(name) {
+                let Some((module, name)) = member_state_variable(&captures["path"]) else {
+                    continue;
+                };
+                variables
+                    .entry((module.clone(), name.clone()))
+                    .or_insert_with(|| StorageVariable {
+                        base_address: storage_base_address(&name),
+                        name: Some(name),
+                        module: Some(module),
+                        kind: StorageKind::Value,
+                        key_type: None,
+                        value_type: None,
+                        readers: Vec::new(),
+                        writers: Vec::new(),
+                    });
+            }
+        }
+
+        // Types of the variables, from their accessors generic arguments
+        for name in &names {
+            for access in MEMBER_ACCESS_REGEX.captures_iter(name) {
+                let arguments = generic_arguments(&name[access.get(0).unwrap().end()..]);
+                let Some(member_state) = arguments
+                    .first()
+                    .and_then(|argument| MEMBER_STATE_REGEX.captures(argument))
+                else {
+                    continue;
+                };
+                let Some(key) = member_state_variable(&member_state["path"]) else {
+                    continue;
+                };
+                let Some(variable) = variables.get_mut(&key) else {
+                    continue;
+                };
+
+                let type_name =
+                    |index: usize| arguments.get(index).map(|arg| shorten_type_name(arg));
+                if access.name("map").is_some() {
+                    variable.kind = StorageKind::Map;
+                    variable.key_type = type_name(1);
+                    variable.value_type = type_name(2);
+                } else {
+                    variable.value_type = type_name(1);
+                }
+            }
+        }
+
+        let mut variables: Vec<StorageVariable> = variables.into_values().collect();
+
+        // Base addresses used by each function, the constants that aren't a declared variable
+        // base address are only kept if they are storage base addresses
+        let mut recovered: BTreeMap<BigUint, StorageVariable> = BTreeMap::new();
+        for function in functions {
+            let function_name = parse_element_name!(function.function.id);
+            let reads = function
+                .statements()
+                .iter()
+                .any(|statement| statement.libfunc_kind == LibfuncKind::StorageRead);
+            let writes = function
+                .statements()
+                .iter()
+                .any(|statement| statement.libfunc_kind == LibfuncKind::StorageWrite);
+            if !reads && !writes {
+                continue;
+            }
+
+            for (address, is_base_address) in function_constants(function) {
+                let mut matching: Vec<&mut StorageVariable> = variables
+                    .iter_mut()
+                    .filter(|variable| variable.base_address == address)
+                    .collect();
+                if matching.is_empty() && is_base_address {
+                    let variable =
+                        recovered
+                            .entry(address.clone())
+                            .or_insert_with(|| StorageVariable {
+                                name: None,
+                                module: None,
+                                base_address: address,
+                                kind: StorageKind::Value,
+                                key_type: None,
+                                value_type: None,
+                                readers: Vec::new(),
+                                writers: Vec::new(),
+                            });
+                    matching.push(variable);
+                }
+
+                for variable in matching {
+                    if reads && !variable.readers.contains(&function_name) {
+                        variable.readers.push(function_name.clone());
+                    }
+                    if writes && !variable.writers.contains(&function_name) {
+                        variable.writers.push(function_name.clone());
+                    }
+                }
+            }
+        }
+
+        variables.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.module.cmp(&b.module)));
+        variables.extend(recovered.into_values());
+
+        Self {
+            collisions: storage_collisions(&variables),
+            variables,
+        }
+    }
+
+    /// Returns the layout as text, one row per variable followed by the collisions
+    pub fn to_text(&self) -> String {
+        let column_width = |header: &str, value: &dyn Fn(&StorageVariable) -> usize| {
+            self.variables
+                .iter()
+                .map(value)
+                .chain(std::iter::once(header.len()))
+                .max()
+                .unwrap_or_default()
+        };
+        let name_width = column_width("Variable", &|variable| variable.display_name().len());
+        let type_width = column_width("Type", &|variable| variable.type_name().len());
+        let address_width = column_width("Base address", &|variable| {
+            format!("{:#x}", variable.base_address).len()
+        });
+
+        let mut text = format!(
+            "{:<name_width$}  {:<type_width$}  {:<address_width$}  Readers / Writers\n",
+            "Variable", "Type", "Base address"
+        );
+        for variable in &self.variables {
+            text += &format!(
+                "{:<name_width$}  {:<type_width$}  {:<address_width$}  {} / {}\n",
+                variable.display_name(),
+                variable.type_name(),
+                format!("{:#x}", variable.base_address),
+                functions_list(&variable.readers),
+                functions_list(&variable.writers)
+            );
+        }
+
+        for collision in &self.collisions {
+            text += &format!("\nCollision: {}", collision);
+        }
+        if !self.collisions.is_empty() {
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Returns the layout as a Markdown table followed by the collisions
+    pub fn to_markdown(&self) -> String {
+        let mut markdown =
+            "| Variable | Type | Base address | Readers | Writers |\n|---|---|---|---|---|\n"
+                .to_string();
+        for variable in &self.variables {
+            markdown += &format!(
+                "| `{}` | `{}` | `{:#x}` | {} | {} |\n",
+                variable.display_name(),
+                variable.type_name(),
+                variable.base_address,
+                functions_list(&variable.readers),
+                functions_list(&variable.writers)
+            );
+        }
+
+        if !self.collisions.is_empty() {
+            markdown += "\n**Collisions**\n\n";
+            for collision in &self.collisions {
+                markdown += &format!("- {}\n", collision);
+            }
+        }
+
+        markdown
+    }
+
+    /// Returns the layout in JSON, with the full names of the readers & writers
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+impl fmt::Display for StorageLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+/// Returns the base address of a storage variable, the starknet keccak of its name
+pub fn storage_base_address(name: &str) -> BigUint {
+    BigUint::from_bytes_be(&starknet_keccak(name.as_bytes()).to_bytes_be())
+}
+
+/// Returns the module & the name of the variable of a member state path
+fn member_state_variable(path: &str) -> Option<(String, String)> {
+    let (module, name) = path.rsplit_once("::")?;
+    let name = name.strip_prefix("__member_module_").unwrap_or(name);
+
+    Some((module.to_string(), name.to_string()))
+}
+
+/// Returns the top-level generic arguments following a `<`, e.g. `A, B::<C, D>>` gives `A` & `B::<C, D>`
+fn generic_arguments(text: &str) -> Vec<&str> {
+    let mut arguments = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            ')' => depth -= 1,
+            '>' if depth == 0 => {
+                arguments.push(text[start..index].trim());
+                break;
+            }
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    arguments
+}
+
+/// Returns the constants of a function, with true for the storage base addresses constants
+fn function_constants(function: &Function) -> Vec<(BigUint, bool)> {
+    function
+        .statements()
+        .iter()
+        .filter_map(|statement| match &statement.statement {
+            GenStatement::Invocation(invocation) => {
+                let libfunc_name = parse_element_name!(invocation.libfunc_id);
+                CONST_REGEXES.iter().find_map(|regex| {
+                    let value = regex.captures(&libfunc_name)?["const"].parse().ok()?;
+                    Some((
+                        value,
+                        libfunc_name.starts_with("storage_base_address_const"),
+                    ))
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the groups of variables with the same base address
+fn storage_collisions(variables: &[StorageVariable]) -> Vec<StorageCollision> {
+    let mut addresses: BTreeMap<&BigUint, Vec<&StorageVariable>> = BTreeMap::new();
+    for variable in variables {
+        addresses
+            .entry(&variable.base_address)
+            .or_default()
+            .push(variable);
+    }
+
+    addresses
+        .into_iter()
+        .filter(|(_, variables)| variables.len() > 1)
+        .map(|(base_address, variables)| StorageCollision {
+            base_address: base_address.clone(),
+            variables: variables
+                .iter()
+                .map(|variable| match &variable.module {
+                    Some(module) => format!("{}::{}", module, variable.display_name()),
+                    None => variable.display_name().to_string(),
+                })
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            has_map: variables
+                .iter()
+                .any(|variable| variable.kind == StorageKind::Map),
+        })
+        .collect()
+}
+
+/// Returns the short names of functions separated by commas, `-` if there is none
+fn functions_list(functions: &[String]) -> String {
+    if functions.is_empty() {
+        return "-".to_string();
+    }
+
+    functions
+        .iter()
+        .map(|function| {
+            let function = function
+                .split_once("::<")
+                .map_or(function.as_str(), |(function, _)| function);
+            function
+                .rsplit_once("::")
+                .map_or(function, |(_, name)| name)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Serializes an address in hexadecimal
+fn serialize_address<S: Serializer>(address: &BigUint, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{:#x}", address))
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use starknet_core::utils::starknet_keccak;
+
+use crate::detectors::detector::FindingReport;
+use crate::error::Result;
+use crate::sierra_program::SierraProgram;
+use crate::version::ANALYZER_VERSION;
+
+/// Version of the cache files format, the files of another format are ignored
+pub const CACHE_SCHEMA_VERSION: u32 = 1;
+
+/// Results of a detector, its text report & its located findings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectorResults {
+    /// Text output of the detector
+    pub report: String,
+    /// Findings of the detector, with their offsets
+    pub findings: Vec<FindingReport>,
+}
+
+/// Analysis results of a program saved in a cache file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CachedResults {
+    /// Version of the cache file format
+    schema: u32,
+    /// Version of the analyzer that computed the results
+    version: String,
+    /// Decompiled outputs, keyed by the decompiler options
+    decompiled: HashMap<String, String>,
+    /// Detectors results, keyed by the detector id & the function filter
+    detectors: HashMap<String, DetectorResults>,
+}
+
+/// On-disk cache of the analysis results of a program
+/// The results are saved in a `<cache key>.json` file of the cache directory,
+/// re-running the tool on the same program & analysis options with other output flags reuses them
+#[derive(Debug)]
+pub struct AnalysisCache {
+    /// Path of the cache file of the program
+    path: PathBuf,
+    /// Results read from the cache file & computed by this run
+    results: CachedResults,
+}
+
+impl AnalysisCache {
+    /// Opens the cache file of a program in a directory, it's created by `save` if it doesn't exist
+    /// `options` describes the analysis options the results depend on (config, invariants, solver
+    /// settings...), see `cache_key`
+    /// The unreadable cache files & the ones of another format or analyzer version are ignored
+    pub fn open(directory: &Path, program: &SierraProgram, options: &str) -> Result<Self> {
+        fs::create_dir_all(directory)?;
+        let path = directory.join(format!("{}.json", cache_key(program, options)));
+
+        let results = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CachedResults>(&content).ok())
+            .filter(|results| {
+                results.schema == CACHE_SCHEMA_VERSION && results.version == ANALYZER_VERSION
+            })
+            .unwrap_or_else(|| CachedResults {
+                schema: CACHE_SCHEMA_VERSION,
+                version: ANALYZER_VERSION.to_string(),
+                ..Default::default()
+            });
+
+        Ok(Self { path, results })
+    }
+
+    /// Returns the path of the cache file
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the cached decompiled output for the decompiler options
+    pub fn decompiled(&self, options: &str) -> Option<&str> {
+        self.results.decompiled.get(options).map(String::as_str)
+    }
+
+    /// Caches the decompiled output for the decompiler options
+    pub fn set_decompiled(&mut self, options: &str, output: String) {
+        self.results.decompiled.insert(options.to_string(), output);
+    }
+
+    /// Returns the cached results of a detector, the findings depend on the function filter
+    pub fn detector_results(
+        &self,
+        detector_id: &str,
+        function_filter: Option<&str>,
+    ) -> Option<&DetectorResults> {
+        self.results
+            .detectors
+            .get(&detector_key(detector_id, function_filter))
+    }
+
+    /// Caches the results of a detector
+    pub fn set_detector_results(
+        &mut self,
+        detector_id: &str,
+        function_filter: Option<&str>,
+        results: DetectorResults,
+    ) {
+        self.results
+            .detectors
+            .insert(detector_key(detector_id, function_filter), results);
+    }
+
+    /// Writes the results to the cache file
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, serde_json::to_string(&self.results)?)?;
+        Ok(())
+    }
+}
+
+/// Returns the hash of a program & of its ABI, the name of its cache file
+/// The programs are the same whether they are read from a file, the network or stdin
+pub fn program_hash(program: &SierraProgram) -> String {
+    let mut content = program.program().to_string();
+    if let Some(abi) = &program.abi {
+        content += &serde_json::to_string(abi).unwrap_or_default();
+    }
+
+    format!("{:#x}", starknet_keccak(content.as_bytes()))
+}
+
+/// Returns the hash of a program & of the analysis options, the name of its cache file
+pub fn cache_key(program: &SierraProgram, options: &str) -> String {
+    let content = format!("{}\n{}", program_hash(program), options);
+
+    format!("{:#x}", starknet_keccak(content.as_bytes()))
+}
+
+/// Returns the key of the results of a detector
+fn detector_key(detector_id: &str, function_filter: Option<&str>) -> String {
+    match function_filter {
+        Some(function_name) => format!("{}@{}", detector_id, function_name),
+        None => detector_id.to_string(),
+    }
+}
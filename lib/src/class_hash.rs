@@ -0,0 +1,40 @@
+use starknet_core::types::contract::SierraClass;
+use starknet_core::types::Felt;
+
+use crate::error::{Result, SierraAnalyzerError};
+
+/// Result of the comparison of a local contract class with an on-chain class hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassHashVerification {
+    /// Class hash computed from the local contract class
+    pub local_class_hash: Felt,
+    /// Class hash fetched from the network
+    pub onchain_class_hash: Felt,
+}
+
+impl ClassHashVerification {
+    /// Returns true if the local contract class is the deployed one
+    #[inline]
+    pub fn is_match(&self) -> bool {
+        self.local_class_hash == self.onchain_class_hash
+    }
+}
+
+/// Computes the class hash of a Sierra contract class (e.g. a `contract_class.json` built by Scarb)
+pub fn compute_class_hash(content: &str) -> Result<Felt> {
+    let contract_class: SierraClass = serde_json::from_str(content)?;
+    contract_class
+        .class_hash()
+        .map_err(|err| SierraAnalyzerError::ClassHash(err.to_string()))
+}
+
+/// Compares the class hash of a local contract class with an on-chain class hash
+pub fn verify_class_hash(content: &str, onchain_class_hash: &str) -> Result<ClassHashVerification> {
+    let onchain_class_hash = Felt::from_hex(onchain_class_hash)
+        .map_err(|err| SierraAnalyzerError::ClassHash(err.to_string()))?;
+
+    Ok(ClassHashVerification {
+        local_class_hash: compute_class_hash(content)?,
+        onchain_class_hash,
+    })
+}
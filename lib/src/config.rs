@@ -1,3 +1,6 @@
+use crate::decompiler::cfg::CfgNodeDetail;
+use crate::sym_exec::hashes::HashModel;
+
 pub struct GraphConfig;
 
 #[allow(dead_code)]
@@ -9,6 +12,8 @@ impl GraphConfig {
     pub const CFG_NODE_ATTR_FILLCOLOR: &'static str = "#F5F5F5";
     pub const CFG_NODE_ATTR_FONTNAME: &'static str = "Helvetica,Arial,sans-serif";
     pub const CFG_NODE_ATTR_MARGIN: &'static str = "0.2";
+    // Content of the CFG nodes, used when no detail level is given
+    pub const CFG_NODE_DETAIL: CfgNodeDetail = CfgNodeDetail::Raw;
 
     // Graph attributes for CFG
     pub const CFG_GRAPH_ATTR_OVERLAP: &'static str = "scale";
@@ -53,4 +58,46 @@ impl GraphConfig {
     // Callgraph colors
     pub const CALLGRAPH_USER_DEFINED_FUNCTIONS_COLOR: &'static str = "#95D2B3";
     pub const CALLGRAPH_LIBFUNCS_COLOR: &'static str = "#E86356";
+
+    // Callgraph modules clusters colors, assigned in the order of the modules
+    pub const CALLGRAPH_CLUSTERS_COLORS: [&'static str; 8] = [
+        "#E3F2FD", "#FFF3E0", "#F3E5F5", "#E8F5E9", "#FFFDE7", "#FCE4EC", "#E0F7FA", "#EFEBE9",
+    ];
+
+    // Colors of the CFG & callgraph nodes containing a detector finding
+    pub const FINDING_SECURITY_COLOR: &'static str = "#EF5350";
+    pub const FINDING_CORRECTNESS_COLOR: &'static str = "#FFB74D";
+    pub const FINDING_OPTIMIZATION_COLOR: &'static str = "#A5D6A7";
+    pub const FINDING_INFORMATIONAL_COLOR: &'static str = "#FFF176";
+    pub const FINDING_TESTING_COLOR: &'static str = "#90CAF9";
+
+    // Cross-contract callgraph colors
+    pub const CROSS_CONTRACT_CALL_CONTRACT_EDGE_COLOR: &'static str = "#1F77B4";
+    pub const CROSS_CONTRACT_LIBRARY_CALL_EDGE_COLOR: &'static str = "#9467BD";
+}
+
+pub struct SymbolicExecutionConfig;
+
+#[allow(dead_code)]
+impl SymbolicExecutionConfig {
+    // Maximum number of paths explored per function
+    pub const MAX_PATHS: usize = 1024;
+    // Maximum number of basic blocks in an explored path
+    pub const MAX_PATH_DEPTH: usize = 256;
+    // Default number of times the loops (recursive functions) are unrolled
+    pub const UNROLL_BOUND: usize = 3;
+    // Maximum number of nested user-defined functions calls inlined to prove the panics reachability
+    pub const INLINE_DEPTH: usize = 4;
+    // Modeling of the hash functions used by the tests generator
+    pub const HASH_MODEL: HashModel = HashModel::Unconstrained;
+    // Maximum length of the arrays & spans arguments generated by the tests generator
+    pub const MAX_ARRAY_LENGTH: usize = 4;
+    // Maximum number of times the hash results of a path are replaced by their concrete values
+    pub const CONCRETIZATION_ROUNDS: usize = 4;
+    // Maximum time of a solver check in milliseconds, the checks reaching it are unknown (no limit by default)
+    pub const SOLVER_TIMEOUT: Option<u32> = None;
+    // Seed of the solver heuristics (Z3 default by default)
+    pub const SOLVER_RANDOM_SEED: Option<u32> = None;
+    // Gas available to the concrete executions of the concolic mode
+    pub const CONCRETE_AVAILABLE_GAS: usize = 1_000_000_000;
 }
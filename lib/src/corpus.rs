@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use similar::TextDiff;
+
+use crate::error::{Result, SierraAnalyzerError};
+use crate::sierra_program::SierraProgram;
+
+/// Prefix of the snapshot files, it is the name of the snapshot test file (`lib/tests/corpus.rs`)
+pub const SNAPSHOT_PREFIX: &str = "corpus";
+
+/// Sierra program or contract class of a decompilation corpus
+#[derive(Debug, Clone)]
+pub struct CorpusEntry {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Name of the snapshot, the path relative to the corpus directory (e.g. "sierra-fib.sierra")
+    pub name: String,
+}
+
+impl CorpusEntry {
+    /// Decompiles the entry with a colorless & non verbose output
+    pub fn decompile(&self) -> Result<String> {
+        let content = fs::read_to_string(&self.path)?;
+
+        // Extract the Sierra program of the contract classes
+        let program = match serde_json::from_str::<ContractClass>(&content) {
+            Ok(contract_class) => {
+                let sierra_program = contract_class
+                    .extract_sierra_program()
+                    .map_err(|err| SierraAnalyzerError::Parse(err.to_string()))?;
+                let mut program = SierraProgram::try_new(sierra_program.to_string())?;
+                if let Some(abi) = contract_class.abi {
+                    program.set_abi(abi);
+                }
+                program
+            }
+            Err(_) => SierraProgram::try_new(content)?,
+        };
+
+        program.decompiler(false).try_decompile(false)
+    }
+
+    /// Returns the snapshotted output of the entry, the decompilation errors are a part of it
+    pub fn snapshot_output(&self) -> String {
+        self.decompile()
+            .unwrap_or_else(|err| format!("error: {}", err))
+    }
+
+    /// Returns the path of the snapshot of the entry
+    pub fn snapshot_path(&self, snapshots_directory: &Path) -> PathBuf {
+        snapshots_directory.join(format!("{}__{}.snap", SNAPSHOT_PREFIX, self.name))
+    }
+}
+
+/// Returns the Sierra programs (`.sierra`) & contract classes (`.contract_class.json`)
+/// of a directory and its sub-directories, sorted by name
+pub fn corpus_entries(directory: &Path) -> Result<Vec<CorpusEntry>> {
+    let mut entries = Vec::new();
+    collect_entries(directory, directory, &mut entries)?;
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(entries)
+}
+
+/// Recursively collects the corpus entries of a directory
+fn collect_entries(root: &Path, directory: &Path, entries: &mut Vec<CorpusEntry>) -> Result<()> {
+    for dir_entry in fs::read_dir(directory)? {
+        let path = dir_entry?.path();
+        if path.is_dir() {
+            collect_entries(root, &path, entries)?;
+            continue;
+        }
+
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or_default();
+        if !file_name.ends_with(".sierra") && !file_name.ends_with(".contract_class.json") {
+            continue;
+        }
+
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .iter()
+            .map(|component| component.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("-");
+        entries.push(CorpusEntry { path, name });
+    }
+
+    Ok(())
+}
+
+/// Reads the content of an insta snapshot, without its metadata header
+pub fn read_snapshot(snapshot_path: &Path) -> Option<String> {
+    let snapshot = fs::read_to_string(snapshot_path).ok()?;
+    let content = snapshot
+        .strip_prefix("---\n")
+        .and_then(|snapshot| snapshot.split_once("\n---\n"))
+        .map_or(snapshot.as_str(), |(_header, content)| content);
+
+    Some(content.to_string())
+}
+
+/// Returns the unified diff between a snapshot and a decompiler output, or None if they are equal
+/// The trailing whitespaces are ignored like in the insta assertions
+pub fn diff_output(snapshot: &str, output: &str) -> Option<String> {
+    let snapshot = snapshot.trim_end();
+    let output = output.trim_end();
+    if snapshot == output {
+        return None;
+    }
+
+    Some(
+        TextDiff::from_lines(snapshot, output)
+            .unified_diff()
+            .context_radius(3)
+            .header("snapshot", "output")
+            .to_string(),
+    )
+}
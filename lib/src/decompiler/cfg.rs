@@ -1,11 +1,31 @@
 use std::cmp::PartialEq;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use cairo_lang_sierra::program::BranchTarget;
 use cairo_lang_sierra::program::GenStatement;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use serde::Serialize;
 
 use crate::config::GraphConfig;
-use crate::decompiler::function::SierraStatement;
+use crate::decompiler::formatting::strip_colors;
+use crate::decompiler::function::{PanicSafety, SierraStatement};
+use crate::detectors::detector::Finding;
+use crate::graph::graph::generate_findings_node;
+use crate::graph::graphml::GraphMlWriter;
+
+/// Content of the CFG nodes in the DOT output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CfgNodeDetail {
+    /// Raw Sierra statements, as in the original Sierra file
+    Raw,
+    /// Decompiled statements, as in the regular decompiler output
+    Decompiled,
+    /// Only the block name & its number of statements, for the large graphs
+    Summary,
+}
 
 /// A struct representing a control flow graph (CFG) for a function
 ///
@@ -19,7 +39,7 @@ pub struct ControlFlowGraph {
     function_name: String,
     /// List of statements in the function
     statements: Vec<SierraStatement>,
-    /// List of basic blocks in the CFG
+    /// List of basic blocks in the CFG, sorted by start offset
     pub basic_blocks: Vec<BasicBlock>,
 }
 
@@ -33,19 +53,18 @@ impl<'a> ControlFlowGraph {
         }
     }
 
-    /// Gets the start and end offsets of basic blocks within the function's control flow graph
-    pub fn get_basic_blocks_delimitations(&self) -> (Vec<u32>, Vec<u32>) {
-        // Initialize vectors to store the start and end offsets of basic blocks
+    /// Gets the start offsets of basic blocks within the function's control flow graph
+    pub fn get_basic_blocks_delimitations(&self) -> Vec<u32> {
+        // Initialize the vector storing the start offsets of basic blocks
         let mut basic_blocks_starts = vec![];
-        let mut basic_blocks_ends = vec![];
 
         // Iterate over each statement in the function
         for statement in &self.statements {
             // Match the type of statement
             match &statement.statement {
-                // If it's a return statement, add its offset to the list of basic block ends
+                // If it's a return statement, the next statement starts a new basic block
                 GenStatement::Return(_) => {
-                    basic_blocks_ends.push(statement.offset);
+                    basic_blocks_starts.push(statement.offset + 1);
                 }
                 // If it's an invocation statement
                 GenStatement::Invocation(invocation) => {
@@ -59,8 +78,6 @@ impl<'a> ControlFlowGraph {
                                 basic_blocks_starts.push(statement.offset + 1);
                                 // Add the offset of the targeted statement as the start of a new basic block
                                 basic_blocks_starts.push(statement_idx.0.try_into().unwrap());
-                                // Add the offset of the targeted statement as the end of the current basic block
-                                basic_blocks_ends.push(statement.offset);
                             }
                             // Ignore other types of branch targets
                             _ => {}
@@ -70,8 +87,7 @@ impl<'a> ControlFlowGraph {
             }
         }
 
-        // Return the vectors containing the start and end offsets of basic blocks
-        (basic_blocks_starts, basic_blocks_ends)
+        basic_blocks_starts
     }
 
     /// Generates the CFG basic blocks
@@ -82,10 +98,9 @@ impl<'a> ControlFlowGraph {
         }
 
         // Retrieve basic blocks delimitations
-        let (basic_blocks_starts, basic_blocks_ends) = self.get_basic_blocks_delimitations();
+        let basic_blocks_starts = self.get_basic_blocks_delimitations();
 
-        // Initialize variables for tracking the current basic block
-        let mut new_basic_block = true;
+        // Initialize the current basic block
         let mut current_basic_block = BasicBlock::new(self.statements[0].clone());
 
         // Iterate through each statement
@@ -94,25 +109,20 @@ impl<'a> ControlFlowGraph {
 
             // Check if the current statement marks the beginning of a new basic block
             if basic_blocks_starts.contains(&statement.offset) {
-                // If it's the beginning of a new basic block, push the previous one to the list
-                if new_basic_block {
+                // Push the previous basic block to the list, including the ones falling through
+                // into the new basic block
+                if !current_basic_block.statements.is_empty() {
                     self.basic_blocks.push(current_basic_block.clone());
                 }
                 // Create a new basic block
                 current_basic_block = BasicBlock::new(statement.clone());
-                new_basic_block = false;
             }
 
             // Add the current statement to the current basic block
             current_basic_block.statements.push(statement.clone());
 
-            // Check if the current statement marks the end of the current basic block
-            if basic_blocks_ends.contains(&statement.offset) {
-                new_basic_block = true;
-            }
-
             // Handle conditional branches
-            if let Some(conditional_branch) = statement.as_conditional_branch(vec![]) {
+            if let Some(conditional_branch) = statement.as_conditional_branch(&[]) {
                 if let Some(edge_2_offset) = conditional_branch.edge_2_offset {
                     // Conditional branch with 2 edges (JNZ)
                     current_basic_block.edges.push(Edge {
@@ -154,39 +164,58 @@ impl<'a> ControlFlowGraph {
     }
 
     /// Returns all the possible paths in a function
+    /// A path stops when it would loop back to one of its own blocks
+    #[inline]
     pub fn paths(&self) -> Vec<Vec<&BasicBlock>> {
-        let mut paths = Vec::new();
+        self.paths_iter(usize::MAX, usize::MAX).collect()
+    }
 
-        // Find the starting blocks
-        let mut start_blocks = Vec::new();
-        for block in &self.basic_blocks {
-            if self.parents(block).is_empty() {
-                start_blocks.push(block);
-            }
+    /// Returns a lazy iterator over the paths of the function
+    ///
+    /// - At most `max_paths` paths are returned
+    /// - Paths are truncated after `max_depth` basic blocks
+    /// - A path stops when it would loop back to one of its own blocks
+    pub fn paths_iter(&self, max_paths: usize, max_depth: usize) -> PathsIter<'_> {
+        // Children blocks indexes of each block
+        let children: Vec<Vec<usize>> = self
+            .basic_blocks
+            .iter()
+            .map(|block| {
+                let edges_destinations: HashSet<_> =
+                    block.edges.iter().map(|edge| edge.destination).collect();
+                self.basic_blocks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, child)| edges_destinations.contains(&child.start_offset))
+                    .map(|(index, _)| index)
+                    .collect()
+            })
+            .collect();
+
+        // Find the starting blocks, the function entry is used if every block has a parent
+        let mut start_blocks: Vec<usize> = (0..self.basic_blocks.len())
+            .filter(|index| self.parents(&self.basic_blocks[*index]).is_empty())
+            .collect();
+        if start_blocks.is_empty() && !self.basic_blocks.is_empty() {
+            start_blocks.push(0);
         }
 
-        // Perform DFS from each start block to find all paths
-        for start_block in start_blocks {
-            let mut stack = vec![(vec![start_block], start_block)];
-            while let Some((current_path, current_block)) = stack.pop() {
-                let children = self.children(current_block);
-                if children.is_empty() {
-                    paths.push(current_path.clone());
-                } else {
-                    for child in children {
-                        let mut new_path = current_path.clone();
-                        new_path.push(child);
-                        stack.push((new_path, child));
-                    }
-                }
-            }
+        // The first start block is explored first
+        start_blocks.reverse();
+
+        PathsIter {
+            cfg: self,
+            children,
+            start_blocks,
+            stack: Vec::new(),
+            max_paths,
+            max_depth: max_depth.max(1),
+            returned_paths: 0,
         }
-
-        paths
     }
 
     /// Returns the children blocks of a basic block
-    fn children(&self, block: &BasicBlock) -> Vec<&BasicBlock> {
+    pub fn children(&self, block: &BasicBlock) -> Vec<&BasicBlock> {
         let mut children = Vec::new();
         let edges_destinations: HashSet<_> =
             block.edges.iter().map(|edge| edge.destination).collect();
@@ -200,7 +229,7 @@ impl<'a> ControlFlowGraph {
     }
 
     /// Returns the parent blocks of a basic block
-    fn parents(&self, block: &BasicBlock) -> Vec<&BasicBlock> {
+    pub fn parents(&self, block: &BasicBlock) -> Vec<&BasicBlock> {
         let mut parents = Vec::new();
         let start_offset = block.start_offset;
 
@@ -217,8 +246,113 @@ impl<'a> ControlFlowGraph {
         parents
     }
 
+    /// Returns the CFG as a directed graph of basic blocks
+    /// The DOT & JSON representations of the CFG are derived from it
+    /// Edges whose destination is not the start of a basic block are skipped
+    pub fn graph(&self) -> DiGraph<BasicBlock, EdgeType> {
+        let mut graph = DiGraph::new();
+        let mut blocks_indexes = HashMap::new();
+
+        // One node per basic block, in the function order
+        for block in &self.basic_blocks {
+            let index = graph.add_node(block.clone());
+            blocks_indexes.entry(block.start_offset).or_insert(index);
+        }
+
+        // Edges in the basic blocks order
+        for (source, block) in graph.node_indices().zip(&self.basic_blocks) {
+            for edge in &block.edges {
+                if let Some(destination) = blocks_indexes.get(&edge.destination) {
+                    graph.add_edge(source, *destination, edge.edge_type.clone());
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the serializable representation of the function CFG, with the panic safety of the function
+    pub(crate) fn json(&self, panic_safety: PanicSafety) -> JsonControlFlowGraph {
+        let graph = self.graph();
+
+        JsonControlFlowGraph {
+            function: self.function_name.clone(),
+            panic_safety,
+            basic_blocks: graph
+                .node_weights()
+                .map(|block| JsonBasicBlock {
+                    name: block.name.clone(),
+                    start_offset: block.start_offset,
+                    statements: block
+                        .statements
+                        .iter()
+                        .map(|statement| statement.raw_statement())
+                        .collect(),
+                })
+                .collect(),
+            edges: graph
+                .edge_references()
+                .map(|edge| JsonCfgEdge {
+                    source: graph[edge.source()].name.clone(),
+                    destination: graph[edge.target()].name.clone(),
+                    kind: edge.weight().clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds the basic blocks & edges of the function CFG to a GraphML document
+    /// The nodes IDs are prefixed by the function name to be unique in the program
+    pub fn add_to_graphml(&self, writer: &mut GraphMlWriter) {
+        let graph = self.graph();
+        let node_id = |block: &BasicBlock| format!("{}::{}", self.function_name, block.name);
+
+        for block in graph.node_weights() {
+            let statements: Vec<String> = block
+                .statements
+                .iter()
+                .map(|statement| format!("{} : {}", statement.offset, statement.raw_statement()))
+                .collect();
+            let start_offset = block.start_offset.to_string();
+            let statements = statements.join("\n");
+            writer.add_node(
+                &node_id(block),
+                &[
+                    ("label", block.name.as_str()),
+                    ("function", self.function_name.as_str()),
+                    ("start_offset", start_offset.as_str()),
+                    ("statements", statements.as_str()),
+                ],
+            );
+        }
+
+        for edge in graph.edge_references() {
+            writer.add_edge(
+                &node_id(&graph[edge.source()]),
+                &node_id(&graph[edge.target()]),
+                &[("kind", edge.weight().as_str())],
+            );
+        }
+    }
+
     /// Generates the DOT format subgraph for function CFG
+    #[inline]
     pub fn generate_dot_graph(&self) -> String {
+        self.generate_dot_graph_with_detail(GraphConfig::CFG_NODE_DETAIL, &[], &[], &[])
+    }
+
+    /// Generates the DOT format subgraph for function CFG with the given nodes content
+    /// The declared libfuncs & types names are used to decompile the statements of remote contracts
+    /// The blocks containing a detector finding are highlighted
+    pub fn generate_dot_graph_with_detail(
+        &self,
+        detail: CfgNodeDetail,
+        declared_libfuncs_names: &[Arc<str>],
+        declared_types_names: &[Arc<str>],
+        findings: &[Finding],
+    ) -> String {
+        let graph = self.graph();
+
         let mut dot_graph = format!("\tsubgraph \"cluster_{}\" {{\n", self.function_name);
         dot_graph += &format!("\t\tlabel=\"{}\"\n", self.function_name);
         dot_graph += &format!(
@@ -228,15 +362,41 @@ impl<'a> ControlFlowGraph {
         dot_graph += &format!("\t\tfontsize={};\n", GraphConfig::CFG_GRAPH_ATTR_FONTSIZE);
 
         // Iterate over each basic block to create nodes
-        for block in &self.basic_blocks {
-            let mut label_instruction = String::new();
-            for statement in &block.statements {
-                label_instruction += &format!(
-                    "{} : {}\t\t\\l",
-                    statement.offset,
-                    statement.raw_statement()
-                );
-            }
+        for block in graph.node_weights() {
+            let label_instruction = match detail {
+                CfgNodeDetail::Raw => block
+                    .statements
+                    .iter()
+                    .map(|statement| {
+                        format!(
+                            "{} : {}\t\t\\l",
+                            statement.offset,
+                            statement.raw_statement()
+                        )
+                    })
+                    .collect(),
+                CfgNodeDetail::Decompiled => block
+                    .statements
+                    .iter()
+                    .filter_map(|statement| {
+                        decompiled_statement(
+                            statement,
+                            declared_libfuncs_names,
+                            declared_types_names,
+                        )
+                        .map(|decompiled| {
+                            format!(
+                                "{} : {}\t\t\\l",
+                                statement.offset,
+                                escape_label(&decompiled)
+                            )
+                        })
+                    })
+                    .collect(),
+                CfgNodeDetail::Summary => {
+                    format!("{} : {} statements\\l", block.name, block.statements.len())
+                }
+            };
 
             dot_graph += &format!(
                 "\t\t\"{}\" [label=\"{}\" shape=\"box\" style=\"{}\" fillcolor=\"{}\" color=\"{}\" fontname=\"{}\" margin=\"{}\"];\n",
@@ -250,50 +410,174 @@ impl<'a> ControlFlowGraph {
             );
         }
 
-        // Add edges between nodes
-        for block in &self.basic_blocks {
-            for edge in &block.edges {
-                let color = match edge.edge_type {
-                    EdgeType::ConditionalTrue => GraphConfig::EDGE_CONDITIONAL_TRUE_COLOR,
-                    EdgeType::ConditionalFalse => GraphConfig::EDGE_CONDITIONAL_FALSE_COLOR,
-                    EdgeType::Unconditional => GraphConfig::EDGE_UNCONDITIONAL_COLOR,
-                    EdgeType::Fallthrough => GraphConfig::EDGE_FALLTHROUGH_COLOR,
-                };
-                if self
-                    .basic_blocks
-                    .iter()
-                    .any(|b| b.start_offset == edge.destination)
-                {
-                    dot_graph += &format!(
-                        "\t\t\"{}\" -> \"{}\" [color=\"{}\" arrowsize={} fontname=\"{}\" labeldistance={} labelfontcolor=\"{}\" penwidth={}];\n",
-                        block.name,
-                        self.get_block_name_by_offset(edge.destination),
-                        color,
-                        GraphConfig::CFG_EDGE_ATTR_ARROWSIZE,
-                        GraphConfig::CFG_EDGE_ATTR_FONTNAME,
-                        GraphConfig::CFG_EDGE_ATTR_LABELDISTANCE,
-                        GraphConfig::CFG_EDGE_ATTR_LABELFONTCOLOR,
-                        GraphConfig::CFG_EDGE_ATTR_PENWIDTH
-                    );
-                }
+        // Highlight the blocks containing a finding, the findings of the whole function
+        // are attached to its entry block
+        let function_findings: Vec<&Finding> = findings
+            .iter()
+            .filter(|finding| finding.function == self.function_name)
+            .collect();
+        for (index, block) in graph.node_weights().enumerate() {
+            let block_findings: Vec<&Finding> = function_findings
+                .iter()
+                .filter(|finding| match finding.offset {
+                    Some(offset) => block
+                        .statements
+                        .iter()
+                        .any(|statement| statement.offset == offset),
+                    None => index == 0,
+                })
+                .copied()
+                .collect();
+            if !block_findings.is_empty() {
+                dot_graph += &format!(
+                    "\t\t{}",
+                    generate_findings_node(&block.name, &block_findings)
+                );
             }
         }
 
+        // Add edges between nodes
+        for edge in graph.edge_references() {
+            let color = match edge.weight() {
+                EdgeType::ConditionalTrue => GraphConfig::EDGE_CONDITIONAL_TRUE_COLOR,
+                EdgeType::ConditionalFalse => GraphConfig::EDGE_CONDITIONAL_FALSE_COLOR,
+                EdgeType::Unconditional => GraphConfig::EDGE_UNCONDITIONAL_COLOR,
+                EdgeType::Fallthrough => GraphConfig::EDGE_FALLTHROUGH_COLOR,
+            };
+            dot_graph += &format!(
+                "\t\t\"{}\" -> \"{}\" [color=\"{}\" arrowsize={} fontname=\"{}\" labeldistance={} labelfontcolor=\"{}\" penwidth={}];\n",
+                graph[edge.source()].name,
+                graph[edge.target()].name,
+                color,
+                GraphConfig::CFG_EDGE_ATTR_ARROWSIZE,
+                GraphConfig::CFG_EDGE_ATTR_FONTNAME,
+                GraphConfig::CFG_EDGE_ATTR_LABELDISTANCE,
+                GraphConfig::CFG_EDGE_ATTR_LABELFONTCOLOR,
+                GraphConfig::CFG_EDGE_ATTR_PENWIDTH
+            );
+        }
+
         dot_graph += "\t}\n";
         dot_graph
     }
+}
 
-    /// Retrieves the name of a basic block based on its start offset
-    fn get_block_name_by_offset(&self, offset: u32) -> String {
-        self.basic_blocks
-            .iter()
-            .find(|&b| b.start_offset == offset)
-            .map_or(String::from("Unknown"), |b| b.name.clone())
+/// Decompiles a statement for a CFG node, the conditional branches are rendered as conditions
+/// The statements hidden in the regular decompiler output are skipped
+fn decompiled_statement(
+    statement: &SierraStatement,
+    declared_libfuncs_names: &[Arc<str>],
+    declared_types_names: &[Arc<str>],
+) -> Option<String> {
+    let decompiled = match statement.as_conditional_branch(declared_libfuncs_names) {
+        Some(conditional_branch) => format!(
+            "if ({}({}))",
+            conditional_branch.function,
+            conditional_branch.parameters.join(", ")
+        ),
+        None => {
+            statement.formatted_statement(false, declared_libfuncs_names, declared_types_names)?
+        }
+    };
+
+    Some(strip_colors(&decompiled))
+}
+
+/// Escapes a string used in a DOT label
+fn escape_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Serializable representation of a basic block
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonBasicBlock {
+    name: String,
+    start_offset: u32,
+    statements: Vec<String>,
+}
+
+/// Serializable representation of a CFG edge
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonCfgEdge {
+    source: String,
+    destination: String,
+    kind: EdgeType,
+}
+
+/// Serializable representation of a function CFG
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonControlFlowGraph {
+    function: String,
+    panic_safety: PanicSafety,
+    basic_blocks: Vec<JsonBasicBlock>,
+    edges: Vec<JsonCfgEdge>,
+}
+
+/// Lazy depth-first iterator over the paths of a control flow graph
+#[derive(Debug)]
+pub struct PathsIter<'a> {
+    /// The explored CFG
+    cfg: &'a ControlFlowGraph,
+    /// Children blocks indexes of each block
+    children: Vec<Vec<usize>>,
+    /// Start blocks indexes that are not explored yet
+    start_blocks: Vec<usize>,
+    /// Partial paths (blocks indexes) to explore
+    stack: Vec<Vec<usize>>,
+    /// Maximum number of returned paths
+    max_paths: usize,
+    /// Maximum number of blocks in a path
+    max_depth: usize,
+    /// Number of paths already returned
+    returned_paths: usize,
+}
+
+impl<'a> Iterator for PathsIter<'a> {
+    type Item = Vec<&'a BasicBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.returned_paths >= self.max_paths {
+            return None;
+        }
+
+        loop {
+            // Start exploring the next start block once the previous one is done
+            let current_path = match self.stack.pop() {
+                Some(path) => path,
+                None => vec![self.start_blocks.pop()?],
+            };
+            let current_block = *current_path.last().unwrap();
+
+            // Skip the children already in the path to handle the loops
+            let children: Vec<usize> = self.children[current_block]
+                .iter()
+                .filter(|child| !current_path.contains(child))
+                .copied()
+                .collect();
+
+            // The path ends on an exit block, a loop or when it is too deep
+            if children.is_empty() || current_path.len() >= self.max_depth {
+                self.returned_paths += 1;
+                return Some(
+                    current_path
+                        .iter()
+                        .map(|index| &self.cfg.basic_blocks[*index])
+                        .collect(),
+                );
+            }
+
+            for child in children {
+                let mut new_path = current_path.clone();
+                new_path.push(child);
+                self.stack.push(new_path);
+            }
+        }
     }
 }
 
 /// Enum representing different types of CFG edges
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum EdgeType {
     Unconditional,
     ConditionalTrue,
@@ -301,6 +585,18 @@ pub enum EdgeType {
     Fallthrough,
 }
 
+impl EdgeType {
+    /// Returns the name of the edge type, as in the JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeType::Unconditional => "unconditional",
+            EdgeType::ConditionalTrue => "conditional_true",
+            EdgeType::ConditionalFalse => "conditional_false",
+            EdgeType::Fallthrough => "fallthrough",
+        }
+    }
+}
+
 /// Struct representing a control flow graph (CFG) edge
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -1,10 +1,11 @@
 use colored::*;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use cairo_lang_sierra::extensions::core::CoreLibfunc;
 use cairo_lang_sierra::extensions::core::CoreType;
 use cairo_lang_sierra::program::GenFunction;
+use cairo_lang_sierra::program::GenStatement;
 use cairo_lang_sierra::program::GenericArg;
 use cairo_lang_sierra::program::LibfuncDeclaration;
 use cairo_lang_sierra::program::StatementIdx;
@@ -13,22 +14,39 @@ use cairo_lang_sierra::program_registry::ProgramRegistry;
 use cairo_lang_starknet_classes::abi::Contract;
 use cairo_lang_starknet_classes::abi::StateMutability;
 use cairo_lang_starknet_classes::abi::{
-    Item::Function as AbiFunction, Item::Interface as AbiInterface, Item::L1Handler as AbiL1Handler,
+    Item as AbiItem, Item::Function as AbiFunction, Item::Interface as AbiInterface,
+    Item::L1Handler as AbiL1Handler,
 };
+use tracing::instrument;
 
+use crate::analysis::context::AnalysisContext;
 use crate::config::GraphConfig;
 use crate::decompiler::cfg::BasicBlock;
+use crate::decompiler::cfg::CfgNodeDetail;
 use crate::decompiler::cfg::EdgeType;
+use crate::decompiler::formatting::FormattingOptions;
 use crate::decompiler::function::Function;
 use crate::decompiler::function::FunctionType;
 use crate::decompiler::function::SierraStatement;
-use crate::decompiler::libfuncs_patterns::IS_ZERO_REGEX;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::{
+    BOUNDED_INT_CONSTRAIN_REGEX, FUNCTION_CALL_REGEX, TYPE_PATH_REGEX,
+};
+use crate::decompiler::names::NamesTable;
 use crate::decompiler::utils::decode_user_defined_type_id;
 use crate::decompiler::utils::replace_types_id;
-use crate::graph::callgraph::process_callgraph;
+use crate::detectors::detector::Finding;
+use crate::error::{Result, SierraAnalyzerError};
+use crate::extract_parameters;
+use crate::graph::callgraph::callgraph_edges;
+use crate::graph::callgraph::process_callgraph_json;
+use crate::graph::callgraph::CallGraph;
+use crate::graph::callgraph::CallKind;
+use crate::graph::graphml::GraphMlWriter;
 use crate::parse_element_name;
 use crate::parse_element_name_with_fallback;
 use crate::sierra_program::SierraProgram;
+use crate::var_id_to_name;
 
 /// A struct that represents a decompiler for a Sierra program
 pub struct Decompiler<'a> {
@@ -37,23 +55,39 @@ pub struct Decompiler<'a> {
     /// ABI of the contract
     /// Only available if the decompiled contract is compiled using starknet-compile
     pub abi: Option<Contract>,
-    /// Program functions
+    /// Program functions, sorted by entry offset
     pub functions: Vec<Function<'a>>,
     /// Program registry
-    registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    registry: Option<&'a ProgramRegistry<CoreType, CoreLibfunc>>,
     /// Current indentation
     indentation: u32,
     /// Already printed basic blocks (to avoid printing two times the same BB)
     printed_blocks: Vec<BasicBlock>,
     /// The function we are currently working on
     current_function: Option<Function<'a>>,
+    /// ByteArray literals of the current function, by offset of their construction
+    byte_array_literals: HashMap<u32, String>,
+    /// Implementations printed in place of their call in the wrappers, by wrapper name
+    inlined_implementations: HashMap<String, String>,
+    /// Panics with an error message of the current function, by offset of their `enum_init`
+    panic_summaries: HashMap<u32, String>,
+    /// Offsets of the statements building the panics data, summarized in the regular output
+    panic_hidden_offsets: HashSet<u32>,
     /// Names of all declared types (in order)
-    pub declared_types_names: Vec<String>,
+    pub declared_types_names: NamesTable,
     /// Names of all declared libfuncs (in order)
-    pub declared_libfuncs_names: Vec<String>,
+    pub declared_libfuncs_names: NamesTable,
+    /// Kinds of the declared libfuncs, indexed by their IDs
+    libfuncs_kinds: HashMap<u64, LibfuncKind>,
     /// Enable / disable the verbose output
     /// Some statements are not included in the regular output to improve the readability
     verbose: bool,
+    /// Indentation, line length & comments options of the output
+    formatting: FormattingOptions,
+    /// Detectors findings, highlighted in the CFG & callgraph outputs
+    findings: Vec<Finding>,
+    /// Group the functions of the callgraph DOT output by module path
+    callgraph_clusters: bool,
 }
 
 impl<'a> Decompiler<'a> {
@@ -66,20 +100,52 @@ impl<'a> Decompiler<'a> {
             indentation: 1,
             printed_blocks: Vec::new(),
             current_function: None,
-            declared_types_names: Vec::new(),
-            declared_libfuncs_names: Vec::new(),
+            byte_array_literals: HashMap::new(),
+            inlined_implementations: HashMap::new(),
+            panic_summaries: HashMap::new(),
+            panic_hidden_offsets: HashSet::new(),
+            declared_types_names: NamesTable::new(),
+            declared_libfuncs_names: NamesTable::new(),
+            libfuncs_kinds: HashMap::new(),
             verbose,
+            formatting: FormattingOptions::default(),
+            findings: Vec::new(),
+            callgraph_clusters: false,
         }
     }
 
-    /// Returns a reference to the program registry
-    pub fn registry(&self) -> &ProgramRegistry<CoreType, CoreLibfunc> {
-        &self.registry
+    /// Sets the formatting options of the output
+    pub fn set_formatting(&mut self, formatting: FormattingOptions) {
+        self.formatting = formatting;
+    }
+
+    /// Sets the detectors findings highlighted in the graphs
+    pub fn set_findings(&mut self, findings: Vec<Finding>) {
+        self.findings = findings;
+    }
+
+    /// Groups the functions of the callgraph DOT output in a cluster per module path
+    pub fn set_callgraph_clusters(&mut self, callgraph_clusters: bool) {
+        self.callgraph_clusters = callgraph_clusters;
+    }
+
+    /// Returns a reference to the program registry, if available
+    pub fn registry(&self) -> Option<&ProgramRegistry<CoreType, CoreLibfunc>> {
+        self.registry
     }
 
     /// Decompiles the Sierra Program and return the string output
     /// Output can be colored or not
+    /// Panics if the program is malformed, see `try_decompile`
     pub fn decompile(&mut self, use_color: bool) -> String {
+        self.try_decompile(use_color)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Decompiles the Sierra Program and return the string output
+    /// Output can be colored or not
+    #[instrument(skip_all)]
+    pub fn try_decompile(&mut self, use_color: bool) -> Result<String> {
         // Disable/enable color output
         colored::control::set_override(use_color);
 
@@ -88,26 +154,22 @@ impl<'a> Decompiler<'a> {
         let libfuncs = self.decompile_libfuncs();
 
         // Load statements into their corresponding functions
-        self.set_functions_offsets();
-        self.decompile_functions_prototypes();
-        self.add_statements_to_functions();
+        self.load_functions()?;
 
         // Decompile the functions
-        let functions = self.decompile_functions();
-
-        // Assign types to functions (works only if the ABI is available)
-        if let Err(_e) = self.set_functions_types() {}
+        let functions = self.decompile_functions()?;
 
         // Clone the functions and the registry data before the mutable borrow occurs
         let functions_ref = self.functions.clone();
-        let registry_data = self.registry();
 
         // Now we can start iterating over decompiler.functions without any borrow conflict
         let mut cloned_functions = self.functions.clone();
 
-        // The the meta informations for each function
-        for function in cloned_functions.iter_mut() {
-            let _ = function.set_meta_informations(&functions_ref, &registry_data);
+        // The the meta informations for each function (they need the program registry)
+        if let Some(registry_data) = self.registry() {
+            for function in cloned_functions.iter_mut() {
+                let _ = function.set_meta_informations(&functions_ref, registry_data);
+            }
         }
 
         // Format the output string
@@ -119,7 +181,34 @@ impl<'a> Decompiler<'a> {
             output.push_str("\n\n");
         }
         output.push_str(&functions);
-        output
+        Ok(output)
+    }
+
+    /// Loads the declarations & the functions of the Sierra program without formatting them
+    /// Used to analyze a program without decompiling it
+    #[instrument(skip_all)]
+    pub fn analyze(&mut self) -> Result<()> {
+        self.decompile_types();
+        self.decompile_libfuncs();
+        self.load_functions()
+    }
+
+    /// Returns the read-only analysis context of the loaded functions, used by the detectors
+    #[inline]
+    pub fn analysis_context(&self) -> AnalysisContext<'a> {
+        AnalysisContext::from_decompiler(self)
+    }
+
+    /// Loads the functions, their prototypes, statements & types
+    fn load_functions(&mut self) -> Result<()> {
+        self.set_functions_offsets()?;
+        self.decompile_functions_prototypes()?;
+        self.add_statements_to_functions()?;
+
+        // Assign types to functions (works only if the ABI is available)
+        if let Err(_e) = self.set_functions_types() {}
+
+        Ok(())
     }
 
     /// Returns the functions that are defined by the user
@@ -197,7 +286,9 @@ impl<'a> Decompiler<'a> {
 
     // Helper function to handle ABI-related function types (External, View, L1Handler)
     fn handle_abi_function_types(f: &mut Function, full_name: &str, abi: Option<Contract>) {
-        let function_name = full_name.rsplit_once("::").unwrap().1;
+        let function_name = full_name
+            .rsplit_once("::")
+            .map_or(full_name, |(_, function_name)| function_name);
         if let Some(abi_items) = abi {
             for item in abi_items.clone() {
                 match item {
@@ -270,9 +361,11 @@ impl<'a> Decompiler<'a> {
     }
 
     // Set the function `function_type` field
-    fn set_functions_types(&mut self) -> Result<(), String> {
+    // The External/View/L1Handler types are given by the ABI, these functions are left untyped without it
+    fn set_functions_types(&mut self) -> std::result::Result<(), String> {
         let mut external_functions: HashSet<String> = HashSet::new();
         let mut constructors: HashSet<String> = HashSet::new();
+        let mut untyped_functions: Vec<String> = Vec::new();
 
         // Gather all the external/l1_handler functions and the constructor of each contract
         for f in self.functions.iter() {
@@ -310,10 +403,17 @@ impl<'a> Decompiler<'a> {
 
             // Check if the function type is set
             if f.function_type.is_none() {
-                return Err(format!("Failed to set function type for: {}", full_name));
+                untyped_functions.push(full_name);
             }
         }
 
+        if !untyped_functions.is_empty() {
+            return Err(format!(
+                "Failed to set function type for: {}",
+                untyped_functions.join(", ")
+            ));
+        }
+
         Ok(())
     }
 
@@ -428,8 +528,7 @@ impl<'a> Decompiler<'a> {
         // Construct the type definition string
         // If the id is not empty, format the type definition with the id and optionally the long ID representation
         let type_definition = if !id.is_empty() {
-            let id_string = id.clone().to_string();
-            self.declared_types_names.push(id_string.clone());
+            self.declared_types_names.push(&id);
             format!(
                 "type {}{}",
                 id.yellow(),
@@ -442,8 +541,7 @@ impl<'a> Decompiler<'a> {
         }
         // If the id is empty, format the type definition with only the long ID representation
         else {
-            let long_id_repr_string = long_id_repr.clone().to_string();
-            self.declared_types_names.push(long_id_repr_string.clone());
+            self.declared_types_names.push(&long_id_repr);
             format!("type {}{}", long_id_repr_colored, "")
         };
 
@@ -475,21 +573,24 @@ impl<'a> Decompiler<'a> {
             id.to_string()
         };
 
-        self.declared_libfuncs_names
-            .push(libfunc_definition.clone()); // Push non-colored version to declared_libfuncs_names
+        self.declared_libfuncs_names.push(&libfunc_definition); // Push non-colored version to declared_libfuncs_names
+
+        // Classify the libfunc once, its statements reuse the kind
+        self.libfuncs_kinds.insert(
+            libfunc_declaration.id.id,
+            LibfuncKind::classify(&libfunc_definition),
+        );
 
         format!("libfunc {}", libfunc_definition.blue())
     }
 
     /// Decompiles the functions prototypes
-    pub fn decompile_functions_prototypes(&mut self) -> String {
+    pub fn decompile_functions_prototypes(&mut self) -> Result<String> {
         let prototypes_and_arguments: Vec<(String, Vec<(String, String)>)> = self
-            .sierra_program
-            .program()
-            .funcs
+            .functions
             .iter()
-            .map(|function_prototype| self.decompile_function_prototype(function_prototype))
-            .collect();
+            .map(|function| self.decompile_function_prototype(function.function))
+            .collect::<Result<_>>()?;
 
         // Set prototypes and arguments for corresponding Function structs
         for ((prototype, arguments), function) in prototypes_and_arguments
@@ -500,21 +601,18 @@ impl<'a> Decompiler<'a> {
             function.set_arguments(arguments.clone());
         }
 
-        prototypes_and_arguments
+        Ok(prototypes_and_arguments
             .iter()
             .map(|(prototype, _)| prototype.clone())
             .collect::<Vec<_>>()
-            .join("\n")
+            .join("\n"))
     }
 
     /// Decompiles a function prototype and returns both the formatted prototype & the arguments
     fn decompile_function_prototype(
         &self,
         function_declaration: &GenFunction<StatementIdx>,
-    ) -> (String, Vec<(String, String)>) {
-        // Parse the function name
-        let id = format!("{}", parse_element_name!(function_declaration.id)).bold();
-
+    ) -> Result<(String, Vec<(String, String)>)> {
         // Get the function signature, which consists of the parameter types and return types
         let signature = &function_declaration.signature;
         let param_types: Vec<String> = signature
@@ -527,23 +625,6 @@ impl<'a> Decompiler<'a> {
             })
             .collect();
 
-        // Create a list of strings representing the function parameters
-        // with each string formatted as "<param_name>: <param_type>"
-        let param_strings: Vec<String> = param_types
-            .iter()
-            .zip(function_declaration.params.iter())
-            .map(|(param_type, param)| {
-                let param_name_string = if let Some(debug_name) = &param.id.debug_name {
-                    debug_name.to_string()
-                } else {
-                    format!("v{}", param.id.id)
-                };
-                let param_name = param_name_string.purple(); // Color param_name in purple
-                let param_type_colored = param_type.yellow(); // Color param_type in yellow
-                format!("{}: {}", param_name, param_type_colored)
-            })
-            .collect();
-
         // Collect arguments as a vector of tuples
         let arguments: Vec<(String, String)> = param_types
             .iter()
@@ -558,141 +639,669 @@ impl<'a> Decompiler<'a> {
             })
             .collect();
 
-        // Join the parameter strings into a single string, separated by commas
-        let param_str = format!("{}", param_strings.join(", "));
+        let ret_types = self.function_return_types(function_declaration)?;
+        let prototype = format_prototype(
+            &parse_element_name!(function_declaration.id),
+            &arguments,
+            &ret_types,
+        );
+
+        Ok((prototype, arguments))
+    }
 
-        // Create a list of strings representing the function return types
-        let ret_types: Vec<String> = signature
+    /// Returns the names of the return types of a function
+    fn function_return_types(
+        &self,
+        function_declaration: &GenFunction<StatementIdx>,
+    ) -> Result<Vec<String>> {
+        function_declaration
+            .signature
             .ret_types
             .iter()
             .map(|ret_type| {
-                let ret_type_string = if let Some(debug_name) = &ret_type.debug_name {
-                    debug_name.to_string()
+                if let Some(debug_name) = &ret_type.debug_name {
+                    Ok(debug_name.to_string())
                 } else {
                     // Replace id with the corresponding type name
-                    format!("[{}]", self.declared_types_names[ret_type.id as usize])
-                };
-                let ret_type_colored = ret_type_string.purple(); // Color ret_type_string in purple
-                ret_type_colored.to_string()
+                    let type_name = self
+                        .declared_types_names
+                        .get(ret_type.id as usize)
+                        .ok_or(SierraAnalyzerError::UnknownType(ret_type.id))?;
+                    Ok(format!("[{}]", type_name))
+                }
             })
-            .collect();
+            .collect()
+    }
 
-        // Join the return type strings into a single string, separated by commas
-        let ret_types_str = format!("{}", ret_types.join(", "));
+    /// Returns the prototype of an entry point using the types of its ABI entry,
+    /// e.g. `u256` instead of `core::integer::u256` & the ABI outputs instead of the `PanicResult`.
+    /// The builtins & the contract state keep their Sierra types
+    fn abi_prototype(&self, function: &Function) -> Option<String> {
+        if !self.formatting.abi_types
+            || !matches!(
+                function.function_type,
+                Some(
+                    FunctionType::External
+                        | FunctionType::View
+                        | FunctionType::Constructor
+                        | FunctionType::L1Handler
+                )
+            )
+        {
+            return None;
+        }
+
+        let full_name = parse_element_name!(function.function.id);
+        let function_name = full_name.rsplit("::").next()?;
+        let (inputs, outputs) = abi_function_signature(self.abi.as_ref()?, function_name)?;
+
+        // The ABI inputs are the last parameters, after the builtins & the contract state
+        let first_input = function.arguments.len().checked_sub(inputs.len())?;
+        let arguments: Vec<(String, String)> = function
+            .arguments
+            .iter()
+            .enumerate()
+            .map(
+                |(index, (name, param_type))| match index.checked_sub(first_input) {
+                    Some(input_index) => (name.clone(), shorten_type_name(&inputs[input_index])),
+                    None => (name.clone(), param_type.clone()),
+                },
+            )
+            .collect();
 
-        // Construct the function declaration string
-        let prototype = format!("func {} ({}) -> ({})", id, param_str, ret_types_str);
+        // The returned value (or its PanicResult) is the last return type
+        let mut ret_types = self.function_return_types(function.function).ok()?;
+        if let Some(ret_type) = ret_types.last_mut() {
+            if ret_type.contains("PanicResult") || !outputs.is_empty() {
+                *ret_type = match outputs.as_slice() {
+                    [output] => shorten_type_name(output),
+                    _ => format!(
+                        "({})",
+                        outputs
+                            .iter()
+                            .map(|output| shorten_type_name(output))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                };
+            }
+        }
 
-        (prototype, arguments)
+        Some(format_prototype(&full_name, &arguments, &ret_types))
     }
 
     /// Sets the start and end offsets for each function in the Sierra program
-    /// They are then used to assign the statements their functions
-    fn set_functions_offsets(&mut self) {
-        let num_functions = self.sierra_program.program().funcs.len();
-
-        for (i, function_declaration) in self.sierra_program.program().funcs.iter().enumerate() {
+    /// They are then used to assign the statements their functions.
+    /// The functions are sorted by entry offset, the declarations order is kept for equal offsets
+    fn set_functions_offsets(&mut self) -> Result<()> {
+        let mut functions_declarations: Vec<_> =
+            self.sierra_program.program().funcs.iter().collect();
+        functions_declarations
+            .sort_by_key(|function_declaration| function_declaration.entry_point.0);
+        let num_functions = functions_declarations.len();
+
+        // All the statements offsets must fit in a u32
+        let total_statements = statement_offset(self.sierra_program.program().statements.len())?;
+
+        for (i, function_declaration) in functions_declarations.iter().enumerate() {
             let mut function = Function::new(function_declaration);
-            function.set_start_offset(function_declaration.entry_point.0.try_into().unwrap());
+            function.set_start_offset(statement_offset(function_declaration.entry_point.0)?);
 
             // Set the end offset of the current function to the start offset of the next function minus one
             if i < num_functions - 1 {
-                let next_function_declaration = &self.sierra_program.program().funcs[i + 1];
-                let next_start_offset: u32 =
-                    next_function_declaration.entry_point.0.try_into().unwrap();
+                let next_function_declaration = functions_declarations[i + 1];
+                let next_start_offset = statement_offset(next_function_declaration.entry_point.0)?;
                 function.set_end_offset(next_start_offset - 1);
             }
 
             self.functions.push(function);
         }
 
-        // Set the end offset of the last function to the total number of statements
+        // Set the end offset of the last function to the offset of the last statement
         if let Some(last_function) = self.functions.last_mut() {
-            let total_statements = self.sierra_program.program().statements.len() as u16;
-            last_function.set_end_offset(total_statements.into());
+            last_function.set_end_offset(total_statements.saturating_sub(1));
         }
+
+        Ok(())
     }
 
-    /// Adds the corresponding statements each function using their offsets
-    fn add_statements_to_functions(&mut self) {
-        for function in &mut self.functions {
-            let start_offset = function.start_offset.unwrap();
-            let end_offset = function.end_offset.unwrap();
+    /// Loads a single function, its prototype & its statements without loading the other functions
+    /// Returns the index of the function in the functions sorted by entry offset & the function
+    fn load_function(&self, name: &str) -> Result<(usize, Function<'a>)> {
+        let mut functions_declarations: Vec<_> =
+            self.sierra_program.program().funcs.iter().collect();
+        functions_declarations
+            .sort_by_key(|function_declaration| function_declaration.entry_point.0);
 
-            // Filter statements based on offset range and map them with their offsets
-            let statements_with_offsets: Vec<SierraStatement> = self
-                .sierra_program
-                .program()
-                .statements
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, statement)| {
-                    let offset = idx as u32;
-                    // Function statements based on their offsets
-                    if offset >= start_offset && offset <= end_offset {
-                        Some(SierraStatement::new(statement.clone(), offset))
-                    }
-                    // Other statements
-                    else {
-                        None
-                    }
-                })
-                .collect();
+        let index = functions_declarations
+            .iter()
+            .position(|function_declaration| parse_element_name!(function_declaration.id) == name)
+            .ok_or_else(|| SierraAnalyzerError::FunctionNotFound(name.to_string()))?;
+        let function_declaration = functions_declarations[index];
+
+        // The function ends before the next function, like in `set_functions_offsets`
+        let end_offset = match functions_declarations.get(index + 1) {
+            Some(next_function_declaration) => {
+                statement_offset(next_function_declaration.entry_point.0)? - 1
+            }
+            None => {
+                statement_offset(self.sierra_program.program().statements.len())?.saturating_sub(1)
+            }
+        };
+
+        let mut function = Function::new(function_declaration);
+        function.set_start_offset(statement_offset(function_declaration.entry_point.0)?);
+        function.set_end_offset(end_offset);
 
-            function.set_statements(statements_with_offsets);
+        let (prototype, arguments) = self.decompile_function_prototype(function_declaration)?;
+        function.set_prototype(prototype);
+        function.set_arguments(arguments);
+        function.set_statements(self.function_statements(&function)?);
+
+        Ok((index, function))
+    }
+
+    /// Adds the corresponding statements each function using their offsets
+    fn add_statements_to_functions(&mut self) -> Result<()> {
+        let functions_statements: Vec<Vec<SierraStatement>> = self
+            .functions
+            .iter()
+            .map(|function| self.function_statements(function))
+            .collect::<Result<_>>()?;
+
+        for (function, statements) in self.functions.iter_mut().zip(functions_statements) {
+            function.set_statements(statements);
         }
+
+        Ok(())
     }
 
-    /// Decompiles all the functions
-    pub fn decompile_functions(&mut self) -> String {
-        // Clone functions to avoid borrowing conflicts
-        let mut functions_clone = self.functions.clone();
+    /// Returns the statements of a function using its offsets
+    fn function_statements(&self, function: &Function) -> Result<Vec<SierraStatement>> {
+        let function_name = || parse_element_name!(function.function.id);
+        let start_offset = function.start_offset.ok_or_else(|| {
+            SierraAnalyzerError::MissingFunctionInfo("Start offset", function_name())
+        })?;
+        let end_offset = function.end_offset.ok_or_else(|| {
+            SierraAnalyzerError::MissingFunctionInfo("End offset", function_name())
+        })?;
+
+        // Take the statements in the offset range and map them with their offsets
+        // The offsets fit in a u32 as they have been checked in `set_functions_offsets`
+        Ok(self
+            .sierra_program
+            .program()
+            .statements
+            .iter()
+            .enumerate()
+            .skip(start_offset as usize)
+            .take_while(|(idx, _)| *idx <= end_offset as usize)
+            .map(|(idx, statement)| {
+                let libfunc_kind = match statement {
+                    GenStatement::Invocation(invocation) => self
+                        .libfuncs_kinds
+                        .get(&invocation.libfunc_id.id)
+                        .copied()
+                        .unwrap_or_default(),
+                    GenStatement::Return(_) => LibfuncKind::Other,
+                };
+                SierraStatement::new(statement.clone(), idx as u32).with_libfunc_kind(libfunc_kind)
+            })
+            .collect())
+    }
 
-        // Initialize a CFG for each function
-        for function in &mut functions_clone {
+    /// Decompiles all the functions
+    pub fn decompile_functions(&mut self) -> Result<String> {
+        // Build the CFG of each function, they are cached for the graphs outputs
+        for function in &mut self.functions {
             function.create_cfg();
         }
 
+        // Clone functions to avoid borrowing conflicts
+        let functions_clone = self.functions.clone();
+
+        // Wrappers merged with their implementation, the implementations only called by
+        // the wrappers inlining them are not printed separately
+        let (inlined_implementations, hidden_implementations) = if self.formatting.inline_wrappers {
+            Self::inlined_implementations(&functions_clone)
+        } else {
+            (HashMap::new(), HashSet::new())
+        };
+        self.inlined_implementations = inlined_implementations.clone();
+
         let function_decompilations: Vec<String> = functions_clone
             .iter()
             .enumerate()
+            .filter(|(_, function)| {
+                !hidden_implementations.contains(&parse_element_name!(function.function.id))
+            })
             .map(|(index, function)| {
-                // Set the current function
-                self.current_function = Some(function.clone());
+                // Name the implementation inlined in the wrapper
+                let inlined_comment = inlined_implementations
+                    .get(&parse_element_name!(function.function.id))
+                    .map(|implementation| {
+                        format!(
+                            "{}\n",
+                            format!("// Inlined implementation {}", implementation).purple()
+                        )
+                    })
+                    .unwrap_or_default();
+
+                self.format_function(index, function, inlined_comment)
+            })
+            .collect::<Result<_>>()?;
 
-                // Extract function prototype
-                let prototype = function
-                    .prototype
-                    .as_ref()
-                    .expect("Function prototype not set");
+        // Join all function decompilations into a single string
+        Ok(function_decompilations.join("\n\n"))
+    }
 
-                let body = if let Some(cfg) = &function.cfg {
-                    cfg.basic_blocks
-                        .iter()
-                        .map(|block| {
-                            self.indentation = 1; // Reset indentation after processing each block
-                            self.basic_block_recursive(block)
-                        })
-                        .collect::<String>()
+    /// Decompiles a single function, without decompiling the whole program
+    /// Only the declarations names, the prototype, the statements & the CFG of the function are
+    /// computed if the functions are not loaded yet. The function type is then unknown (it needs the
+    /// other functions), so the calldata deserialization of the wrappers is not summarized
+    #[instrument(skip(self))]
+    pub fn decompile_function(&mut self, name: &str) -> Result<String> {
+        if self.declared_types_names.is_empty() && self.declared_libfuncs_names.is_empty() {
+            self.decompile_types();
+            self.decompile_libfuncs();
+        }
+
+        let loaded_function = self
+            .functions
+            .iter()
+            .position(|function| parse_element_name!(function.function.id) == name);
+        let (index, function) = match loaded_function {
+            Some(index) => (index, self.functions[index].clone()),
+            None => self.load_function(name)?,
+        };
+
+        // The blocks printed by a previous decompilation are printed again
+        self.printed_blocks.clear();
+        self.format_function(index, &function, String::new())
+    }
+
+    /// Formats the prototype & the body of a function, the comments are printed before the prototype
+    fn format_function(
+        &mut self,
+        index: usize,
+        function: &Function<'a>,
+        inlined_comment: String,
+    ) -> Result<String> {
+        self.set_current_function(function);
+
+        // Extract function prototype, with the ABI types if enabled
+        let prototype = match self.abi_prototype(function) {
+            Some(prototype) => prototype,
+            None => function.prototype.clone().ok_or_else(|| {
+                SierraAnalyzerError::MissingFunctionInfo(
+                    "Prototype",
+                    parse_element_name!(function.function.id),
+                )
+            })?,
+        };
+
+        // The calldata deserialization of the wrappers can be summarized
+        let (summary, blocks) = self
+            .calldata_deserialization(function)
+            .unwrap_or_else(|| (String::new(), function.cfg().basic_blocks.clone()));
+
+        let body = summary + &self.function_body(&blocks, 1);
+
+        // Define bold braces for function body enclosure
+        let bold_brace_open = "{".bold();
+        let bold_brace_close = "}".bold();
+
+        // Combine prototype and body into a formatted string
+        let purple_comment = if self.formatting.function_comments {
+            format!("{}\n", format!("// Function {}", index + 1).purple())
+        } else {
+            String::new()
+        };
+        // The functions that can panic are annotated with their panic paths, the others are `nopanic`
+        let (panic_comment, nopanic) = match self.formatting.panic_annotations {
+            true => {
+                let panic_safety = function.panic_safety();
+                let panic_comment = if panic_safety.can_panic {
+                    let comment = format!(
+                        "// Can panic ({} panic paths{})",
+                        panic_safety.panic_paths,
+                        if panic_safety.returns_panic_result {
+                            ", returns PanicResult"
+                        } else {
+                            ""
+                        }
+                    );
+                    format!("{}\n", comment.purple())
                 } else {
                     String::new()
                 };
+                let nopanic = if panic_safety.nopanic {
+                    format!(" {}", "nopanic".magenta())
+                } else {
+                    String::new()
+                };
+                (panic_comment, nopanic)
+            }
+            false => (String::new(), String::new()),
+        };
 
-                // Define bold braces for function body enclosure
-                let bold_brace_open = "{".bold();
-                let bold_brace_close = "}".bold();
+        Ok(format!(
+            "{}{}{}{}{} {}\n{}{}", // Added bold braces around the function body
+            purple_comment,
+            inlined_comment,
+            panic_comment,
+            prototype,
+            nopanic,
+            bold_brace_open,
+            body,
+            bold_brace_close
+        ))
+    }
 
-                // Combine prototype and body into a formatted string
-                let purple_comment = format!("// Function {}", index + 1).purple();
-                format!(
-                    "{}\n{} {}\n{}{}", // Added bold braces around the function body
-                    purple_comment, prototype, bold_brace_open, body, bold_brace_close
+    /// Sets the function being decompiled, with its ByteArray literals & its panics summaries
+    fn set_current_function(&mut self, function: &Function<'a>) {
+        self.current_function = Some(function.clone());
+        self.byte_array_literals = function
+            .byte_array_literals(&self.declared_libfuncs_names, &self.declared_types_names)
+            .into_iter()
+            .map(|literal| (literal.offset, literal.value))
+            .collect();
+        let panic_summaries =
+            function.panic_summaries(&self.declared_libfuncs_names, &self.declared_types_names);
+        self.panic_hidden_offsets = panic_summaries
+            .iter()
+            .flat_map(|summary| summary.hidden_offsets.iter().copied())
+            .collect();
+        self.panic_summaries = panic_summaries
+            .into_iter()
+            .map(|summary| {
+                let message = format!("({})", summary.message.join(", "));
+                (
+                    summary.offset,
+                    format!("{} = {}{}", summary.variable, "panic".blue(), message),
                 )
             })
             .collect();
+    }
 
-        // Join all function decompilations into a single string
-        function_decompilations.join("\n\n")
+    /// Decompiles the basic blocks of a function body, the root blocks are at the given indentation
+    fn function_body(&mut self, blocks: &[BasicBlock], indentation: u32) -> String {
+        blocks
+            .iter()
+            .map(|block| {
+                self.indentation = indentation; // Reset indentation after processing each block
+                self.basic_block_recursive(block)
+            })
+            .collect()
+    }
+
+    /// Returns the implementations to inline, by wrapper name, & the implementations to hide
+    /// A wrapper inlines its implementation if it only calls one user-defined function that is not
+    /// a core function, the other calls deserialize the calldata & serialize the return values.
+    /// The implementations that are only called by the wrappers inlining them are hidden
+    fn inlined_implementations(
+        functions: &[Function],
+    ) -> (HashMap<String, String>, HashSet<String>) {
+        let functions_types: HashMap<String, Option<FunctionType>> = functions
+            .iter()
+            .map(|function| {
+                (
+                    parse_element_name!(function.function.id),
+                    function.function_type.clone(),
+                )
+            })
+            .collect();
+
+        // Non-core user-defined functions called by each wrapper
+        let mut wrappers_callees: HashMap<String, Vec<String>> = HashMap::new();
+        let edges = callgraph_edges(functions);
+        for (caller, callee, kind) in edges.iter().cloned() {
+            let is_wrapper = matches!(
+                functions_types.get(&caller),
+                Some(Some(FunctionType::Wrapper))
+            );
+            let is_implementation = matches!(
+                functions_types.get(&callee),
+                Some(
+                    None | Some(
+                        FunctionType::Constructor
+                            | FunctionType::External
+                            | FunctionType::View
+                            | FunctionType::L1Handler
+                            | FunctionType::Private
+                    )
+                )
+            );
+            if kind == CallKind::UserDefined && is_wrapper && is_implementation {
+                wrappers_callees.entry(caller).or_default().push(callee);
+            }
+        }
+
+        let inlined_implementations: HashMap<String, String> = wrappers_callees
+            .into_iter()
+            .filter_map(|(wrapper, callees)| match callees.as_slice() {
+                [implementation] => Some((wrapper, implementation.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let mut hidden_implementations: HashSet<String> =
+            inlined_implementations.values().cloned().collect();
+        for (caller, callee, _) in &edges {
+            if inlined_implementations.get(caller) != Some(callee) {
+                hidden_implementations.remove(callee);
+            }
+        }
+
+        (inlined_implementations, hidden_implementations)
+    }
+
+    /// Summarizes the calldata deserialization of a wrapper using the input types of its ABI function
+    /// e.g. `let args = deserialize::<(u256, ContractAddress)>(v3)`, followed by the panics of the
+    /// deserialization failure branches on one line
+    /// Returns the summary & the basic blocks to print, from the call of the implementation.
+    /// The implementation is called with the deserialized `args.N` & the builtins of the wrapper
+    /// The wrappers inlining their implementation are not summarized
+    fn calldata_deserialization(&self, function: &Function) -> Option<(String, Vec<BasicBlock>)> {
+        if !self.formatting.summarize_calldata
+            || !matches!(function.function_type, Some(FunctionType::Wrapper))
+        {
+            return None;
+        }
+
+        // Input types of the ABI function
+        let wrapper_name = parse_element_name!(function.function.id);
+        let function_name = wrapper_name
+            .rsplit("::")
+            .next()
+            .and_then(|name| name.rsplit("__").next())?;
+        let inputs = abi_function_inputs(self.abi.as_ref()?, function_name)?;
+
+        // Calldata parameter of the wrapper
+        let calldata = function.function.params.iter().find_map(|param| {
+            let param_type = parse_element_name_with_fallback!(param.ty, self.declared_types_names);
+            param_type.contains("Span::<core::felt252>").then(|| {
+                param
+                    .id
+                    .debug_name
+                    .as_ref()
+                    .map_or_else(|| format!("v{}", param.id.id), |name| name.to_string())
+            })
+        })?;
+
+        // Basic block & statement calling the implementation, the deserialization happens before it
+        let cfg = function.cfg();
+        let (call_block, call_statement, callee) =
+            cfg.basic_blocks
+                .iter()
+                .enumerate()
+                .find_map(|(block_index, block)| {
+                    block
+                        .statements
+                        .iter()
+                        .enumerate()
+                        .find_map(|(statement_index, statement)| {
+                            let callee = self.called_function(statement)?;
+                            (!callee.starts_with("user@core::")).then_some((
+                                block_index,
+                                statement_index,
+                                callee,
+                            ))
+                        })
+                })?;
+
+        // Basic blocks reachable from the implementation call
+        let mut reachable: HashSet<u32> = HashSet::new();
+        let mut stack = vec![cfg.basic_blocks[call_block].start_offset];
+        while let Some(offset) = stack.pop() {
+            if !reachable.insert(offset) {
+                continue;
+            }
+            if let Some(block) = cfg.basic_blocks.iter().find(|b| b.start_offset == offset) {
+                stack.extend(block.edges.iter().map(|edge| edge.destination));
+            }
+        }
+        let mut blocks: Vec<BasicBlock> = cfg.basic_blocks[call_block..]
+            .iter()
+            .filter(|block| reachable.contains(&block.start_offset))
+            .cloned()
+            .collect();
+
+        // The implementation is called with the deserialized arguments, which are its last
+        // parameters, & with the builtins of the wrapper, of the same types
+        let callee_name = callee.trim_start_matches("user@");
+        let callee_params = &self
+            .functions
+            .iter()
+            .find(|function| parse_element_name!(function.function.id) == callee_name)?
+            .function
+            .params;
+        if let GenStatement::Invocation(invocation) =
+            &mut blocks[0].statements[call_statement].statement
+        {
+            let args_start = invocation.args.len().checked_sub(inputs.len())?;
+            for (index, (arg, param)) in invocation.args.iter_mut().zip(callee_params).enumerate() {
+                if index >= args_start {
+                    arg.debug_name = Some(format!("args.{}", index - args_start).into());
+                } else if let Some(builtin) = function
+                    .function
+                    .params
+                    .iter()
+                    .find(|wrapper_param| wrapper_param.ty == param.ty)
+                {
+                    let builtin_name: String = var_id_to_name!(builtin.id);
+                    arg.debug_name = Some(builtin_name.into());
+                }
+            }
+        }
+
+        // Panics of the deserialization failure branches, e.g. "Out of gas"
+        let printed_offsets: HashSet<u32> = blocks
+            .iter()
+            .flat_map(|block| block.statements.iter().map(|statement| statement.offset))
+            .collect();
+        let mut panic_summaries =
+            function.panic_summaries(&self.declared_libfuncs_names, &self.declared_types_names);
+        panic_summaries.sort_by_key(|summary| summary.offset);
+        let mut panics: Vec<String> = Vec::new();
+        for summary in panic_summaries {
+            let message = summary.message.join(", ");
+            if !printed_offsets.contains(&summary.offset) && !panics.contains(&message) {
+                panics.push(message);
+            }
+        }
+
+        let mut summary = format!(
+            "{} args {} {}::<({})>({})",
+            "let".magenta(),
+            "=".magenta(),
+            "deserialize".blue(),
+            inputs
+                .iter()
+                .map(|input| shorten_type_name(input))
+                .collect::<Vec<_>>()
+                .join(", "),
+            calldata
+        );
+        if !panics.is_empty() {
+            summary += &format!(
+                " {} {}({})",
+                "else".magenta(),
+                "panic".blue(),
+                panics.join(" | ")
+            );
+        }
+
+        Some((self.formatting.format_line(&summary, 1), blocks))
+    }
+
+    /// Returns the name of the function called by a statement, e.g. `user@test::foo`
+    fn called_function(&self, statement: &SierraStatement) -> Option<String> {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            return None;
+        };
+        if statement.libfunc_kind != LibfuncKind::FunctionCall {
+            return None;
+        }
+        let libfunc =
+            parse_element_name_with_fallback!(invocation.libfunc_id, self.declared_libfuncs_names);
+        FUNCTION_CALL_REGEX
+            .captures(&libfunc)
+            .and_then(|captures| captures.get(1))
+            .map(|callee| callee.as_str().to_string())
+    }
+
+    /// Formats the call of the implementation inlined in the current wrapper, followed by the
+    /// body of the implementation in a nested block. The implementation keeps its variables names,
+    /// its prototype is printed in a comment
+    fn inlined_implementation_call(&mut self, statement: &SierraStatement) -> Option<String> {
+        let wrapper_name = parse_element_name!(self.current_function.as_ref()?.function.id);
+        let implementation_name = self.inlined_implementations.get(&wrapper_name)?;
+        if self.called_function(statement)?.trim_start_matches("user@") != implementation_name {
+            return None;
+        }
+        let implementation = self
+            .functions
+            .iter()
+            .find(|function| parse_element_name!(function.function.id) == *implementation_name)?
+            .clone();
+        let prototype = implementation.prototype.clone()?;
+        let call = statement.formatted_statement(
+            self.verbose,
+            &self.declared_libfuncs_names,
+            &self.declared_types_names,
+        )?;
+
+        // The state of the wrapper is restored once the implementation is decompiled
+        let indentation = self.indentation;
+        let wrapper = self.current_function.take();
+        let byte_array_literals = std::mem::take(&mut self.byte_array_literals);
+        let panic_summaries = std::mem::take(&mut self.panic_summaries);
+        let panic_hidden_offsets = std::mem::take(&mut self.panic_hidden_offsets);
+        let printed_blocks = std::mem::take(&mut self.printed_blocks);
+
+        self.set_current_function(&implementation);
+        let body = self.function_body(&implementation.cfg().basic_blocks, indentation + 1);
+
+        self.current_function = wrapper;
+        self.byte_array_literals = byte_array_literals;
+        self.panic_summaries = panic_summaries;
+        self.panic_hidden_offsets = panic_hidden_offsets;
+        self.printed_blocks = printed_blocks;
+        self.indentation = indentation;
+
+        Some(format!(
+            "{}{}{}{}",
+            self.formatting.format_line(
+                &format!("// Inlined {}", prototype).purple().to_string(),
+                indentation as usize
+            ),
+            self.formatting
+                .format_line(&format!("{} {}", call, "{".bold()), indentation as usize),
+            body,
+            self.formatting
+                .format_line(&"}".bold().to_string(), indentation as usize)
+        ))
     }
 
     /// Recursively decompile basic blocks
@@ -706,6 +1315,18 @@ impl<'a> Decompiler<'a> {
         // Add the root basic block
         basic_blocks_str += &self.basic_block_to_string(block);
 
+        // The out-of-gas branch of the summarized gas checks is hidden
+        // and the main path is not indented
+        if let Some((success_block, out_of_gas_block)) = self.gas_check_blocks(block) {
+            self.printed_blocks.push(out_of_gas_block);
+            basic_blocks_str += &self.basic_block_recursive(&success_block);
+            return basic_blocks_str;
+        }
+
+        // The if blocks ending with a return are rendered as guard clauses,
+        // the else block follows them without being nested
+        let mut guard_clause = false;
+
         // Add the edges
         for edge in &block.edges {
             // If branch
@@ -716,15 +1337,16 @@ impl<'a> Decompiler<'a> {
                 if let Some(edge_basic_block) = self
                     .current_function
                     .as_ref()
-                    .unwrap()
-                    .cfg
-                    .clone()
-                    .unwrap()
-                    .basic_blocks
-                    .iter()
-                    .find(|b| edge.destination == b.start_offset)
+                    .map(|function| function.cfg())
+                    .and_then(|cfg| {
+                        cfg.basic_blocks
+                            .iter()
+                            .find(|b| edge.destination == b.start_offset)
+                    })
+                    .cloned()
                 {
-                    basic_blocks_str += &self.basic_block_recursive(edge_basic_block);
+                    guard_clause = self.early_returns() && is_returning_block(&edge_basic_block);
+                    basic_blocks_str += &self.basic_block_recursive(&edge_basic_block);
                 }
             }
             // Else branch
@@ -732,32 +1354,44 @@ impl<'a> Decompiler<'a> {
                 if let Some(edge_basic_block) = self
                     .current_function
                     .as_ref()
-                    .unwrap()
-                    .cfg
-                    .clone()
-                    .unwrap()
-                    .basic_blocks
-                    .iter()
-                    .find(|b| edge.destination == b.start_offset)
+                    .map(|function| function.cfg())
+                    .and_then(|cfg| {
+                        cfg.basic_blocks
+                            .iter()
+                            .find(|b| edge.destination == b.start_offset)
+                    })
+                    .cloned()
                 {
-                    if !self.printed_blocks.contains(edge_basic_block) {
+                    if guard_clause && !self.printed_blocks.contains(&edge_basic_block) {
+                        // End of the guard clause, the else block is not indented
+                        self.indentation -= 1;
+                        basic_blocks_str += &format!(
+                            "{}{}\n",
+                            self.formatting.indent(self.indentation as usize),
+                            bold_brace_close
+                        );
+                        basic_blocks_str += &self.basic_block_recursive(&edge_basic_block);
+                        continue;
+                    }
+
+                    if !self.printed_blocks.contains(&edge_basic_block) {
                         // End of if block
                         self.indentation -= 1;
 
                         let magenta_else = "else".magenta();
                         basic_blocks_str += &format!(
                             "{}{} {} {}{}\n",
-                            "\t".repeat(self.indentation as usize),
+                            self.formatting.indent(self.indentation as usize),
                             bold_brace_close,
                             magenta_else,
                             bold_brace_open,
-                            "\t".repeat(self.indentation as usize)
+                            self.formatting.indent(self.indentation as usize)
                         );
 
                         // Indent the else block
                         self.indentation += 1;
 
-                        basic_blocks_str += &self.basic_block_recursive(edge_basic_block);
+                        basic_blocks_str += &self.basic_block_recursive(&edge_basic_block);
                     }
                 }
 
@@ -767,7 +1401,7 @@ impl<'a> Decompiler<'a> {
                 if !basic_blocks_str.is_empty() {
                     basic_blocks_str += &format!(
                         "{}{}\n",
-                        "\t".repeat(self.indentation as usize),
+                        self.formatting.indent(self.indentation as usize),
                         bold_brace_close
                     );
                 }
@@ -777,6 +1411,52 @@ impl<'a> Decompiler<'a> {
         basic_blocks_str
     }
 
+    /// Returns true if the if blocks ending with a return are rendered as guard clauses
+    #[inline]
+    fn early_returns(&self) -> bool {
+        self.formatting.early_returns && !self.verbose
+    }
+
+    /// Returns true if the gas checks are summarized in the output
+    #[inline]
+    fn summarize_gas_checks(&self) -> bool {
+        self.formatting.summarize_gas_checks && !self.verbose
+    }
+
+    /// Returns the blocks executed when the gas check ending a block succeeds & when it fails
+    /// (success block, out-of-gas block)
+    /// None if the block doesn't end with a gas check or if the gas checks are not summarized
+    fn gas_check_blocks(&self, block: &BasicBlock) -> Option<(BasicBlock, BasicBlock)> {
+        if !self.summarize_gas_checks() || block.edges.len() != 2 {
+            return None;
+        }
+
+        let statement = block.statements.last()?;
+        if !statement.is_conditional_branch || statement.libfunc_kind != LibfuncKind::GasCheck {
+            return None;
+        }
+
+        // The gas check falls through when it succeeds & jumps to the out-of-gas branch
+        let edge_block = |edge_type: EdgeType| {
+            let edge = block
+                .edges
+                .iter()
+                .find(|edge| edge.edge_type == edge_type)?;
+            self.current_function
+                .as_ref()?
+                .cfg()
+                .basic_blocks
+                .iter()
+                .find(|b| b.start_offset == edge.destination)
+                .cloned()
+        };
+
+        Some((
+            edge_block(EdgeType::ConditionalFalse)?,
+            edge_block(EdgeType::ConditionalTrue)?,
+        ))
+    }
+
     /// Converts a Sierra BasicBlock object to a string
     fn basic_block_to_string(&mut self, block: &BasicBlock) -> String {
         // Check if the block has already been printed
@@ -789,7 +1469,6 @@ impl<'a> Decompiler<'a> {
 
         // Initialize the basic block string
         let mut decompiled_basic_block = String::new();
-        let indentation = "\t".repeat(self.indentation as usize);
 
         // Append each statement to the string block
         for statement in &block.statements {
@@ -797,14 +1476,42 @@ impl<'a> Decompiler<'a> {
             if let Some(conditional_branch) =
                 // We pass it the declared libfunc names to allow the method to reconstruct function calls
                 // For remote contracts
-                statement.as_conditional_branch(self.declared_libfuncs_names.clone())
+                statement.as_conditional_branch(&self.declared_libfuncs_names)
             {
                 if block.edges.len() == 2 {
                     let function_name = &conditional_branch.function;
                     let function_arguments = conditional_branch.parameters.join(", ");
+
+                    // The summarized gas checks are replaced by a comment
+                    if statement.libfunc_kind == LibfuncKind::GasCheck
+                        && self.summarize_gas_checks()
+                    {
+                        let comment = format!(
+                            "// out-of-gas guard: {}({})",
+                            function_name, function_arguments
+                        );
+                        decompiled_basic_block += &self.formatting.format_line(
+                            &comment.bright_black().to_string(),
+                            self.indentation as usize,
+                        );
+                        continue;
+                    }
+
+                    // Variables assigned by the first branch (e.g. the value of an Option)
+                    let branch_variables = match &statement.statement {
+                        GenStatement::Invocation(invocation) => invocation
+                            .branches
+                            .first()
+                            .map(|branch| extract_parameters!(&branch.results))
+                            .unwrap_or_default(),
+                        GenStatement::Return(_) => Vec::new(),
+                    };
+
                     decompiled_basic_block += &self.format_if_statement(
                         function_name,
+                        statement.libfunc_kind,
                         function_arguments,
+                        &branch_variables,
                         self.indentation as usize,
                     );
                 }
@@ -813,11 +1520,27 @@ impl<'a> Decompiler<'a> {
             else if let Some(_unconditional_branch) =
                 // We pass it the declared libfunc names to allow the method to reconstruct function calls
                 // For remote contracts
-                statement.as_conditional_branch(self.declared_libfuncs_names.clone())
+                statement.as_conditional_branch(&self.declared_libfuncs_names)
             {
                 // Handle unconditional branch logic
                 todo!()
             }
+            // Panics with an error message are summarized in the regular output
+            else if !self.verbose && self.panic_hidden_offsets.contains(&statement.offset) {
+                continue;
+            } else if let Some(summary) = self
+                .panic_summaries
+                .get(&statement.offset)
+                .filter(|_| !self.verbose)
+            {
+                decompiled_basic_block += &self
+                    .formatting
+                    .format_line(summary, self.indentation as usize);
+            }
+            // The implementation inlined in a wrapper is printed in place of its call
+            else if let Some(inlined_call) = self.inlined_implementation_call(statement) {
+                decompiled_basic_block += &inlined_call;
+            }
             // Default case
             else {
                 // Add the formatted statements to the block
@@ -827,10 +1550,23 @@ impl<'a> Decompiler<'a> {
                 // to reconstruct function calls & used types for remote contracts
                 if let Some(formatted_statement) = statement.formatted_statement(
                     self.verbose,
-                    self.declared_libfuncs_names.clone(),
-                    self.declared_types_names.clone(),
+                    &self.declared_libfuncs_names,
+                    &self.declared_types_names,
                 ) {
-                    decompiled_basic_block += &format!("{}{}\n", indentation, formatted_statement);
+                    // The reassembled ByteArray literals are added as a comment
+                    let formatted_statement = match self.byte_array_literals.get(&statement.offset)
+                    {
+                        Some(literal) => format!(
+                            "{} {}",
+                            formatted_statement,
+                            format!(r#"// "{}""#, literal.escape_debug()).bright_black()
+                        ),
+                        None => formatted_statement,
+                    };
+
+                    decompiled_basic_block += &self
+                        .formatting
+                        .format_line(&formatted_statement, self.indentation as usize);
                 }
             }
         }
@@ -842,22 +1578,68 @@ impl<'a> Decompiler<'a> {
     fn format_if_statement(
         &self,
         function_name: &str,
+        libfunc_kind: LibfuncKind,
         function_arguments: String,
+        branch_variables: &[String],
         indentation: usize,
     ) -> String {
         let magenta_if = "if".magenta();
         let bold_brace_open = "{".bold();
-        let indentation_str = "\t".repeat(indentation);
+        let indentation_str = self.formatting.indent(indentation);
 
-        // Check if the function name matches the IS_ZERO_REGEX
-        if IS_ZERO_REGEX.is_match(function_name) && !self.verbose {
+        // Zero checks
+        if libfunc_kind == LibfuncKind::IsZero && !self.verbose {
             let argument = function_arguments.trim();
             return format!(
                 "{}{} ({argument} == 0) {}{}\n",
                 indentation_str,
                 magenta_if,
                 bold_brace_open,
-                "\t".repeat(indentation + 1)
+                self.formatting.indent(indentation + 1)
+            );
+        }
+
+        // Options matching, branch 0 is taken by the Some variant
+        if libfunc_kind == LibfuncKind::OptionMatch && !self.verbose {
+            return format!(
+                "{}{} {}({}) = {} {}{}\n",
+                indentation_str,
+                "if let".magenta(),
+                "Some".blue(),
+                branch_variables.join(", "),
+                function_arguments.trim(),
+                bold_brace_open,
+                self.formatting.indent(indentation + 1)
+            );
+        }
+
+        // Nullables matching, branch 0 is taken when the value is null
+        if libfunc_kind == LibfuncKind::MatchNullable && !self.verbose {
+            return format!(
+                "{}{} ({} == {}) {}{}\n",
+                indentation_str,
+                magenta_if,
+                function_arguments.trim(),
+                "null".blue(),
+                bold_brace_open,
+                self.formatting.indent(indentation + 1)
+            );
+        }
+
+        // Bounded integers constraints, branch 0 is taken when the value is lower than the boundary
+        if let Some(captures) = (libfunc_kind == LibfuncKind::BoundedIntConstrain && !self.verbose)
+            .then(|| BOUNDED_INT_CONSTRAIN_REGEX.captures(function_name))
+            .flatten()
+        {
+            let value = function_arguments.split(", ").last().unwrap_or_default();
+            return format!(
+                "{}{} ({} < {}) {}{}\n",
+                indentation_str,
+                magenta_if,
+                value,
+                &captures["boundary"],
+                bold_brace_open,
+                self.formatting.indent(indentation + 1)
             );
         }
 
@@ -869,7 +1651,7 @@ impl<'a> Decompiler<'a> {
             replace_types_id(&self.declared_types_names, function_name).blue(),
             function_arguments,
             bold_brace_open,
-            "\t".repeat(indentation + 1) // Adjust for nested content indentation
+            self.formatting.indent(indentation + 1) // Adjust for nested content indentation
         )
     }
 
@@ -886,14 +1668,99 @@ impl<'a> Decompiler<'a> {
         });
     }
 
+    /// Returns the entry points of the program
+    /// Wrappers, External, View, Constructor & L1Handler functions
+    pub fn entry_points(&self) -> impl Iterator<Item = &Function> {
+        self.functions.iter().filter(|f| {
+            matches!(
+                f.function_type,
+                Some(
+                    FunctionType::Wrapper
+                        | FunctionType::External
+                        | FunctionType::View
+                        | FunctionType::Constructor
+                        | FunctionType::L1Handler
+                )
+            )
+        })
+    }
+
+    /// Returns the callgraph of the program
+    #[inline]
+    pub fn callgraph(&self) -> CallGraph {
+        CallGraph::new(&self.functions)
+    }
+
+    /// Returns the names of the callgraph nodes reachable from the program entry points
+    pub fn reachable_from_entry_points(&self) -> HashSet<String> {
+        let roots: Vec<String> = self
+            .entry_points()
+            .map(|function| parse_element_name!(function.function.id))
+            .collect();
+        self.callgraph().reachable_from(&roots)
+    }
+
+    /// Returns the names of all the direct & indirect callers of a function (function included)
+    #[inline]
+    pub fn callers(&self, function_name: &str) -> HashSet<String> {
+        self.callgraph().callers(function_name)
+    }
+
     /// Generate a callgraph representation in DOT Format
     #[inline]
     pub fn generate_callgraph(&mut self) -> String {
-        process_callgraph(&self.functions)
+        CallGraph::new(&self.functions)
+            .with_modules_clusters(self.callgraph_clusters)
+            .to_dot_with_findings(None, &self.findings)
+    }
+
+    /// Generate a callgraph representation in DOT Format containing only the given nodes
+    #[inline]
+    pub fn generate_filtered_callgraph(&self, filter: &HashSet<String>) -> String {
+        CallGraph::new(&self.functions)
+            .with_modules_clusters(self.callgraph_clusters)
+            .to_dot_with_findings(Some(filter), &self.findings)
+    }
+
+    /// Generate a callgraph representation in GraphML format
+    #[inline]
+    pub fn generate_callgraph_graphml(&self) -> String {
+        CallGraph::new(&self.functions).to_graphml(None)
+    }
+
+    /// Generate a callgraph representation in GraphML format containing only the given nodes
+    #[inline]
+    pub fn generate_filtered_callgraph_graphml(&self, filter: &HashSet<String>) -> String {
+        CallGraph::new(&self.functions).to_graphml(Some(filter))
+    }
+
+    /// Returns the callgraph edges as (caller, callee, kind) tuples
+    #[inline]
+    pub fn callgraph_edges(&self) -> Vec<(String, String, CallKind)> {
+        callgraph_edges(&self.functions)
+    }
+
+    /// Generate a callgraph representation in JSON format (nodes & adjacency lists)
+    #[inline]
+    pub fn generate_callgraph_json(&self) -> String {
+        process_callgraph_json(&self.functions, None)
+    }
+
+    /// Generate a callgraph representation in JSON format containing only the given nodes
+    #[inline]
+    pub fn generate_filtered_callgraph_json(&self, filter: &HashSet<String>) -> String {
+        process_callgraph_json(&self.functions, Some(filter))
     }
 
     /// Generates a control flow graph representation (CFG) in DOT format
+    #[inline]
     pub fn generate_cfg(&mut self) -> String {
+        self.generate_cfg_with_detail(GraphConfig::CFG_NODE_DETAIL)
+    }
+
+    /// Generates a control flow graph representation (CFG) in DOT format
+    /// The nodes contain the raw statements, the decompiled statements or a summary of the blocks
+    pub fn generate_cfg_with_detail(&mut self, detail: CfgNodeDetail) -> String {
         let mut dot = String::from("digraph {\n");
 
         // Global graph configuration
@@ -924,13 +1791,15 @@ impl<'a> Decompiler<'a> {
         ));
 
         // Add a CFG representation for each function
-        for function in &mut self.functions {
-            function.create_cfg();
-            if let Some(cfg) = &function.cfg {
-                // Generate function subgraph
-                let subgraph = cfg.generate_dot_graph();
-                dot += &subgraph;
-            }
+        for function in &self.functions {
+            // Generate function subgraph
+            let subgraph = function.cfg().generate_dot_graph_with_detail(
+                detail,
+                &self.declared_libfuncs_names,
+                &self.declared_types_names,
+                &self.findings,
+            );
+            dot += &subgraph;
         }
 
         // Add the closing curly braces to the DOT graph representation
@@ -938,4 +1807,113 @@ impl<'a> Decompiler<'a> {
 
         dot
     }
+
+    /// Generates a control flow graph representation (CFG) in GraphML format
+    /// The basic blocks of all the functions are in the same graph
+    pub fn generate_cfg_graphml(&mut self) -> String {
+        let mut writer = GraphMlWriter::new(
+            &["label", "function", "start_offset", "statements"],
+            &["kind"],
+        );
+
+        for function in &self.functions {
+            function.cfg().add_to_graphml(&mut writer);
+        }
+
+        writer.finish("cfg")
+    }
+
+    /// Generates a control flow graph representation (CFG) in JSON format
+    /// Each function CFG contains its basic blocks and its edges
+    pub fn generate_cfg_json(&mut self) -> String {
+        let mut cfgs = Vec::new();
+
+        for function in &self.functions {
+            cfgs.push(function.cfg().json(function.panic_safety()));
+        }
+
+        serde_json::to_string_pretty(&cfgs)
+            .unwrap_or_else(|e| format!("Error serializing CFG: {}", e))
+    }
+}
+
+/// Converts a statement index to a function offset
+fn statement_offset(statement_idx: usize) -> Result<u32> {
+    statement_idx
+        .try_into()
+        .map_err(|_| SierraAnalyzerError::InvalidOffset(statement_idx))
+}
+
+/// Returns true if a basic block ends with a return & has no successor (e.g. a panic)
+fn is_returning_block(block: &BasicBlock) -> bool {
+    block.edges.is_empty()
+        && matches!(
+            block
+                .statements
+                .last()
+                .map(|statement| &statement.statement),
+            Some(GenStatement::Return(_))
+        )
+}
+
+/// Returns the input types of a function of the ABI (constructor, external, view or L1 handler)
+pub(crate) fn abi_function_inputs(abi: &Contract, function_name: &str) -> Option<Vec<String>> {
+    abi_function_signature(abi, function_name).map(|(inputs, _)| inputs)
+}
+
+/// Returns the input & output types of a function of the ABI (constructor, external, view or L1 handler)
+pub(crate) fn abi_function_signature(
+    abi: &Contract,
+    function_name: &str,
+) -> Option<(Vec<String>, Vec<String>)> {
+    let mut items: Vec<AbiItem> = abi.clone().into_iter().collect();
+    while let Some(item) = items.pop() {
+        let (inputs, outputs) = match item {
+            AbiFunction(function) if function.name == function_name => {
+                (function.inputs, function.outputs)
+            }
+            AbiL1Handler(l1handler) if l1handler.name == function_name => {
+                (l1handler.inputs, l1handler.outputs)
+            }
+            AbiItem::Constructor(constructor) if constructor.name == function_name => {
+                (constructor.inputs, Vec::new())
+            }
+            AbiInterface(interface) => {
+                items.extend(interface.items);
+                continue;
+            }
+            _ => continue,
+        };
+
+        return Some((
+            inputs.into_iter().map(|input| input.ty).collect(),
+            outputs.into_iter().map(|output| output.ty).collect(),
+        ));
+    }
+
+    None
+}
+
+/// Formats a function prototype, e.g. `func examples::fib::fib (v0: felt252) -> (felt252)`
+fn format_prototype(name: &str, arguments: &[(String, String)], ret_types: &[String]) -> String {
+    let parameters: Vec<String> = arguments
+        .iter()
+        .map(|(param_name, param_type)| format!("{}: {}", param_name.purple(), param_type.yellow()))
+        .collect();
+    let ret_types: Vec<String> = ret_types
+        .iter()
+        .map(|ret_type| ret_type.purple().to_string())
+        .collect();
+
+    format!(
+        "func {} ({}) -> ({})",
+        name.bold(),
+        parameters.join(", "),
+        ret_types.join(", ")
+    )
+}
+
+/// Removes the paths of a type name, e.g. `core::array::Array::<core::felt252>` becomes `Array::<felt252>`
+pub(crate) fn shorten_type_name(type_name: &str) -> String {
+    TYPE_PATH_REGEX.replace_all(type_name, "$name").to_string()
 }
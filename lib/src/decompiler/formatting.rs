@@ -0,0 +1,170 @@
+use std::fs;
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::error::Result;
+
+lazy_static! {
+    /// ANSI color codes, they are not counted in the lines length
+    static ref ANSI_COLOR_REGEX: Regex = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+}
+
+/// Indentation of the decompiler output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndentStyle {
+    Tabs,
+    Spaces,
+}
+
+/// Formatting options of the decompiler output
+/// They can be loaded from a JSON config file, the missing fields keep their default values
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FormattingOptions {
+    /// Indent using tabs or spaces
+    pub indent_style: IndentStyle,
+    /// Number of spaces of an indentation level (ignored with tabs)
+    pub indent_width: usize,
+    /// Maximum length of a line, the arguments of the longer invocations are wrapped (one per line)
+    pub max_line_length: Option<usize>,
+    /// Print the `// Function <index>` comments
+    pub function_comments: bool,
+    /// Merge the entry point wrappers (calldata deserialization) with the function they call
+    pub inline_wrappers: bool,
+    /// Summarize the calldata deserialization of the wrappers using the ABI input types
+    pub summarize_calldata: bool,
+    /// Print the prototypes of the entry points using the input & output types of the ABI
+    pub abi_types: bool,
+    /// Annotate the prototypes with the panic safety of the functions (`nopanic` or their panic paths)
+    pub panic_annotations: bool,
+    /// Replace the `withdraw_gas` checks & their out-of-gas branch by a comment
+    pub summarize_gas_checks: bool,
+    /// Render the if blocks ending with a return as guard clauses, without nesting the else blocks
+    pub early_returns: bool,
+}
+
+impl Default for FormattingOptions {
+    fn default() -> Self {
+        Self {
+            indent_style: IndentStyle::Tabs,
+            indent_width: 4,
+            max_line_length: None,
+            function_comments: true,
+            inline_wrappers: false,
+            summarize_calldata: false,
+            abi_types: false,
+            panic_annotations: false,
+            summarize_gas_checks: false,
+            early_returns: false,
+        }
+    }
+}
+
+impl FormattingOptions {
+    /// Returns the indentation string of the given level
+    pub fn indent(&self, level: usize) -> String {
+        match self.indent_style {
+            IndentStyle::Tabs => "\t".repeat(level),
+            IndentStyle::Spaces => " ".repeat(level * self.indent_width),
+        }
+    }
+
+    /// Formats a statement at the given indentation level
+    /// The arguments of the statements longer than the maximum line length are wrapped
+    pub fn format_line(&self, statement: &str, level: usize) -> String {
+        let line = format!("{}{}\n", self.indent(level), statement);
+
+        let Some(max_line_length) = self.max_line_length else {
+            return line;
+        };
+        if visible_length(&line) <= max_line_length {
+            return line;
+        }
+
+        // Wrap the arguments of the last parentheses
+        let Some((callee, arguments)) = split_arguments(statement) else {
+            return line;
+        };
+        let arguments: Vec<String> = split_top_level(arguments)
+            .into_iter()
+            .map(|argument| self.indent(level + 1) + argument)
+            .collect();
+
+        format!(
+            "{}{}(\n{}\n{})\n",
+            self.indent(level),
+            callee,
+            arguments.join(",\n"),
+            self.indent(level)
+        )
+    }
+}
+
+/// Loads the formatting options from a JSON config file
+pub fn load_formatting_options(path: &Path) -> Result<FormattingOptions> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Returns the length of a line without its color codes
+fn visible_length(line: &str) -> usize {
+    strip_colors(line).trim_end().chars().count()
+}
+
+/// Removes the color codes of a line
+pub(crate) fn strip_colors(line: &str) -> String {
+    ANSI_COLOR_REGEX.replace_all(line, "").to_string()
+}
+
+/// Splits a statement ending with `(<arguments>)` into its callee & its arguments
+fn split_arguments(statement: &str) -> Option<(&str, &str)> {
+    let statement = statement.strip_suffix(')')?;
+
+    // Find the opening parenthesis matching the last one
+    let mut depth = 0;
+    let (start, _) = statement.char_indices().rev().find(|(_, c)| {
+        match c {
+            ')' => depth += 1,
+            '(' if depth == 0 => return true,
+            '(' => depth -= 1,
+            _ => {}
+        }
+        false
+    })?;
+
+    let arguments = &statement[start + 1..];
+    if arguments.is_empty() {
+        return None;
+    }
+
+    Some((&statement[..start], arguments))
+}
+
+/// Splits the arguments on the `, ` separators that are not nested in parentheses, brackets,
+/// generic arguments or strings
+fn split_top_level(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth: usize = 0;
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '(' | '[' | '<' if !in_string => depth += 1,
+            ')' | ']' | '>' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 && arguments[index..].starts_with(", ") => {
+                parts.push(&arguments[start..index]);
+                start = index + 2;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&arguments[start..]);
+
+    parts
+}
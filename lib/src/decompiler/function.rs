@@ -1,5 +1,8 @@
 use colored::*;
 use num_bigint::BigInt;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc;
 use cairo_lang_sierra::extensions::core::CoreLibfunc;
@@ -12,15 +15,19 @@ use cairo_lang_sierra::program::Invocation;
 use cairo_lang_sierra::program::StatementIdx;
 use cairo_lang_sierra::program_registry::ProgramRegistry;
 
+use crate::analysis::dataflow::DataFlow;
+use crate::analysis::ssa::SsaForm;
 use crate::decompiler::cfg::ControlFlowGraph;
 use crate::decompiler::cfg::SierraConditionalBranch;
+use crate::decompiler::libfunc_kind::LibfuncKind;
 use crate::decompiler::libfuncs_patterns::{
-    ADDITION_REGEX, ARRAY_APPEND_REGEX, CONST_REGEXES, DROP_REGEX, DUP_REGEX, FUNCTION_CALL_REGEX,
-    MULTIPLICATION_REGEX, NEW_ARRAY_REGEX, STORE_TEMP_REGEX, SUBSTRACTION_REGEX,
-    VARIABLE_ASSIGNMENT_REGEX,
+    ARRAY_APPEND_REGEX, CONST_REGEXES, DICT_NEW_REGEX, FUNCTION_CALL_REGEX, INTO_BOX_REGEX,
+    NEW_ARRAY_REGEX, NULLABLE_FROM_BOX_REGEX, OPTION_INIT_REGEX, PANIC_DATA_REGEX,
+    WIDE_INTEGER_DIVMOD_REGEX, WIDE_INTEGER_OPERATION_REGEX,
 };
-use crate::decompiler::utils::decode_hex_bigint;
 use crate::decompiler::utils::replace_types_id;
+use crate::decompiler::utils::{decode_byte_array, format_constant};
+use crate::error::SierraAnalyzerError;
 use crate::extract_parameters;
 use crate::parse_element_name;
 use crate::parse_element_name_with_fallback;
@@ -31,9 +38,13 @@ pub struct SierraStatement {
     /// Statement extracted from the parsed program
     pub statement: cairo_lang_sierra::program::Statement,
     /// We store the statement offset in the struct because it not present in cairo_lang_sierra::program::Statement
+    /// Programs with offsets that don't fit in a u32 are rejected by the decompiler
     pub offset: u32,
     /// A statement is considered a "conditional branch" if it has branching behavior
     pub is_conditional_branch: bool,
+    /// Kind of the invoked libfunc, classified when the libfunc is declared
+    /// The return statements & the statements created outside of the decompiler are `Other`
+    pub libfunc_kind: LibfuncKind,
 }
 
 impl SierraStatement {
@@ -53,16 +64,23 @@ impl SierraStatement {
             statement,
             offset,
             is_conditional_branch,
+            libfunc_kind: LibfuncKind::Other,
         }
     }
 
+    /// Sets the kind of the invoked libfunc
+    pub fn with_libfunc_kind(mut self, libfunc_kind: LibfuncKind) -> Self {
+        self.libfunc_kind = libfunc_kind;
+        self
+    }
+
     /// Formats the statement as a string
     /// We try to format them in a way that is as similar as possible to the Cairo syntax
     pub fn formatted_statement(
         &self,
         verbose: bool,
-        declared_libfuncs_names: Vec<String>,
-        declared_types_names: Vec<String>,
+        declared_libfuncs_names: &[Arc<str>],
+        declared_types_names: &[Arc<str>],
     ) -> Option<String> {
         match &self.statement {
             GenStatement::Return(vars) => self.format_return_statement(vars),
@@ -94,8 +112,8 @@ impl SierraStatement {
         &self,
         invocation: &Invocation,
         verbose: bool,
-        declared_libfuncs_names: Vec<String>,
-        declared_types_names: Vec<String>,
+        declared_libfuncs_names: &[Arc<str>],
+        declared_types_names: &[Arc<str>],
     ) -> Option<String> {
         // Try to get the debug name of the libfunc_id
         // We use `parse_element_name_with_fallback` and not `parse_element_name` because
@@ -103,7 +121,7 @@ impl SierraStatement {
         let libfunc_id =
             parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
 
-        if !Self::is_function_allowed(&libfunc_id, verbose) {
+        if !Self::is_function_allowed(&libfunc_id, self.libfunc_kind, verbose) {
             return None; // Skip formatting if function is not allowed
         }
         let libfunc_id_str = libfunc_id.blue();
@@ -120,7 +138,7 @@ impl SierraStatement {
             String::new()
         };
 
-        if STORE_TEMP_REGEX.is_match(&libfunc_id)
+        if self.libfunc_kind == LibfuncKind::StoreTemp
         && assigned_variables_str == parameters.join(", ")
         // Print the redundant store_temp in the verbose output
         && !verbose
@@ -133,12 +151,13 @@ impl SierraStatement {
             &libfunc_id_str,
             &parameters,
             &verbose,
-            &declared_types_names,
+            self.libfunc_kind,
+            declared_types_names,
         ))
     }
 
     /// Checks if the given function name is allowed to be included in the formatted statement
-    fn is_function_allowed(function_name: &str, verbose: bool) -> bool {
+    fn is_function_allowed(function_name: &str, libfunc_kind: LibfuncKind, verbose: bool) -> bool {
         // We allow every function in the verbose output
         if verbose {
             return true;
@@ -151,14 +170,8 @@ impl SierraStatement {
             | "finalize_locals"
             | "revoke_ap_tracking"
             | "get_builtin_costs" => false,
-            _ => {
-                // Check blacklisted functions patterns
-                if DROP_REGEX.is_match(function_name) {
-                    false
-                } else {
-                    true
-                }
-            }
+            // Check blacklisted functions patterns
+            _ => libfunc_kind != LibfuncKind::Drop,
         }
     }
 
@@ -168,7 +181,8 @@ impl SierraStatement {
         libfunc_id_str: &str,
         parameters: &[String],
         verbose: &bool,
-        declared_types_names: &Vec<String>,
+        libfunc_kind: LibfuncKind,
+        declared_types_names: &[Arc<str>],
     ) -> String {
         // We set the sign color to magenta for all invocations statements
         let equal_sign: &str = &"=".magenta().to_string();
@@ -180,8 +194,71 @@ impl SierraStatement {
         // Join parameters for general use
         let parameters_str = parameters.join(", ");
 
+        // Handling u256 & u512 operations, the range check & the panic of the corelib call are hidden
+        // <result> = <lhs> <operator> <rhs> (u256)
+        if let Some(captures) = (libfunc_kind == LibfuncKind::WideIntegerOperation && !*verbose)
+            .then(|| WIDE_INTEGER_OPERATION_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
+            if let ([.., lhs, rhs], Some(result)) =
+                (parameters, assigned_variables_str.split(", ").last())
+            {
+                let operator = match &captures["operation"] {
+                    "add" => "+",
+                    "sub" => "-",
+                    "mul" => "*",
+                    "div" => "/",
+                    _ => "%",
+                };
+                return format!(
+                    "{} {} {} {} {} ({})",
+                    result,
+                    equal_sign,
+                    lhs,
+                    operator,
+                    rhs,
+                    captures["type"].to_lowercase()
+                );
+            }
+        }
+
+        // Handling u256 & u512 divisions, they return the quotient & the remainder
+        // <quotient>, <remainder> = <lhs> / <rhs>, <lhs> % <rhs> (u256)
+        if let Some(captures) = (libfunc_kind == LibfuncKind::WideIntegerDivmod && !*verbose)
+            .then(|| WIDE_INTEGER_DIVMOD_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
+            let assigned_variables: Vec<&str> = assigned_variables_str.split(", ").collect();
+            if let ([_, lhs, rhs], [_, quotient, remainder, ..]) =
+                (parameters, assigned_variables.as_slice())
+            {
+                return format!(
+                    "{}, {} {} {} / {}, {} % {} ({})",
+                    quotient, remainder, equal_sign, lhs, rhs, lhs, rhs, &captures["type"]
+                );
+            }
+        }
+
+        // Handling bounded integers divisions
+        // <quotient>, <remainder> = <lhs> / <rhs>, <lhs> % <rhs>
+        if libfunc_kind == LibfuncKind::BoundedIntDivRem && !*verbose {
+            let assigned_variables: Vec<&str> = assigned_variables_str.split(", ").collect();
+            if let ([_, lhs, rhs], [_, quotient, remainder]) =
+                (parameters, assigned_variables.as_slice())
+            {
+                return format!(
+                    "{}, {} {} {} / {}, {} % {}",
+                    quotient, remainder, equal_sign, lhs, rhs, lhs, rhs
+                );
+            }
+        }
+
         // Handling user-defined function calls
-        if let Some(caps) = FUNCTION_CALL_REGEX.captures(libfunc_id_str) {
+        if let Some(caps) = libfunc_kind
+            .may_be_function_call()
+            .then(|| FUNCTION_CALL_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
             if let Some(inner_func) = caps.get(1) {
                 let formatted_func = inner_func.as_str();
                 if !assigned_variables_str.is_empty() {
@@ -214,26 +291,176 @@ impl SierraStatement {
         // Handling variables duplications
         // In the Sierra IR it it represented like : v1, v2 = dup<felt252>(v1)
         // But we can represent it as a variable assignment such as : v2 = v1
-        if DUP_REGEX.is_match(libfunc_id_str) {
+        if libfunc_kind == LibfuncKind::Dup {
             if let Some((first_var, second_var)) = assigned_variables_str.split_once(", ") {
                 return format!("{} {} {}", second_var, equal_sign, first_var);
             }
         }
 
-        // Handling variables assignments
-        if VARIABLE_ASSIGNMENT_REGEX
-            .iter()
-            .any(|regex| regex.is_match(libfunc_id_str))
+        // Handling snapshots, boxes & spans, they are rendered as casts
+        // <original>, <snapshot> = <variable>, @<variable>
+        // <box> = <variable> as Box<<type>>
+        // <span> = <tuple> as Span
+        if libfunc_kind == LibfuncKind::SnapshotTake {
+            if let (Some(variable), Some((original, snapshot))) =
+                (parameters.first(), assigned_variables_str.split_once(", "))
+            {
+                // The original variable is usually kept
+                if original == variable {
+                    return format!("{} {} @{}", snapshot, equal_sign, variable);
+                }
+                return format!(
+                    "{} {} {}, @{}",
+                    assigned_variables_str, equal_sign, variable, variable
+                );
+            }
+        }
+        if let Some(captures) = (libfunc_kind == LibfuncKind::IntoBox)
+            .then(|| INTO_BOX_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
+            if let Some(variable) = parameters.first() {
+                return format!(
+                    "{} {} {} {} {}<{}>",
+                    assigned_variables_str,
+                    equal_sign,
+                    variable,
+                    "as".magenta(),
+                    "Box".blue(),
+                    &captures["type"]
+                );
+            }
+        }
+        if libfunc_kind == LibfuncKind::SpanFromTuple {
+            if let Some(variable) = parameters.first() {
+                return format!(
+                    "{} {} {} {} {}",
+                    assigned_variables_str,
+                    equal_sign,
+                    variable,
+                    "as".magenta(),
+                    "Span".blue()
+                );
+            }
+        }
+
+        // Handling options & nullables creation
+        // <option> = Some(<value>) or <option> = None
+        // <nullable> = null or <nullable> = <box> as Nullable<<type>>
+        if let Some(captures) = (libfunc_kind == LibfuncKind::OptionInit)
+            .then(|| OPTION_INIT_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
+            return match &captures["variant"] {
+                "0" => format!(
+                    "{} {} {}({})",
+                    assigned_variables_str,
+                    equal_sign,
+                    "Some".blue(),
+                    parameters_str
+                ),
+                _ => format!(
+                    "{} {} {}",
+                    assigned_variables_str,
+                    equal_sign,
+                    "None".blue()
+                ),
+            };
+        }
+        if libfunc_kind == LibfuncKind::Null {
+            return format!(
+                "{} {} {}",
+                assigned_variables_str,
+                equal_sign,
+                "null".blue()
+            );
+        }
+        if let Some(captures) = (libfunc_kind == LibfuncKind::NullableFromBox)
+            .then(|| NULLABLE_FROM_BOX_REGEX.captures(libfunc_id_str))
+            .flatten()
         {
+            if let Some(variable) = parameters.first() {
+                return format!(
+                    "{} {} {} {} {}<{}>",
+                    assigned_variables_str,
+                    equal_sign,
+                    variable,
+                    "as".magenta(),
+                    "Nullable".blue(),
+                    &captures["type"]
+                );
+            }
+        }
+
+        // Handling variables assignments
+        if libfunc_kind.is_assignment() {
             if let Some(old_var) = parameters.first().cloned() {
                 let assigned_variable = assigned_variables_str.to_string();
                 return format!("{} {} {}", assigned_variable, equal_sign, old_var);
             }
         }
 
+        // Handling dictionaries operations
+        // <dict> = Felt252Dict<<value type>>::new()
+        // <entry>, <value> = <dict>[<key>]
+        // <dict> = <entry>.write(<value>)
+        // <squashed dict> = <dict>.squash()
+        let assigned_variables: Vec<&str> = assigned_variables_str.split(", ").collect();
+        if let Some(captures) = (libfunc_kind == LibfuncKind::DictNew)
+            .then(|| DICT_NEW_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
+            if let Some(dict) = assigned_variables.last() {
+                return format!(
+                    "{} {} {}<{}>::{}()",
+                    dict,
+                    equal_sign,
+                    "Felt252Dict".blue(),
+                    &captures["value_type"],
+                    "new".blue()
+                );
+            }
+        }
+        if libfunc_kind == LibfuncKind::DictEntryGet {
+            if let [dict, key] = parameters {
+                return format!(
+                    "{} {} {}[{}]",
+                    assigned_variables_str, equal_sign, dict, key
+                );
+            }
+        }
+        if libfunc_kind == LibfuncKind::DictEntryFinalize {
+            if let [entry, value] = parameters {
+                return format!(
+                    "{} {} {}.{}({})",
+                    assigned_variables_str,
+                    equal_sign,
+                    entry,
+                    "write".blue(),
+                    value
+                );
+            }
+        }
+        if libfunc_kind == LibfuncKind::DictSquash {
+            if let (Some(dict), Some(squashed_dict)) =
+                (parameters.last(), assigned_variables.last())
+            {
+                return format!(
+                    "{} {} {}.{}()",
+                    squashed_dict,
+                    equal_sign,
+                    dict,
+                    "squash".blue()
+                );
+            }
+        }
+
         // Handling array declarations
         // <variable> = Array<<array type>>::new()
-        if let Some(captures) = NEW_ARRAY_REGEX.captures(libfunc_id_str) {
+        if let Some(captures) = (libfunc_kind == LibfuncKind::ArrayNew)
+            .then(|| NEW_ARRAY_REGEX.captures(libfunc_id_str))
+            .flatten()
+        {
             if let Some(array_type) = captures.get(1) {
                 let formatted_array_type = array_type.as_str();
 
@@ -253,7 +480,7 @@ impl SierraStatement {
 
         // Handling array append operations
         // <variable> = <array>.append(<variable>)
-        if ARRAY_APPEND_REGEX.is_match(libfunc_id_str) {
+        if libfunc_kind == LibfuncKind::ArrayAppend {
             let array_name = parameters[0].clone();
             let appent_value_name = parameters[1].clone();
             return format!(
@@ -267,26 +494,32 @@ impl SierraStatement {
         }
 
         // Handling const declarations
-        for regex in CONST_REGEXES.iter() {
+        let const_regexes = match libfunc_kind {
+            LibfuncKind::Const => CONST_REGEXES.as_slice(),
+            _ => &[],
+        };
+        for regex in const_regexes {
             if let Some(captures) = regex.captures(libfunc_id_str) {
                 if let Some(const_value) = captures.name("const") {
                     // Convert string to a BigInt in order to decode it
                     let const_value_str = const_value.as_str();
-                    let const_value_bigint =
-                        BigInt::parse_bytes(const_value_str.as_bytes(), 10).unwrap();
+                    let const_value_bigint = BigInt::parse_bytes(const_value_str.as_bytes(), 10);
 
-                    // If the const integer can be decoded to a valid string, use the string as a comment
-                    if let Some(decoded_string) = decode_hex_bigint(&const_value_bigint) {
-                        let string_comment = format!(r#"// "{}""#, decoded_string).bright_black();
+                    // If the const integer is a string, a negative number, a power of two or an address,
+                    // use its readable value as a comment
+                    if let Some(readable_value) =
+                        const_value_bigint.as_ref().and_then(format_constant)
+                    {
+                        let comment = format!("// {}", readable_value).bright_black();
                         return format!(
                             "{} {} {} {}",
                             assigned_variables_str,
                             equal_sign,
                             const_value_str.cyan(),
-                            string_comment
+                            comment
                         );
                     }
-                    // If the string can not be decoded as a valid string
+                    // If the const integer has no readable value
                     else {
                         return format!(
                             "{} {} {}",
@@ -300,34 +533,24 @@ impl SierraStatement {
         }
 
         // Handling arithmetic operations
-        let operator = if ADDITION_REGEX
-            .iter()
-            .any(|regex| regex.is_match(libfunc_id_str))
-        {
-            "+"
-        } else if SUBSTRACTION_REGEX
-            .iter()
-            .any(|regex| regex.is_match(libfunc_id_str))
-        {
-            "-"
-        } else if MULTIPLICATION_REGEX
-            .iter()
-            .any(|regex| regex.is_match(libfunc_id_str))
-        {
-            "*"
-        } else {
-            // Return default formatting if no special formatting is applicable
-            return if !assigned_variables_str.is_empty() {
-                format!(
-                    "{} {} {}({})",
-                    assigned_variables_str,
-                    equal_sign,
-                    libfunc_id_str.blue(),
-                    parameters_str
-                )
-            } else {
-                format!("{}({})", libfunc_id_str.blue(), parameters_str)
-            };
+        let operator = match libfunc_kind {
+            LibfuncKind::Addition => "+",
+            LibfuncKind::Subtraction => "-",
+            LibfuncKind::Multiplication => "*",
+            _ => {
+                // Return default formatting if no special formatting is applicable
+                return if !assigned_variables_str.is_empty() {
+                    format!(
+                        "{} {} {}({})",
+                        assigned_variables_str,
+                        equal_sign,
+                        libfunc_id_str.blue(),
+                        parameters_str
+                    )
+                } else {
+                    format!("{}({})", libfunc_id_str.blue(), parameters_str)
+                };
+            }
         };
 
         // Format arithmetic operations more explicitly
@@ -353,17 +576,14 @@ impl SierraStatement {
     /// Returns a reference to this statement as a conditional branch if it is one
     pub fn as_conditional_branch(
         &self,
-        declared_libfuncs_names: Vec<String>,
+        declared_libfuncs_names: &[Arc<str>],
     ) -> Option<SierraConditionalBranch> {
         // Check if the statement is a conditional branch
         if self.is_conditional_branch {
             // Match the statement to an invocation
             if let GenStatement::Invocation(invocation) = &self.statement {
-                // Clone the statement
-                let statement = self.statement.clone();
-
                 // Get the function name
-                let libfunc_id_str = Self::get_function_name(invocation, &declared_libfuncs_names);
+                let libfunc_id_str = Self::get_function_name(invocation, declared_libfuncs_names);
 
                 // Extract parameters
                 let parameters = extract_parameters!(invocation.args);
@@ -377,7 +597,7 @@ impl SierraStatement {
 
                 // Create and return SierraConditionalBranch instance
                 return Some(SierraConditionalBranch::new(
-                    SierraStatement::new(statement, self.offset),
+                    self.clone(),
                     libfunc_id_str,
                     parameters,
                     edge_1_offset,
@@ -392,7 +612,7 @@ impl SierraStatement {
     }
 
     /// Extracts the function name from the invocation
-    fn get_function_name(invocation: &Invocation, declared_libfuncs_names: &Vec<String>) -> String {
+    fn get_function_name(invocation: &Invocation, declared_libfuncs_names: &[Arc<str>]) -> String {
         // Try to get the debug name first
         invocation
             .libfunc_id
@@ -404,11 +624,10 @@ impl SierraStatement {
                 declared_libfuncs_names
                     .get(invocation.libfunc_id.id as usize)
                     .map(|name| name.to_string())
-                    // If neither the debug name nor the name from declared_libfuncs_names is present,
-                    // format the id as a string
-                    .or_else(|| Some(format!("[{}]", invocation.libfunc_id.id)))
             })
-            .unwrap()
+            // If neither the debug name nor the name from declared_libfuncs_names is present,
+            // format the id as a string
+            .unwrap_or_else(|| format!("[{}]", invocation.libfunc_id.id))
     }
 
     /// Checks if any branch in the invocation is a fallthrough branch
@@ -496,6 +715,57 @@ pub enum FunctionType {
     Loop,
 }
 
+/// A ByteArray literal (string longer than 31 bytes) reassembled from its constants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteArrayLiteral {
+    /// Offset of the `struct_construct<core::byte_array::ByteArray>` statement
+    pub offset: u32,
+    /// Offsets of the constants of the data words & the pending word
+    pub words_offsets: Vec<u32>,
+    /// Reassembled string
+    pub value: String,
+}
+
+/// A panic with an error message, summarized from the statements building the panic data
+/// array_new<felt252>, array_append<felt252>(...), struct_construct<core::panics::Panic>,
+/// struct_construct<Tuple<core::panics::Panic, Array<felt252>>> & enum_init<core::panics::PanicResult::<...>, 1>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicSummary {
+    /// Offset of the `enum_init<core::panics::PanicResult::<...>, 1>` statement
+    pub offset: u32,
+    /// Offsets of the statements building the panic data
+    pub hidden_offsets: Vec<u32>,
+    /// Variable assigned by the `enum_init` statement
+    pub variable: String,
+    /// Elements of the error message, the constants are decoded
+    pub message: Vec<String>,
+}
+
+/// Panic safety of a function, used to audit the errors handling of its callers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PanicSafety {
+    /// The function returns a `PanicResult`, its callers have to handle its panics
+    pub returns_panic_result: bool,
+    /// Number of statements returning a panic, constructed by the function or propagated from a callee
+    pub panic_paths: usize,
+    /// The function can panic : it returns a `PanicResult` or it has panic paths
+    pub can_panic: bool,
+    /// The signature of the function can't panic, like the `nopanic` Cairo functions
+    pub nopanic: bool,
+}
+
+impl PanicSafety {
+    /// Creates a new `PanicSafety` instance
+    pub fn new(returns_panic_result: bool, panic_paths: usize) -> Self {
+        Self {
+            returns_panic_result,
+            panic_paths,
+            can_panic: returns_panic_result || panic_paths > 0,
+            nopanic: !returns_panic_result,
+        }
+    }
+}
+
 /// A struct representing a function in a Sierra program
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -525,9 +795,11 @@ pub struct Function<'a> {
     // Function end offset
     pub end_offset: Option<u32>,
     /// A vector of `SierraStatement` instances representing the function's body with offsets
-    pub statements: Vec<SierraStatement>,
+    /// They are modified with `set_statements`, which invalidates the cached CFG
+    statements: Vec<SierraStatement>,
     /// A `ControlFlowGraph` representing the function's CFG
-    pub cfg: Option<ControlFlowGraph>,
+    /// Built on its first access & cached until the statements change
+    cfg: OnceLock<ControlFlowGraph>,
     /// The prototype of the function
     pub prototype: Option<String>,
     /// Arguments of the function
@@ -551,7 +823,7 @@ impl<'a> Function<'a> {
             statements: Vec::new(),
             start_offset: None,
             end_offset: None,
-            cfg: None,
+            cfg: OnceLock::new(),
             prototype: None,
             arguments: Vec::new(),
         }
@@ -563,7 +835,7 @@ impl<'a> Function<'a> {
         &mut self,
         functions: &[Function],
         registry: &ProgramRegistry<CoreType, CoreLibfunc>,
-    ) -> Result<(), String> {
+    ) -> Result<(), SierraAnalyzerError> {
         // Collect the necessary information during the immutable borrow
         let mut updates = Vec::new();
 
@@ -571,7 +843,7 @@ impl<'a> Function<'a> {
             if let GenStatement::Invocation(invoc) = &statement.statement {
                 let lib_func = registry
                     .get_libfunc(&invoc.libfunc_id)
-                    .expect("Library function not found in the registry");
+                    .map_err(|_| SierraAnalyzerError::UnknownLibfunc(invoc.libfunc_id.id))?;
 
                 if let CoreConcreteLibfunc::FunctionCall(f_called) = lib_func {
                     let function_name = parse_element_name!(f_called.function.id);
@@ -591,7 +863,10 @@ impl<'a> Function<'a> {
                                     break;
                                 }
                                 None => {
-                                    return Err("Function type is None".into());
+                                    return Err(SierraAnalyzerError::MissingFunctionInfo(
+                                        "Type",
+                                        current_function_name,
+                                    ));
                                 }
                             }
                         }
@@ -635,19 +910,294 @@ impl<'a> Function<'a> {
         }
     }
 
-    /// Initializes the control flow graph (CFG) for the function
-    pub fn create_cfg(&mut self) {
-        // Create a new control flow graph instance
-        let mut cfg = ControlFlowGraph::new(
-            parse_element_name!(self.function.id.clone()),
-            self.statements.clone(),
-        );
+    /// Returns the panic safety of the function, the returned `PanicResult` is read from its prototype
+    pub fn panic_safety(&self) -> PanicSafety {
+        let returns_panic_result = self
+            .prototype
+            .as_deref()
+            .and_then(|prototype| prototype.rsplit_once("->"))
+            .is_some_and(|(_, ret_types)| ret_types.contains("PanicResult"));
+        let panic_paths = self
+            .statements
+            .iter()
+            .filter(|statement| statement.libfunc_kind == LibfuncKind::PanicResultErr)
+            .count();
 
-        // Generate the CFG basic blocks
-        cfg.generate_basic_blocks();
+        PanicSafety::new(returns_panic_result, panic_paths)
+    }
 
-        // Assign the control flow graph to the function's CFG field
-        self.cfg = Some(cfg);
+    /// Summarizes the panics with an error message of the function
+    pub fn panic_summaries(
+        &self,
+        declared_libfuncs_names: &[Arc<str>],
+        declared_types_names: &[Arc<str>],
+    ) -> Vec<PanicSummary> {
+        // Elements (rendering & offsets of their statements) by variable ID
+        let mut values: HashMap<u64, (String, Vec<u32>)> = HashMap::new();
+        let mut arrays: HashMap<u64, (Vec<String>, Vec<u32>)> = HashMap::new();
+        let mut panics: HashMap<u64, u32> = HashMap::new();
+        let mut panic_data: HashMap<u64, (Vec<String>, Vec<u32>)> = HashMap::new();
+        let mut summaries = Vec::new();
+
+        for statement in &self.statements {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+            let libfunc_id =
+                parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+            let libfunc_id = replace_types_id(declared_types_names, &libfunc_id);
+            let results: Vec<u64> = invocation
+                .branches
+                .first()
+                .map(|branch| branch.results.iter().map(|var| var.id).collect())
+                .unwrap_or_default();
+            let args: Vec<u64> = invocation.args.iter().map(|var| var.id).collect();
+            let offset = statement.offset;
+
+            // Constants, decoded as strings when possible
+            if let Some(value) = (statement.libfunc_kind == LibfuncKind::Const)
+                .then(|| {
+                    CONST_REGEXES
+                        .iter()
+                        .find_map(|regex| regex.captures(&libfunc_id))
+                })
+                .flatten()
+                .and_then(|captures| captures["const"].parse::<BigInt>().ok())
+            {
+                if let [result] = results.as_slice() {
+                    let rendering = format_constant(&value).unwrap_or_else(|| value.to_string());
+                    values.insert(*result, (rendering, vec![offset]));
+                }
+            } else if libfunc_id == "array_new<felt252>" {
+                if let [result] = results.as_slice() {
+                    arrays.insert(*result, (Vec::new(), vec![offset]));
+                }
+            } else if libfunc_id == "array_append<felt252>" {
+                let ([array, value], [result]) = (args.as_slice(), results.as_slice()) else {
+                    continue;
+                };
+                let Some((mut elements, mut offsets)) = arrays.get(array).cloned() else {
+                    continue;
+                };
+
+                // The appended variables that are not constants are kept
+                match values.get(value) {
+                    Some((rendering, value_offsets)) => {
+                        elements.push(rendering.clone());
+                        offsets.extend(value_offsets);
+                    }
+                    None => elements.push(format!("v{}", value)),
+                }
+                offsets.push(offset);
+                arrays.insert(*result, (elements, offsets));
+            } else if statement.libfunc_kind == LibfuncKind::Panic {
+                if let [result] = results.as_slice() {
+                    panics.insert(*result, offset);
+                }
+            } else if PANIC_DATA_REGEX.is_match(&libfunc_id) {
+                let ([panic, array], [result]) = (args.as_slice(), results.as_slice()) else {
+                    continue;
+                };
+                if let (Some(panic_offset), Some((elements, offsets))) =
+                    (panics.get(panic), arrays.get(array))
+                {
+                    let mut offsets = offsets.clone();
+                    offsets.extend([*panic_offset, offset]);
+                    panic_data.insert(*result, (elements.clone(), offsets));
+                }
+            } else if statement.libfunc_kind == LibfuncKind::PanicResultErr {
+                let [data] = args.as_slice() else {
+                    continue;
+                };
+                if let Some((message, hidden_offsets)) = panic_data.get(data) {
+                    let variable = extract_parameters!(&invocation.branches[0].results).join(", ");
+                    summaries.push(PanicSummary {
+                        offset,
+                        hidden_offsets: hidden_offsets.clone(),
+                        variable,
+                        message: message.clone(),
+                    });
+                }
+            }
+            // The elements are kept through the variables renamings
+            else if statement.libfunc_kind.is_assignment() {
+                let (Some(arg), Some(result)) = (args.first(), results.first()) else {
+                    continue;
+                };
+                if let Some((rendering, mut offsets)) = values.get(arg).cloned() {
+                    offsets.push(offset);
+                    values.insert(*result, (rendering, offsets));
+                }
+                if let Some((elements, mut offsets)) = arrays.get(arg).cloned() {
+                    offsets.push(offset);
+                    arrays.insert(*result, (elements, offsets));
+                }
+                if let Some((elements, mut offsets)) = panic_data.get(arg).cloned() {
+                    offsets.push(offset);
+                    panic_data.insert(*result, (elements, offsets));
+                }
+            }
+            // The duplicated variables are used elsewhere, their statements can't be hidden
+            else if statement.libfunc_kind == LibfuncKind::Dup {
+                if let Some(arg) = args.first() {
+                    values.remove(arg);
+                    arrays.remove(arg);
+                    panic_data.remove(arg);
+                }
+            }
+        }
+
+        summaries
+    }
+
+    /// Reassembles the ByteArray literals constructed by the function
+    /// A ByteArray is a struct of an array of 31 bytes words (`data`), a pending word & its length
+    /// e.g. array_new<bytes31>, array_append<bytes31>(...) then struct_construct<core::byte_array::ByteArray>
+    pub fn byte_array_literals(
+        &self,
+        declared_libfuncs_names: &[Arc<str>],
+        declared_types_names: &[Arc<str>],
+    ) -> Vec<ByteArrayLiteral> {
+        // Constants (value & offset) and bytes31 arrays (words values & offsets) by variable ID
+        let mut constants: HashMap<u64, (BigInt, u32)> = HashMap::new();
+        let mut arrays: HashMap<u64, (Vec<BigInt>, Vec<u32>)> = HashMap::new();
+        let mut literals = Vec::new();
+
+        for statement in &self.statements {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+            let libfunc_id =
+                parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+            let libfunc_id = replace_types_id(declared_types_names, &libfunc_id);
+            let results: Vec<u64> = invocation
+                .branches
+                .first()
+                .map(|branch| branch.results.iter().map(|var| var.id).collect())
+                .unwrap_or_default();
+            let args: Vec<u64> = invocation.args.iter().map(|var| var.id).collect();
+
+            // Constants
+            if let Some(value) = (statement.libfunc_kind == LibfuncKind::Const)
+                .then(|| {
+                    CONST_REGEXES
+                        .iter()
+                        .find_map(|regex| regex.captures(&libfunc_id))
+                })
+                .flatten()
+                .and_then(|captures| captures["const"].parse::<BigInt>().ok())
+            {
+                if let [result] = results.as_slice() {
+                    constants.insert(*result, (value, statement.offset));
+                }
+            }
+            // bytes31 arrays
+            else if statement.libfunc_kind == LibfuncKind::ArrayNew
+                && NEW_ARRAY_REGEX
+                    .captures(&libfunc_id)
+                    .map_or(false, |captures| &captures["array_type"] == "bytes31")
+            {
+                if let [result] = results.as_slice() {
+                    arrays.insert(*result, (Vec::new(), Vec::new()));
+                }
+            } else if statement.libfunc_kind == LibfuncKind::ArrayAppend
+                && ARRAY_APPEND_REGEX
+                    .captures(&libfunc_id)
+                    .map_or(false, |captures| &captures[1] == "bytes31")
+            {
+                if let ([array, word], [result]) = (args.as_slice(), results.as_slice()) {
+                    if let (Some((mut words, mut offsets)), Some((word, offset))) =
+                        (arrays.get(array).cloned(), constants.get(word).cloned())
+                    {
+                        words.push(word);
+                        offsets.push(offset);
+                        arrays.insert(*result, (words, offsets));
+                    }
+                }
+            }
+            // ByteArray construction
+            else if libfunc_id == "struct_construct<core::byte_array::ByteArray>" {
+                let [data, pending_word, pending_word_len] = args.as_slice() else {
+                    continue;
+                };
+                let (Some((words, offsets)), Some((pending, pending_offset)), Some((len, _))) = (
+                    arrays.get(data),
+                    constants.get(pending_word),
+                    constants.get(pending_word_len),
+                ) else {
+                    continue;
+                };
+
+                if let Some(value) = decode_byte_array(words, pending, len) {
+                    let mut words_offsets = offsets.clone();
+                    words_offsets.push(*pending_offset);
+                    literals.push(ByteArrayLiteral {
+                        offset: statement.offset,
+                        words_offsets,
+                        value,
+                    });
+                }
+            }
+            // The values are kept through the variables renamings & duplications
+            else if statement.libfunc_kind.is_assignment()
+                || statement.libfunc_kind == LibfuncKind::Dup
+            {
+                let Some(arg) = args.first() else {
+                    continue;
+                };
+                for result in &results {
+                    if let Some(constant) = constants.get(arg).cloned() {
+                        constants.insert(*result, constant);
+                    }
+                    if let Some(array) = arrays.get(arg).cloned() {
+                        arrays.insert(*result, array);
+                    }
+                }
+            }
+        }
+
+        literals
+    }
+
+    /// Returns the statement-level def-use chains of the function variables
+    #[inline]
+    pub fn def_use(&self) -> DataFlow {
+        DataFlow::new(self)
+    }
+
+    /// Returns the SSA form of the function
+    #[inline]
+    pub fn ssa(&self) -> Result<SsaForm, SierraAnalyzerError> {
+        SsaForm::new(self)
+    }
+
+    /// Returns the control flow graph (CFG) of the function, it's built on the first call
+    pub fn cfg(&self) -> &ControlFlowGraph {
+        self.cfg.get_or_init(|| {
+            // Create a new control flow graph instance
+            let mut cfg = ControlFlowGraph::new(
+                parse_element_name!(self.function.id.clone()),
+                self.statements.clone(),
+            );
+
+            // Generate the CFG basic blocks
+            cfg.generate_basic_blocks();
+
+            cfg
+        })
+    }
+
+    /// Returns true if the CFG of the function has already been built
+    #[inline]
+    pub fn has_cfg(&self) -> bool {
+        self.cfg.get().is_some()
+    }
+
+    /// Builds the control flow graph (CFG) of the function if it's not cached yet
+    /// e.g. before cloning or sharing the function, so the copies don't build it again
+    #[inline]
+    pub fn create_cfg(&mut self) {
+        self.cfg();
     }
 
     /// Sets the start offset of the function
@@ -662,10 +1212,24 @@ impl<'a> Function<'a> {
         self.end_offset = Some(end_offset);
     }
 
+    /// Returns the statements of the function's body
+    #[inline]
+    pub fn statements(&self) -> &[SierraStatement] {
+        &self.statements
+    }
+
     /// Sets the statements for the function's body
     #[inline]
     pub fn set_statements(&mut self, statements: Vec<SierraStatement>) {
         self.statements = statements;
+        self.cfg = OnceLock::new();
+    }
+
+    /// Returns the name of the function in its prototype, e.g. `examples::fib::fib`
+    pub fn prototype_name(&self) -> Option<&str> {
+        let prototype = self.prototype.as_deref()?.strip_prefix("func ")?;
+        let name_end = prototype.find('(')?;
+        Some(prototype[..name_end].trim())
     }
 
     /// Sets the prototype of the function
@@ -0,0 +1,185 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::decompiler::libfuncs_patterns::{
+    ADDITION_REGEX, ARRAY_APPEND_REGEX, ARRAY_GET_REGEX, ARRAY_LEN_REGEX, ARRAY_POP_BACK_REGEX,
+    ARRAY_POP_FRONT_REGEX, ARRAY_SLICE_REGEX, BOOL_MATCH_REGEX, BOUNDED_INT_CONSTRAIN_REGEX,
+    BOUNDED_INT_DIV_REM_REGEX, CALLER_ADDRESS_REGEX, CONST_REGEXES, DEPLOY_REGEX,
+    DICT_ENTRY_FINALIZE_REGEX, DICT_ENTRY_GET_REGEX, DICT_NEW_REGEX, DICT_SQUASH_REGEX, DROP_REGEX,
+    DUP_REGEX, EMIT_EVENT_REGEX, EQUAL_REGEX, EXTERNAL_CALL_REGEX, FUNCTION_CALL_REGEX,
+    GAS_CHECK_REGEX, INTO_BOX_REGEX, IS_ZERO_REGEX, LESS_THAN_OR_EQUAL_REGEX, LESS_THAN_REGEX,
+    MATCH_NULLABLE_REGEX, MULTIPLICATION_REGEX, NEW_ARRAY_REGEX, NULLABLE_FROM_BOX_REGEX,
+    NULL_REGEX, OPTION_INIT_REGEX, OPTION_MATCH_REGEX, PANIC_REGEX, PANIC_RESULT_ERR_REGEX,
+    SEND_MESSAGE_TO_L1_REGEX, SNAPSHOT_TAKE_REGEX, SPAN_FROM_TUPLE_REGEX, STORAGE_READ_REGEX,
+    STORAGE_WRITE_REGEX, STORE_TEMP_REGEX, SUBSTRACTION_REGEX, VARIABLE_ASSIGNMENT_REGEX,
+    WIDE_INTEGER_DIVMOD_REGEX, WIDE_INTEGER_OPERATION_REGEX,
+};
+
+/// Kind of a declared libfunc
+/// The libfuncs are classified once from their name when they are declared, the statements
+/// carry the kind of their libfunc so the patterns are not matched again for every statement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LibfuncKind {
+    // Syscalls
+    StorageRead,
+    StorageWrite,
+    EmitEvent,
+    SendMessageToL1,
+    ExternalCall,
+    Deploy,
+    CallerAddress,
+    GasCheck,
+
+    // Panic construction
+    Panic,
+    PanicResultErr,
+
+    // Arithmetic operations, including the corelib functions implementing them
+    WideIntegerOperation,
+    WideIntegerDivmod,
+    BoundedIntDivRem,
+    Addition,
+    Subtraction,
+    Multiplication,
+
+    // User defined function call
+    FunctionCall,
+
+    // Variables drops, copies & casts
+    Drop,
+    StoreTemp,
+    Dup,
+    SnapshotTake,
+    IntoBox,
+    SpanFromTuple,
+    Assignment,
+
+    // Options & nullables creation
+    OptionInit,
+    Null,
+    NullableFromBox,
+
+    // Dictionaries & arrays operations
+    DictNew,
+    DictEntryGet,
+    DictEntryFinalize,
+    DictSquash,
+    ArrayNew,
+    ArrayAppend,
+    ArrayPopFront,
+    ArrayPopBack,
+    ArrayGet,
+    ArraySlice,
+    ArrayLen,
+
+    // Consts declarations
+    Const,
+
+    // Conditions
+    IsZero,
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+    BoolMatch,
+    OptionMatch,
+    MatchNullable,
+    BoundedIntConstrain,
+
+    // Libfuncs without a dedicated handling
+    #[default]
+    Other,
+}
+
+lazy_static! {
+    /// Patterns of the libfuncs kinds, tried in order
+    /// The arithmetic operations are matched before the function calls as the corelib
+    /// implements some of them (e.g. `core::Felt252Add::add`)
+    static ref LIBFUNC_KINDS_PATTERNS: Vec<(LibfuncKind, Vec<Regex>)> = vec![
+        (LibfuncKind::StorageRead, vec![STORAGE_READ_REGEX.clone()]),
+        (LibfuncKind::StorageWrite, vec![STORAGE_WRITE_REGEX.clone()]),
+        (LibfuncKind::EmitEvent, vec![EMIT_EVENT_REGEX.clone()]),
+        (LibfuncKind::SendMessageToL1, vec![SEND_MESSAGE_TO_L1_REGEX.clone()]),
+        (LibfuncKind::ExternalCall, vec![EXTERNAL_CALL_REGEX.clone()]),
+        (LibfuncKind::Deploy, vec![DEPLOY_REGEX.clone()]),
+        (LibfuncKind::CallerAddress, vec![CALLER_ADDRESS_REGEX.clone()]),
+        (LibfuncKind::GasCheck, vec![GAS_CHECK_REGEX.clone()]),
+        (LibfuncKind::Panic, vec![PANIC_REGEX.clone()]),
+        (LibfuncKind::PanicResultErr, vec![PANIC_RESULT_ERR_REGEX.clone()]),
+        (LibfuncKind::WideIntegerOperation, vec![WIDE_INTEGER_OPERATION_REGEX.clone()]),
+        (LibfuncKind::WideIntegerDivmod, vec![WIDE_INTEGER_DIVMOD_REGEX.clone()]),
+        (LibfuncKind::BoundedIntDivRem, vec![BOUNDED_INT_DIV_REM_REGEX.clone()]),
+        (LibfuncKind::Addition, ADDITION_REGEX.clone()),
+        (LibfuncKind::Subtraction, SUBSTRACTION_REGEX.clone()),
+        (LibfuncKind::Multiplication, MULTIPLICATION_REGEX.clone()),
+        (LibfuncKind::FunctionCall, vec![FUNCTION_CALL_REGEX.clone()]),
+        (LibfuncKind::Drop, vec![DROP_REGEX.clone()]),
+        (LibfuncKind::StoreTemp, vec![STORE_TEMP_REGEX.clone()]),
+        (LibfuncKind::Dup, vec![DUP_REGEX.clone()]),
+        (LibfuncKind::SnapshotTake, vec![SNAPSHOT_TAKE_REGEX.clone()]),
+        (LibfuncKind::IntoBox, vec![INTO_BOX_REGEX.clone()]),
+        (LibfuncKind::SpanFromTuple, vec![SPAN_FROM_TUPLE_REGEX.clone()]),
+        (LibfuncKind::Assignment, VARIABLE_ASSIGNMENT_REGEX.clone()),
+        (LibfuncKind::OptionInit, vec![OPTION_INIT_REGEX.clone()]),
+        (LibfuncKind::Null, vec![NULL_REGEX.clone()]),
+        (LibfuncKind::NullableFromBox, vec![NULLABLE_FROM_BOX_REGEX.clone()]),
+        (LibfuncKind::DictNew, vec![DICT_NEW_REGEX.clone()]),
+        (LibfuncKind::DictEntryGet, vec![DICT_ENTRY_GET_REGEX.clone()]),
+        (LibfuncKind::DictEntryFinalize, vec![DICT_ENTRY_FINALIZE_REGEX.clone()]),
+        (LibfuncKind::DictSquash, vec![DICT_SQUASH_REGEX.clone()]),
+        (LibfuncKind::ArrayNew, vec![NEW_ARRAY_REGEX.clone()]),
+        (LibfuncKind::ArrayAppend, vec![ARRAY_APPEND_REGEX.clone()]),
+        (LibfuncKind::ArrayPopFront, vec![ARRAY_POP_FRONT_REGEX.clone()]),
+        (LibfuncKind::ArrayPopBack, vec![ARRAY_POP_BACK_REGEX.clone()]),
+        (LibfuncKind::ArrayGet, vec![ARRAY_GET_REGEX.clone()]),
+        (LibfuncKind::ArraySlice, vec![ARRAY_SLICE_REGEX.clone()]),
+        (LibfuncKind::ArrayLen, vec![ARRAY_LEN_REGEX.clone()]),
+        (LibfuncKind::Const, CONST_REGEXES.clone()),
+        (LibfuncKind::IsZero, vec![IS_ZERO_REGEX.clone()]),
+        (LibfuncKind::Equal, vec![EQUAL_REGEX.clone()]),
+        (LibfuncKind::LessThan, vec![LESS_THAN_REGEX.clone()]),
+        (LibfuncKind::LessThanOrEqual, vec![LESS_THAN_OR_EQUAL_REGEX.clone()]),
+        (LibfuncKind::BoolMatch, vec![BOOL_MATCH_REGEX.clone()]),
+        (LibfuncKind::OptionMatch, vec![OPTION_MATCH_REGEX.clone()]),
+        (LibfuncKind::MatchNullable, vec![MATCH_NULLABLE_REGEX.clone()]),
+        (LibfuncKind::BoundedIntConstrain, vec![BOUNDED_INT_CONSTRAIN_REGEX.clone()]),
+    ];
+}
+
+impl LibfuncKind {
+    /// Classifies a libfunc from its name, see `LIBFUNC_KINDS_PATTERNS`
+    pub fn classify(libfunc_name: &str) -> Self {
+        LIBFUNC_KINDS_PATTERNS
+            .iter()
+            .find(|(_, regexes)| regexes.iter().any(|regex| regex.is_match(libfunc_name)))
+            .map(|(kind, _)| *kind)
+            .unwrap_or_default()
+    }
+
+    /// Checks if the libfunc assigns its argument to a new variable (e.g. `rename`, `store_temp`)
+    #[inline]
+    pub fn is_assignment(&self) -> bool {
+        matches!(self, Self::StoreTemp | Self::Assignment)
+    }
+
+    /// Checks if the libfunc is an integer arithmetic operation
+    #[inline]
+    pub fn is_arithmetic(&self) -> bool {
+        matches!(
+            self,
+            Self::WideIntegerOperation | Self::Addition | Self::Subtraction | Self::Multiplication
+        )
+    }
+
+    /// Checks if the libfunc may be a user defined function call
+    /// The corelib functions implementing arithmetic operations are classified as operations
+    #[inline]
+    pub fn may_be_function_call(&self) -> bool {
+        *self == Self::FunctionCall || self.is_arithmetic()
+    }
+
+    /// Checks if the libfunc is a condition on a value (e.g. zero checks, equalities)
+    #[inline]
+    pub fn is_condition(&self) -> bool {
+        matches!(self, Self::IsZero | Self::Equal | Self::BoolMatch)
+    }
+}
@@ -19,19 +19,37 @@ lazy_static! {
     pub static ref FUNCTION_CALL_REGEX: Regex = Regex::new(r"function_call<(.*)>").unwrap();
 
     // Arithmetic operations
+    // Signed integers operations are `i<bits>_overflowing_<operation>_impl` & `i<bits>_wide_mul`
     pub static ref ADDITION_REGEX: Vec<Regex> = vec![
-        Regex::new(r"(felt|u)_?(8|16|32|64|128|252)(_overflowing)?_add").unwrap(),
+        Regex::new(r"(felt|u|i)_?(8|16|32|64|128|252)(_overflowing)?_add").unwrap(),
         Regex::new(r"function_call<user@core::Felt(8|16|32|64|128|252)Add::add>").unwrap(),
+        Regex::new(r"function_call<user@core::integer::I(8|16|32|64|128)Add::add>").unwrap(),
+        Regex::new(r"bounded_int_add<.+>").unwrap(),
     ];
     pub static ref SUBSTRACTION_REGEX: Vec<Regex> = vec![
-        Regex::new(r"(felt|u)_?(8|16|32|64|128|252)(_overflowing)?_sub").unwrap(),
+        Regex::new(r"(felt|u|i)_?(8|16|32|64|128|252)(_overflowing)?_sub").unwrap(),
         Regex::new(r"function_call<user@core::Felt(8|16|32|64|128|252)Sub::sub>").unwrap(),
+        Regex::new(r"function_call<user@core::integer::I(8|16|32|64|128)Sub::sub>").unwrap(),
+        Regex::new(r"bounded_int_sub<.+>").unwrap(),
      ];
     pub static ref MULTIPLICATION_REGEX: Vec<Regex> = vec![
         Regex::new(r"(felt|u)_?(8|16|32|64|128|252)(_overflowing)?_mul").unwrap(),
+        Regex::new(r"i(8|16|32|64)_wide_mul").unwrap(),
         Regex::new(r"function_call<user@core::Felt(8|16|32|64|128|252)Mul::mul>").unwrap(),
+        Regex::new(r"function_call<user@core::integer::I(8|16|32|64|128)Mul::mul>").unwrap(),
+        Regex::new(r"bounded_int_mul<.+>").unwrap(),
     ];
 
+    // u256 & u512 operations, their operands are structs of u128 limbs
+    // They are implemented by the corelib (e.g. core::integer::U256Add::add)
+    pub static ref WIDE_INTEGER_OPERATION_REGEX: Regex = Regex::new(r"function_call<user@core::integer::(?P<type>U256|U512)(Add|Sub|Mul|Div|Rem)::(?P<operation>add|sub|mul|div|rem)>").unwrap();
+    pub static ref WIDE_INTEGER_DIVMOD_REGEX: Regex = Regex::new(r"(?P<type>u256|u512)_safe_divmod(_by_u256)?").unwrap();
+
+    // Bounded integers divisions & constraints
+    // The bounds of the operands & the results are generic arguments of the libfuncs
+    pub static ref BOUNDED_INT_DIV_REM_REGEX: Regex = Regex::new(r"bounded_int_div_rem<.+>").unwrap();
+    pub static ref BOUNDED_INT_CONSTRAIN_REGEX: Regex = Regex::new(r"bounded_int_constrain<.+, (?P<boundary>-?[0-9]+)>").unwrap();
+
     // Variable duplication
     pub static ref DUP_REGEX: Regex = Regex::new(r"dup(<.*>)?").unwrap();
 
@@ -43,14 +61,117 @@ lazy_static! {
         Regex::new(r"unbox<.+>").unwrap()
     ];
 
+    // Snapshots, boxes & spans creation
+    // They don't change the values & are rendered as casts in the regular output
+    pub static ref SNAPSHOT_TAKE_REGEX: Regex = Regex::new(r"snapshot_take<.+>").unwrap();
+    pub static ref INTO_BOX_REGEX: Regex = Regex::new(r"into_box<(?P<type>.+)>").unwrap();
+    pub static ref SPAN_FROM_TUPLE_REGEX: Regex = Regex::new(r"span_from_tuple<.+>").unwrap();
+
+    // Gas checks, branch 1 is taken when there is not enough gas
+    pub static ref GAS_CHECK_REGEX: Regex = Regex::new(r"^withdraw_gas(_all)?$").unwrap();
+
     // Check if an integer is 0
-    pub static ref IS_ZERO_REGEX: Regex = Regex::new(r"(felt|u)_?(8|16|32|64|128|252)_is_zero").unwrap();
+    pub static ref IS_ZERO_REGEX: Regex = Regex::new(r"((felt|u|i)_?(8|16|32|64|128|252|256)|bounded_int)_is_zero").unwrap();
+
+    // Integers comparisons
+    // Branch 0 is taken when the comparison is false, branch 1 when it is true
+    // Signed integers are compared using `i<bits>_diff`, its branch 1 is taken when lhs < rhs
+    pub static ref LESS_THAN_REGEX: Regex = Regex::new(r"^((u|i)(8|16|32|64|128|256)_lt|i(8|16|32|64|128)_diff)$").unwrap();
+    pub static ref LESS_THAN_OR_EQUAL_REGEX: Regex = Regex::new(r"^(u|i)(8|16|32|64|128|256)_le$").unwrap();
+    pub static ref EQUAL_REGEX: Regex = Regex::new(r"^(u|i)(8|16|32|64|128|256)_eq$").unwrap();
+    // Comparisons implemented by the core library (e.g. the u256 & the contract addresses ones)
+    pub static ref COMPARISON_FUNCTION_CALL_REGEX: Regex = Regex::new(r"^function_call<user@core::.+(PartialEq|PartialOrd)(::<.+>)?::(eq|ne|lt|le|gt|ge)>$").unwrap();
+
+    // Unsigned integers overflowing operations
+    // Branch 0 is taken when the result is in range, branch 1 returns the wrapped result
+    pub static ref OVERFLOWING_OPERATION_REGEX: Regex = Regex::new(r"^u(?P<bits>8|16|32|64|128)_overflowing_(?P<operator>add|sub)$").unwrap();
+
+    // Integers types & conversions, used by the value-range analysis
+    // The results of the integer libfuncs are range checked to the type of their operands
+    // (wide multiplications & signed differences excluded)
+    pub static ref INTEGER_TYPE_REGEX: Regex = Regex::new(r"^(?P<sign>u|i)(?P<bits>8|16|32|64|128)$").unwrap();
+    pub static ref INTEGER_LIBFUNC_REGEX: Regex = Regex::new(r"^(?P<type>(u|i)(8|16|32|64|128))_(overflowing_(add|sub|mul)(_impl)?|safe_divmod|sqrt|bitwise|byte_reverse)$").unwrap();
+    pub static ref INTEGER_TO_FELT252_REGEX: Regex = Regex::new(r"^(u|i)(8|16|32|64|128)_to_felt252$").unwrap();
+    pub static ref INTEGER_FROM_FELT252_REGEX: Regex = Regex::new(r"^(?P<type>(u|i)(8|16|32|64|128))_try_from_felt252$").unwrap();
+    pub static ref DOWNCAST_REGEX: Regex = Regex::new(r"^downcast<.+, (?P<type>(u|i)(8|16|32|64|128))>$").unwrap();
+    // Conversions of a felt252 to a range checked integer
+    pub static ref FELT252_RANGE_CHECK_REGEX: Regex = Regex::new(r"^(u128s_from_felt252|(u|i)(8|16|32|64|128)_try_from_felt252|downcast<.+>|bounded_int_constrain<.+>)$").unwrap();
+    // Splits of a felt252 & of a u256 into their low & high u128 words
+    pub static ref U128S_FROM_FELT252_REGEX: Regex = Regex::new(r"^u128s_from_felt252$").unwrap();
+    pub static ref U256_DECONSTRUCT_REGEX: Regex = Regex::new(r"^struct_deconstruct<core::integer::u256>$").unwrap();
+    // Comparisons of a felt252, the ordering converts it to a u256 first
+    pub static ref FELT252_COMPARISON_REGEX: Regex = Regex::new(r"^(felt252_is_zero|u128s_from_felt252|function_call<user@core::Felt252PartialOrd::(lt|le|gt|ge)>)$").unwrap();
+    pub static ref FELT252_OPERATION_REGEX: Regex = Regex::new(r"^(felt252_|function_call<user@core::Felt252(Add|Sub|Mul)::)(?P<operator>add|sub|mul)>?$").unwrap();
+
+    // Hash functions, a wrapped around input only changes the digest
+    pub static ref HASH_REGEX: Regex = Regex::new(r"^(pedersen|hades_permutation|keccak_syscall|sha256_process_block_syscall|function_call<user@core::(pedersen|poseidon|keccak|sha256)::.+>)$").unwrap();
+    pub static ref PEDERSEN_REGEX: Regex = Regex::new(r"^pedersen$").unwrap();
+    pub static ref HADES_PERMUTATION_REGEX: Regex = Regex::new(r"^hades_permutation$").unwrap();
+
+    // Panic construction (assert-like patterns)
+    pub static ref PANIC_REGEX: Regex = Regex::new(r"^struct_construct<core::panics::Panic>$").unwrap();
+    pub static ref PANIC_DATA_REGEX: Regex = Regex::new(r"^struct_construct<Tuple<core::panics::Panic, Array<felt252>>>$").unwrap();
+    pub static ref PANIC_RESULT_ERR_REGEX: Regex = Regex::new(r"^enum_init<core::panics::PanicResult::<.+>, 1>$").unwrap();
+
+    // Booleans operations
+    pub static ref BOOL_AND_REGEX: Regex = Regex::new(r"^bool_and_impl$").unwrap();
+    pub static ref BOOL_OR_REGEX: Regex = Regex::new(r"^bool_or_impl$").unwrap();
+    pub static ref BOOL_XOR_REGEX: Regex = Regex::new(r"^bool_xor_impl$").unwrap();
+    pub static ref BOOL_NOT_REGEX: Regex = Regex::new(r"^bool_not_impl$").unwrap();
+    pub static ref BOOL_INIT_REGEX: Regex = Regex::new(r"^enum_init<core::bool, (?P<variant>[01])>$").unwrap();
+    pub static ref BOOL_MATCH_REGEX: Regex = Regex::new(r"^enum_match<core::bool>$").unwrap();
+
+    // Enums initialization & matching
+    pub static ref ENUM_INIT_REGEX: Regex = Regex::new(r"^enum_init<.+, (?P<variant>[0-9]+)>$").unwrap();
+    pub static ref ENUM_MATCH_REGEX: Regex = Regex::new(r"^enum_match<.+>$").unwrap();
+
+    // Options initialization & matching
+    // Some is the variant (and the branch) 0, None the variant 1
+    pub static ref OPTION_INIT_REGEX: Regex = Regex::new(r"enum_init<core::option::Option::<.+>, (?P<variant>[01])>").unwrap();
+    pub static ref OPTION_MATCH_REGEX: Regex = Regex::new(r"enum_match<core::option::Option::<.+>>").unwrap();
+
+    // Nullables creation & matching
+    // The branch 0 of match_nullable is taken when the value is null
+    pub static ref NULL_REGEX: Regex = Regex::new(r"null<.+>").unwrap();
+    pub static ref NULLABLE_FROM_BOX_REGEX: Regex = Regex::new(r"nullable_from_box<(?P<type>.+)>").unwrap();
+    pub static ref MATCH_NULLABLE_REGEX: Regex = Regex::new(r"match_nullable<.+>").unwrap();
+
+    // Storage syscalls & storage addresses
+    pub static ref STORAGE_READ_REGEX: Regex = Regex::new(r"^storage_read_syscall$").unwrap();
+    pub static ref STORAGE_WRITE_REGEX: Regex = Regex::new(r"^storage_write_syscall$").unwrap();
+    pub static ref STORAGE_ADDRESS_FROM_BASE_REGEX: Regex = Regex::new(r"^storage_address_from_base$").unwrap();
+    pub static ref STORAGE_ADDRESS_FROM_BASE_AND_OFFSET_REGEX: Regex = Regex::new(r"^storage_address_from_base_and_offset$").unwrap();
+    pub static ref STORAGE_BASE_ADDRESS_FROM_FELT252_REGEX: Regex = Regex::new(r"^storage_base_address_from_felt252$").unwrap();
+    pub static ref STORAGE_BASE_ADDRESS_CONST_REGEX: Regex = Regex::new(r"^storage_base_address_const<(?P<const>[0-9]+)>$").unwrap();
+    pub static ref ADDRESS_TO_FELT252_REGEX: Regex = Regex::new(r"^(contract_address|class_hash)_to_felt252$").unwrap();
+
+    // Syscalls calling another contract or class, deploying a contract, emitting an event & sending a message to L1
+    pub static ref EXTERNAL_CALL_REGEX: Regex = Regex::new(r"^(call_contract|library_call)_syscall$").unwrap();
+    pub static ref DEPLOY_REGEX: Regex = Regex::new(r"^deploy_syscall$").unwrap();
+    pub static ref EMIT_EVENT_REGEX: Regex = Regex::new(r"^emit_event_syscall$").unwrap();
+    pub static ref SEND_MESSAGE_TO_L1_REGEX: Regex = Regex::new(r"^send_message_to_l1_syscall$").unwrap();
+
+    // Syscalls reading the caller address
+    pub static ref CALLER_ADDRESS_REGEX: Regex = Regex::new(r"^(get_caller_address_syscall|get_execution_info(_v2)?_syscall)$").unwrap();
+
+    // Execution & transaction infos deconstruction
+    // The caller address is the field 2 of the execution info, the account address the field 1 of the transaction info
+    pub static ref EXECUTION_INFO_DECONSTRUCT_REGEX: Regex = Regex::new(r"^struct_deconstruct<core::starknet::info::(v2::)?ExecutionInfo>$").unwrap();
+    pub static ref TX_INFO_DECONSTRUCT_REGEX: Regex = Regex::new(r"^struct_deconstruct<core::starknet::info::(v2::)?TxInfo>$").unwrap();
 
     // Consts declarations
     pub static ref CONST_REGEXES: Vec<Regex> = vec![
         Regex::new(r"const_as_immediate<Const<.*, (?P<const>-?[0-9]+)>>").unwrap(),
         Regex::new(r"storage_base_address_const<(?P<const>-?[0-9]+)>").unwrap(),
-        Regex::new(r"(felt|u)_?(8|16|32|64|128|252)_const<(?P<const>-?[0-9]+)>").unwrap(),
+        Regex::new(r"(felt|u|i)_?(8|16|32|64|128|252)_const<(?P<const>-?[0-9]+)>").unwrap(),
+        Regex::new(r"bytes31_const<(?P<const>[0-9]+)>").unwrap(),
+    ];
+
+    // Class hashes constants
+    // Used to follow the library calls to a known class
+    pub static ref CLASS_HASH_CONST_REGEXES: Vec<Regex> = vec![
+        Regex::new(r"class_hash_const<(?P<class_hash>[0-9]+)>").unwrap(),
+        Regex::new(r"const_as_immediate<Const<ClassHash, (?P<class_hash>[0-9]+)>>").unwrap(),
     ];
 
     // User defined function
@@ -60,10 +181,33 @@ lazy_static! {
     pub static ref NEW_ARRAY_REGEX: Regex = Regex::new(r"array_new<(?P<array_type>.+)>").unwrap();
     pub static ref ARRAY_APPEND_REGEX: Regex = Regex::new(r"array_append<(.+)>").unwrap();
 
+    // Arrays accesses, branch 1 is taken when the array is empty or the access is out of bounds
+    pub static ref ARRAY_POP_FRONT_REGEX: Regex = Regex::new(r"^array_(snapshot_)?pop_front(_consume)?<.+>$").unwrap();
+    pub static ref ARRAY_POP_BACK_REGEX: Regex = Regex::new(r"^array_snapshot_pop_back<.+>$").unwrap();
+    pub static ref ARRAY_GET_REGEX: Regex = Regex::new(r"^array_get<.+>$").unwrap();
+    pub static ref ARRAY_SLICE_REGEX: Regex = Regex::new(r"^array_slice<.+>$").unwrap();
+    pub static ref ARRAY_LEN_REGEX: Regex = Regex::new(r"^array_len<.+>$").unwrap();
+
+    // Spans are structs wrapping an array snapshot
+    pub static ref SPAN_STRUCT_REGEX: Regex = Regex::new(r"^struct_(de)?construct<core::array::Span::<.+>>$").unwrap();
+    // Arrays, spans & their snapshots types
+    pub static ref ARRAY_TYPE_REGEX: Regex = Regex::new(r"^(Snapshot<)*(Array<.+>|core::array::Span::<.+>)>*$").unwrap();
+
+    // Dictionaries (Felt252Dict) operations
+    // The values are read & written through entries, the squash validates the accesses
+    pub static ref DICT_NEW_REGEX: Regex = Regex::new(r"felt252_dict_new<(?P<value_type>.+)>").unwrap();
+    pub static ref DICT_ENTRY_GET_REGEX: Regex = Regex::new(r"felt252_dict_entry_get<.+>").unwrap();
+    pub static ref DICT_ENTRY_FINALIZE_REGEX: Regex = Regex::new(r"felt252_dict_entry_finalize<.+>").unwrap();
+    pub static ref DICT_SQUASH_REGEX: Regex = Regex::new(r"felt252_dict_squash<.+>").unwrap();
+
     // Regex of a type ID
     // Used to match and replace them in remote contracts
     pub static ref TYPE_ID_REGEX: Regex = Regex::new(r"(?<type_id>\[[0-9]+\])").unwrap();
 
+    // Path of a type name (e.g. core::integer::u256)
+    // Used to shorten the types names
+    pub static ref TYPE_PATH_REGEX: Regex = Regex::new(r"\b(?:[A-Za-z_][A-Za-z0-9_]*::)+(?P<name>[A-Za-z_][A-Za-z0-9_]*)").unwrap();
+
     // User defined types IDs are the 250 first bits of the id name Keccak hash
     // https://github.com/starkware-libs/cairo/blob/b29f639c2090822914f52db6696d71748a8b93a6/crates/cairo-lang-sierra/src/ids.rs#L118
     pub static ref USER_DEFINED_TYPE_ID_REGEX: Regex = Regex::new(r"ut@\[(?<type_id>[0-9]+)\]").unwrap();
@@ -95,4 +239,16 @@ lazy_static! {
         regexes.extend(VARIABLE_ASSIGNMENT_REGEX.clone());
         regexes
     };
+
+    // Libfuncs copying (or converting to a felt252) their first argument
+    // They are followed to find the origin of a value in the SSA form
+    pub static ref COPY_REGEXES: Vec<Regex> = {
+        let mut regexes = VARIABLE_ASSIGNMENT_REGEX.clone();
+        regexes.extend([
+            DUP_REGEX.clone(),
+            SNAPSHOT_TAKE_REGEX.clone(),
+            INTEGER_TO_FELT252_REGEX.clone(),
+        ]);
+        regexes
+    };
 }
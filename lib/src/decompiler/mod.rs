@@ -1,6 +1,10 @@
 pub mod cfg;
 pub mod decompiler;
+pub mod formatting;
 pub mod function;
+pub mod libfunc_kind;
 pub mod libfuncs_patterns;
 pub mod macros;
+pub mod names;
 pub mod utils;
+pub mod validation;
@@ -0,0 +1,44 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Names of the declared types or libfuncs, indexed by their IDs
+/// The names are `Arc<str>` in a reference-counted table, so cloning the table or a name
+/// doesn't copy the strings, e.g. the decompiler, the statements formatting & the analysis
+/// sessions share the same names
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamesTable {
+    names: Arc<Vec<Arc<str>>>,
+}
+
+impl NamesTable {
+    /// Creates an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the name of the next declared ID
+    /// The table is only copied if it's already shared
+    pub fn push(&mut self, name: &str) {
+        Arc::make_mut(&mut self.names).push(Arc::from(name));
+    }
+}
+
+impl Deref for NamesTable {
+    type Target = [Arc<str>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.names
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for NamesTable {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            names: Arc::new(
+                iter.into_iter()
+                    .map(|name| Arc::from(name.as_ref()))
+                    .collect(),
+            ),
+        }
+    }
+}
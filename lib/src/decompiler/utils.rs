@@ -1,31 +1,103 @@
 use num_bigint::BigInt;
 use std::str;
+use std::sync::Arc;
 
 use crate::decompiler::libfuncs_patterns::TYPE_ID_REGEX;
 use crate::decompiler::libfuncs_patterns::USER_DEFINED_TYPE_ID_REGEX;
 
-/// Convert an integer to it's string value or hex value
+/// Convert an integer to its string value or hex value
 /// Used to decode consts
 #[inline]
 pub fn decode_hex_bigint(bigint: &BigInt) -> Option<String> {
-    // Convert the BigInt to a hexadecimal string
+    // Convert the BigInt to a hexadecimal string, the odd-length ones can't be decoded
     let hex_string = format!("{:x}", bigint);
+    if hex_string.len() % 2 != 0 {
+        return None;
+    }
+
+    // Convert the value to a string or hex value
+    Some(decode_utf8_bigint(bigint).unwrap_or_else(|| format!("0x{hex_string}")))
+}
 
-    // Decode the hexadecimal string to a byte vector
-    let bytes = hex::decode(hex_string.clone()).ok()?;
+/// Convert an integer to its string value, if it is a valid UTF-8 string
+fn decode_utf8_bigint(bigint: &BigInt) -> Option<String> {
+    let bytes = hex::decode(format!("{:x}", bigint)).ok()?;
+    str::from_utf8(&bytes).ok().map(|s| s.to_string())
+}
+
+/// Returns the prime of the felt252 field (2**251 + 17 * 2**192 + 1)
+pub fn felt252_prime() -> BigInt {
+    (BigInt::from(1) << 251) + (BigInt::from(17) << 192) + 1
+}
+
+/// Formats a constant value as a readable comment
+/// - Values close to the prime are negative numbers: `-1`
+/// - Powers of two are annotated: `2**128`
+/// - Valid UTF-8 values are strings: `"ERC20: transfer"`
+/// - Large values are addresses or class hashes, padded to 64 hex digits
+/// - Other values don't need a comment
+pub fn format_constant(bigint: &BigInt) -> Option<String> {
+    let prime = felt252_prime();
+
+    // Negative felts are stored as `prime - value`
+    // Only the values close to the prime are negative, the other ones can be addresses
+    if *bigint < prime && (&prime - bigint).bits() <= 128 {
+        return Some(format!("-{}", &prime - bigint));
+    }
+
+    // Powers of two starting from 2**8 (bit masks & integers bounds)
+    let zero = BigInt::from(0);
+    if *bigint > zero && (bigint & (bigint - 1)) == zero && bigint.bits() > 8 {
+        return Some(format!("2**{}", bigint.bits() - 1));
+    }
 
-    // Convert the byte vector to a string or hex value
-    let string = match str::from_utf8(&bytes) {
-        Ok(s) => Some(s.to_string()),
-        Err(_) => Some(format!("0x{hex_string}")),
+    if let Some(decoded_string) = decode_utf8_bigint(bigint) {
+        return Some(format!(r#""{}""#, decoded_string));
+    }
+
+    // Addresses & class hashes are 251 bits values, most of them are at least 200 bits long
+    if bigint.bits() >= 200 {
+        return Some(format!("{:#066x}", bigint));
+    }
+
+    None
+}
+
+/// Decodes a ByteArray from its 31 bytes data words & its pending word
+/// Returns None if the bytes are not a valid UTF-8 string
+pub fn decode_byte_array(
+    words: &[BigInt],
+    pending_word: &BigInt,
+    pending_word_len: &BigInt,
+) -> Option<String> {
+    let pending_word_len = usize::try_from(pending_word_len)
+        .ok()
+        .filter(|len| *len < 31)?;
+
+    // The words are big-endian numbers, left-padded with zeros
+    let word_bytes = |word: &BigInt, len: usize| -> Option<Vec<u8>> {
+        let (_, bytes) = word.to_bytes_be();
+        let bytes = if *word == BigInt::from(0) {
+            Vec::new()
+        } else {
+            bytes
+        };
+        let padding = len.checked_sub(bytes.len())?;
+        Some([vec![0; padding], bytes].concat())
     };
 
-    string
+    let mut bytes = Vec::new();
+    for word in words {
+        bytes.extend(word_bytes(word, 31)?);
+    }
+    bytes.extend(word_bytes(pending_word, pending_word_len)?);
+
+    String::from_utf8(bytes).ok()
 }
 
 /// Replaces type IDs in the given invocation string with the corresponding type names from the declared_types_names list
 /// If there are no matches or if there is an error in the process, the original string is returned
-pub fn replace_types_id(declared_types_names: &Vec<String>, invocation: &str) -> String {
+pub fn replace_types_id(declared_types_names: &[Arc<str>], invocation: &str) -> String {
     // Use the TYPE_ID_REGEX to replace all matches in the invocation string
     TYPE_ID_REGEX
         .replace_all(&invocation, |caps: &regex::Captures| {
@@ -53,7 +125,7 @@ pub fn replace_types_id(declared_types_names: &Vec<String>, invocation: &str) ->
                     }
                 })
                 // Use the parsed type ID as an index into the declared_types_names list
-                .and_then(|index| declared_types_names.get(index).cloned())
+                .and_then(|index| declared_types_names.get(index).map(|name| name.to_string()))
                 // If there was an error, return the original type ID
                 .unwrap_or_else(|| caps[0].to_string())
         })
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::decompiler::cfg::{BasicBlock, EdgeType};
+use crate::decompiler::decompiler::Decompiler;
+use crate::decompiler::utils::replace_types_id;
+use crate::error::Result;
+use crate::parse_element_name;
+use crate::sierra_program::SierraProgram;
+
+/// Statement of the verbose decompiler output, or a delimiter of the `if` blocks
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParsedStatement {
+    /// Libfunc invocation (user-defined function calls are named `user@<function>`)
+    Invocation(String),
+    /// Conditional branch, `if (<libfunc>(<args>) == 0) {`
+    Branch(String),
+    /// Return statement
+    Return,
+    /// `} else {`
+    Else,
+    /// `}`
+    End,
+}
+
+impl ParsedStatement {
+    /// Returns the libfunc name of the statement, or None for the blocks delimiters
+    fn libfunc(&self) -> Option<&str> {
+        match self {
+            ParsedStatement::Invocation(libfunc) | ParsedStatement::Branch(libfunc) => {
+                Some(libfunc)
+            }
+            ParsedStatement::Return => Some("return"),
+            ParsedStatement::Else | ParsedStatement::End => None,
+        }
+    }
+}
+
+/// Difference between the decompiler output and the original program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MismatchKind {
+    /// The function is not in the decompiler output
+    MissingFunction,
+    /// Statements of the program are not in the decompiler output
+    DroppedStatement { libfunc: String, count: usize },
+    /// Statements of the decompiler output are not in the program
+    AddedStatement { libfunc: String, count: usize },
+    /// The statements of a basic block are not in the same order in the decompiler output
+    BlockOrder { offset: u32 },
+    /// The `if` or `else` block of a conditional branch doesn't start with its target
+    BranchTarget { offset: u32, libfunc: String },
+    /// The conditional branch has more than 2 targets, only 2 of them are decompiled
+    MissingBranchTargets {
+        offset: u32,
+        libfunc: String,
+        targets: usize,
+    },
+}
+
+/// Difference between the decompiler output of a function and its original statements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Name of the function
+    pub function: String,
+    /// Kind of difference
+    pub kind: MismatchKind,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            MismatchKind::MissingFunction => {
+                write!(f, "{} : missing from the output", self.function)
+            }
+            MismatchKind::DroppedStatement { libfunc, count } => {
+                write!(f, "{} : {} x {} dropped", self.function, count, libfunc)
+            }
+            MismatchKind::AddedStatement { libfunc, count } => {
+                write!(f, "{} : {} x {} added", self.function, count, libfunc)
+            }
+            MismatchKind::BlockOrder { offset } => write!(
+                f,
+                "{} : statements of the basic block {} are reordered",
+                self.function, offset
+            ),
+            MismatchKind::BranchTarget { offset, libfunc } => write!(
+                f,
+                "{} : wrong target of the {} branch at offset {}",
+                self.function, libfunc, offset
+            ),
+            MismatchKind::MissingBranchTargets {
+                offset,
+                libfunc,
+                targets,
+            } => write!(
+                f,
+                "{} : only 2 of the {} targets of the {} branch at offset {} are decompiled",
+                self.function, targets, libfunc, offset
+            ),
+        }
+    }
+}
+
+/// Result of the round-trip validation of a program
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// Number of validated functions
+    pub functions: usize,
+    /// Number of validated statements
+    pub statements: usize,
+    /// Differences between the decompiler output & the program
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ValidationReport {
+    /// Returns true if the decompiler output is equivalent to the program
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Decompiles a program with the verbose output, re-parses the decompiled statements and checks
+/// that they are structurally equivalent to the original program : same libfuncs sequences in each
+/// basic block & same branches targets
+pub fn validate_program(sierra_program: &SierraProgram) -> Result<ValidationReport> {
+    let mut decompiler = sierra_program.decompiler(true);
+    let output = decompiler.try_decompile(false)?;
+    let parsed_functions = parse_output(&output);
+
+    let mut report = ValidationReport::default();
+    for function in &decompiler.functions {
+        let name = parse_element_name!(function.function.id);
+        let mismatch = |kind| Mismatch {
+            function: name.clone(),
+            kind,
+        };
+
+        let Some(parsed) = parsed_functions.get(&name) else {
+            report
+                .mismatches
+                .push(mismatch(MismatchKind::MissingFunction));
+            continue;
+        };
+
+        let blocks = &function.cfg().basic_blocks;
+        let expected: Vec<Vec<ParsedStatement>> = blocks
+            .iter()
+            .map(|block| expected_statements(&decompiler, block))
+            .collect();
+
+        report.functions += 1;
+        report.statements += expected.iter().map(Vec::len).sum::<usize>();
+
+        // Same libfuncs in the output & the program
+        for (libfunc, count) in count_differences(expected.iter().flatten(), parsed) {
+            let kind = if count > 0 {
+                MismatchKind::DroppedStatement {
+                    libfunc,
+                    count: count as usize,
+                }
+            } else {
+                MismatchKind::AddedStatement {
+                    libfunc,
+                    count: count.unsigned_abs() as usize,
+                }
+            };
+            report.mismatches.push(mismatch(kind));
+        }
+
+        // The statements of each basic block are printed in order
+        let statements: Vec<&ParsedStatement> =
+            parsed.iter().filter(|s| s.libfunc().is_some()).collect();
+        for (block, expected_block) in blocks.iter().zip(&expected) {
+            if !expected_block.is_empty() && find_sequence(&statements, expected_block).is_none() {
+                report.mismatches.push(mismatch(MismatchKind::BlockOrder {
+                    offset: block.start_offset,
+                }));
+            }
+        }
+
+        // Branches targets
+        for (block, expected_block) in blocks.iter().zip(&expected) {
+            report.mismatches.extend(
+                check_branch(blocks, &expected, block, expected_block, parsed)
+                    .into_iter()
+                    .map(&mismatch),
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+/// Returns the statements of a basic block as they are printed in the verbose output
+/// The unconditional jumps are not printed, they are a part of the blocks structure
+fn expected_statements(decompiler: &Decompiler, block: &BasicBlock) -> Vec<ParsedStatement> {
+    block
+        .statements
+        .iter()
+        .filter_map(|statement| {
+            match statement.as_conditional_branch(&decompiler.declared_libfuncs_names) {
+                Some(branch) if branch.edge_2_offset.is_some() => Some(ParsedStatement::Branch(
+                    replace_types_id(&decompiler.declared_types_names, &branch.function),
+                )),
+                Some(_) => None,
+                None => statement
+                    .formatted_statement(
+                        true,
+                        &decompiler.declared_libfuncs_names,
+                        &decompiler.declared_types_names,
+                    )
+                    .and_then(|line| parse_line(&line)),
+            }
+        })
+        .collect()
+}
+
+/// Checks that the `if` & `else` blocks of the conditional branch of a basic block
+/// start with the statements of its targets
+fn check_branch(
+    blocks: &[BasicBlock],
+    expected: &[Vec<ParsedStatement>],
+    block: &BasicBlock,
+    expected_block: &[ParsedStatement],
+    parsed: &[ParsedStatement],
+) -> Vec<MismatchKind> {
+    let mut mismatches = Vec::new();
+    let Some(ParsedStatement::Branch(libfunc)) = expected_block.last() else {
+        return mismatches;
+    };
+    let Some(branch_statement) = block.statements.last() else {
+        return mismatches;
+    };
+
+    // Only 2 targets of the multi-way branches (e.g. enum_match) are decompiled
+    if let GenStatement::Invocation(invocation) = &branch_statement.statement {
+        if invocation.branches.len() > 2 {
+            mismatches.push(MismatchKind::MissingBranchTargets {
+                offset: branch_statement.offset,
+                libfunc: libfunc.clone(),
+                targets: invocation.branches.len(),
+            });
+        }
+    }
+
+    // Position of the branch in the output
+    let Some(position) = find_sequence(&parsed.iter().collect::<Vec<_>>(), expected_block)
+        .map(|start| start + expected_block.len() - 1)
+    else {
+        return mismatches;
+    };
+
+    // First printed statement of a target block
+    let target_statement = |edge_type: EdgeType| {
+        let destination = block
+            .edges
+            .iter()
+            .find(|edge| edge.edge_type == edge_type)?
+            .destination;
+        let index = blocks
+            .iter()
+            .position(|block| block.start_offset == destination)?;
+        expected[index].first()
+    };
+
+    // The if block starts right after the branch
+    let if_statement = parsed.get(position + 1);
+    if let (Some(statement), Some(target)) =
+        (if_statement, target_statement(EdgeType::ConditionalTrue))
+    {
+        if statement.libfunc().is_some() && statement != target {
+            mismatches.push(MismatchKind::BranchTarget {
+                offset: branch_statement.offset,
+                libfunc: libfunc.clone(),
+            });
+        }
+    }
+
+    // The else block starts after the `} else {` of the same depth
+    let mut depth = 0;
+    for (index, statement) in parsed.iter().enumerate().skip(position + 1) {
+        match statement {
+            ParsedStatement::Branch(_) => depth += 1,
+            ParsedStatement::End if depth == 0 => break,
+            ParsedStatement::End => depth -= 1,
+            ParsedStatement::Else if depth == 0 => {
+                if let (Some(statement), Some(target)) = (
+                    parsed.get(index + 1),
+                    target_statement(EdgeType::ConditionalFalse),
+                ) {
+                    if statement.libfunc().is_some() && statement != target {
+                        mismatches.push(MismatchKind::BranchTarget {
+                            offset: branch_statement.offset,
+                            libfunc: libfunc.clone(),
+                        });
+                    }
+                }
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    mismatches
+}
+
+/// Returns the libfuncs counts differences between the program & the output
+/// A positive count is a dropped statement, a negative count an added one
+fn count_differences<'s>(
+    expected: impl Iterator<Item = &'s ParsedStatement>,
+    parsed: &[ParsedStatement],
+) -> Vec<(String, i64)> {
+    let mut counts: HashMap<&str, i64> = HashMap::new();
+    for libfunc in expected.filter_map(ParsedStatement::libfunc) {
+        *counts.entry(libfunc).or_default() += 1;
+    }
+    for libfunc in parsed.iter().filter_map(ParsedStatement::libfunc) {
+        *counts.entry(libfunc).or_default() -= 1;
+    }
+
+    let mut differences: Vec<(String, i64)> = counts
+        .into_iter()
+        .filter(|(_, count)| *count != 0)
+        .map(|(libfunc, count)| (libfunc.to_string(), count))
+        .collect();
+    differences.sort();
+    differences
+}
+
+/// Returns the index of the first occurrence of a sequence of statements
+fn find_sequence(statements: &[&ParsedStatement], sequence: &[ParsedStatement]) -> Option<usize> {
+    if sequence.is_empty() || statements.len() < sequence.len() {
+        return None;
+    }
+
+    statements.windows(sequence.len()).position(|window| {
+        window
+            .iter()
+            .zip(sequence)
+            .all(|(statement, expected)| *statement == expected)
+    })
+}
+
+/// Parses the functions of the verbose & colorless decompiler output
+/// Returns the statements of each function using its name
+fn parse_output(output: &str) -> HashMap<String, Vec<ParsedStatement>> {
+    let mut functions: HashMap<String, Vec<ParsedStatement>> = HashMap::new();
+    let mut current_function: Option<String> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        // Function prototype
+        if let Some(prototype) = line.strip_prefix("func ") {
+            let name = prototype.split(" (").next().unwrap_or_default().to_string();
+            functions.insert(name.clone(), Vec::new());
+            current_function = Some(name);
+            continue;
+        }
+
+        let Some(statements) = current_function
+            .as_ref()
+            .and_then(|name| functions.get_mut(name))
+        else {
+            continue;
+        };
+
+        match line {
+            "" => {}
+            "}" => statements.push(ParsedStatement::End),
+            "} else {" => statements.push(ParsedStatement::Else),
+            _ if line.starts_with("// Function") => {}
+            _ => statements.extend(parse_line(line)),
+        }
+    }
+
+    // The last closing brace of each function ends its body
+    for statements in functions.values_mut() {
+        if statements.last() == Some(&ParsedStatement::End) {
+            statements.pop();
+        }
+    }
+
+    functions
+}
+
+/// Parses a statement of the verbose output
+/// `if (<libfunc>(<args>) == 0) {`, `return (<vars>)` or `[<vars> = ]<libfunc>(<args>)`
+fn parse_line(line: &str) -> Option<ParsedStatement> {
+    let line = line.trim();
+
+    if let Some(condition) = line.strip_prefix("if (") {
+        return libfunc_name(condition).map(ParsedStatement::Branch);
+    }
+
+    if line.starts_with("return (") {
+        return Some(ParsedStatement::Return);
+    }
+
+    libfunc_name(line).map(ParsedStatement::Invocation)
+}
+
+/// Returns the libfunc name of an invocation, the assigned variables are ignored
+/// The parentheses of the generic arguments (e.g. `PanicResult::<(felt252)>`) are a part of the name
+fn libfunc_name(invocation: &str) -> Option<String> {
+    let mut depth = 0;
+    let arguments_start = invocation.char_indices().find_map(|(index, c)| {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            '(' if depth == 0 => return Some(index),
+            _ => {}
+        }
+        None
+    })?;
+
+    let callee = &invocation[..arguments_start];
+    let name = callee.rsplit_once(" = ").map_or(callee, |(_, name)| name);
+
+    Some(name.trim().to_string())
+}
@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_sierra::program::GenStatement;
+use num_bigint::BigUint;
+
+use crate::analysis::access_control::{
+    builtin_variables, propagate_tainted_variables, AccessControlMatrix,
+};
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::invariants::{called_function, storage_base_addresses, transitive_callers};
+use crate::analysis::ssa::SsaVariable;
+use crate::decompiler::function::{Function, FunctionType};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::{
+    ADDRESS_TO_FELT252_REGEX, COMPARISON_FUNCTION_CALL_REGEX, COPY_REGEXES, FELT252_OPERATION_REGEX,
+};
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+#[derive(Debug)]
+pub struct ConstructorDetector;
+
+impl ConstructorDetector {
+    /// Creates a new `ConstructorDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for ConstructorDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "constructor"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Constructor sanity"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the uninitialized owners & the unvalidated constructor parameters written to the storage."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::SECURITY
+    }
+
+    /// Returns the constructors issues
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        constructor_issues(context)
+            .into_iter()
+            .map(|(function_name, offset, message)| match offset {
+                Some(offset) => format!("{}: {} (statement {})\n", function_name, message, offset),
+                None => format!("{}: {}\n", function_name, message),
+            })
+            .collect()
+    }
+
+    /// Returns the constructors issues, located at the storage writes when known
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        constructor_issues(context)
+            .into_iter()
+            .map(|(function, offset, message)| Finding {
+                detector_type,
+                function,
+                offset,
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the issues of the constructors
+/// (function name, statement offset, message)
+fn constructor_issues(context: &AnalysisContext) -> Vec<(String, Option<u32>, String)> {
+    let mut issues = Vec::new();
+
+    let functions = context.functions();
+    let callgraph = CallGraph::new(functions);
+    let storage_writers = transitive_callers(&callgraph, functions, LibfuncKind::StorageWrite);
+    let matrix = AccessControlMatrix::new(functions);
+
+    // The constructors wrappers & the constructors they call
+    let constructor_wrappers: Vec<String> = functions
+        .iter()
+        .filter(|function| matches!(function.function_type, Some(FunctionType::Wrapper)))
+        .map(|function| parse_element_name!(function.function.id))
+        .filter(|wrapper| {
+            wrapper.contains("::__constructor::")
+                || wrapper_entry_point_name(wrapper).as_deref() == Some("constructor")
+        })
+        .collect();
+    let constructors: Vec<&Function> = functions
+        .iter()
+        .filter(|function| {
+            matches!(function.function_type, Some(FunctionType::Constructor))
+                || constructor_wrappers.contains(&parse_element_name!(function.function.id))
+        })
+        .collect();
+    let constructors_names: HashSet<String> = constructors
+        .iter()
+        .map(|function| parse_element_name!(function.function.id))
+        .collect();
+
+    // The storage variables compared with the caller address must be set by the constructor
+    let mut owner_functions: Vec<&String> = matrix
+        .entries
+        .iter()
+        .flat_map(|entry| &entry.owner_checks)
+        .collect();
+    owner_functions.sort();
+    owner_functions.dedup();
+    let owner_slots: HashSet<BigUint> = functions
+        .iter()
+        .filter(|function| owner_functions.contains(&&parse_element_name!(function.function.id)))
+        .flat_map(storage_base_addresses)
+        .collect();
+
+    if let Some(owner_function) = owner_functions.first() {
+        if constructor_wrappers.is_empty() {
+            issues.push((
+                owner_function.to_string(),
+                None,
+                "the owner compared with the caller address is never initialized, the contract has no constructor".to_string(),
+            ));
+        }
+
+        for wrapper in &constructor_wrappers {
+            let reachable = callgraph.reachable_from(&[wrapper.clone()]);
+            let initializes_owner = functions
+                .iter()
+                .filter(|function| reachable.contains(&parse_element_name!(function.function.id)))
+                .flat_map(storage_base_addresses)
+                .any(|slot| owner_slots.contains(&slot));
+
+            if !owner_slots.is_empty() && !initializes_owner {
+                issues.push((
+                    wrapper.clone(),
+                    None,
+                    format!(
+                        "the constructor doesn't initialize the owner compared with the caller address by {}",
+                        owner_function
+                    ),
+                ));
+            }
+        }
+    }
+
+    // The constructors parameters written to the storage without any check
+    for constructor in &constructors {
+        let function_name = parse_element_name!(constructor.function.id);
+        for offset in unvalidated_storage_writes(constructor, &storage_writers, &constructors_names)
+        {
+            issues.push((
+                function_name.clone(),
+                Some(offset),
+                "constructor parameter written to the storage without validation".to_string(),
+            ));
+        }
+    }
+
+    // The whole program is analyzed, only the issues of the functions in scope are reported
+    issues.retain(|(function_name, _, _)| {
+        context
+            .session()
+            .function(function_name)
+            .is_some_and(|function| context.is_in_scope(function))
+    });
+
+    issues
+}
+
+/// Returns the offsets of the storage writes (syscalls or calls to functions writing the storage)
+/// of values derived from the constructor parameters, without any comparison of these values
+/// The calls to the other constructors are skipped, their writes are reported in the callee
+fn unvalidated_storage_writes(
+    function: &Function,
+    storage_writers: &HashSet<String>,
+    constructors_names: &HashSet<String>,
+) -> Vec<u32> {
+    let mut writes = Vec::new();
+
+    let Ok(ssa) = function.ssa() else {
+        return writes;
+    };
+    let builtins = builtin_variables(function, &ssa);
+
+    // The parameters are tainted, except the builtins & the contract (or component) state
+    let mut tainted: HashSet<SsaVariable> = ssa
+        .parameters()
+        .iter()
+        .zip(&function.function.params)
+        .filter(|(parameter, param)| {
+            let is_state = param.ty.debug_name.as_ref().is_some_and(|type_name| {
+                type_name.contains("ContractState") || type_name.contains("ComponentState")
+            });
+            !builtins.contains(*parameter) && !is_state
+        })
+        .map(|(parameter, _)| *parameter)
+        .collect();
+
+    // Source of the copies & operands of the felt252 substractions (compiled equalities)
+    let mut copies: HashMap<SsaVariable, SsaVariable> = HashMap::new();
+    let mut substractions: HashMap<SsaVariable, Vec<SsaVariable>> = HashMap::new();
+    let mut compared: Vec<SsaVariable> = Vec::new();
+
+    for statement in function.statements() {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            continue;
+        };
+        let Some(ssa_statement) = ssa.statement(statement.offset) else {
+            continue;
+        };
+        let libfunc_name = parse_element_name!(invocation.libfunc_id);
+
+        if COPY_REGEXES
+            .iter()
+            .any(|regex| regex.is_match(&libfunc_name))
+            || ADDRESS_TO_FELT252_REGEX.is_match(&libfunc_name)
+        {
+            if let Some(source) = ssa_statement.uses.first() {
+                for result in ssa_statement.definitions.iter().flatten() {
+                    copies.insert(*result, *source);
+                }
+            }
+        } else if FELT252_OPERATION_REGEX
+            .captures(&libfunc_name)
+            .is_some_and(|captures| &captures["operator"] == "sub")
+        {
+            for result in ssa_statement.definitions.iter().flatten() {
+                substractions.insert(*result, ssa_statement.uses.clone());
+            }
+        } else if matches!(
+            statement.libfunc_kind,
+            LibfuncKind::IsZero
+                | LibfuncKind::Equal
+                | LibfuncKind::LessThan
+                | LibfuncKind::LessThanOrEqual
+        ) || COMPARISON_FUNCTION_CALL_REGEX.is_match(&libfunc_name)
+        {
+            compared.extend(
+                ssa_statement
+                    .uses
+                    .iter()
+                    .filter(|variable| !builtins.contains(*variable)),
+            );
+        }
+    }
+
+    // The values derived from the parameters, until the taints of the loops are stable
+    propagate_tainted_variables(function, &ssa, &builtins, &mut tainted, |_| false);
+
+    let origin = |mut variable: SsaVariable| -> SsaVariable {
+        while let Some(source) = copies.get(&variable) {
+            variable = *source;
+        }
+        variable
+    };
+
+    // The origins of the compared values, the operands of the compared substractions included
+    let mut validated: HashSet<SsaVariable> = HashSet::new();
+    for variable in compared {
+        let variable = origin(variable);
+        validated.insert(variable);
+        if let Some(operands) = substractions.get(&variable) {
+            validated.extend(operands.iter().map(|operand| origin(*operand)));
+        }
+    }
+
+    let is_unvalidated = |variable: &SsaVariable| {
+        tainted.contains(variable) && !validated.contains(&origin(*variable))
+    };
+
+    for statement in function.statements() {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            continue;
+        };
+        let Some(ssa_statement) = ssa.statement(statement.offset) else {
+            continue;
+        };
+        let libfunc_name = parse_element_name!(invocation.libfunc_id);
+
+        // storage_write_syscall(gas, system, address_domain, address, value)
+        let unvalidated = if statement.libfunc_kind == LibfuncKind::StorageWrite {
+            ssa_statement.uses.get(4).is_some_and(is_unvalidated)
+        } else if let Some(callee) = called_function(&libfunc_name) {
+            storage_writers.contains(&callee)
+                && !constructors_names.contains(&callee)
+                && ssa_statement
+                    .uses
+                    .iter()
+                    .filter(|variable| !builtins.contains(*variable))
+                    .any(is_unvalidated)
+        } else {
+            false
+        };
+
+        if unvalidated {
+            writes.push(statement.offset);
+        }
+    }
+
+    writes
+}
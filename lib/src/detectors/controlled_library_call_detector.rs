@@ -4,8 +4,8 @@ use cairo_lang_sierra::extensions::starknet::StarkNetConcreteLibfunc;
 use cairo_lang_sierra::ids::VarId;
 use cairo_lang_sierra::program::GenStatement;
 
-use crate::decompiler::decompiler::Decompiler;
-use crate::detectors::detector::{Detector, DetectorType};
+use crate::analysis::context::AnalysisContext;
+use crate::detectors::detector::{Detector, DetectorType, Finding};
 use crate::parse_element_name;
 
 #[derive(Debug)]
@@ -81,63 +81,106 @@ impl Detector for ControlledLibraryCallDetector {
         DetectorType::SECURITY
     }
 
+    /// The External, View & L1Handler functions are only known using the contract ABI
+    #[inline]
+    fn requires_abi(&self) -> bool {
+        true
+    }
+
+    /// The libfuncs signatures are given by the program registry
+    #[inline]
+    fn requires_registry(&self) -> bool {
+        true
+    }
+
     /// Detect library calls with a user controlled class hash
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String {
-        let mut result = String::new();
-
-        for function in decompiler.user_defined_functions() {
-            for statement in function.library_functions_calls.clone() {
-                if let GenStatement::Invocation(statement) = statement.statement {
-                    let libfunc = decompiler
-                        .registry()
-                        .get_libfunc(&statement.libfunc_id)
-                        .expect("Library function not found in the registry");
-
-                    if let CoreConcreteLibfunc::FunctionCall(abi_function) = libfunc {
-                        if check_user_controlled(
-                            &abi_function.signature.param_signatures,
-                            statement.args.clone(),
-                            parse_element_name!(function.function.id.clone()).as_str(),
-                        ) {
-                            result += &format!(
-                                "{} in {}",
-                                statement,
-                                parse_element_name!(function.function.id.clone()).as_str()
-                            )
-                            .to_string();
-                        };
-                    }
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        controlled_library_calls(context)
+            .into_iter()
+            .map(|(function_name, _, statement)| format!("{} in {}", statement, function_name))
+            .collect()
+    }
+
+    /// Returns the library calls with a user controlled class hash located at their call
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        controlled_library_calls(context)
+            .into_iter()
+            .map(|(function_name, offset, statement)| Finding {
+                detector_type,
+                function: function_name,
+                offset: Some(offset),
+                message: format!(
+                    "library call with a user controlled class hash: {}",
+                    statement
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Returns the library calls with a user controlled class hash
+/// (function name, statement offset, statement)
+fn controlled_library_calls(context: &AnalysisContext) -> Vec<(String, u32, String)> {
+    let mut calls = Vec::new();
+
+    let Some(registry) = context.registry() else {
+        return calls;
+    };
+
+    for function in context.user_defined_functions() {
+        if !context.is_in_scope(function) {
+            continue;
+        }
+        let function_name = parse_element_name!(function.function.id.clone());
+
+        for statement in function.library_functions_calls.clone() {
+            if let GenStatement::Invocation(invocation) = &statement.statement {
+                let libfunc = registry
+                    .get_libfunc(&invocation.libfunc_id)
+                    .expect("Library function not found in the registry");
+
+                if let CoreConcreteLibfunc::FunctionCall(abi_function) = libfunc {
+                    if check_user_controlled(
+                        &abi_function.signature.param_signatures,
+                        invocation.args.clone(),
+                        function_name.as_str(),
+                    ) {
+                        calls.push((
+                            function_name.clone(),
+                            statement.offset,
+                            invocation.to_string(),
+                        ));
+                    };
                 }
             }
+        }
 
-            for statement in function.statements.clone() {
-                if let GenStatement::Invocation(statement) = statement.statement {
-                    let libfunc = decompiler
-                        .registry()
-                        .get_libfunc(&statement.libfunc_id)
-                        .expect("Library function not found in the registry");
-
-                    // We care only about a library call
-                    if let CoreConcreteLibfunc::StarkNet(StarkNetConcreteLibfunc::LibraryCall(l)) =
-                        libfunc
-                    {
-                        if check_user_controlled(
-                            &l.signature.param_signatures,
-                            statement.args.clone(),
-                            parse_element_name!(function.function.id.clone()).as_str(),
-                        ) {
-                            result += &format!(
-                                "{} in {}",
-                                statement,
-                                parse_element_name!(function.function.id.clone()).as_str()
-                            )
-                            .to_string();
-                        };
-                    }
+        for statement in function.statements() {
+            if let GenStatement::Invocation(invocation) = &statement.statement {
+                let libfunc = registry
+                    .get_libfunc(&invocation.libfunc_id)
+                    .expect("Library function not found in the registry");
+
+                // We care only about a library call
+                if let CoreConcreteLibfunc::StarkNet(StarkNetConcreteLibfunc::LibraryCall(l)) =
+                    libfunc
+                {
+                    if check_user_controlled(
+                        &l.signature.param_signatures,
+                        invocation.args.clone(),
+                        function_name.as_str(),
+                    ) {
+                        calls.push((
+                            function_name.clone(),
+                            statement.offset,
+                            invocation.to_string(),
+                        ));
+                    };
                 }
             }
         }
-
-        result
     }
+
+    calls
 }
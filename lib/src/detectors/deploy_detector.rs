@@ -0,0 +1,186 @@
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::origins::{FunctionOrigins, ProgramOrigins};
+use crate::analysis::ssa::SsaVariable;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::BOOL_INIT_REGEX;
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::graph::callgraph::CallGraph;
+use crate::parse_element_name;
+
+#[derive(Debug)]
+pub struct DeployDetector;
+
+impl DeployDetector {
+    /// Creates a new `DeployDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for DeployDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "deploy"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Contract deployment"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the contracts deployments with a user-controlled class hash or constructor calldata & the deployments from zero."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::SECURITY
+    }
+
+    /// Returns the deployments & the origins of their arguments
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        deployments(context)
+            .into_iter()
+            .map(|(function_name, offset, message)| {
+                format!("{}: {} (statement {})\n", function_name, message, offset)
+            })
+            .collect()
+    }
+
+    /// Returns the deployments located at their syscall
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        deployments(context)
+            .into_iter()
+            .map(|(function, offset, message)| Finding {
+                detector_type,
+                function,
+                offset: Some(offset),
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the `deploy_syscall` invocations of the functions in scope
+/// (function name, statement offset, message)
+fn deployments(context: &AnalysisContext) -> Vec<(String, u32, String)> {
+    let functions = context.functions();
+    let has_deploy = functions.iter().any(|function| {
+        function
+            .statements()
+            .iter()
+            .any(|statement| statement.libfunc_kind == LibfuncKind::Deploy)
+    });
+    if !has_deploy {
+        return Vec::new();
+    }
+
+    // The arguments origins are propagated through all the functions, including the ones out of scope
+    let callgraph = CallGraph::new(functions);
+    let origins = ProgramOrigins::new(functions, &callgraph);
+
+    let mut deployments = Vec::new();
+    for analysis in &origins.functions {
+        if !context.is_in_scope(analysis.function) {
+            continue;
+        }
+        let arrays = analysis.arrays_fields();
+
+        for statement in analysis.function.statements() {
+            if statement.libfunc_kind != LibfuncKind::Deploy {
+                continue;
+            }
+
+            // deploy_syscall(gas, system, class_hash, salt, calldata, deploy_from_zero)
+            let arguments = analysis.arguments(statement.offset);
+            let origin = |index: usize| {
+                arguments
+                    .get(index)
+                    .map(|variable| analysis.origin(variable))
+                    .unwrap_or_default()
+            };
+
+            let class_hash = origin(0);
+            let calldata = arguments
+                .get(2)
+                .and_then(|calldata| arrays.get(calldata))
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .fold(origin(2), |calldata, field| calldata.union(*field))
+                })
+                .unwrap_or_else(|| origin(2));
+            let deploy_from_zero = match arguments
+                .get(3)
+                .and_then(|variable| bool_constant(analysis, variable))
+            {
+                Some(true) => "true",
+                Some(false) => "false",
+                None => "unknown",
+            };
+
+            let mut risks = Vec::new();
+            if class_hash.is_user_controlled() {
+                risks.push("user-controlled class hash");
+            }
+            if calldata.is_user_controlled() {
+                risks.push("user-controlled constructor calldata");
+            }
+            if deploy_from_zero == "true" {
+                risks.push("deployed from zero");
+            }
+            let summary = if risks.is_empty() {
+                "contract deployment".to_string()
+            } else {
+                format!("contract deployment with {}", risks.join(", "))
+            };
+
+            deployments.push((
+                analysis.name.clone(),
+                statement.offset,
+                format!(
+                    "{} (class hash: {}, calldata: {}, deploy_from_zero: {})",
+                    summary,
+                    class_hash.as_str(),
+                    calldata.as_str(),
+                    deploy_from_zero
+                ),
+            ));
+        }
+    }
+
+    deployments
+}
+
+/// Returns the value of a boolean built by `enum_init<core::bool, _>`, following its copies
+/// None if the boolean isn't a constant of the function
+fn bool_constant(analysis: &FunctionOrigins, variable: &SsaVariable) -> Option<bool> {
+    let mut variable = *variable;
+    loop {
+        let statement = analysis.definition(&variable)?;
+        match statement.libfunc_kind {
+            LibfuncKind::StoreTemp
+            | LibfuncKind::Dup
+            | LibfuncKind::SnapshotTake
+            | LibfuncKind::Assignment => {
+                variable = *analysis.arguments(statement.offset).first()?;
+            }
+            _ => {
+                let GenStatement::Invocation(invocation) = &statement.statement else {
+                    return None;
+                };
+                let libfunc_name = parse_element_name!(invocation.libfunc_id);
+                let captures = BOOL_INIT_REGEX.captures(&libfunc_name)?;
+                return Some(&captures["variant"] == "1");
+            }
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
-use crate::decompiler::decompiler::Decompiler;
+use crate::analysis::context::AnalysisContext;
+use crate::config::GraphConfig;
 
 /// Possible types of a detector
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DetectorType {
     INFORMATIONAL,
     SECURITY,
+    OPTIMIZATION,
+    CORRECTNESS,
     TESTING,
 }
 
@@ -22,14 +26,106 @@ impl DetectorType {
             // Security detectors types are blue
             DetectorType::SECURITY => "Security".normal().blue(),
 
+            // Optimization detectors types are cyan
+            DetectorType::OPTIMIZATION => "Optimization".normal().cyan(),
+
+            // Correctness detectors types are magenta
+            DetectorType::CORRECTNESS => "Correctness".normal().magenta(),
+
             // Testing detectors types are yellow
             DetectorType::TESTING => "Testing".normal().yellow(),
         }
     }
+
+    /// Returns the tag of the detector type, used to select the detectors from the command-line
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DetectorType::INFORMATIONAL => "informational",
+            DetectorType::SECURITY => "security",
+            DetectorType::OPTIMIZATION => "optimization",
+            DetectorType::CORRECTNESS => "correctness",
+            DetectorType::TESTING => "testing",
+        }
+    }
+
+    /// Returns the color of the graphs nodes containing a finding of this type
+    pub fn graph_color(&self) -> &'static str {
+        match self {
+            DetectorType::INFORMATIONAL => GraphConfig::FINDING_INFORMATIONAL_COLOR,
+            DetectorType::SECURITY => GraphConfig::FINDING_SECURITY_COLOR,
+            DetectorType::OPTIMIZATION => GraphConfig::FINDING_OPTIMIZATION_COLOR,
+            DetectorType::CORRECTNESS => GraphConfig::FINDING_CORRECTNESS_COLOR,
+            DetectorType::TESTING => GraphConfig::FINDING_TESTING_COLOR,
+        }
+    }
+}
+
+/// A detector result located in a function
+/// The findings are highlighted in the CFG & callgraph outputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Type of the detector that reported the finding
+    pub detector_type: DetectorType,
+    /// Name of the function containing the finding
+    pub function: String,
+    /// Offset of the statement, None if the finding concerns the whole function
+    pub offset: Option<u32>,
+    /// Text of the finding
+    pub message: String,
+}
+
+/// Machine-readable finding of the structured detectors output
+/// The offsets locate the finding in the decompiled output, e.g. for an IDE extension
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FindingReport {
+    /// Id of the detector that reported the finding
+    pub detector: String,
+    /// Tag of the detector type
+    pub detector_type: String,
+    /// Name of the function containing the finding
+    pub function: String,
+    /// Offset of the first statement of the function
+    pub function_offset: Option<u32>,
+    /// Offsets of the statements, empty if the finding concerns the whole function
+    pub statements: Vec<u32>,
+    /// Text of the finding
+    pub message: String,
+}
+
+impl FindingReport {
+    /// Creates the report of a finding, the function offset is read from the analysis context
+    pub fn new(detector: &str, finding: Finding, context: &AnalysisContext) -> Self {
+        let function_offset = context
+            .session()
+            .function(&finding.function)
+            .and_then(|function| function.start_offset);
+
+        Self {
+            detector: detector.to_string(),
+            detector_type: finding.detector_type.tag().to_string(),
+            function: finding.function,
+            function_offset,
+            statements: finding.offset.into_iter().collect(),
+            message: finding.message,
+        }
+    }
+
+    /// Key of the reports order: by function entry offset & name, then by statements offsets
+    /// The reports of the same location keep the detectors order
+    fn order_key(&self) -> (Option<u32>, &str, &[u32]) {
+        (self.function_offset, &self.function, &self.statements)
+    }
+}
+
+/// Returns the findings reports in JSON, see `FindingReport::order_key` for their order
+pub fn findings_to_json(reports: &[FindingReport]) -> String {
+    let mut reports: Vec<&FindingReport> = reports.iter().collect();
+    reports.sort_by(|a, b| a.order_key().cmp(&b.order_key()));
+    serde_json::to_string_pretty(&reports).unwrap_or_else(|_| "[]".to_string())
 }
 
 /// Detector marker trait
-pub trait Detector: Debug {
+pub trait Detector: Debug + Send {
     // The id of a detector is used to select it using a command-line argument
     // e.g. the id of the detector with the name "Protoypes detector" is "prototypes"
     fn id(&self) -> &'static str;
@@ -37,8 +133,33 @@ pub trait Detector: Debug {
     fn name(&self) -> &'static str;
     // Description of the detector
     fn description(&self) -> &'static str;
-    // A detector can be either a security detector or an informational detector
+    // A detector can be a security, correctness, optimization, informational or testing detector
     fn detector_type(&self) -> DetectorType;
-    // Run the detector on the decompiler instance
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String;
+    // A detector requiring the contract ABI is skipped when analyzing a raw Sierra program
+    fn requires_abi(&self) -> bool {
+        false
+    }
+    // A detector requiring the program registry is skipped when analyzing a legacy Sierra program
+    fn requires_registry(&self) -> bool {
+        false
+    }
+    // A detector using the symbolic execution is slow, it's tagged "slow" instead of "fast"
+    fn uses_symbolic_execution(&self) -> bool {
+        false
+    }
+    // Tags used to select the detectors: the detector type tag & its speed
+    fn tags(&self) -> Vec<&'static str> {
+        let speed = if self.uses_symbolic_execution() {
+            "slow"
+        } else {
+            "fast"
+        };
+        vec![self.detector_type().tag(), speed]
+    }
+    // Run the detector on the read-only analysis context
+    fn detect(&mut self, context: &AnalysisContext) -> String;
+    // Findings located in the program functions, the detectors only printing a report have none
+    fn findings(&mut self, _context: &AnalysisContext) -> Vec<Finding> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::analysis::context::AnalysisContext;
+use crate::decompiler::function::{Function, FunctionType};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::USER_DEFINED_FUNCTION_REGEX;
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+use crate::parse_element_name_with_fallback;
+
+#[derive(Debug)]
+pub struct EffectsTimelineDetector;
+
+impl EffectsTimelineDetector {
+    /// Creates a new `EffectsTimelineDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Kind of a side effect of an entry point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EffectKind {
+    ExternalCall,
+    StorageRead,
+    StorageWrite,
+    Event,
+}
+
+impl EffectKind {
+    /// Returns the kind of the effect performed by a libfunc, if any
+    fn from_libfunc_kind(libfunc_kind: LibfuncKind) -> Option<Self> {
+        match libfunc_kind {
+            LibfuncKind::ExternalCall => Some(EffectKind::ExternalCall),
+            LibfuncKind::StorageRead => Some(EffectKind::StorageRead),
+            LibfuncKind::StorageWrite => Some(EffectKind::StorageWrite),
+            LibfuncKind::EmitEvent => Some(EffectKind::Event),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the effect kind, as in the timeline
+    fn as_str(&self) -> &'static str {
+        match self {
+            EffectKind::ExternalCall => "external call",
+            EffectKind::StorageRead => "storage read",
+            EffectKind::StorageWrite => "storage write",
+            EffectKind::Event => "event",
+        }
+    }
+}
+
+/// A side effect located in one of the functions called by an entry point
+#[derive(Debug, Clone)]
+struct Effect {
+    kind: EffectKind,
+    /// Name of the function performing the syscall
+    function: String,
+    /// Offset of the syscall statement
+    offset: u32,
+    /// Offset of the first external call preceding a storage write
+    after_external_call: Option<u32>,
+}
+
+/// Effects timeline of an entry point
+#[derive(Debug, Clone)]
+struct Timeline {
+    /// ABI name of the entry point
+    entry_point: String,
+    /// Name of the compiler wrapper of the entry point
+    wrapper: String,
+    effects: Vec<Effect>,
+}
+
+impl Detector for EffectsTimelineDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "effects_timeline"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Effects timeline"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Prints the ordered external calls, storage accesses & events of each entry point."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::INFORMATIONAL
+    }
+
+    /// Returns the effects timeline of each entry point
+    /// The storage writes following an external call (checks-effects-interactions violations) are marked
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        let mut result = String::new();
+
+        for timeline in effects_timelines(context) {
+            result += &format!("{} ({}):\n", timeline.entry_point, timeline.wrapper);
+            for effect in &timeline.effects {
+                result += &format!(
+                    "    {} in {} (statement {})",
+                    effect.kind.as_str(),
+                    effect.function,
+                    effect.offset
+                );
+                if let Some(call_offset) = effect.after_external_call {
+                    result += &format!(" after the external call of statement {}", call_offset);
+                }
+                result += "\n";
+            }
+        }
+
+        result
+    }
+
+    /// Returns the storage writes following an external call, located at the write
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        let mut findings = Vec::new();
+        let mut reported = HashSet::new();
+
+        for timeline in effects_timelines(context) {
+            for effect in timeline.effects {
+                let Some(call_offset) = effect.after_external_call else {
+                    continue;
+                };
+
+                // The functions shared by several entry points are reported once
+                if !reported.insert((effect.function.clone(), effect.offset)) {
+                    continue;
+                }
+
+                findings.push(Finding {
+                    detector_type,
+                    function: effect.function,
+                    offset: Some(effect.offset),
+                    message: format!(
+                        "storage write after the external call of statement {} (entry point {})",
+                        call_offset, timeline.entry_point
+                    ),
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+/// Returns the effects timeline of each entry point, in the program order
+/// The entry points are the compiler wrappers, the called functions are expanded in the order
+/// of their call statements
+fn effects_timelines(context: &AnalysisContext) -> Vec<Timeline> {
+    let functions: HashMap<String, &Function> = context
+        .functions()
+        .iter()
+        .map(|function| (parse_element_name!(function.function.id), function))
+        .collect();
+
+    let mut timelines = Vec::new();
+
+    for function in context.scoped_functions() {
+        if !matches!(function.function_type, Some(FunctionType::Wrapper)) {
+            continue;
+        }
+
+        let wrapper = parse_element_name!(function.function.id);
+        let Some(entry_point) = wrapper_entry_point_name(&wrapper) else {
+            continue;
+        };
+
+        let mut effects = Vec::new();
+        collect_effects(
+            function,
+            &functions,
+            context.declared_libfuncs_names(),
+            &mut vec![wrapper.clone()],
+            &mut effects,
+        );
+        if effects.is_empty() {
+            continue;
+        }
+
+        // Mark the storage writes following an external call
+        let mut first_external_call = None;
+        for effect in effects.iter_mut() {
+            match effect.kind {
+                EffectKind::ExternalCall => {
+                    first_external_call = first_external_call.or(Some(effect.offset))
+                }
+                EffectKind::StorageWrite => effect.after_external_call = first_external_call,
+                _ => {}
+            }
+        }
+
+        timelines.push(Timeline {
+            entry_point,
+            wrapper,
+            effects,
+        });
+    }
+
+    timelines
+}
+
+/// Appends the effects of a function & of the functions it calls, in the statements order
+/// The recursive calls are not expanded again, `call_stack` holds the functions being expanded
+fn collect_effects(
+    function: &Function,
+    functions: &HashMap<String, &Function>,
+    declared_libfuncs_names: &[Arc<str>],
+    call_stack: &mut Vec<String>,
+    effects: &mut Vec<Effect>,
+) {
+    let function_name = parse_element_name!(function.function.id);
+
+    for statement in function.statements() {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            continue;
+        };
+
+        if let Some(kind) = EffectKind::from_libfunc_kind(statement.libfunc_kind) {
+            effects.push(Effect {
+                kind,
+                function: function_name.clone(),
+                offset: statement.offset,
+                after_external_call: None,
+            });
+            continue;
+        }
+
+        // Expand the user-defined function calls
+        let libfunc_name =
+            parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+        let Some(captures) = USER_DEFINED_FUNCTION_REGEX.captures(&libfunc_name) else {
+            continue;
+        };
+        let callee_name = &captures["function_id"];
+        if call_stack.iter().any(|name| name == callee_name) {
+            continue;
+        }
+        if let Some(callee) = functions.get(callee_name) {
+            call_stack.push(callee_name.to_string());
+            collect_effects(
+                callee,
+                functions,
+                declared_libfuncs_names,
+                call_stack,
+                effects,
+            );
+            call_stack.pop();
+        }
+    }
+}
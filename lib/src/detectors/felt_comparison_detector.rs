@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::intervals::IntervalAnalysis;
+use crate::analysis::ssa::SsaVariable;
+use crate::decompiler::function::FunctionType;
+use crate::decompiler::libfuncs_patterns::{
+    COPY_REGEXES, FELT252_COMPARISON_REGEX, FELT252_OPERATION_REGEX,
+};
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::parse_element_name;
+use crate::parse_element_name_with_fallback;
+
+#[derive(Debug)]
+pub struct FeltComparisonDetector;
+
+impl FeltComparisonDetector {
+    /// Creates a new `FeltComparisonDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for FeltComparisonDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "felt_comparison"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Felt Comparison"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the comparisons of felt252 arithmetic results, which wrap around instead of failing."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::CORRECTNESS
+    }
+
+    /// Returns the comparisons of felt252 arithmetic results
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        felt_comparisons(context)
+            .into_iter()
+            .map(|(function_name, _, message)| format!("{}: {}\n", function_name, message))
+            .collect()
+    }
+
+    /// Returns the comparisons of felt252 arithmetic results located at the comparison
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        felt_comparisons(context)
+            .into_iter()
+            .map(|(function, offset, message)| Finding {
+                detector_type,
+                function,
+                offset: Some(offset),
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the comparisons of felt252 arithmetic results, e.g. `balance - amount` compared
+/// to 0 or converted to a u256 to be ordered, the operations proven in range are ignored
+/// The zero check of a substraction is how the felt252 equality is compiled, it's not reported
+/// (function name, comparison offset, message)
+fn felt_comparisons(context: &AnalysisContext) -> Vec<(String, u32, String)> {
+    let mut comparisons = Vec::new();
+
+    for function in context.scoped_functions() {
+        // Skip core functions
+        if matches!(function.function_type, Some(FunctionType::Core)) {
+            continue;
+        }
+
+        let Ok(intervals) = IntervalAnalysis::new(function, context.declared_libfuncs_names())
+        else {
+            continue;
+        };
+        let ssa = intervals.ssa();
+        let function_name = parse_element_name!(function.function.id);
+
+        // Source of the copies & felt252 operations that could wrap around
+        let mut copies: HashMap<SsaVariable, SsaVariable> = HashMap::new();
+        let mut operations: HashMap<SsaVariable, (u32, String, String)> = HashMap::new();
+        let mut compared_variables = Vec::new();
+
+        for statement in function.statements() {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+            let Some(ssa_statement) = ssa.statement(statement.offset) else {
+                continue;
+            };
+            let libfunc_name = parse_element_name_with_fallback!(
+                invocation.libfunc_id,
+                context.declared_libfuncs_names()
+            );
+
+            if COPY_REGEXES
+                .iter()
+                .any(|regex| regex.is_match(&libfunc_name))
+            {
+                if let Some(source) = ssa_statement.uses.first() {
+                    for result in ssa_statement.definitions.iter().flatten() {
+                        copies.insert(*result, *source);
+                    }
+                }
+            } else if let Some(captures) = FELT252_OPERATION_REGEX.captures(&libfunc_name) {
+                if !intervals.proves_no_overflow(statement.offset, &libfunc_name) {
+                    for result in ssa_statement.definitions.iter().flatten() {
+                        operations.insert(
+                            *result,
+                            (
+                                statement.offset,
+                                libfunc_name.clone(),
+                                captures["operator"].to_string(),
+                            ),
+                        );
+                    }
+                }
+            } else if FELT252_COMPARISON_REGEX.is_match(&libfunc_name) {
+                // The compared felt252 is the last argument, after the range check
+                if let Some(compared) = ssa_statement.uses.last() {
+                    compared_variables.push((statement.offset, libfunc_name, *compared));
+                }
+            }
+        }
+
+        for (offset, libfunc_name, mut compared) in compared_variables {
+            while let Some(source) = copies.get(&compared) {
+                compared = *source;
+            }
+            let Some((operation_offset, operation_name, operator)) = operations.get(&compared)
+            else {
+                continue;
+            };
+            if libfunc_name == "felt252_is_zero" && operator == "sub" {
+                continue;
+            }
+
+            comparisons.push((
+                function_name.clone(),
+                offset,
+                format!(
+                    "{} result (statement {}) is compared by {}, the felt252 arithmetic wraps around instead of failing",
+                    operation_name, operation_offset, libfunc_name
+                ),
+            ));
+        }
+    }
+
+    comparisons
+}
@@ -1,12 +1,20 @@
-use crate::decompiler::decompiler::Decompiler;
-use crate::decompiler::function::FunctionType;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::intervals::IntervalAnalysis;
+use crate::analysis::ssa::{SsaForm, SsaVariable};
+use crate::decompiler::function::{Function, FunctionType};
 use crate::decompiler::libfuncs_patterns::{
-    ADDITION_REGEX, MULTIPLICATION_REGEX, SUBSTRACTION_REGEX,
+    COPY_REGEXES, DROP_REGEX, EQUAL_REGEX, FELT252_OPERATION_REGEX, FELT252_RANGE_CHECK_REGEX,
+    HASH_REGEX, LESS_THAN_OR_EQUAL_REGEX, LESS_THAN_REGEX,
 };
-use crate::detectors::detector::{Detector, DetectorType};
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::parse_element_name;
 use crate::parse_element_name_with_fallback;
 use crate::var_id_to_name;
 
+use cairo_lang_sierra::ids::FunctionId;
 use cairo_lang_sierra::program::GenStatement;
 
 #[derive(Debug)]
@@ -44,85 +52,14 @@ impl Detector for FeltOverflowDetector {
         DetectorType::SECURITY
     }
 
-    /// Returns all the functions names
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String {
+    /// Returns the potential felt overflows
+    fn detect(&mut self, context: &AnalysisContext) -> String {
         let mut result = String::new();
-        let mut found_vulnerabilities = Vec::new();
-
-        // We extract the functions names from the prototypes
-        decompiler.decompile_functions_prototypes();
-
-        for function in decompiler.functions.clone() {
-            // Skip core functions
-            if let Some(function_type) = function.function_type {
-                if matches!(function_type, FunctionType::Core) {
-                    continue;
-                }
-            }
-
-            let function_name = function.function.id.clone();
-
-            let arguments = function.arguments.clone();
-
-            // Filter arguments felt arguments
-            let felt_arguments: Vec<_> = arguments
-                .iter()
-                .filter(|&&(_, ref arg_type)| arg_type == "felt252")
-                .collect();
-
-            for statement in function.statements {
-                if let GenStatement::Invocation(invocation) = &statement.statement {
-                    let arguments = invocation.args.clone();
-                    let mut local_found_felt_arguments = Vec::new();
-
-                    for argument in arguments {
-                        let element_name = var_id_to_name!(argument);
-
-                        // Check if the argument is in the felt_arguments
-                        if felt_arguments
-                            .iter()
-                            .any(|&(ref arg_name, _)| arg_name == &element_name)
-                        {
-                            local_found_felt_arguments.push(element_name);
-                        }
-                    }
-
-                    // Parse the libfunc name used in the statement
-                    let libfunc_name = parse_element_name_with_fallback!(
-                        invocation.libfunc_id,
-                        decompiler.declared_libfuncs_names
-                    );
-
-                    // Detect if we perform an arithmetic operation with a felt argument
-                    if ADDITION_REGEX
-                        .iter()
-                        .any(|regex| regex.is_match(&libfunc_name))
-                        || SUBSTRACTION_REGEX
-                            .iter()
-                            .any(|regex| regex.is_match(&libfunc_name))
-                        || MULTIPLICATION_REGEX
-                            .iter()
-                            .any(|regex| regex.is_match(&libfunc_name))
-                    {
-                        let confidence = if !local_found_felt_arguments.is_empty() {
-                            "High"
-                        } else {
-                            "Low"
-                        };
-                        found_vulnerabilities.push((
-                            function_name.clone(),
-                            local_found_felt_arguments,
-                            confidence,
-                            libfunc_name,
-                        ));
-                    }
-                }
-            }
-        }
+        let found_vulnerabilities = felt_overflows(context);
 
         // Append the found vulnerabilities to the result
         if !found_vulnerabilities.is_empty() {
-            for (function_name, arguments, confidence, libfunc_name) in found_vulnerabilities {
+            for (function_name, arguments, confidence, libfunc_name, _) in found_vulnerabilities {
                 let arguments_str = arguments.join(", ");
                 let confidence_str = if confidence == "High" {
                     "\x1b[1;31mHigh\x1b[0m"
@@ -145,4 +82,294 @@ impl Detector for FeltOverflowDetector {
 
         result
     }
+
+    /// Returns the potential felt overflows located at their arithmetic operation
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        felt_overflows(context)
+            .into_iter()
+            .map(
+                |(function_name, arguments, confidence, libfunc_name, offset)| Finding {
+                    detector_type,
+                    function: parse_element_name!(function_name),
+                    offset: Some(offset),
+                    message: if arguments.is_empty() {
+                        format!(
+                            "method {} could be used to trigger a felt overflow/underflow (Confidence: {})",
+                            libfunc_name, confidence
+                        )
+                    } else {
+                        format!(
+                            "parameters {} could be used to trigger a felt overflow/underflow (Confidence: {})",
+                            arguments.join(", "),
+                            confidence
+                        )
+                    },
+                },
+            )
+            .collect()
+    }
+}
+
+/// Returns the arithmetic operations that could overflow
+/// The operations proven in range by the value-range analysis & the ones whose result is only
+/// hashed are not reported. The operations on range checked or compared operands are downgraded.
+/// (function name, felt parameters used, confidence, libfunc name, statement offset)
+fn felt_overflows(
+    context: &AnalysisContext,
+) -> Vec<(FunctionId, Vec<String>, &'static str, String, u32)> {
+    let mut found_vulnerabilities = Vec::new();
+
+    for function in context.scoped_functions() {
+        // Skip core functions
+        if let Some(function_type) = &function.function_type {
+            if matches!(function_type, FunctionType::Core) {
+                continue;
+            }
+        }
+
+        let function_name = function.function.id.clone();
+        let intervals = IntervalAnalysis::new(function, context.declared_libfuncs_names()).ok();
+        let safe_patterns = intervals.as_ref().map(|intervals| {
+            SafePatterns::new(function, intervals.ssa(), context.declared_libfuncs_names())
+        });
+
+        let arguments = function.arguments.clone();
+
+        // Filter arguments felt arguments
+        let felt_arguments: Vec<_> = arguments
+            .iter()
+            .filter(|&&(_, ref arg_type)| arg_type == "felt252")
+            .collect();
+
+        for statement in function.statements() {
+            if let GenStatement::Invocation(invocation) = &statement.statement {
+                let arguments = invocation.args.clone();
+                let mut local_found_felt_arguments = Vec::new();
+
+                for argument in arguments {
+                    let element_name = var_id_to_name!(argument);
+
+                    // Check if the argument is in the felt_arguments
+                    if felt_arguments
+                        .iter()
+                        .any(|&(ref arg_name, _)| arg_name == &element_name)
+                    {
+                        local_found_felt_arguments.push(element_name);
+                    }
+                }
+
+                // Parse the libfunc name used in the statement
+                let libfunc_name = parse_element_name_with_fallback!(
+                    invocation.libfunc_id,
+                    context.declared_libfuncs_names()
+                );
+
+                // Detect if we perform an arithmetic operation with a felt argument
+                // The u256 & u512 operations are corelib calls
+                if statement.libfunc_kind.is_arithmetic() {
+                    if intervals.as_ref().is_some_and(|intervals| {
+                        intervals.proves_no_overflow(statement.offset, &libfunc_name)
+                    }) {
+                        continue;
+                    }
+                    if safe_patterns
+                        .as_ref()
+                        .is_some_and(|patterns| patterns.is_only_hashed(statement.offset))
+                    {
+                        continue;
+                    }
+
+                    // A guarded operation is downgraded, or not reported if it's already low
+                    let guarded = safe_patterns
+                        .as_ref()
+                        .is_some_and(|patterns| patterns.is_guarded(statement.offset));
+                    if guarded && local_found_felt_arguments.is_empty() {
+                        continue;
+                    }
+
+                    let confidence = if !local_found_felt_arguments.is_empty() && !guarded {
+                        "High"
+                    } else {
+                        "Low"
+                    };
+                    found_vulnerabilities.push((
+                        function_name.clone(),
+                        local_found_felt_arguments,
+                        confidence,
+                        libfunc_name,
+                        statement.offset,
+                    ));
+                }
+            }
+        }
+    }
+
+    found_vulnerabilities
+}
+
+/// Safe patterns of the arithmetic operations of a function, found using its SSA form
+struct SafePatterns<'a> {
+    ssa: &'a SsaForm,
+    /// Libfunc name of each reachable invocation
+    libfuncs: HashMap<u32, String>,
+    /// Offsets of the statements using each variable
+    uses: HashMap<SsaVariable, Vec<u32>>,
+    /// Results of the phi functions merging each variable
+    phi_results: HashMap<SsaVariable, Vec<SsaVariable>>,
+    /// Source of each variable defined by a copy or a conversion
+    copies: HashMap<SsaVariable, SsaVariable>,
+    /// Range checked or compared variables, with the start offset of the checking block
+    checks: Vec<(SsaVariable, u32)>,
+}
+
+impl<'a> SafePatterns<'a> {
+    /// Indexes the uses, the copies & the checks of the variables of a function
+    fn new(function: &Function, ssa: &'a SsaForm, declared_libfuncs_names: &[Arc<str>]) -> Self {
+        let mut patterns = Self {
+            ssa,
+            libfuncs: HashMap::new(),
+            uses: HashMap::new(),
+            phi_results: HashMap::new(),
+            copies: HashMap::new(),
+            checks: Vec::new(),
+        };
+
+        for statement in function.statements() {
+            let Some(ssa_statement) = ssa.statement(statement.offset) else {
+                continue;
+            };
+            for used in &ssa_statement.uses {
+                patterns
+                    .uses
+                    .entry(*used)
+                    .or_default()
+                    .push(statement.offset);
+            }
+
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+            let libfunc_name =
+                parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+
+            if is_copy(&libfunc_name) {
+                if let Some(source) = ssa_statement.uses.first() {
+                    for result in ssa_statement.definitions.iter().flatten() {
+                        patterns.copies.insert(*result, *source);
+                    }
+                }
+            } else if FELT252_RANGE_CHECK_REGEX.is_match(&libfunc_name) {
+                // `u8_try_from_felt252(range_check, value) { fallthrough(range_check, checked_value) 42(range_check) }`
+                if let Some(checked) = ssa_statement.uses.last() {
+                    patterns.checks.push((*checked, ssa_statement.block));
+                }
+            } else if LESS_THAN_REGEX.is_match(&libfunc_name)
+                || LESS_THAN_OR_EQUAL_REGEX.is_match(&libfunc_name)
+                || EQUAL_REGEX.is_match(&libfunc_name)
+            {
+                // The operands are the last arguments, after the range check
+                for compared in ssa_statement.uses.iter().rev().take(2) {
+                    patterns.checks.push((*compared, ssa_statement.block));
+                }
+            }
+
+            patterns.libfuncs.insert(statement.offset, libfunc_name);
+        }
+
+        for (_, phi) in ssa.all_phis() {
+            for operand in phi.operands.values().flatten() {
+                patterns
+                    .phi_results
+                    .entry(*operand)
+                    .or_default()
+                    .push(phi.result);
+            }
+        }
+
+        patterns
+    }
+
+    /// Returns the variable a copy originates from
+    fn origin(&self, mut variable: SsaVariable) -> SsaVariable {
+        while let Some(source) = self.copies.get(&variable) {
+            variable = *source;
+        }
+        variable
+    }
+
+    /// Checks if a block is strictly dominated by another one
+    fn strictly_dominates(&self, dominator: u32, block: u32) -> bool {
+        let mut current_block = self.ssa.immediate_dominator(block);
+        while let Some(block) = current_block {
+            if block == dominator {
+                return true;
+            }
+            current_block = self.ssa.immediate_dominator(block);
+        }
+        false
+    }
+
+    /// Checks if an operand of an operation (or a copy of it) is range checked or compared
+    /// before the operation, i.e. in a block dominating it
+    fn is_guarded(&self, offset: u32) -> bool {
+        let Some(statement) = self.ssa.statement(offset) else {
+            return false;
+        };
+        let operands: HashSet<SsaVariable> = statement
+            .uses
+            .iter()
+            .map(|operand| self.origin(*operand))
+            .collect();
+
+        self.checks.iter().any(|(checked, block)| {
+            operands.contains(&self.origin(*checked))
+                && self.strictly_dominates(*block, statement.block)
+        })
+    }
+
+    /// Checks if the result of an operation is only used as a hash input
+    /// The result can be copied or used by other felt252 operations before being hashed
+    fn is_only_hashed(&self, offset: u32) -> bool {
+        let Some(statement) = self.ssa.statement(offset) else {
+            return false;
+        };
+
+        let mut worklist: Vec<SsaVariable> =
+            statement.definitions.iter().flatten().copied().collect();
+        let mut visited = HashSet::new();
+        let mut hashed = false;
+        while let Some(variable) = worklist.pop() {
+            if !visited.insert(variable) {
+                continue;
+            }
+            worklist.extend(self.phi_results.get(&variable).into_iter().flatten());
+
+            for use_offset in self.uses.get(&variable).into_iter().flatten() {
+                // The returned values are not invocations
+                let Some(libfunc_name) = self.libfuncs.get(use_offset) else {
+                    return false;
+                };
+
+                if HASH_REGEX.is_match(libfunc_name) {
+                    hashed = true;
+                } else if is_copy(libfunc_name) || FELT252_OPERATION_REGEX.is_match(libfunc_name) {
+                    if let Some(use_statement) = self.ssa.statement(*use_offset) {
+                        worklist.extend(use_statement.definitions.iter().flatten());
+                    }
+                } else if !DROP_REGEX.is_match(libfunc_name) {
+                    return false;
+                }
+            }
+        }
+
+        hashed
+    }
+}
+
+/// Checks if a libfunc copies its first argument (or converts it to a felt252)
+fn is_copy(libfunc_name: &str) -> bool {
+    COPY_REGEXES
+        .iter()
+        .any(|regex| regex.is_match(libfunc_name))
 }
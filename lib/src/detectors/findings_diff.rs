@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::detectors::detector::FindingReport;
+use crate::error::Result;
+
+/// Differences between the findings of two audit iterations
+/// The findings are matched by detector, function & message, their offsets can change
+/// between the two versions of the program
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FindingsDiff {
+    /// Findings of the old report that are not reported anymore
+    pub fixed: Vec<FindingReport>,
+    /// Findings of the new report that were not reported
+    pub new: Vec<FindingReport>,
+    /// Findings reported by both, with their offsets in the new report
+    pub unchanged: Vec<FindingReport>,
+}
+
+impl FindingsDiff {
+    /// Compares the old & the new findings, a finding reported several times is matched as many times
+    pub fn new(old: &[FindingReport], new: &[FindingReport]) -> Self {
+        let mut old_counts: HashMap<(&str, &str, &str), usize> = HashMap::new();
+        for report in old {
+            *old_counts.entry(finding_key(report)).or_default() += 1;
+        }
+
+        let mut diff = FindingsDiff::default();
+        for report in new {
+            match old_counts.get_mut(&finding_key(report)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    diff.unchanged.push(report.clone());
+                }
+                _ => diff.new.push(report.clone()),
+            }
+        }
+
+        // The old findings left unmatched are the fixed ones
+        for report in old {
+            if let Some(count) = old_counts.get_mut(&finding_key(report)) {
+                if *count > 0 {
+                    *count -= 1;
+                    diff.fixed.push(report.clone());
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Returns true if the new report contains findings that were not reported
+    pub fn has_new(&self) -> bool {
+        !self.new.is_empty()
+    }
+
+    /// Returns the diff as text, the fixed, new & unchanged findings followed by their counts
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (title, reports) in [
+            ("Fixed", &self.fixed),
+            ("New", &self.new),
+            ("Unchanged", &self.unchanged),
+        ] {
+            text += &format!("{} ({})\n", title, reports.len());
+            for report in reports {
+                text += &format!(
+                    "\t- [{}] {} in {}: {}\n",
+                    report.detector_type, report.detector, report.function, report.message
+                );
+            }
+        }
+
+        text += &format!(
+            "\n{} fixed, {} new, {} unchanged\n",
+            self.fixed.len(),
+            self.new.len(),
+            self.unchanged.len()
+        );
+        text
+    }
+
+    /// Returns the diff in JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Reads a findings file, the JSON output of the detectors (`--detectors-format json`)
+pub fn read_findings(path: &Path) -> Result<Vec<FindingReport>> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Key matching the same finding in two reports
+fn finding_key(report: &FindingReport) -> (&str, &str, &str) {
+    (&report.detector, &report.function, &report.message)
+}
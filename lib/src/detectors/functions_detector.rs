@@ -1,5 +1,5 @@
-use crate::decompiler::decompiler::Decompiler;
-use crate::decompiler::function::FunctionType;
+use crate::analysis::context::AnalysisContext;
+use crate::decompiler::function::{Function, FunctionType};
 use crate::detectors::detector::{Detector, DetectorType};
 
 #[derive(Debug)]
@@ -38,13 +38,13 @@ impl Detector for FunctionsDetector {
     }
 
     /// Returns all the functions names
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String {
+    fn detect(&mut self, context: &AnalysisContext) -> String {
         let mut result = String::new();
 
         // We extract the functions names from the prototypes
-        decompiler.decompile_functions_prototypes();
-        let total_functions = decompiler.functions.len();
-        for (index, function) in decompiler.functions.iter().enumerate() {
+        let functions: Vec<&Function> = context.scoped_functions().collect();
+        let total_functions = functions.len();
+        for (index, function) in functions.into_iter().enumerate() {
             if let Some(prototype) = &function.prototype {
                 // Remove the "func " prefix and then split at the first space
                 let stripped_prototype = &prototype[5..];
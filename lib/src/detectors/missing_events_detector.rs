@@ -0,0 +1,107 @@
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::invariants::transitive_callers;
+use crate::decompiler::function::FunctionType;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+#[derive(Debug)]
+pub struct MissingEventsDetector;
+
+impl MissingEventsDetector {
+    /// Creates a new `MissingEventsDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for MissingEventsDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "missing_events"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Missing events"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the entry points writing the storage without emitting any event."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::CORRECTNESS
+    }
+
+    /// Returns the entry points writing the storage without emitting any event
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        missing_events(context)
+            .into_iter()
+            .map(|(wrapper, message)| format!("{}: {}\n", wrapper, message))
+            .collect()
+    }
+
+    /// Returns the entry points writing the storage without emitting any event,
+    /// the findings concern the whole wrapper
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        missing_events(context)
+            .into_iter()
+            .map(|(function, message)| Finding {
+                detector_type,
+                function,
+                offset: None,
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the entry points writing the storage, directly or through their callees, without
+/// emitting any event. The constructors are not reported, they initialize the storage
+/// (wrapper name, message)
+fn missing_events(context: &AnalysisContext) -> Vec<(String, String)> {
+    let functions = context.functions();
+    let callgraph = CallGraph::new(functions);
+    let storage_writers = transitive_callers(&callgraph, functions, LibfuncKind::StorageWrite);
+    let event_emitters = transitive_callers(&callgraph, functions, LibfuncKind::EmitEvent);
+
+    let mut entry_points = Vec::new();
+
+    for function in functions {
+        if !matches!(function.function_type, Some(FunctionType::Wrapper))
+            || !context.is_in_scope(function)
+        {
+            continue;
+        }
+
+        let wrapper = parse_element_name!(function.function.id);
+        let Some(entry_point) = wrapper_entry_point_name(&wrapper) else {
+            continue;
+        };
+        if entry_point == "constructor" || wrapper.contains("::__constructor::") {
+            continue;
+        }
+
+        if storage_writers.contains(&wrapper) && !event_emitters.contains(&wrapper) {
+            entry_points.push((
+                wrapper,
+                format!(
+                    "entry point {} writes the storage without emitting any event",
+                    entry_point
+                ),
+            ));
+        }
+    }
+
+    entry_points
+}
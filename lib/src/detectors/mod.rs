@@ -1,18 +1,40 @@
+pub mod constructor_detector;
 pub mod controlled_library_call_detector;
+pub mod deploy_detector;
 pub mod detector;
+pub mod effects_timeline_detector;
+pub mod felt_comparison_detector;
 pub mod felt_overflow_detector;
+pub mod findings_diff;
 pub mod functions_detector;
+pub mod missing_events_detector;
+pub mod recursion_detector;
+pub mod reinitialization_detector;
+pub mod runner;
 pub mod statistics_detector;
 pub mod strings_detector;
+#[cfg(feature = "z3")]
 pub mod tests_generator_detector;
+pub mod truncation_detector;
+pub mod tx_origin_detector;
 
+use crate::detectors::constructor_detector::ConstructorDetector;
 use crate::detectors::controlled_library_call_detector::ControlledLibraryCallDetector;
+use crate::detectors::deploy_detector::DeployDetector;
 use crate::detectors::detector::Detector;
+use crate::detectors::effects_timeline_detector::EffectsTimelineDetector;
+use crate::detectors::felt_comparison_detector::FeltComparisonDetector;
 use crate::detectors::felt_overflow_detector::FeltOverflowDetector;
 use crate::detectors::functions_detector::FunctionsDetector;
+use crate::detectors::missing_events_detector::MissingEventsDetector;
+use crate::detectors::recursion_detector::RecursionDetector;
+use crate::detectors::reinitialization_detector::ReinitializationDetector;
 use crate::detectors::statistics_detector::StatisticsDetector;
 use crate::detectors::strings_detector::StringsDetector;
+#[cfg(feature = "z3")]
 use crate::detectors::tests_generator_detector::TestsGeneratorDetector;
+use crate::detectors::truncation_detector::TruncationDetector;
+use crate::detectors::tx_origin_detector::TxOriginDetector;
 
 /// Macro to create a vector of detectors
 macro_rules! create_detectors {
@@ -27,12 +49,27 @@ macro_rules! create_detectors {
 
 /// Returns a vector of all the instantiated detectors
 pub fn get_detectors() -> Vec<Box<dyn Detector>> {
-    create_detectors!(
+    #[allow(unused_mut)]
+    let mut detectors: Vec<Box<dyn Detector>> = create_detectors!(
         FunctionsDetector,
         StringsDetector,
         StatisticsDetector,
-        TestsGeneratorDetector,
         ControlledLibraryCallDetector,
-        FeltOverflowDetector
-    )
+        FeltOverflowDetector,
+        FeltComparisonDetector,
+        EffectsTimelineDetector,
+        MissingEventsDetector,
+        ConstructorDetector,
+        ReinitializationDetector,
+        TxOriginDetector,
+        TruncationDetector,
+        DeployDetector,
+        RecursionDetector
+    );
+
+    // The tests generator requires a solver backend
+    #[cfg(feature = "z3")]
+    detectors.push(Box::new(TestsGeneratorDetector::new()));
+
+    detectors
 }
@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+
+use crate::analysis::context::AnalysisContext;
+use crate::decompiler::function::FunctionType;
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::graph::callgraph::{CallDepth, CallGraph, RecursionCycle};
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+#[derive(Debug)]
+pub struct RecursionDetector;
+
+impl RecursionDetector {
+    /// Creates a new `RecursionDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for RecursionDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "recursion"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Recursion"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the recursive functions & the entry points reaching them, their gas consumption is unbounded."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::SECURITY
+    }
+
+    /// Returns the recursion cycles followed by the maximum calls depth of the entry points
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        let mut result: String = recursions(context)
+            .into_iter()
+            .map(|(function, message)| format!("{}: {}\n", function, message))
+            .collect();
+
+        for (function, depth) in max_call_depths(context) {
+            result.push_str(&format!("Max call depth of {}: {}\n", function, depth));
+        }
+
+        result
+    }
+
+    /// Returns the recursion cycles, the findings concern the first function of each cycle
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        recursions(context)
+            .into_iter()
+            .map(|(function, message)| Finding {
+                detector_type,
+                function,
+                offset: None,
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the names of the entry points wrappers
+fn wrappers(context: &AnalysisContext) -> Vec<String> {
+    context
+        .functions()
+        .iter()
+        .filter(|function| matches!(function.function_type, Some(FunctionType::Wrapper)))
+        .map(|function| parse_element_name!(function.function.id))
+        .collect()
+}
+
+/// Returns the recursion cycles containing a user function, the loops generated by the compiler
+/// are not reported. The cycles reachable from an entry point are flagged as a potential
+/// unbounded gas consumption
+/// (first function of the cycle in scope, message)
+fn recursions(context: &AnalysisContext) -> Vec<(String, String)> {
+    let callgraph = CallGraph::new(context.functions());
+    let user_functions: HashSet<String> = context
+        .user_defined_functions()
+        .filter(|function| !matches!(function.function_type, Some(FunctionType::Loop)))
+        .map(|function| parse_element_name!(function.function.id))
+        .collect();
+    let entry_points_reachable: Vec<(String, HashSet<String>)> = wrappers(context)
+        .into_iter()
+        .map(|wrapper| {
+            let reachable = callgraph.reachable_from(&[wrapper.clone()]);
+            (wrapper, reachable)
+        })
+        .collect();
+
+    let mut recursions = Vec::new();
+
+    for cycle in callgraph.recursion_cycles() {
+        if !cycle
+            .functions
+            .iter()
+            .any(|function| user_functions.contains(function))
+        {
+            continue;
+        }
+        let Some(function) = cycle_function_in_scope(context, &cycle) else {
+            continue;
+        };
+
+        let kind = if cycle.is_direct() {
+            "directly recursive function"
+        } else {
+            "mutually recursive functions"
+        };
+        let entry_points: Vec<String> = entry_points_reachable
+            .iter()
+            .filter(|(_, reachable)| reachable.contains(&cycle.functions[0]))
+            .filter_map(|(wrapper, _)| wrapper_entry_point_name(wrapper))
+            .collect();
+
+        let message = if entry_points.is_empty() {
+            format!("{} {}", kind, cycle.path())
+        } else {
+            format!(
+                "{} {} reachable from the entry points {}, potential unbounded gas consumption",
+                kind,
+                cycle.path(),
+                entry_points.join(", ")
+            )
+        };
+        recursions.push((function, message));
+    }
+
+    recursions
+}
+
+/// Returns the first function of a cycle reported by the detectors
+fn cycle_function_in_scope(context: &AnalysisContext, cycle: &RecursionCycle) -> Option<String> {
+    cycle
+        .functions
+        .iter()
+        .find(|name| {
+            context
+                .session()
+                .function(name)
+                .is_some_and(|function| context.is_in_scope(function))
+        })
+        .cloned()
+}
+
+/// Returns the maximum calls depth of the entry points wrappers, or of the user functions if the
+/// program has no entry point
+/// (function name, depth)
+fn max_call_depths(context: &AnalysisContext) -> Vec<(String, CallDepth)> {
+    let callgraph = CallGraph::new(context.functions());
+    let mut roots = wrappers(context);
+    if roots.is_empty() {
+        roots = context
+            .user_defined_functions()
+            .filter(|function| !matches!(function.function_type, Some(FunctionType::Loop)))
+            .map(|function| parse_element_name!(function.function.id))
+            .collect();
+    }
+
+    roots
+        .into_iter()
+        .filter(|name| {
+            context
+                .session()
+                .function(name)
+                .is_some_and(|function| context.is_in_scope(function))
+        })
+        .filter_map(|name| {
+            let depth = callgraph.max_call_depth(&name)?;
+            Some((name, depth))
+        })
+        .collect()
+}
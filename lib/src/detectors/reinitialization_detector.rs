@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use cairo_lang_sierra::program::GenStatement;
+use lazy_static::lazy_static;
+use num_bigint::BigUint;
+use regex::Regex;
+
+use crate::analysis::access_control::{builtin_variables, propagate_tainted_variables};
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::invariants::{called_function, storage_base_addresses, transitive_callers};
+use crate::analysis::ssa::SsaVariable;
+use crate::decompiler::function::{Function, FunctionType};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::graph::callgraph::CallGraph;
+use crate::graph::cross_contract::wrapper_entry_point_name;
+use crate::parse_element_name;
+
+lazy_static! {
+    /// Names of the entry points initializing a contract outside of its constructor
+    static ref INITIALIZER_REGEX: Regex =
+        Regex::new(r"^(init|initialize|initializer|initialise|setup|set_up)(_.+)?$").unwrap();
+}
+
+#[derive(Debug)]
+pub struct ReinitializationDetector;
+
+impl ReinitializationDetector {
+    /// Creates a new `ReinitializationDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for ReinitializationDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "reinitialization"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Re-initialization"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the external initializers writing the storage without checking an already initialized flag."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::SECURITY
+    }
+
+    /// Returns the initializers that can be called again
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        reinitializable_entry_points(context)
+            .into_iter()
+            .map(|(wrapper, message)| format!("{}: {}\n", wrapper, message))
+            .collect()
+    }
+
+    /// Returns the initializers that can be called again, the findings concern the whole wrapper
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        reinitializable_entry_points(context)
+            .into_iter()
+            .map(|(function, message)| Finding {
+                detector_type,
+                function,
+                offset: None,
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the `initialize`-style entry points writing the storage without checking a flag
+/// The flag is a storage variable whose value is tested & that is written by the entry point,
+/// the variables are identified by their storage base address
+/// (wrapper name, message)
+fn reinitializable_entry_points(context: &AnalysisContext) -> Vec<(String, String)> {
+    let mut entry_points = Vec::new();
+
+    let functions = context.functions();
+    let callgraph = CallGraph::new(functions);
+    let storage_writers = transitive_callers(&callgraph, functions, LibfuncKind::StorageWrite);
+    let storage_readers = transitive_callers(&callgraph, functions, LibfuncKind::StorageRead);
+
+    for function in functions {
+        if !matches!(function.function_type, Some(FunctionType::Wrapper))
+            || !context.is_in_scope(function)
+        {
+            continue;
+        }
+
+        let wrapper = parse_element_name!(function.function.id);
+        let Some(entry_point) = wrapper_entry_point_name(&wrapper) else {
+            continue;
+        };
+        if !INITIALIZER_REGEX.is_match(&entry_point) || !storage_writers.contains(&wrapper) {
+            continue;
+        }
+
+        // Storage variables tested & written by the functions reachable from the entry point
+        let reachable = callgraph.reachable_from(&[wrapper.clone()]);
+        let mut tested_slots: HashSet<BigUint> = HashSet::new();
+        let mut written_slots: HashSet<BigUint> = HashSet::new();
+        for reachable_function in functions
+            .iter()
+            .filter(|function| reachable.contains(&parse_element_name!(function.function.id)))
+        {
+            let function_name = parse_element_name!(reachable_function.function.id);
+            if storage_writers.contains(&function_name) {
+                written_slots.extend(storage_base_addresses(reachable_function));
+            }
+            if tests_storage_value(reachable_function, &storage_readers) {
+                tested_slots.extend(storage_base_addresses(reachable_function));
+            }
+        }
+
+        if tested_slots.is_disjoint(&written_slots) {
+            entry_points.push((
+                wrapper,
+                format!(
+                    "the initializer {} writes the storage without checking an already initialized flag, it can be called again",
+                    entry_point
+                ),
+            ));
+        }
+    }
+
+    entry_points
+}
+
+/// Returns true if the function branches (zero check, equality or boolean match) on a value
+/// derived from a storage value, read directly or by a called function
+fn tests_storage_value(function: &Function, storage_readers: &HashSet<String>) -> bool {
+    let Ok(ssa) = function.ssa() else {
+        return false;
+    };
+    let builtins = builtin_variables(function, &ssa);
+    let mut tainted: HashSet<SsaVariable> = HashSet::new();
+
+    // The statements are evaluated until the taints of the loops are stable
+    propagate_tainted_variables(function, &ssa, &builtins, &mut tainted, |statement| {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            return false;
+        };
+        let libfunc_name = parse_element_name!(invocation.libfunc_id);
+        statement.libfunc_kind == LibfuncKind::StorageRead
+            || called_function(&libfunc_name)
+                .is_some_and(|callee| storage_readers.contains(&callee))
+    });
+
+    function.statements().iter().any(|statement| {
+        if !statement.libfunc_kind.is_condition() {
+            return false;
+        }
+
+        ssa.statement(statement.offset)
+            .is_some_and(|ssa_statement| {
+                ssa_statement
+                    .uses
+                    .iter()
+                    .any(|variable| tainted.contains(variable))
+            })
+    })
+}
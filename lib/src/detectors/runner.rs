@@ -0,0 +1,179 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tracing::info_span;
+
+use crate::analysis::context::AnalysisContext;
+use crate::cache::DetectorResults;
+use crate::detectors::detector::{Detector, FindingReport};
+use crate::error::{Result, SierraAnalyzerError};
+use crate::sierra_program::SierraProgram;
+
+/// Options of a detectors run
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Name of the only function analyzed by the detectors
+    pub function_filter: Option<String>,
+    /// Compute the text reports of the detectors
+    pub with_report: bool,
+    /// Compute the located findings of the detectors
+    pub with_findings: bool,
+    /// Time given to each detector from its start
+    pub timeout: Option<Duration>,
+}
+
+/// Outcome of a detector run by `run_detectors`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetectorOutcome {
+    /// The detector finished with its results
+    Finished(DetectorResults),
+    /// The detector didn't finish before the timeout
+    TimedOut,
+    /// The detector panicked
+    Failed,
+}
+
+/// Events sent by the detectors threads
+enum DetectorEvent {
+    /// The detector at the given index started
+    Started(usize),
+    /// The detector at the given index finished with its results
+    Finished(usize, DetectorResults),
+    /// The detector at the given index panicked
+    Panicked(usize),
+    /// The program could not be analyzed
+    ContextFailed(SierraAnalyzerError),
+}
+
+/// Runs the detectors concurrently against the same read-only analysis context & returns their
+/// outcomes in the detectors order, `on_outcome` is called as soon as the outcome of a detector
+/// is known (e.g. to report the progress)
+///
+/// The detectors run in a detached thread owning a copy of the program. A running detector can't
+/// be interrupted: the threads of the detectors that time out are leaked until they return, so a
+/// process should exit rather than start new runs while they are alive
+pub fn run_detectors(
+    program: &SierraProgram,
+    detectors: Vec<Box<dyn Detector>>,
+    options: RunOptions,
+    mut on_outcome: impl FnMut(usize, &DetectorOutcome),
+) -> Result<Vec<DetectorOutcome>> {
+    let count = detectors.len();
+    let RunOptions {
+        function_filter,
+        with_report,
+        with_findings,
+        timeout,
+    } = options;
+
+    let (sender, receiver) = mpsc::channel();
+    let worker_program = program.clone();
+    thread::spawn(move || {
+        let context = match AnalysisContext::new(&worker_program) {
+            Ok(context) => match &function_filter {
+                Some(function_name) => context.with_function_filter(function_name),
+                None => context,
+            },
+            Err(e) => {
+                let _ = sender.send(DetectorEvent::ContextFailed(e));
+                return;
+            }
+        };
+
+        thread::scope(|scope| {
+            let context = &context;
+            for (index, mut detector) in detectors.into_iter().enumerate() {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    let _ = sender.send(DetectorEvent::Started(index));
+                    let _span = info_span!("detector", id = detector.id()).entered();
+
+                    // A panicking detector is reported as failed, the other ones keep running
+                    let results = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let report = if with_report {
+                            detector.detect(context)
+                        } else {
+                            String::new()
+                        };
+                        let findings = if with_findings {
+                            let id = detector.id();
+                            detector
+                                .findings(context)
+                                .into_iter()
+                                .map(|finding| FindingReport::new(id, finding, context))
+                                .collect()
+                        } else {
+                            Vec::new()
+                        };
+                        DetectorResults { report, findings }
+                    }));
+                    let _ = sender.send(match results {
+                        Ok(results) => DetectorEvent::Finished(index, results),
+                        Err(_) => DetectorEvent::Panicked(index),
+                    });
+                });
+            }
+        });
+    });
+
+    // Collect the outcomes, each detector is given `timeout` from its start
+    let mut started: Vec<Option<Instant>> = vec![None; count];
+    let mut outcomes: Vec<Option<DetectorOutcome>> = vec![None; count];
+    let mut pending = count;
+    while pending > 0 {
+        let deadline = timeout.and_then(|timeout| {
+            started
+                .iter()
+                .zip(&outcomes)
+                .filter(|(_, outcome)| outcome.is_none())
+                .filter_map(|(start, _)| start.map(|start| start + timeout))
+                .min()
+        });
+        let event = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(RecvTimeoutError::from),
+        };
+
+        let mut set_outcome = |index: usize, outcome: DetectorOutcome| {
+            if outcomes[index].is_none() {
+                on_outcome(index, &outcome);
+                outcomes[index] = Some(outcome);
+                pending -= 1;
+            }
+        };
+        match event {
+            Ok(DetectorEvent::Started(index)) => started[index] = Some(Instant::now()),
+            Ok(DetectorEvent::Finished(index, results)) => {
+                set_outcome(index, DetectorOutcome::Finished(results))
+            }
+            Ok(DetectorEvent::Panicked(index)) => set_outcome(index, DetectorOutcome::Failed),
+            Ok(DetectorEvent::ContextFailed(e)) => return Err(e),
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                for index in 0..count {
+                    let expired = timeout
+                        .zip(started[index])
+                        .is_some_and(|(timeout, start)| now >= start + timeout);
+                    if expired {
+                        set_outcome(index, DetectorOutcome::TimedOut);
+                    }
+                }
+            }
+            // The detectors that never sent their results failed
+            Err(RecvTimeoutError::Disconnected) => {
+                for index in 0..count {
+                    set_outcome(index, DetectorOutcome::Failed);
+                }
+            }
+        }
+    }
+
+    Ok(outcomes
+        .into_iter()
+        .map(|outcome| outcome.unwrap_or(DetectorOutcome::Failed))
+        .collect())
+}
@@ -1,4 +1,4 @@
-use crate::decompiler::decompiler::Decompiler;
+use crate::analysis::context::AnalysisContext;
 use crate::detectors::detector::{Detector, DetectorType};
 
 #[derive(Debug)]
@@ -37,8 +37,8 @@ impl Detector for StatisticsDetector {
     }
 
     /// Returns all the functions statistics
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String {
-        let sierra_program = decompiler.sierra_program.program();
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        let sierra_program = context.program();
 
         let libfuncs_len = sierra_program.libfunc_declarations.len();
         let types_len = sierra_program.type_declarations.len();
@@ -1,4 +1,4 @@
-use crate::decompiler::decompiler::Decompiler;
+use crate::analysis::context::AnalysisContext;
 use crate::decompiler::libfuncs_patterns::CONST_REGEXES;
 use crate::decompiler::utils::decode_hex_bigint;
 use crate::decompiler::utils::replace_types_id;
@@ -7,7 +7,7 @@ use crate::parse_element_name;
 
 use cairo_lang_sierra::program::GenStatement;
 use num_bigint::BigInt;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 
 #[derive(Debug)]
 pub struct StringsDetector;
@@ -45,14 +45,33 @@ impl Detector for StringsDetector {
     }
 
     /// Detects unique strings in the decompiled Sierra code and returns them as a single string
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String {
+    fn detect(&mut self, context: &AnalysisContext) -> String {
         // A set to store the extracted unique strings
         // We use a BTreeSet instead of HashSet to get an ordered result
         let mut extracted_strings: BTreeSet<String> = BTreeSet::new();
 
         // Iterate over all the program statements
-        for function in &decompiler.functions {
-            for statement in &function.statements {
+        for function in context.scoped_functions() {
+            // Long strings are ByteArrays, their words are reassembled into a single string
+            let byte_array_literals = function.byte_array_literals(
+                context.declared_libfuncs_names(),
+                context.declared_types_names(),
+            );
+            let byte_array_words: HashSet<u32> = byte_array_literals
+                .iter()
+                .flat_map(|literal| literal.words_offsets.iter().copied())
+                .collect();
+            extracted_strings.extend(
+                byte_array_literals
+                    .into_iter()
+                    .map(|literal| literal.value)
+                    .filter(|value| value.chars().any(|c| c.is_ascii_graphic())),
+            );
+
+            for statement in function.statements() {
+                if byte_array_words.contains(&statement.offset) {
+                    continue;
+                }
                 let statement = &statement.statement;
                 match statement {
                     GenStatement::Invocation(invocation) => {
@@ -62,10 +81,9 @@ impl Detector for StringsDetector {
                         // If the libfunc id is an integer
                         let libfunc_id_str = if let Ok(index) = libfunc_id_str.parse::<usize>() {
                             // If it's a remote contract we try to convert the types IDs to their equivalents types names
-                            if let Some(libfunc_name) =
-                                decompiler.declared_libfuncs_names.get(index)
+                            if let Some(libfunc_name) = context.declared_libfuncs_names().get(index)
                             {
-                                replace_types_id(&decompiler.declared_types_names, libfunc_name)
+                                replace_types_id(context.declared_types_names(), libfunc_name)
                             } else {
                                 continue;
                             }
@@ -1,6 +1,7 @@
-use crate::decompiler::decompiler::Decompiler;
+use crate::analysis::context::AnalysisContext;
+use crate::config::SymbolicExecutionConfig;
 use crate::detectors::detector::{Detector, DetectorType};
-use crate::sym_exec::sym_exec::generate_test_cases_for_function;
+use crate::sym_exec::sym_exec::generate_test_cases_in_program;
 
 #[derive(Debug)]
 pub struct TestsGeneratorDetector;
@@ -38,30 +39,31 @@ impl Detector for TestsGeneratorDetector {
         DetectorType::TESTING
     }
 
+    /// The tests cases are generated using the symbolic execution
+    #[inline]
+    fn uses_symbolic_execution(&self) -> bool {
+        true
+    }
+
     /// Returns the generated unit tests for the function if they exist
-    fn detect(&mut self, decompiler: &mut Decompiler) -> String {
+    fn detect(&mut self, context: &AnalysisContext) -> String {
         let mut result = String::new();
 
-        for function in &mut decompiler.functions {
-            // Determine the function name
-            let function_name = if let Some(prototype) = &function.prototype {
-                // Remove the "func " prefix and then split at the first parenthese
-                let stripped_prototype = &prototype[5..];
-                if let Some(first_space_index) = stripped_prototype.find('(') {
-                    Some(stripped_prototype[..first_space_index].trim().to_string())
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+        // The CFGs of the context are used to unroll the loops called by the functions
+        for (function_index, function) in context.functions().iter().enumerate() {
+            if !context.is_in_scope(function) {
+                continue;
+            }
 
-            // If a function name was found, proceed with the mutable borrow
-            if let Some(function_name) = function_name {
+            // If a function name was found, generate its test cases
+            if let Some(function_name) = function.prototype_name() {
                 // Add the test cases to the result
-                let test_cases = generate_test_cases_for_function(
-                    function,
-                    decompiler.declared_libfuncs_names.clone(),
+                let test_cases = generate_test_cases_in_program(
+                    context.functions(),
+                    function_index,
+                    context.declared_libfuncs_names(),
+                    SymbolicExecutionConfig::UNROLL_BOUND,
+                    SymbolicExecutionConfig::HASH_MODEL,
                 );
 
                 if !test_cases.is_empty() {
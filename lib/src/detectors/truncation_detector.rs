@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_sierra::program::{BranchTarget, GenStatement};
+
+use crate::analysis::access_control::{builtin_variables, propagate_tainted_variables};
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::ssa::{SsaForm, SsaVariable};
+use crate::decompiler::function::{Function, FunctionType, SierraStatement};
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::{
+    DOWNCAST_REGEX, INTEGER_FROM_FELT252_REGEX, U128S_FROM_FELT252_REGEX, U256_DECONSTRUCT_REGEX,
+};
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::parse_element_name;
+
+#[derive(Debug)]
+pub struct TruncationDetector;
+
+impl TruncationDetector {
+    /// Creates a new `TruncationDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for TruncationDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "truncation"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Felt252 truncation"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the conversions of the parameters to smaller integers that panic or truncate the values."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::SECURITY
+    }
+
+    /// Returns the panicking & truncating conversions
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        conversions(context)
+            .into_iter()
+            .map(|(function_name, offset, message)| {
+                format!("{}: {} (statement {})\n", function_name, message, offset)
+            })
+            .collect()
+    }
+
+    /// Returns the panicking & truncating conversions, located at the conversion statements
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        conversions(context)
+            .into_iter()
+            .map(|(function, offset, message)| Finding {
+                detector_type,
+                function,
+                offset: Some(offset),
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Returns the conversions of values derived from the parameters of the user functions that
+/// panic when the value is out of range (`try_into().unwrap()`) or silently drop its high word
+/// The wrappers are skipped, their conversions deserialize the calldata
+/// (function name, statement offset, message)
+fn conversions(context: &AnalysisContext) -> Vec<(String, u32, String)> {
+    let mut conversions = Vec::new();
+
+    for function in context.scoped_functions() {
+        if matches!(
+            function.function_type,
+            Some(FunctionType::Core) | Some(FunctionType::Wrapper)
+        ) {
+            continue;
+        }
+        let Ok(ssa) = function.ssa() else {
+            continue;
+        };
+
+        let function_name = parse_element_name!(function.function.id);
+        let tainted = parameters_derived_variables(function, &ssa);
+        let dropped_only = dropped_only_variables(function, &ssa);
+
+        for statement in function.statements() {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+            let Some(ssa_statement) = ssa.statement(statement.offset) else {
+                continue;
+            };
+            let libfunc_name = parse_element_name!(invocation.libfunc_id);
+
+            // The converted value is the last argument, after the range check
+            let Some(converted) = ssa_statement.uses.last() else {
+                continue;
+            };
+            if !tainted.contains(converted) {
+                continue;
+            }
+
+            let target_type = INTEGER_FROM_FELT252_REGEX
+                .captures(&libfunc_name)
+                .or_else(|| DOWNCAST_REGEX.captures(&libfunc_name))
+                .map(|captures| captures["type"].to_string());
+
+            if let Some(target_type) = target_type {
+                // `u8_try_from_felt252(range_check, value) { fallthrough(range_check, checked_value) 42(range_check) }`
+                let panics = invocation.branches.get(1).is_some_and(|branch| {
+                    branch_panics(function, &branch.target, statement.offset)
+                });
+                if panics {
+                    conversions.push((
+                        function_name.clone(),
+                        statement.offset,
+                        format!(
+                            "conversion of a parameter to {} panics when the value is out of range",
+                            target_type
+                        ),
+                    ));
+                }
+            } else if U128S_FROM_FELT252_REGEX.is_match(&libfunc_name) {
+                // `u128s_from_felt252(range_check, value) { fallthrough(range_check, low) 42(range_check, high, low) }`
+                let truncates = ssa_statement
+                    .definitions
+                    .get(1)
+                    .and_then(|results| results.get(1))
+                    .is_some_and(|high| dropped_only.contains(high));
+                if truncates {
+                    conversions.push((
+                        function_name.clone(),
+                        statement.offset,
+                        "truncation of a felt252 parameter to u128, its high 128 bits are dropped"
+                            .to_string(),
+                    ));
+                }
+            } else if U256_DECONSTRUCT_REGEX.is_match(&libfunc_name) {
+                // `struct_deconstruct<core::integer::u256>(value) -> (low, high)`
+                let truncates = ssa_statement
+                    .definitions
+                    .first()
+                    .and_then(|results| results.get(1))
+                    .is_some_and(|high| dropped_only.contains(high));
+                if truncates {
+                    conversions.push((
+                        function_name.clone(),
+                        statement.offset,
+                        "truncation of a u256 parameter to u128, its high 128 bits are dropped"
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    conversions
+}
+
+/// Returns the variables derived from the parameters of a function,
+/// except the builtins & the contract (or component) state
+fn parameters_derived_variables(function: &Function, ssa: &SsaForm) -> HashSet<SsaVariable> {
+    let builtins = builtin_variables(function, ssa);
+    let mut tainted: HashSet<SsaVariable> = ssa
+        .parameters()
+        .iter()
+        .zip(&function.function.params)
+        .filter(|(parameter, param)| {
+            let is_state = param.ty.debug_name.as_ref().is_some_and(|type_name| {
+                type_name.contains("ContractState") || type_name.contains("ComponentState")
+            });
+            !builtins.contains(*parameter) && !is_state
+        })
+        .map(|(parameter, _)| *parameter)
+        .collect();
+
+    // The statements are evaluated until the taints of the loops are stable
+    propagate_tainted_variables(function, ssa, &builtins, &mut tainted, |_| false);
+
+    tainted
+}
+
+/// Returns the variables defined by an invocation & only used by drops
+fn dropped_only_variables(function: &Function, ssa: &SsaForm) -> HashSet<SsaVariable> {
+    let mut dropped: HashMap<SsaVariable, bool> = HashMap::new();
+
+    for statement in function.statements() {
+        let Some(ssa_statement) = ssa.statement(statement.offset) else {
+            continue;
+        };
+        // The definitions are only dropped until a use that isn't a drop clears them
+        for result in ssa_statement.definitions.iter().flatten() {
+            dropped.entry(*result).or_insert(true);
+        }
+
+        let is_drop = statement.libfunc_kind == LibfuncKind::Drop;
+        for used in &ssa_statement.uses {
+            let only_dropped = dropped.entry(*used).or_insert(true);
+            *only_dropped &= is_drop;
+        }
+    }
+
+    // The values merged by a phi function are used by its result
+    for (_, phi) in ssa.all_phis() {
+        for operand in phi.operands.values().flatten() {
+            dropped.insert(*operand, false);
+        }
+    }
+
+    dropped
+        .into_iter()
+        .filter(|(_, only_dropped)| *only_dropped)
+        .map(|(variable, _)| variable)
+        .collect()
+}
+
+/// Checks if a branch of a statement leads to a panic, following its fallthroughs & jumps
+/// The branch doesn't just panic if it branches again or returns without panicking
+fn branch_panics(function: &Function, target: &BranchTarget, offset: u32) -> bool {
+    let statements: HashMap<u32, &SierraStatement> = function
+        .statements()
+        .iter()
+        .map(|statement| (statement.offset, statement))
+        .collect();
+
+    let mut next_offset = match target {
+        BranchTarget::Fallthrough => offset + 1,
+        BranchTarget::Statement(statement_idx) => statement_idx.0 as u32,
+    };
+    let mut visited = HashSet::new();
+
+    while visited.insert(next_offset) {
+        let Some(statement) = statements.get(&next_offset) else {
+            return false;
+        };
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            return false;
+        };
+        if matches!(
+            statement.libfunc_kind,
+            LibfuncKind::Panic | LibfuncKind::PanicResultErr
+        ) {
+            return true;
+        }
+
+        let [branch] = invocation.branches.as_slice() else {
+            return false;
+        };
+        next_offset = match &branch.target {
+            BranchTarget::Fallthrough => next_offset + 1,
+            BranchTarget::Statement(statement_idx) => statement_idx.0 as u32,
+        };
+    }
+
+    false
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::analysis::access_control::{builtin_variables, propagate_taints};
+use crate::analysis::context::AnalysisContext;
+use crate::analysis::ssa::SsaVariable;
+use crate::decompiler::function::Function;
+use crate::decompiler::libfuncs_patterns::{
+    COMPARISON_FUNCTION_CALL_REGEX, EXECUTION_INFO_DECONSTRUCT_REGEX, TX_INFO_DECONSTRUCT_REGEX,
+};
+use crate::detectors::detector::{Detector, DetectorType, Finding};
+use crate::parse_element_name;
+
+#[derive(Debug)]
+pub struct TxOriginDetector;
+
+impl TxOriginDetector {
+    /// Creates a new `TxOriginDetector` instance
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Detector for TxOriginDetector {
+    /// Returns the id of the detector
+    #[inline]
+    fn id(&self) -> &'static str {
+        "tx_origin"
+    }
+
+    /// Returns the name of the detector
+    #[inline]
+    fn name(&self) -> &'static str {
+        "Tx origin authentication"
+    }
+
+    /// Returns the description of the detector
+    #[inline]
+    fn description(&self) -> &'static str {
+        "Detects the authorization decisions based on the transaction account address instead of the caller address."
+    }
+
+    /// Returns the type of the detector
+    #[inline]
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::SECURITY
+    }
+
+    /// Returns the decisions based on the transaction account address
+    fn detect(&mut self, context: &AnalysisContext) -> String {
+        account_decisions(context)
+            .into_iter()
+            .map(|(function_name, offset, message)| {
+                format!("{}: {} (statement {})\n", function_name, message, offset)
+            })
+            .collect()
+    }
+
+    /// Returns the decisions based on the transaction account address, located at the branches
+    fn findings(&mut self, context: &AnalysisContext) -> Vec<Finding> {
+        let detector_type = self.detector_type();
+        account_decisions(context)
+            .into_iter()
+            .map(|(function, offset, message)| Finding {
+                detector_type,
+                function,
+                offset: Some(offset),
+                message,
+            })
+            .collect()
+    }
+}
+
+/// Addresses a value is derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct AddressTaint {
+    caller: bool,
+    account: bool,
+}
+
+impl AddressTaint {
+    fn union(self, other: Self) -> Self {
+        Self {
+            caller: self.caller || other.caller,
+            account: self.account || other.account,
+        }
+    }
+}
+
+/// Taint of the caller address
+const CALLER: AddressTaint = AddressTaint {
+    caller: true,
+    account: false,
+};
+/// Taint of the transaction account address
+const ACCOUNT: AddressTaint = AddressTaint {
+    caller: false,
+    account: true,
+};
+
+/// Returns the branches on the transaction account address (`get_tx_info().account_contract_address`)
+/// The message depends on the caller address being compared by the same branch or by another one
+/// (function name, statement offset, message)
+fn account_decisions(context: &AnalysisContext) -> Vec<(String, u32, String)> {
+    // The caller address can be used by a function out of scope
+    let decisions: Vec<(String, u32, AddressTaint, bool)> = context
+        .functions()
+        .iter()
+        .flat_map(|function| {
+            let function_name = parse_element_name!(function.function.id);
+            let in_scope = context.is_in_scope(function);
+            address_decisions(function)
+                .into_iter()
+                .map(move |(offset, taint)| (function_name.clone(), offset, taint, in_scope))
+        })
+        .collect();
+
+    let caller_function = decisions
+        .iter()
+        .find(|(_, _, taint, _)| taint.caller)
+        .map(|(function_name, _, _, _)| function_name.clone());
+
+    decisions
+        .into_iter()
+        .filter(|(_, _, taint, in_scope)| taint.account && *in_scope)
+        .map(|(function_name, offset, taint, _)| {
+            let message = if taint.caller {
+                "authorization decision mixing the caller address with the transaction account address".to_string()
+            } else if let Some(caller_function) = &caller_function {
+                format!(
+                    "authorization decision on the transaction account address while {} uses the caller address",
+                    caller_function
+                )
+            } else {
+                "authorization decision on the transaction account address, the contracts called by the account pass it".to_string()
+            };
+            (function_name, offset, message)
+        })
+        .collect()
+}
+
+/// Returns the branches (zero checks, equalities, comparisons & boolean matches) on values derived
+/// from the caller address or from the transaction account address, read in the function
+/// (statement offset, taint)
+fn address_decisions(function: &Function) -> Vec<(u32, AddressTaint)> {
+    let mut decisions = Vec::new();
+
+    let Ok(ssa) = function.ssa() else {
+        return decisions;
+    };
+    let builtins = builtin_variables(function, &ssa);
+    let mut taints: HashMap<SsaVariable, AddressTaint> = HashMap::new();
+
+    // The statements are evaluated until the taints of the loops are stable
+    propagate_taints(
+        function,
+        &ssa,
+        &mut taints,
+        |taints, phi| {
+            let taint = phi
+                .operands
+                .values()
+                .flatten()
+                .fold(AddressTaint::default(), |taint, operand| {
+                    taint.union(taints.get(operand).copied().unwrap_or_default())
+                });
+            update_taint(taints, phi.result, taint)
+        },
+        |taints, statement, ssa_statement| {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                return false;
+            };
+            let libfunc_name = parse_element_name!(invocation.libfunc_id);
+
+            let arguments = ssa_statement
+                .uses
+                .iter()
+                .filter(|variable| !builtins.contains(*variable))
+                .fold(AddressTaint::default(), |taint, variable| {
+                    taint.union(taints.get(variable).copied().unwrap_or_default())
+                });
+
+            // Only the address fields of the infos are tainted, e.g. not the transaction version
+            let results = ssa_statement.definitions.first();
+            let source_field = if libfunc_name == "get_caller_address_syscall" {
+                results
+                    .and_then(|results| results.last())
+                    .map(|result| (*result, CALLER))
+            } else if EXECUTION_INFO_DECONSTRUCT_REGEX.is_match(&libfunc_name) {
+                results
+                    .and_then(|results| results.get(2))
+                    .map(|result| (*result, CALLER))
+            } else if TX_INFO_DECONSTRUCT_REGEX.is_match(&libfunc_name) {
+                results
+                    .and_then(|results| results.get(1))
+                    .map(|result| (*result, ACCOUNT))
+            } else {
+                None
+            };
+
+            if let Some((result, taint)) = source_field {
+                return update_taint(taints, result, taint);
+            }
+
+            let mut changed = false;
+            for result in ssa_statement.definitions.iter().flatten() {
+                if !builtins.contains(result) {
+                    changed |= update_taint(taints, *result, arguments);
+                }
+            }
+            changed
+        },
+    );
+
+    for statement in function.statements() {
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            continue;
+        };
+        let libfunc_name = parse_element_name!(invocation.libfunc_id);
+        if !(statement.libfunc_kind.is_condition()
+            || COMPARISON_FUNCTION_CALL_REGEX.is_match(&libfunc_name))
+        {
+            continue;
+        }
+        let Some(ssa_statement) = ssa.statement(statement.offset) else {
+            continue;
+        };
+
+        let taint = ssa_statement
+            .uses
+            .iter()
+            .filter(|variable| !builtins.contains(*variable))
+            .fold(AddressTaint::default(), |taint, variable| {
+                taint.union(taints.get(variable).copied().unwrap_or_default())
+            });
+        if taint != AddressTaint::default() {
+            decisions.push((statement.offset, taint));
+        }
+    }
+
+    decisions
+}
+
+/// Merges a taint into the taint of a variable, returns true if it changed
+fn update_taint(
+    taints: &mut HashMap<SsaVariable, AddressTaint>,
+    variable: SsaVariable,
+    taint: AddressTaint,
+) -> bool {
+    if taint == AddressTaint::default() {
+        return false;
+    }
+    let current = taints.entry(variable).or_default();
+    let merged = current.union(taint);
+    let changed = merged != *current;
+    *current = merged;
+    changed
+}
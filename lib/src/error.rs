@@ -0,0 +1,65 @@
+use thiserror::Error;
+
+/// Errors of the sierra-analyzer library
+/// They are returned instead of panicking when the analyzed program or contract class is malformed
+#[derive(Debug, Error)]
+pub enum SierraAnalyzerError {
+    /// The content is a compiled contract class, which only contains the CASM bytecode
+    #[error("The file is a compiled contract class (CASM) that doesn't contain the Sierra program, use the contract class (.contract_class.json) instead")]
+    CasmClass,
+    /// The Sierra code can't be parsed
+    #[error("Error parsing Sierra code: {0}")]
+    Parse(String),
+    /// The program registry can't be created (e.g. unknown libfunc or inconsistent types)
+    #[error("Error creating program registry: {0}")]
+    Registry(String),
+    /// A type ID is not declared in the program
+    #[error("Type [{0}] is not declared")]
+    UnknownType(u64),
+    /// A libfunc ID is not in the program registry
+    #[error("Library function [{0}] not found in the registry")]
+    UnknownLibfunc(u64),
+    /// A function is not declared in the program
+    #[error("Function {0} not found")]
+    FunctionNotFound(String),
+    /// A statement offset doesn't fit the offsets type
+    #[error("Invalid statement offset {0}")]
+    InvalidOffset(usize),
+    /// A function is missing an information computed by a previous step
+    #[error("{0} of function {1} is not set")]
+    MissingFunctionInfo(&'static str, String),
+    /// The RPC request failed
+    #[cfg(feature = "remote")]
+    #[error("Error calling RPC: {0}")]
+    Rpc(#[from] reqwest::Error),
+    /// The RPC node returned a JSON-RPC error (e.g. an unknown contract or transaction)
+    #[error("RPC node error: {0}")]
+    RpcNode(String),
+    /// A JSON document can't be (de)serialized
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A field is missing in a contract class
+    #[error("Missing {0} field")]
+    MissingField(&'static str),
+    /// A class is not available in a local provider
+    #[error("Class {0} not found")]
+    ClassNotFound(String),
+    /// A local provider can't be read
+    #[error("Error reading the local provider: {0}")]
+    LocalProvider(String),
+    /// The class hash can't be computed or parsed
+    #[error("Invalid class hash: {0}")]
+    ClassHash(String),
+    /// A search pattern is not a valid regex
+    #[error("Invalid regex: {0}")]
+    Regex(#[from] regex::Error),
+    /// The concrete execution of a program failed (e.g. CASM compilation or unsupported parameters)
+    #[error("Error running the program: {0}")]
+    Runner(String),
+    /// A file can't be read
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type of the sierra-analyzer library
+pub type Result<T> = std::result::Result<T, SierraAnalyzerError>;
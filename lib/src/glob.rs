@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::error::Result;
+
+/// Returns the files matching a glob pattern (e.g. `contracts/**/*.contract_class.json`), sorted by path
+/// `*` & `?` match inside a path component and `**` matches any number of directories.
+/// A pattern without wildcards is a file, or a directory whose Sierra programs (`.sierra`)
+/// & contract classes (`.contract_class.json`) are collected recursively
+pub fn glob_files(pattern: &str) -> Result<Vec<PathBuf>> {
+    let (base, relative_pattern) = split_pattern(pattern);
+    if relative_pattern.is_empty() && !base.is_dir() {
+        return Ok(vec![base]);
+    }
+
+    let regex = (!relative_pattern.is_empty()).then(|| glob_regex(&relative_pattern));
+    let mut files = Vec::new();
+    collect_files(&base, &base, regex.as_ref(), &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
+
+/// Splits a glob pattern into its base directory, the components before the first wildcard,
+/// and the pattern of the paths relative to it
+fn split_pattern(pattern: &str) -> (PathBuf, String) {
+    let components: Vec<&str> = pattern.split('/').collect();
+    let wildcard_index = components
+        .iter()
+        .position(|component| component.contains(['*', '?']))
+        .unwrap_or(components.len());
+
+    let base = match components[..wildcard_index].join("/").as_str() {
+        "" if pattern.starts_with('/') => PathBuf::from("/"),
+        "" => PathBuf::from("."),
+        base => PathBuf::from(base),
+    };
+
+    (base, components[wildcard_index..].join("/"))
+}
+
+/// Converts the relative part of a glob pattern into an anchored regex
+fn glob_regex(pattern: &str) -> Regex {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches the files of the directory itself
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    // The literal characters are escaped, the regex is always valid
+    Regex::new(&regex).unwrap()
+}
+
+/// Recursively collects the files of a directory matching the regex of their relative path
+/// Without regex, the Sierra programs & contract classes are collected
+fn collect_files(
+    root: &Path,
+    directory: &Path,
+    regex: Option<&Regex>,
+    files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for dir_entry in fs::read_dir(directory)? {
+        let path = dir_entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, regex, files)?;
+            continue;
+        }
+
+        let is_matching = match regex {
+            Some(regex) => {
+                let relative_path = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .iter()
+                    .map(|component| component.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                regex.is_match(&relative_path)
+            }
+            None => {
+                let file_name = path
+                    .file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .unwrap_or_default();
+                file_name.ends_with(".sierra") || file_name.ends_with(".contract_class.json")
+            }
+        };
+        if is_matching {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -1,64 +1,601 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+
 use cairo_lang_sierra::program::GenStatement;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{Bfs, EdgeRef, Reversed};
+use serde::Serialize;
 
 use crate::config::GraphConfig;
 use crate::decompiler::function::Function;
 use crate::decompiler::libfuncs_patterns::{
     IRRELEVANT_CALLGRAPH_FUNCTIONS_REGEXES, USER_DEFINED_FUNCTION_REGEX,
 };
+use crate::detectors::detector::Finding;
+use crate::graph::graph::generate_findings_node;
+use crate::graph::graphml::GraphMlWriter;
 use crate::parse_element_name;
 
-/// Generates the callgraph dotgraph from a vector of Function objects
-pub fn process_callgraph(functions: &[Function]) -> String {
-    let mut dot = String::from("strict digraph G {\n");
+/// Kind of a callgraph edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallKind {
+    /// Call to a user-defined function
+    UserDefined,
+    /// Call to a libfunc
+    Libfunc,
+}
 
-    // Global Graph configuration
-    dot.push_str(&generate_graph_config());
+/// Returns the functions & libfuncs called by a function, in the order they are invoked
+/// Irrelevant libfuncs (drop, store_temp, dup...) are skipped
+fn function_calls(function: &Function) -> Vec<(String, CallKind)> {
+    let mut calls = Vec::new();
+
+    for statement in function.statements() {
+        if let GenStatement::Invocation(statement) = &statement.statement {
+            let called_function = parse_element_name!(&statement.libfunc_id);
+
+            // User-defined function call
+            if let Some(captures) = USER_DEFINED_FUNCTION_REGEX.captures(&called_function) {
+                if let Some(matched_group) = captures.name("function_id") {
+                    calls.push((matched_group.as_str().to_string(), CallKind::UserDefined));
+                }
+            }
+            // Libfunc call
+            else {
+                // Skip irrelevant functions
+                if IRRELEVANT_CALLGRAPH_FUNCTIONS_REGEXES
+                    .iter()
+                    .any(|regex| regex.is_match(&called_function))
+                {
+                    continue;
+                }
+
+                calls.push((called_function, CallKind::Libfunc));
+            }
+        }
+    }
 
-    // Node attributes
-    dot.push_str(&generate_node_attributes());
+    calls
+}
 
-    // Edge attributes
-    dot.push_str(&generate_edge_attributes());
+/// Returns the callgraph edges (caller, callee, kind) from a vector of Function objects
+/// Each edge is only returned once, in the order it is first encountered
+#[inline]
+pub fn callgraph_edges(functions: &[Function]) -> Vec<(String, String, CallKind)> {
+    CallGraph::new(functions).edges()
+}
 
-    for function in functions {
-        let function_name = format!("{}", parse_element_name!(function.function.id));
+impl CallKind {
+    /// Returns the name of the call kind, as in the JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallKind::UserDefined => "user_defined",
+            CallKind::Libfunc => "libfunc",
+        }
+    }
+}
 
-        // Constructing the node entry for DOT format
-        dot.push_str(&generate_function_node(&function_name));
+/// A cycle of user-defined functions calling each other
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecursionCycle {
+    /// Functions of the cycle in the order they call each other, the last one calls the first one
+    /// The cycle starts with its first function in the program order
+    pub functions: Vec<String>,
+}
 
-        for statement in &function.statements {
-            if let GenStatement::Invocation(statement) = &statement.statement {
-                let called_function = parse_element_name!(&statement.libfunc_id);
+impl RecursionCycle {
+    /// Returns true if the cycle is a function calling itself
+    #[inline]
+    pub fn is_direct(&self) -> bool {
+        self.functions.len() == 1
+    }
 
-                // Add user-defined function to the callgraph
-                if let Some(captures) = USER_DEFINED_FUNCTION_REGEX.captures(&called_function) {
-                    if let Some(matched_group) = captures.name("function_id") {
-                        let called_function_name = format!("{}", matched_group.as_str());
-                        dot.push_str(&generate_user_defined_function_node(&called_function_name));
-                        dot.push_str(&generate_edge(&function_name, &called_function_name));
-                    }
+    /// Returns the path of the cycle, e.g. `is_even -> is_odd -> is_even`
+    pub fn path(&self) -> String {
+        self.functions
+            .iter()
+            .chain(self.functions.first())
+            .map(String::as_str)
+            .collect::<Vec<&str>>()
+            .join(" -> ")
+    }
+}
+
+/// Maximum depth of the nested user-defined functions calls of a function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallDepth {
+    /// Maximum number of nested calls, 0 if the function doesn't call any user-defined function
+    Bounded(usize),
+    /// The function reaches a recursion cycle, the depth depends on its arguments
+    Unbounded,
+}
+
+impl fmt::Display for CallDepth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CallDepth::Bounded(depth) => write!(f, "{}", depth),
+            CallDepth::Unbounded => write!(f, "unbounded"),
+        }
+    }
+}
+
+/// A node of the callgraph
+#[derive(Debug, Clone)]
+pub struct CallGraphNode {
+    /// Name of the function or libfunc
+    pub name: String,
+    /// A node is either a user-defined function or a libfunc
+    pub kind: CallKind,
+}
+
+/// Callgraph of a program, used to run graph algorithms on it
+/// The DOT & JSON representations of the callgraph are derived from it, their nodes are the
+/// program functions by entry offset followed by the callees in the order they are first called
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// The underlying directed graph
+    /// There is one edge per call site, in the order the calls are invoked
+    pub graph: DiGraph<CallGraphNode, CallKind>,
+    /// Index of each node in the graph, by name
+    nodes_indexes: HashMap<String, NodeIndex>,
+    /// Number of program functions, they are the first nodes of the graph
+    functions_count: usize,
+    /// Group the functions of the DOT output in a cluster per module path
+    modules_clusters: bool,
+}
+
+impl CallGraph {
+    /// Builds the callgraph from a vector of Function objects
+    pub fn new(functions: &[Function]) -> Self {
+        let mut callgraph = Self::default();
+
+        // Add the program functions first so they keep the program order
+        for function in functions {
+            callgraph.add_node(
+                &parse_element_name!(function.function.id),
+                CallKind::UserDefined,
+            );
+        }
+        callgraph.functions_count = callgraph.graph.node_count();
+
+        for function in functions {
+            let caller_index = callgraph
+                .node_index(&parse_element_name!(function.function.id))
+                .unwrap();
+
+            for (called_function_name, kind) in function_calls(function) {
+                let callee_index = callgraph.add_node(&called_function_name, kind);
+                callgraph.graph.add_edge(caller_index, callee_index, kind);
+            }
+        }
+
+        callgraph
+    }
+
+    /// Groups the functions of the DOT output in a cluster per module path
+    pub fn with_modules_clusters(mut self, modules_clusters: bool) -> Self {
+        self.modules_clusters = modules_clusters;
+        self
+    }
+
+    /// Returns the indexes of the program functions nodes, in the program order
+    pub fn functions_indexes(&self) -> impl Iterator<Item = NodeIndex> {
+        (0..self.functions_count).map(NodeIndex::new)
+    }
+
+    /// Returns the edges (caller, callee, kind) of the graph
+    /// Each edge is only returned once, in the order it is first encountered
+    pub fn edges(&self) -> Vec<(String, String, CallKind)> {
+        let mut edges = Vec::new();
+        let mut seen_edges = HashSet::new();
+
+        for edge in self.graph.edge_references() {
+            let edge = (
+                self.graph[edge.source()].name.clone(),
+                self.graph[edge.target()].name.clone(),
+                *edge.weight(),
+            );
+            if seen_edges.insert(edge.clone()) {
+                edges.push(edge);
+            }
+        }
+
+        edges
+    }
+
+    /// Returns the outgoing edges of each node, in the order the calls are invoked
+    fn outgoing_edges(&self) -> Vec<Vec<(NodeIndex, CallKind)>> {
+        let mut outgoing_edges = vec![Vec::new(); self.graph.node_count()];
+
+        for edge in self.graph.edge_references() {
+            outgoing_edges[edge.source().index()].push((edge.target(), *edge.weight()));
+        }
+
+        outgoing_edges
+    }
+
+    /// Adds a node to the graph if it doesn't exist yet and returns its index
+    fn add_node(&mut self, name: &str, kind: CallKind) -> NodeIndex {
+        if let Some(index) = self.nodes_indexes.get(name) {
+            return *index;
+        }
+
+        let index = self.graph.add_node(CallGraphNode {
+            name: name.to_string(),
+            kind,
+        });
+        self.nodes_indexes.insert(name.to_string(), index);
+        index
+    }
+
+    /// Returns the index of a node using its name
+    #[inline]
+    pub fn node_index(&self, name: &str) -> Option<NodeIndex> {
+        self.nodes_indexes.get(name).copied()
+    }
+
+    /// Returns the names of all the nodes reachable from the given roots (roots included)
+    pub fn reachable_from(&self, roots: &[String]) -> HashSet<String> {
+        let mut reachable = HashSet::new();
+
+        for root in roots {
+            if let Some(root_index) = self.node_index(root) {
+                let mut bfs = Bfs::new(&self.graph, root_index);
+                while let Some(index) = bfs.next(&self.graph) {
+                    reachable.insert(self.graph[index].name.clone());
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Returns the names of all the direct & indirect callers of a function (function included)
+    pub fn callers(&self, name: &str) -> HashSet<String> {
+        let mut callers = HashSet::new();
+
+        if let Some(index) = self.node_index(name) {
+            let reversed_graph = Reversed(&self.graph);
+            let mut bfs = Bfs::new(reversed_graph, index);
+            while let Some(caller_index) = bfs.next(reversed_graph) {
+                callers.insert(self.graph[caller_index].name.clone());
+            }
+        }
+
+        callers
+    }
+
+    /// Returns the recursion cycles of the functions, in the program order of their first function
+    /// There is one cycle per group of mutually recursive functions, the shortest one going
+    /// through its first function
+    pub fn recursion_cycles(&self) -> Vec<RecursionCycle> {
+        self.recursive_components()
+            .iter()
+            .filter_map(|component| self.component_cycle(component))
+            .collect()
+    }
+
+    /// Returns the maximum depth of the user-defined functions calls of a function,
+    /// None if the function isn't in the callgraph
+    pub fn max_call_depth(&self, name: &str) -> Option<CallDepth> {
+        let index = self.node_index(name)?;
+        let recursive_functions: HashSet<NodeIndex> =
+            self.recursive_components().into_iter().flatten().collect();
+
+        Some(self.call_depth(index, &recursive_functions, &mut HashMap::new()))
+    }
+
+    /// Returns the strongly connected components containing a cycle of user-defined calls
+    /// The nodes of each component are sorted and the components are in the program order
+    fn recursive_components(&self) -> Vec<Vec<NodeIndex>> {
+        let mut components: Vec<Vec<NodeIndex>> = tarjan_scc(&self.graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.graph.contains_edge(component[0], component[0])
+            })
+            .collect();
+        for component in &mut components {
+            component.sort();
+        }
+        components.sort();
+
+        components
+    }
+
+    /// Returns the shortest cycle of a recursive component going through its first node
+    fn component_cycle(&self, component: &[NodeIndex]) -> Option<RecursionCycle> {
+        let start = *component.first()?;
+        let mut predecessors: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(index) = queue.pop_front() {
+            for edge in self.graph.edges(index) {
+                let callee = edge.target();
+                if *edge.weight() != CallKind::UserDefined || !component.contains(&callee) {
+                    continue;
                 }
-                // Add libfuncs to the callgraph
-                else {
-                    let called_function_name = format!("{}\t\t", called_function.as_str());
-
-                    // Skip irrelevant functions
-                    if IRRELEVANT_CALLGRAPH_FUNCTIONS_REGEXES
-                        .iter()
-                        .any(|regex| regex.is_match(&called_function_name))
-                    {
-                        continue;
+
+                // The cycle is closed, walk it back to its first node
+                if callee == start {
+                    let mut functions = vec![self.graph[index].name.clone()];
+                    let mut current = index;
+                    while current != start {
+                        current = predecessors[&current];
+                        functions.push(self.graph[current].name.clone());
                     }
+                    functions.reverse();
+
+                    return Some(RecursionCycle { functions });
+                }
 
-                    dot.push_str(&generate_libfunc_node(&called_function_name));
-                    dot.push_str(&generate_edge(&function_name, &called_function_name));
+                if let Entry::Vacant(entry) = predecessors.entry(callee) {
+                    entry.insert(index);
+                    queue.push_back(callee);
                 }
             }
         }
+
+        None
+    }
+
+    /// Returns the maximum calls depth of a node, the depths of the visited nodes are memoized
+    fn call_depth(
+        &self,
+        index: NodeIndex,
+        recursive_functions: &HashSet<NodeIndex>,
+        depths: &mut HashMap<NodeIndex, CallDepth>,
+    ) -> CallDepth {
+        if recursive_functions.contains(&index) {
+            return CallDepth::Unbounded;
+        }
+        if let Some(depth) = depths.get(&index) {
+            return *depth;
+        }
+
+        // The nodes that are not part of a cycle are visited once, the recursion terminates
+        let mut depth = CallDepth::Bounded(0);
+        for edge in self.graph.edges(index) {
+            if *edge.weight() != CallKind::UserDefined {
+                continue;
+            }
+            let callee_depth = self.call_depth(edge.target(), recursive_functions, depths);
+            depth = match (depth, callee_depth) {
+                (CallDepth::Bounded(current_depth), CallDepth::Bounded(callee_depth)) => {
+                    CallDepth::Bounded(current_depth.max(callee_depth + 1))
+                }
+                _ => CallDepth::Unbounded,
+            };
+        }
+        depths.insert(index, depth);
+
+        depth
     }
+}
 
-    dot.push_str("}\n");
-    dot
+/// Serializable representation of a callgraph edge
+#[derive(Debug, Serialize)]
+struct JsonCallgraphEdge<'a> {
+    caller: &'a str,
+    callee: &'a str,
+    kind: CallKind,
+}
+
+/// Serializable representation of a callgraph node and its adjacency list
+#[derive(Debug, Serialize)]
+struct JsonCallgraphNode<'a> {
+    name: &'a str,
+    kind: CallKind,
+    callees: Vec<&'a str>,
+}
+
+/// Serializable representation of the callgraph
+#[derive(Debug, Serialize)]
+struct JsonCallgraph<'a> {
+    nodes: Vec<JsonCallgraphNode<'a>>,
+    edges: Vec<JsonCallgraphEdge<'a>>,
+}
+
+/// Generates the callgraph in JSON format from a vector of Function objects
+/// The output contains the list of nodes (with their adjacency lists) and the list of edges
+/// If a filter is given, only the nodes it contains are kept
+#[inline]
+pub fn process_callgraph_json(functions: &[Function], filter: Option<&HashSet<String>>) -> String {
+    CallGraph::new(functions).to_json(filter)
+}
+
+/// Generates the callgraph dotgraph from a vector of Function objects
+/// If a filter is given, only the nodes it contains are kept
+#[inline]
+pub fn process_callgraph(functions: &[Function], filter: Option<&HashSet<String>>) -> String {
+    CallGraph::new(functions).to_dot(filter)
+}
+
+impl CallGraph {
+    /// Generates the callgraph in JSON format
+    /// The output contains the list of nodes (with their adjacency lists) and the list of edges
+    /// If a filter is given, only the nodes it contains are kept
+    pub fn to_json(&self, filter: Option<&HashSet<String>>) -> String {
+        let is_retained = |name: &str| filter.map_or(true, |filter| filter.contains(name));
+
+        let edges: Vec<(String, String, CallKind)> = self
+            .edges()
+            .into_iter()
+            .filter(|(caller, callee, _)| is_retained(caller) && is_retained(callee))
+            .collect();
+        let functions_names: Vec<&str> = self
+            .functions_indexes()
+            .map(|index| self.graph[index].name.as_str())
+            .filter(|name| is_retained(name))
+            .collect();
+
+        // Nodes are the program functions followed by the called libfuncs & functions
+        let mut nodes: Vec<JsonCallgraphNode> = functions_names
+            .iter()
+            .map(|name| JsonCallgraphNode {
+                name,
+                kind: CallKind::UserDefined,
+                callees: Vec::new(),
+            })
+            .collect();
+
+        for (caller, callee, kind) in &edges {
+            if !nodes.iter().any(|node| node.name == callee) {
+                nodes.push(JsonCallgraphNode {
+                    name: callee,
+                    kind: *kind,
+                    callees: Vec::new(),
+                });
+            }
+            if let Some(node) = nodes.iter_mut().find(|node| node.name == caller) {
+                node.callees.push(callee);
+            }
+        }
+
+        let callgraph = JsonCallgraph {
+            nodes,
+            edges: edges
+                .iter()
+                .map(|(caller, callee, kind)| JsonCallgraphEdge {
+                    caller,
+                    callee,
+                    kind: *kind,
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&callgraph)
+            .unwrap_or_else(|e| format!("Error serializing callgraph: {}", e))
+    }
+
+    /// Generates the callgraph in GraphML format, for Gephi, yEd or Cytoscape
+    /// If a filter is given, only the nodes it contains are kept
+    pub fn to_graphml(&self, filter: Option<&HashSet<String>>) -> String {
+        let is_retained = |name: &str| filter.map_or(true, |filter| filter.contains(name));
+        let mut writer = GraphMlWriter::new(&["label", "kind"], &["kind"]);
+
+        // Nodes are the program functions followed by the called libfuncs & functions
+        let edges: Vec<(String, String, CallKind)> = self
+            .edges()
+            .into_iter()
+            .filter(|(caller, callee, _)| is_retained(caller) && is_retained(callee))
+            .collect();
+        let callees: HashSet<&str> = edges.iter().map(|(_, callee, _)| callee.as_str()).collect();
+        for index in self.graph.node_indices() {
+            let node = &self.graph[index];
+            let is_function = index.index() < self.functions_count;
+            if (is_function && is_retained(&node.name))
+                || (!is_function && callees.contains(node.name.as_str()))
+            {
+                writer.add_node(
+                    &node.name,
+                    &[("label", node.name.as_str()), ("kind", node.kind.as_str())],
+                );
+            }
+        }
+
+        for (caller, callee, kind) in &edges {
+            writer.add_edge(caller, callee, &[("kind", kind.as_str())]);
+        }
+
+        writer.finish("callgraph")
+    }
+
+    /// Generates the callgraph dotgraph
+    /// If a filter is given, only the nodes it contains are kept
+    #[inline]
+    pub fn to_dot(&self, filter: Option<&HashSet<String>>) -> String {
+        self.to_dot_with_findings(filter, &[])
+    }
+
+    /// Generates the callgraph dotgraph, the functions containing a detector finding are highlighted
+    /// If a filter is given, only the nodes it contains are kept
+    pub fn to_dot_with_findings(
+        &self,
+        filter: Option<&HashSet<String>>,
+        findings: &[Finding],
+    ) -> String {
+        let is_retained = |name: &str| filter.map_or(true, |filter| filter.contains(name));
+        let outgoing_edges = self.outgoing_edges();
+
+        let mut dot = String::from("strict digraph G {\n");
+
+        // Global Graph configuration
+        dot.push_str(&generate_graph_config());
+
+        // Node attributes
+        dot.push_str(&generate_node_attributes());
+
+        // Edge attributes
+        dot.push_str(&generate_edge_attributes());
+
+        // Program functions clusters, by module path
+        let functions_names: Vec<&str> = self
+            .functions_indexes()
+            .map(|index| self.graph[index].name.as_str())
+            .filter(|name| is_retained(name))
+            .collect();
+        if self.modules_clusters {
+            dot.push_str(&generate_modules_clusters(&functions_names));
+        }
+
+        for function_index in self.functions_indexes() {
+            let function_name = &self.graph[function_index].name;
+
+            // Skip the filtered out functions
+            if !is_retained(function_name) {
+                continue;
+            }
+
+            // Constructing the node entry for DOT format
+            dot.push_str(&generate_function_node(function_name));
+
+            for (called_function_index, kind) in &outgoing_edges[function_index.index()] {
+                let called_function_name = &self.graph[*called_function_index].name;
+
+                // Skip the filtered out called functions
+                if !is_retained(called_function_name) {
+                    continue;
+                }
+
+                match kind {
+                    // Add user-defined function to the callgraph
+                    CallKind::UserDefined => {
+                        dot.push_str(&generate_user_defined_function_node(called_function_name));
+                        dot.push_str(&generate_edge(function_name, called_function_name));
+                    }
+                    // Add libfuncs to the callgraph
+                    CallKind::Libfunc => {
+                        let called_function_name = format!("{}\t\t", called_function_name);
+                        dot.push_str(&generate_libfunc_node(&called_function_name));
+                        dot.push_str(&generate_edge(function_name, &called_function_name));
+                    }
+                }
+            }
+        }
+
+        // Highlight the functions containing a finding
+        for function_name in &functions_names {
+            let function_findings: Vec<&Finding> = findings
+                .iter()
+                .filter(|finding| finding.function == *function_name)
+                .collect();
+            if !function_findings.is_empty() {
+                dot.push_str(&format!(
+                    "   {}",
+                    generate_findings_node(function_name, &function_findings)
+                ));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
 }
 
 /// Generates the graph configuration for the DOT format
@@ -96,6 +633,81 @@ fn generate_edge_attributes() -> String {
     )
 }
 
+/// Generates a DOT cluster per module path containing the module functions
+/// Each cluster gets its own background color
+fn generate_modules_clusters(functions_names: &[&str]) -> String {
+    // Modules in the order of their first function
+    let mut modules: Vec<(&str, Vec<&str>)> = Vec::new();
+    for function_name in functions_names {
+        let Some(module) = module_path(function_name) else {
+            continue;
+        };
+        match modules.iter_mut().find(|(name, _)| *name == module) {
+            Some((_, functions)) => functions.push(function_name),
+            None => modules.push((module, vec![function_name])),
+        }
+    }
+
+    let mut clusters = String::new();
+    for (index, (module, functions)) in modules.iter().enumerate() {
+        let color = GraphConfig::CALLGRAPH_CLUSTERS_COLORS
+            [index % GraphConfig::CALLGRAPH_CLUSTERS_COLORS.len()];
+        clusters.push_str(&format!(
+            "   subgraph \"cluster_{}\" {{\n      label=\"{}\";\n      style=\"filled\";\n      fillcolor=\"{}\";\n",
+            module, module, color
+        ));
+        for function in functions {
+            clusters.push_str(&format!("      \"{}\";\n", function));
+        }
+        clusters.push_str("   }\n");
+    }
+
+    clusters
+}
+
+/// Returns the module path of a function, e.g. `contracts::erc20` for `contracts::erc20::transfer`
+/// The generic arguments & the impls names (CamelCase) are not part of the module path,
+/// e.g. `erc20::erc20` for `erc20::erc20::ERC20Impl::<ContractState>::transfer`
+pub fn module_path(function_name: &str) -> Option<&str> {
+    // Top-level segments (end offsets), the `::` of the generic arguments are ignored
+    let mut segments: Vec<(usize, usize)> = Vec::new();
+    let mut depth = 0;
+    let mut segment_start = 0;
+    for (index, c) in function_name.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ':' if depth == 0 && function_name[index..].starts_with("::") => {
+                segments.push((segment_start, index));
+                segment_start = index + 2;
+            }
+            _ => {}
+        }
+    }
+    segments.push((segment_start, function_name.len()));
+
+    // The last segment is the function name
+    let segments: Vec<&(usize, usize)> = segments
+        .iter()
+        .filter(|(start, _)| !function_name[*start..].starts_with('<'))
+        .collect();
+    let (_, module_segments) = segments.split_last()?;
+
+    // Modules names are snake_case
+    let module_end = module_segments
+        .iter()
+        .take_while(|(start, end)| {
+            function_name[*start..*end]
+                .chars()
+                .next()
+                .map_or(false, |c| c.is_ascii_lowercase() || c == '_')
+        })
+        .last()
+        .map(|(_, end)| *end)?;
+
+    Some(&function_name[..module_end])
+}
+
 /// Generates a function node for the DOT format
 fn generate_function_node(function_name: &str) -> String {
     format!(
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use cairo_lang_sierra::program::GenStatement;
+use num_bigint::BigUint;
+use serde::Serialize;
+
+use crate::config::GraphConfig;
+use crate::decompiler::function::Function;
+use crate::decompiler::function::FunctionType;
+use crate::decompiler::libfuncs_patterns::CLASS_HASH_CONST_REGEXES;
+use crate::graph::callgraph::callgraph_edges;
+use crate::graph::callgraph::CallKind;
+use crate::parse_element_name;
+
+/// Kind of an inter-contract call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossContractCallKind {
+    /// Call through a contract dispatcher (call_contract_syscall)
+    ContractCall,
+    /// Call through a library dispatcher (library_call_syscall)
+    LibraryCall,
+}
+
+/// A contract of the analyzed batch
+#[derive(Debug, Clone)]
+pub struct CrossContract<'a> {
+    /// Name used to identify the contract in the graph (usually the file stem)
+    pub name: String,
+    /// Class hash of the contract, used to resolve the library calls to a known class
+    pub class_hash: Option<BigUint>,
+    /// Functions of the contract, with their types already set by the decompiler
+    pub functions: &'a [Function<'a>],
+}
+
+impl<'a> CrossContract<'a> {
+    /// Creates a new `CrossContract` instance
+    pub fn new(name: &str, class_hash: Option<BigUint>, functions: &'a [Function<'a>]) -> Self {
+        Self {
+            name: name.to_string(),
+            class_hash,
+            functions,
+        }
+    }
+
+    /// Returns the entry points of the contract, indexed by their ABI name
+    /// The user-defined External & View functions are preferred to the compiler wrappers
+    fn entry_points(&self) -> HashMap<String, String> {
+        let mut entry_points = HashMap::new();
+
+        for function in self.functions {
+            let function_name = parse_element_name!(function.function.id);
+
+            match function.function_type {
+                Some(FunctionType::Wrapper) => {
+                    if let Some(entry_point_name) = wrapper_entry_point_name(&function_name) {
+                        entry_points
+                            .entry(entry_point_name)
+                            .or_insert(function_name);
+                    }
+                }
+                Some(
+                    FunctionType::External
+                    | FunctionType::View
+                    | FunctionType::Constructor
+                    | FunctionType::L1Handler,
+                ) => {
+                    if let Some((_, entry_point_name)) = function_name.rsplit_once("::") {
+                        entry_points.insert(entry_point_name.to_string(), function_name.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        entry_points
+    }
+
+    /// Returns the class hashes hardcoded in the contract (class_hash_const libfuncs)
+    fn class_hashes_constants(&self) -> HashSet<BigUint> {
+        let mut class_hashes = HashSet::new();
+
+        for function in self.functions {
+            for statement in function.statements() {
+                if let GenStatement::Invocation(invocation) = &statement.statement {
+                    let libfunc_name = parse_element_name!(&invocation.libfunc_id);
+                    for regex in CLASS_HASH_CONST_REGEXES.iter() {
+                        if let Some(class_hash) = regex
+                            .captures(&libfunc_name)
+                            .and_then(|captures| captures.name("class_hash"))
+                            .and_then(|class_hash| class_hash.as_str().parse::<BigUint>().ok())
+                        {
+                            class_hashes.insert(class_hash);
+                        }
+                    }
+                }
+            }
+        }
+
+        class_hashes
+    }
+}
+
+/// Returns the ABI name of the entry point wrapped by a compiler-generated wrapper
+/// e.g. `erc_20::__wrapper__IERC20Impl__transfer_from` or `erc_20::__external::transfer_from`
+/// The generic arguments of the components wrappers are ignored (`ownable::__wrapper__Transfer__owner::<...>`)
+pub(crate) fn wrapper_entry_point_name(function_name: &str) -> Option<String> {
+    let function_name = function_name
+        .split_once("::<")
+        .map_or(function_name, |(function_name, _)| function_name);
+    let (_, last_segment) = function_name.rsplit_once("::")?;
+
+    match last_segment.strip_prefix("__wrapper__") {
+        // The wrapper name is __wrapper__<Impl>__<entry point> or __wrapper__<entry point>
+        Some(wrapped_name) => Some(
+            wrapped_name
+                .split_once("__")
+                .map_or(wrapped_name, |(_, entry_point_name)| entry_point_name)
+                .to_string(),
+        ),
+        None => Some(last_segment.to_string()),
+    }
+}
+
+/// An edge between two functions of different contracts
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct CrossContractEdge {
+    /// Name of the calling contract
+    pub caller_contract: String,
+    /// Dispatcher function performing the call
+    pub caller: String,
+    /// Name of the called contract
+    pub callee_contract: String,
+    /// Called entry point
+    pub callee: String,
+    /// Contract call or library call
+    pub kind: CrossContractCallKind,
+}
+
+/// Returns the inter-contract edges of a batch of contracts
+/// Dispatcher calls are linked to the entry points of the other contracts sharing the same name.
+/// Library calls are restricted to the classes whose hash is hardcoded in the caller, if any
+pub fn cross_contract_edges(contracts: &[CrossContract]) -> Vec<CrossContractEdge> {
+    let mut edges = Vec::new();
+
+    let entry_points: Vec<HashMap<String, String>> = contracts
+        .iter()
+        .map(|contract| contract.entry_points())
+        .collect();
+
+    for (caller_index, caller_contract) in contracts.iter().enumerate() {
+        // Known classes the contract library calls are resolved to
+        let class_hashes = caller_contract.class_hashes_constants();
+        let known_classes: Vec<usize> = contracts
+            .iter()
+            .enumerate()
+            .filter(|(_, contract)| {
+                contract
+                    .class_hash
+                    .as_ref()
+                    .map_or(false, |class_hash| class_hashes.contains(class_hash))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        for function in caller_contract.functions {
+            let kind = match function.function_type {
+                Some(FunctionType::AbiCallContract) => CrossContractCallKind::ContractCall,
+                Some(FunctionType::AbiLibraryCall) => CrossContractCallKind::LibraryCall,
+                _ => continue,
+            };
+
+            let function_name = parse_element_name!(function.function.id);
+            let Some((_, entry_point_name)) = function_name.rsplit_once("::") else {
+                continue;
+            };
+
+            for (callee_index, callee_contract) in contracts.iter().enumerate() {
+                // Skip the calls to the contract itself
+                if callee_index == caller_index {
+                    continue;
+                }
+
+                // Skip the classes that are not hardcoded in the caller
+                if kind == CrossContractCallKind::LibraryCall
+                    && !known_classes.is_empty()
+                    && !known_classes.contains(&callee_index)
+                {
+                    continue;
+                }
+
+                if let Some(callee) = entry_points[callee_index].get(entry_point_name) {
+                    edges.push(CrossContractEdge {
+                        caller_contract: caller_contract.name.clone(),
+                        caller: function_name.clone(),
+                        callee_contract: callee_contract.name.clone(),
+                        callee: callee.clone(),
+                        kind,
+                    });
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// Serializable representation of a contract of the batch
+#[derive(Debug, Serialize)]
+struct JsonCrossContract<'a> {
+    name: &'a str,
+    class_hash: Option<String>,
+    entry_points: Vec<String>,
+}
+
+/// Serializable representation of the cross-contract callgraph
+#[derive(Debug, Serialize)]
+struct JsonCrossContractCallgraph<'a> {
+    contracts: Vec<JsonCrossContract<'a>>,
+    edges: Vec<CrossContractEdge>,
+}
+
+/// Generates the cross-contract callgraph in JSON format
+/// The output contains the list of contracts (with their entry points) and the inter-contract edges
+pub fn process_cross_contract_callgraph_json(contracts: &[CrossContract]) -> String {
+    let json_contracts = contracts
+        .iter()
+        .map(|contract| {
+            let mut entry_points: Vec<String> = contract.entry_points().into_values().collect();
+            entry_points.sort();
+
+            JsonCrossContract {
+                name: &contract.name,
+                class_hash: contract
+                    .class_hash
+                    .as_ref()
+                    .map(|class_hash| format!("0x{:x}", class_hash)),
+                entry_points,
+            }
+        })
+        .collect();
+
+    let callgraph = JsonCrossContractCallgraph {
+        contracts: json_contracts,
+        edges: cross_contract_edges(contracts),
+    };
+
+    serde_json::to_string_pretty(&callgraph)
+        .unwrap_or_else(|e| format!("Error serializing cross-contract callgraph: {}", e))
+}
+
+/// Generates the cross-contract callgraph dotgraph
+/// Each contract is a cluster containing its user-defined functions calls,
+/// the inter-contract calls are the edges between the clusters
+pub fn process_cross_contract_callgraph(contracts: &[CrossContract]) -> String {
+    let mut dot = String::from("strict digraph G {\n");
+
+    // Global Graph configuration
+    dot.push_str(&format!(
+        "    graph [fontname=\"{}\", fontsize={}, layout=\"{}\", rankdir=\"{}\", newrank={}, compound=true];\n",
+        GraphConfig::CALLGRAPH_GRAPH_ATTR_FONTNAME,
+        GraphConfig::CALLGRAPH_GRAPH_ATTR_FONTSIZE,
+        GraphConfig::CALLGRAPH_GRAPH_ATTR_LAYOUT,
+        GraphConfig::CALLGRAPH_GRAPH_ATTR_RANKDIR,
+        GraphConfig::CALLGRAPH_GRAPH_ATTR_NEWRANK,
+    ));
+
+    // Node attributes
+    dot.push_str(&format!(
+        "    node [style=\"{}\", shape=\"rectangle\", fillcolor=\"{}\", pencolor=\"{}\", margin=\"0.5,0.1\", fontname=\"{}\"];\n",
+        GraphConfig::CALLGRAPH_NODE_ATTR_STYLE,
+        GraphConfig::CALLGRAPH_USER_DEFINED_FUNCTIONS_COLOR,
+        GraphConfig::CALLGRAPH_NODE_ATTR_PENCOLOR,
+        GraphConfig::CALLGRAPH_NODE_ATTR_FONTNAME,
+    ));
+
+    // Edge attributes
+    dot.push_str(&format!(
+        "    edge [arrowsize={}, fontname=\"{}\", penwidth={}];\n",
+        GraphConfig::CALLGRAPH_EDGE_ATTR_ARROWSIZE,
+        GraphConfig::CALLGRAPH_EDGE_ATTR_FONTNAME,
+        GraphConfig::CALLGRAPH_EDGE_ATTR_PENWIDTH,
+    ));
+
+    // One cluster per contract
+    for (index, contract) in contracts.iter().enumerate() {
+        dot.push_str(&format!("    subgraph \"cluster_{}\" {{\n", index));
+        dot.push_str(&format!("        label=\"{}\";\n", contract.name));
+
+        for function in contract.functions {
+            let function_name = parse_element_name!(function.function.id);
+            dot.push_str(&generate_node(&contract.name, &function_name));
+        }
+
+        for (caller, callee, kind) in callgraph_edges(contract.functions) {
+            // Only keep the calls between user-defined functions
+            if kind != CallKind::UserDefined {
+                continue;
+            }
+
+            dot.push_str(&format!(
+                "        \"{}\" -> \"{}\";\n",
+                node_id(&contract.name, &caller),
+                node_id(&contract.name, &callee),
+            ));
+        }
+
+        dot.push_str("    }\n");
+    }
+
+    // Inter-contract edges
+    for edge in cross_contract_edges(contracts) {
+        let color = match edge.kind {
+            CrossContractCallKind::ContractCall => {
+                GraphConfig::CROSS_CONTRACT_CALL_CONTRACT_EDGE_COLOR
+            }
+            CrossContractCallKind::LibraryCall => {
+                GraphConfig::CROSS_CONTRACT_LIBRARY_CALL_EDGE_COLOR
+            }
+        };
+
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\" [color=\"{}\", style=\"dashed\"];\n",
+            node_id(&edge.caller_contract, &edge.caller),
+            node_id(&edge.callee_contract, &edge.callee),
+            color,
+        ));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Returns the unique identifier of a function node in the cross-contract callgraph
+#[inline]
+fn node_id(contract_name: &str, function_name: &str) -> String {
+    format!("{}::{}", contract_name, function_name)
+}
+
+/// Generates a function node, labelled with the function name, for the DOT format
+fn generate_node(contract_name: &str, function_name: &str) -> String {
+    format!(
+        "        \"{}\" [label=\"{}\"];\n",
+        node_id(contract_name, function_name),
+        function_name
+    )
+}
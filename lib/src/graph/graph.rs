@@ -1,12 +1,22 @@
+#[cfg(feature = "graph")]
 use graphviz_rust::cmd::Format;
+#[cfg(feature = "graph")]
 use graphviz_rust::dot_structures::*;
+#[cfg(feature = "graph")]
 use graphviz_rust::exec;
+#[cfg(feature = "graph")]
 use graphviz_rust::parse;
+#[cfg(feature = "graph")]
 use graphviz_rust::printer::PrinterContext;
+#[cfg(feature = "graph")]
 use std::fs::File;
+#[cfg(feature = "graph")]
 use std::io::{self, Write};
 
+use crate::detectors::detector::{DetectorType, Finding};
+
 /// Converts a DOT graph provided as a string to SVG format and saves it to a file
+#[cfg(feature = "graph")]
 pub fn save_svg_graph_to_file(filename: &str, graph: String) -> io::Result<()> {
     // Parse the graph from the string input
     let parsed_graph: Graph = parse(&graph).unwrap();
@@ -24,3 +34,31 @@ pub fn save_svg_graph_to_file(filename: &str, graph: String) -> io::Result<()> {
 
     Ok(())
 }
+
+/// Generates the DOT attributes highlighting a node containing detector findings
+/// The node is colored using the most severe finding & the findings texts are its tooltip
+pub(crate) fn generate_findings_node(node_name: &str, findings: &[&Finding]) -> String {
+    let severity = |finding: &&&Finding| match finding.detector_type {
+        DetectorType::SECURITY => 4,
+        DetectorType::CORRECTNESS => 3,
+        DetectorType::OPTIMIZATION => 2,
+        DetectorType::INFORMATIONAL => 1,
+        DetectorType::TESTING => 0,
+    };
+    let Some(most_severe) = findings.iter().max_by_key(severity) else {
+        return String::new();
+    };
+
+    let tooltip = findings
+        .iter()
+        .map(|finding| finding.message.replace('\\', "\\\\").replace('"', "\\\""))
+        .collect::<Vec<_>>()
+        .join("\\n");
+
+    format!(
+        "\"{}\" [fillcolor=\"{}\", style=\"filled\", tooltip=\"{}\"];\n",
+        node_name,
+        most_severe.detector_type.graph_color(),
+        tooltip
+    )
+}
@@ -0,0 +1,85 @@
+/// Writer of a GraphML document, the graphs can be loaded into Gephi, yEd or Cytoscape
+/// The nodes & edges attributes are declared as string keys
+#[derive(Debug, Clone)]
+pub struct GraphMlWriter {
+    /// Attributes names of the nodes
+    node_keys: Vec<&'static str>,
+    /// Attributes names of the edges
+    edge_keys: Vec<&'static str>,
+    /// Nodes & edges elements
+    elements: String,
+}
+
+impl GraphMlWriter {
+    /// Creates a GraphML writer with the attributes names of the nodes & edges
+    pub fn new(node_keys: &[&'static str], edge_keys: &[&'static str]) -> Self {
+        Self {
+            node_keys: node_keys.to_vec(),
+            edge_keys: edge_keys.to_vec(),
+            elements: String::new(),
+        }
+    }
+
+    /// Adds a node with its attributes values
+    pub fn add_node(&mut self, id: &str, data: &[(&str, &str)]) {
+        self.elements
+            .push_str(&format!("    <node id=\"{}\">\n", escape_xml(id)));
+        self.push_data(data);
+        self.elements.push_str("    </node>\n");
+    }
+
+    /// Adds a directed edge with its attributes values
+    pub fn add_edge(&mut self, source: &str, target: &str, data: &[(&str, &str)]) {
+        self.elements.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\">\n",
+            escape_xml(source),
+            escape_xml(target)
+        ));
+        self.push_data(data);
+        self.elements.push_str("    </edge>\n");
+    }
+
+    /// Returns the GraphML document
+    pub fn finish(self, graph_id: &str) -> String {
+        let mut document = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        document.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+
+        for (target, keys) in [("node", &self.node_keys), ("edge", &self.edge_keys)] {
+            for key in keys {
+                document.push_str(&format!(
+                    "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"string\"/>\n",
+                    key, target, key
+                ));
+            }
+        }
+
+        document.push_str(&format!(
+            "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+            escape_xml(graph_id)
+        ));
+        document.push_str(&self.elements);
+        document.push_str("  </graph>\n</graphml>\n");
+        document
+    }
+
+    /// Adds the attributes values of a node or an edge
+    fn push_data(&mut self, data: &[(&str, &str)]) {
+        for (key, value) in data {
+            self.elements.push_str(&format!(
+                "      <data key=\"{}\">{}</data>\n",
+                key,
+                escape_xml(value)
+            ));
+        }
+    }
+}
+
+/// Escapes the XML special characters of a text or an attribute value
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
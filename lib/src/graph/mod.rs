@@ -1,2 +1,4 @@
 pub mod callgraph;
+pub mod cross_contract;
 pub mod graph;
+pub mod graphml;
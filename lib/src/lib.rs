@@ -1,7 +1,76 @@
+//! Decompiler, static analyzer & symbolic execution engine of Sierra programs
+//!
+//! The types used to analyze a program are re-exported at the crate root, the paths of the
+//! modules defining them may change between versions.
+//!
+//! ```
+//! use sierra_analyzer_lib::{get_detectors, AnalysisContext, SierraProgram};
+//!
+//! let program = SierraProgram::try_new(
+//!     r#"type felt252 = felt252;
+//!
+//! libfunc felt252_add = felt252_add;
+//! libfunc store_temp<felt252> = store_temp<felt252>;
+//!
+//! felt252_add([0], [1]) -> ([2]);
+//! store_temp<felt252>([2]) -> ([2]);
+//! return([2]);
+//!
+//! examples::add::add@0([0]: felt252, [1]: felt252) -> (felt252);"#
+//!         .to_string(),
+//! )?;
+//!
+//! // Decompile the program
+//! let mut decompiler = program.decompiler(false);
+//! let output = decompiler.try_decompile(false)?;
+//! assert!(output.contains("func examples::add::add ("));
+//!
+//! // The functions, their CFGs & the callgraph are computed once
+//! let session = program.session()?;
+//! let function = session.function("examples::add::add").unwrap();
+//! assert_eq!(function.cfg().basic_blocks.len(), 1);
+//!
+//! // Run the detectors that don't need the ABI of a contract
+//! let context = AnalysisContext::new(&program)?;
+//! for mut detector in get_detectors() {
+//!     if !detector.requires_abi() {
+//!         detector.detect(&context);
+//!     }
+//! }
+//! # Ok::<(), sierra_analyzer_lib::SierraAnalyzerError>(())
+//! ```
+
+// The libfuncs patterns `lazy_static!` block exceeds the default macro recursion limit
+#![recursion_limit = "256"]
+
+pub mod analysis;
+pub mod cache;
+pub mod class_hash;
 mod config;
+pub mod corpus;
 pub mod decompiler;
 pub mod detectors;
+pub mod error;
+pub mod glob;
 pub mod graph;
+pub mod local_provider;
 pub mod provider;
+pub mod scarb;
 pub mod sierra_program;
 pub mod sym_exec;
+pub mod version;
+
+pub use crate::analysis::context::AnalysisContext;
+pub use crate::analysis::session::AnalysisSession;
+pub use crate::decompiler::cfg::{CfgNodeDetail, ControlFlowGraph};
+pub use crate::decompiler::decompiler::Decompiler;
+pub use crate::decompiler::formatting::FormattingOptions;
+pub use crate::decompiler::function::Function;
+pub use crate::detectors::detector::{Detector, DetectorType, Finding};
+pub use crate::detectors::get_detectors;
+pub use crate::error::{Result, SierraAnalyzerError};
+pub use crate::graph::callgraph::{CallDepth, CallGraph, CallKind, RecursionCycle};
+pub use crate::graph::cross_contract::CrossContract;
+#[cfg(feature = "graph")]
+pub use crate::graph::graph::save_svg_graph_to_file;
+pub use crate::sierra_program::{ProgramKind, SierraProgram};
@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use starknet_core::types::Felt;
+use tracing::instrument;
+
+use crate::error::{Result, SierraAnalyzerError};
+use crate::provider::RpcResponse;
+
+/// Local source of contract classes, used instead of a rate-limited public RPC node
+#[derive(Debug, Clone)]
+pub enum LocalProvider {
+    /// Directory dump of classes, stored as `<class hash>.json` files
+    Directory(PathBuf),
+    /// SQLite database of a Pathfinder full node
+    #[cfg(feature = "pathfinder")]
+    Pathfinder(PathBuf),
+}
+
+impl LocalProvider {
+    /// Creates a local provider from a path, a directory is a dump & a file a Pathfinder database
+    pub fn from_path(path: &Path) -> Result<Self> {
+        if path.is_dir() {
+            return Ok(LocalProvider::Directory(path.to_path_buf()));
+        }
+
+        #[cfg(feature = "pathfinder")]
+        if path.is_file() {
+            return Ok(LocalProvider::Pathfinder(path.to_path_buf()));
+        }
+
+        Err(SierraAnalyzerError::LocalProvider(format!(
+            "unsupported provider {}",
+            path.display()
+        )))
+    }
+
+    /// Reads a contract class using its hash
+    /// The class is returned like a starknet_getClass response
+    #[instrument(skip(self))]
+    pub fn get_class(&self, class_hash: &str) -> Result<RpcResponse> {
+        let class_hash = parse_class_hash(class_hash)?;
+
+        let result = match self {
+            LocalProvider::Directory(directory) => read_directory_class(directory, &class_hash)?,
+            #[cfg(feature = "pathfinder")]
+            LocalProvider::Pathfinder(database) => read_pathfinder_class(database, &class_hash)?,
+        };
+
+        Ok(RpcResponse {
+            result,
+            error: None,
+        })
+    }
+}
+
+/// Parses a class hash
+fn parse_class_hash(class_hash: &str) -> Result<Felt> {
+    Felt::from_hex(class_hash).map_err(|err| SierraAnalyzerError::ClassHash(err.to_string()))
+}
+
+/// Returns the path of a class in a directory dump, the class hash is padded with zeros
+pub(crate) fn class_file_path(directory: &Path, class_hash: &str) -> Result<PathBuf> {
+    let class_hash = parse_class_hash(class_hash)?;
+    Ok(directory.join(format!("0x{}.json", hex::encode(class_hash.to_bytes_be()))))
+}
+
+/// Reads a class from a directory dump
+/// The class hash of the file name can be padded with zeros or not
+fn read_directory_class(directory: &Path, class_hash: &Felt) -> Result<Value> {
+    let padded_hash = format!("0x{}", hex::encode(class_hash.to_bytes_be()));
+    let compact_hash = format!("0x{}", padded_hash[2..].trim_start_matches('0'));
+
+    for file_name in [&padded_hash, &compact_hash] {
+        let path = directory.join(format!("{}.json", file_name));
+        if path.is_file() {
+            let content = fs::read_to_string(&path)
+                .map_err(|err| SierraAnalyzerError::LocalProvider(err.to_string()))?;
+            return Ok(serde_json::from_str(&content)?);
+        }
+    }
+
+    Err(SierraAnalyzerError::ClassNotFound(padded_hash))
+}
+
+/// Reads a class from the `class_definitions` table of a Pathfinder database
+/// The definitions are compressed using zstd
+#[cfg(feature = "pathfinder")]
+fn read_pathfinder_class(database: &Path, class_hash: &Felt) -> Result<Value> {
+    use rusqlite::{Connection, OpenFlags, OptionalExtension};
+
+    let connection = Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|err| SierraAnalyzerError::LocalProvider(err.to_string()))?;
+
+    let definition: Option<Vec<u8>> = connection
+        .query_row(
+            "SELECT definition FROM class_definitions WHERE hash = ?",
+            [class_hash.to_bytes_be().to_vec()],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|err| SierraAnalyzerError::LocalProvider(err.to_string()))?;
+
+    let definition = definition
+        .ok_or_else(|| SierraAnalyzerError::ClassNotFound(format!("{:#x}", class_hash)))?;
+    let definition = zstd::decode_all(definition.as_slice())
+        .map_err(|err| SierraAnalyzerError::LocalProvider(err.to_string()))?;
+
+    Ok(serde_json::from_slice(&definition)?)
+}
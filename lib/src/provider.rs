@@ -1,6 +1,26 @@
-use reqwest::{Client, Error};
+#[cfg(feature = "remote")]
+use std::fs;
+#[cfg(feature = "remote")]
+use std::path::Path;
+#[cfg(feature = "remote")]
+use std::time::Duration;
+
+#[cfg(feature = "remote")]
+use futures::future::join_all;
+#[cfg(feature = "remote")]
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+#[cfg(feature = "remote")]
+use tokio::sync::{Mutex, Semaphore};
+#[cfg(feature = "remote")]
+use tokio::time::{sleep, sleep_until, Instant};
+#[cfg(feature = "remote")]
+use tracing::{instrument, warn};
+
+use crate::error::{Result, SierraAnalyzerError};
+#[cfg(feature = "remote")]
+use crate::local_provider::{class_file_path, LocalProvider};
 
 /// Configuration for endpoints
 /// TODO : Add other endpoints in case of failure
@@ -16,69 +36,399 @@ impl NetworkConfig {
         "https://starknet-sepolia.public.blastapi.io/rpc/v0_7";
 }
 
+/// Placeholder of the API key in the URL of an RPC node
+pub const API_KEY_PLACEHOLDER: &str = "{api_key}";
+
+/// RPC providers requiring an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcProvider {
+    Blast,
+    Infura,
+    Alchemy,
+}
+
+impl RpcProvider {
+    /// Returns the provider using its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "blast" => Some(RpcProvider::Blast),
+            "infura" => Some(RpcProvider::Infura),
+            "alchemy" => Some(RpcProvider::Alchemy),
+            _ => None,
+        }
+    }
+
+    /// Returns the URL of the provider for a network (mainnet or sepolia)
+    /// The API key is a part of the URL path and replaces the `{api_key}` placeholder
+    pub fn url(&self, network: &str) -> Option<&'static str> {
+        match (self, network) {
+            (RpcProvider::Blast, "mainnet") => {
+                Some("https://starknet-mainnet.blastapi.io/{api_key}/rpc/v0_7")
+            }
+            (RpcProvider::Blast, "sepolia") => {
+                Some("https://starknet-sepolia.blastapi.io/{api_key}/rpc/v0_7")
+            }
+            (RpcProvider::Infura, "mainnet") => {
+                Some("https://starknet-mainnet.infura.io/v3/{api_key}")
+            }
+            (RpcProvider::Infura, "sepolia") => {
+                Some("https://starknet-sepolia.infura.io/v3/{api_key}")
+            }
+            (RpcProvider::Alchemy, "mainnet") => {
+                Some("https://starknet-mainnet.g.alchemy.com/starknet/version/rpc/v0_7/{api_key}")
+            }
+            (RpcProvider::Alchemy, "sepolia") => {
+                Some("https://starknet-sepolia.g.alchemy.com/starknet/version/rpc/v0_7/{api_key}")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Where the API key is injected in the requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    /// Replaces the `{api_key}` placeholder of the node URL
+    UrlPath,
+    /// URL query parameter with the given name
+    UrlParameter(String),
+    /// HTTP header with the given name
+    Header(String),
+}
+
+/// API key of an RPC provider
+/// It is not logged, the instrumented requests only record the node URL with the placeholder
+#[derive(Clone)]
+pub struct ApiKey {
+    /// Value of the key
+    key: String,
+    /// Where the key is injected in the requests
+    location: ApiKeyLocation,
+}
+
+impl ApiKey {
+    /// Creates a new `ApiKey` instance
+    pub fn new(key: String, location: ApiKeyLocation) -> Self {
+        ApiKey { key, location }
+    }
+}
+
+/// Default maximum number of concurrent requests
+#[cfg(feature = "remote")]
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default maximum number of retries of a rate-limited request
+#[cfg(feature = "remote")]
+const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// Delay before the first retry of a rate-limited request, doubled at each retry
+#[cfg(feature = "remote")]
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Maximum delay between two retries of a rate-limited request
+#[cfg(feature = "remote")]
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
 /// Struct representing an RPC client
+/// The requests are rate limited (concurrency & pacing) so the public endpoints don't reject them
+#[cfg(feature = "remote")]
 pub struct RpcClient<'a> {
     /// The client used for sending requests
     client: Client,
     /// The address of the RPC node
     node_address: &'a str,
+    /// Limits the number of concurrent requests
+    permits: Semaphore,
+    /// Minimum delay between two requests
+    request_interval: Duration,
+    /// Time of the next allowed request
+    next_request: Mutex<Instant>,
+    /// Maximum number of retries of a rate-limited request
+    max_retries: u32,
+    /// API key of the RPC provider
+    api_key: Option<ApiKey>,
 }
 
+#[cfg(feature = "remote")]
 impl<'a> RpcClient<'a> {
     pub fn new(node_address: &'a str) -> Self {
         RpcClient {
             client: Client::new(),
             node_address,
+            permits: Semaphore::new(DEFAULT_CONCURRENCY),
+            request_interval: Duration::ZERO,
+            next_request: Mutex::new(Instant::now()),
+            max_retries: DEFAULT_MAX_RETRIES,
+            api_key: None,
         }
     }
 
-    /// Sends a starknet_getClass request to the RPC node.
-    pub async fn get_class(&self, contract_class: &str) -> Result<RpcResponse, Error> {
-        let url = format!("{}", self.node_address);
+    /// Sets the API key injected in the requests
+    pub fn with_api_key(mut self, api_key: ApiKey) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    /// Sets the maximum number of concurrent requests
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.permits = Semaphore::new(concurrency.max(1));
+        self
+    }
+
+    /// Sets the minimum delay between two requests
+    pub fn with_request_interval(mut self, request_interval: Duration) -> Self {
+        self.request_interval = request_interval;
+        self
+    }
+
+    /// Sets the maximum number of retries of a rate-limited request
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sends a JSON-RPC request to the RPC node.
+    /// The rate-limited requests (HTTP 429) are retried with a capped exponential backoff,
+    /// the JSON-RPC errors returned by the node are surfaced as `RpcNode` errors
+    async fn request(&self, method: &str, params: Value) -> Result<RpcResponse> {
         let request_body = serde_json::json!({
             "id": 1,
             "jsonrpc": "2.0",
-            "method": "starknet_getClass",
-            "params": ["pending", contract_class],
+            "method": method,
+            "params": params,
         });
 
+        // The semaphore is never closed
+        let _permit = self.permits.acquire().await;
+
+        // The errors are stripped of the request URL, it may contain the API key
+        let mut attempt = 0;
+        loop {
+            self.wait_for_turn().await;
+
+            let response = self
+                .request_builder()
+                .json(&request_body)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)?;
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS && attempt < self.max_retries {
+                let delay = retry_after(&response).unwrap_or_else(|| {
+                    INITIAL_RETRY_DELAY
+                        .saturating_mul(2u32.saturating_pow(attempt))
+                        .min(MAX_RETRY_DELAY)
+                });
+                warn!("{} rate limited, retrying in {:?}", method, delay);
+                sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            let response = response
+                .error_for_status()
+                .map_err(reqwest::Error::without_url)?
+                .json::<RpcResponse>()
+                .await
+                .map_err(reqwest::Error::without_url)?;
+
+            return match response.error {
+                Some(error) => Err(SierraAnalyzerError::RpcNode(error.to_string())),
+                None => Ok(response),
+            };
+        }
+    }
+
+    /// Creates a POST request to the RPC node, with the API key if any
+    fn request_builder(&self) -> RequestBuilder {
+        let Some(ref api_key) = self.api_key else {
+            return self.client.post(self.node_address);
+        };
+
+        match api_key.location {
+            ApiKeyLocation::UrlPath => self
+                .client
+                .post(self.node_address.replace(API_KEY_PLACEHOLDER, &api_key.key)),
+            ApiKeyLocation::UrlParameter(ref name) => self
+                .client
+                .post(self.node_address)
+                .query(&[(name, &api_key.key)]),
+            ApiKeyLocation::Header(ref name) => self
+                .client
+                .post(self.node_address)
+                .header(name.as_str(), &api_key.key),
+        }
+    }
+
+    /// Waits until the minimum delay since the previous request is elapsed
+    async fn wait_for_turn(&self) {
+        if self.request_interval.is_zero() {
+            return;
+        }
+
+        let mut next_request = self.next_request.lock().await;
+        sleep_until(*next_request).await;
+        *next_request = Instant::now() + self.request_interval;
+    }
+
+    /// Sends a starknet_getClass request to the RPC node.
+    #[instrument(skip(self), fields(node_address = self.node_address))]
+    pub async fn get_class(&self, contract_class: &str) -> Result<RpcResponse> {
+        self.request(
+            "starknet_getClass",
+            serde_json::json!(["pending", contract_class]),
+        )
+        .await
+    }
+
+    /// Sends a starknet_getClassHashAt request to the RPC node.
+    #[instrument(skip(self), fields(node_address = self.node_address))]
+    pub async fn get_class_hash_at(&self, contract_address: &str) -> Result<String> {
         let response = self
-            .client
-            .post(&url)
-            .json(&request_body)
-            .send()
-            .await?
-            .json::<RpcResponse>()
+            .request(
+                "starknet_getClassHashAt",
+                serde_json::json!(["latest", contract_address]),
+            )
             .await?;
 
+        response.class_hash()
+    }
+
+    /// Sends a starknet_getTransactionByHash request to the RPC node.
+    /// Returns the hash of the class declared by the transaction, or None if it's not a DECLARE transaction
+    #[instrument(skip(self), fields(node_address = self.node_address))]
+    pub async fn get_declared_class_hash(&self, transaction_hash: &str) -> Result<Option<String>> {
+        let response = self
+            .request(
+                "starknet_getTransactionByHash",
+                serde_json::json!([transaction_hash]),
+            )
+            .await?;
+
+        response.declared_class_hash()
+    }
+
+    /// Returns the hashes of the classes declared by several transactions, fetched concurrently
+    pub async fn get_declared_class_hashes(
+        &self,
+        transaction_hashes: &[String],
+    ) -> Vec<Result<Option<String>>> {
+        join_all(
+            transaction_hashes
+                .iter()
+                .map(|transaction_hash| self.get_declared_class_hash(transaction_hash)),
+        )
+        .await
+    }
+
+    /// Fetches several classes concurrently
+    /// With a cache directory, the fetched classes are saved as `<class hash>.json` files
+    /// and the classes of an interrupted batch that are already in the cache are not fetched again
+    pub async fn get_classes(
+        &self,
+        class_hashes: &[String],
+        cache: Option<&Path>,
+    ) -> Vec<Result<RpcResponse>> {
+        join_all(
+            class_hashes
+                .iter()
+                .map(|class_hash| self.get_cached_class(class_hash, cache)),
+        )
+        .await
+    }
+
+    /// Reads a class from the cache directory, or fetches it & saves it in the cache
+    async fn get_cached_class(
+        &self,
+        class_hash: &str,
+        cache: Option<&Path>,
+    ) -> Result<RpcResponse> {
+        let Some(cache) = cache else {
+            return self.get_class(class_hash).await;
+        };
+
+        if let Ok(response) = LocalProvider::Directory(cache.to_path_buf()).get_class(class_hash) {
+            return Ok(response);
+        }
+
+        let response = self.get_class(class_hash).await?;
+        fs::write(
+            class_file_path(cache, class_hash)?,
+            serde_json::to_string(&response.result)?,
+        )
+        .map_err(|err| SierraAnalyzerError::LocalProvider(err.to_string()))?;
+
         Ok(response)
     }
 }
 
+/// Returns the delay requested by the Retry-After header of a response
+#[cfg(feature = "remote")]
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
 /// The expected response structure from the starknet_getClass RPC call
+/// A failed call has an error instead of a result
 #[derive(Deserialize, Serialize, Debug)]
 pub struct RpcResponse {
+    #[serde(default)]
     pub result: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
 }
 
 impl RpcResponse {
+    /// Returns the class hash of a starknet_getClassHashAt response
+    pub fn class_hash(&self) -> Result<String> {
+        self.result
+            .as_str()
+            .map(|class_hash| class_hash.to_string())
+            .ok_or(SierraAnalyzerError::MissingField("class hash"))
+    }
+
+    /// Returns the hash of the class declared by the transaction of a
+    /// starknet_getTransactionByHash response, or None if it's not a DECLARE transaction
+    pub fn declared_class_hash(&self) -> Result<Option<String>> {
+        let transaction_type = self.result["type"]
+            .as_str()
+            .ok_or(SierraAnalyzerError::MissingField("transaction type"))?;
+        if transaction_type != "DECLARE" {
+            return Ok(None);
+        }
+
+        self.result["class_hash"]
+            .as_str()
+            .map(|class_hash| Some(class_hash.to_string()))
+            .ok_or(SierraAnalyzerError::MissingField("class hash"))
+    }
+
     /// Returns the response JSON
-    pub fn to_json(&self) -> String {
+    pub fn to_json(&self) -> Result<String> {
         // Serialize the RpcResponse into a JSON string
-        let json_string = serde_json::to_string_pretty(&self.result)
-            .unwrap_or_else(|e| format!("Error serializing JSON: {}", e));
+        let json_string = serde_json::to_string_pretty(&self.result)?;
 
         // Parse the JSON string into a serde_json::Value
-        let value: Value = serde_json::from_str(&json_string)
-            .unwrap_or_else(|e| panic!("Error parsing JSON: {}", e));
+        let value: Value = serde_json::from_str(&json_string)?;
 
         // Clean the ABI field
-        let clean_abi = value["abi"]
-            .as_str()
-            .unwrap_or_else(|| panic!("Missing ABI field"))
-            .replace(r#"\""#, "") // Remove escaped quotes
-            .trim_matches('"') // Trim surrounding quotes
-            .to_string();
+        let clean_abi = match &value["abi"] {
+            // The local contract classes can store the ABI as a JSON array
+            abi @ Value::Array(_) => abi.to_string(),
+            abi => abi
+                .as_str()
+                .ok_or(SierraAnalyzerError::MissingField("ABI"))?
+                .replace(r#"\""#, "") // Remove escaped quotes
+                .trim_matches('"') // Trim surrounding quotes
+                .to_string(),
+        };
 
         // Extract other fields
         let sierra_program = &value["sierra_program"].to_string();
@@ -104,6 +454,6 @@ impl RpcResponse {
         .trim() // Trim leading and trailing whitespaces
         .to_string();
 
-        sierra_json
+        Ok(sierra_json)
     }
 }
@@ -0,0 +1,28 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// Returns true if a file is a contract class built by Scarb, the compiled (CASM) contract classes are skipped
+pub fn is_contract_class_file(file_name: &str) -> bool {
+    file_name.ends_with("contract_class.json")
+        && !file_name.ends_with("compiled_contract_class.json")
+}
+
+/// Returns the contract classes built by Scarb in the dev profile directory of a target directory, sorted by name
+pub fn contract_classes(target_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut contract_class_files: Vec<PathBuf> = fs::read_dir(target_dir.join("dev"))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(is_contract_class_file)
+        })
+        .collect();
+    contract_class_files.sort();
+
+    Ok(contract_class_files)
+}
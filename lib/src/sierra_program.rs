@@ -1,46 +1,150 @@
+use std::fs;
+use std::path::Path;
+
 use cairo_lang_sierra::extensions::core::CoreLibfunc;
 use cairo_lang_sierra::extensions::core::CoreType;
 use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra::program_registry::ProgramRegistry;
 use cairo_lang_sierra::ProgramParser;
 use cairo_lang_starknet_classes::abi::Contract;
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use serde_json::Value;
+use tracing::{info, warn};
 
+use crate::analysis::session::AnalysisSession;
 use crate::decompiler::decompiler::Decompiler;
+use crate::error::{Result, SierraAnalyzerError};
+use crate::version::{detect_class_version, SierraVersion};
+
+/// Kind of an analyzed program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramKind {
+    /// Sierra program without ABI, the External/View/L1Handler functions are unknown
+    RawSierra,
+    /// Sierra program extracted from a Starknet contract class, with its ABI
+    ContractClass,
+}
 
 /// A struct that represents a Sierra program
 pub struct SierraProgram {
     /// The parsed Sierra program
     program: Program,
 
-    /// Program registry
-    registry: ProgramRegistry<CoreType, CoreLibfunc>,
+    /// Program registry, not available for legacy programs using unsupported libfuncs
+    registry: Option<ProgramRegistry<CoreType, CoreLibfunc>>,
 
     /// Contract ABI
     pub abi: Option<Contract>,
+
+    /// Sierra version of the contract class
+    pub sierra_version: Option<SierraVersion>,
 }
 
 impl SierraProgram {
     /// Creates a new `SierraProgram` instance by parsing the given Sierra code
+    /// Panics if the Sierra code is malformed, see `try_new`
     pub fn new(content: String) -> Self {
-        let program = match ProgramParser::new().parse(&content) {
-            Ok(program) => program,
-            Err(err) => {
-                panic!("Error parsing Sierra code: {}", err);
+        Self::try_new(content).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new `SierraProgram` instance by parsing the given Sierra code
+    pub fn try_new(content: String) -> Result<Self> {
+        let program = Self::parse(&content)?;
+
+        let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&program)
+            .map_err(|err| SierraAnalyzerError::Registry(err.to_string()))?;
+
+        Ok(SierraProgram {
+            program,
+            registry: Some(registry),
+            abi: None,
+            sierra_version: None,
+        })
+    }
+
+    /// Creates a new `SierraProgram` instance, without a program registry if it can't be created
+    /// Used for legacy Sierra versions whose libfuncs are not supported anymore,
+    /// only the text-based analyses (decompilation, strings, statistics...) are available
+    pub fn try_new_legacy(content: String) -> Result<Self> {
+        let program = Self::parse(&content)?;
+
+        let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&program).ok();
+
+        Ok(SierraProgram {
+            program,
+            registry,
+            abi: None,
+            sierra_version: None,
+        })
+    }
+
+    /// Creates a new `SierraProgram` instance from Sierra code or a Starknet contract class
+    /// The ABI & the Sierra version of the contract classes are set. The programs using
+    /// unsupported libfuncs (e.g. the legacy ones, older than `SUPPORTED_SIERRA_VERSION`) are
+    /// loaded without a program registry, see `try_new_legacy`
+    pub fn from_content(content: String) -> Result<Self> {
+        let contract_class = serde_json::from_str::<ContractClass>(&content).ok();
+
+        // Extract the Sierra program of the contract class
+        let program_string = match contract_class
+            .as_ref()
+            .map(ContractClass::extract_sierra_program)
+        {
+            Some(Ok(sierra_program)) => sierra_program.to_string(),
+            Some(Err(err)) => {
+                warn!("Error extracting Sierra program: {}", err);
+                content.clone()
             }
+            None => content.clone(),
         };
+        let sierra_version =
+            detect_class_version(&content).map(|class_version| class_version.sierra_version);
 
-        let registry = match ProgramRegistry::<CoreType, CoreLibfunc>::new(&program) {
-            Ok(registry) => registry,
-            Err(err) => {
-                panic!("Error creating program registry: {}", err);
+        let mut program = match Self::try_new(program_string.clone()) {
+            Ok(program) => program,
+            Err(SierraAnalyzerError::Registry(err)) => {
+                match sierra_version {
+                    // The libfuncs of the legacy programs are expected to be unsupported
+                    Some(version) if version.is_legacy() => info!(
+                        "Legacy Sierra program (Sierra {}), only the text-based analyses are available: {}",
+                        version, err
+                    ),
+                    Some(version) => warn!(
+                        "Unsupported Sierra program (Sierra {}), only the text-based analyses are available: {}",
+                        version, err
+                    ),
+                    None => warn!(
+                        "Unsupported Sierra program, only the text-based analyses are available: {}",
+                        err
+                    ),
+                }
+                Self::try_new_legacy(program_string)?
             }
+            Err(err) => return Err(err),
         };
 
-        SierraProgram {
-            program,
-            registry,
-            abi: None,
+        program.sierra_version = sierra_version;
+        program.abi = contract_class.and_then(|contract_class| contract_class.abi);
+
+        Ok(program)
+    }
+
+    /// Creates a new `SierraProgram` instance from a Sierra program or a contract class file
+    /// See `from_content`
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Self::from_content(fs::read_to_string(path)?)
+    }
+
+    /// Parses the Sierra code
+    fn parse(content: &str) -> Result<Program> {
+        // A compiled contract class can't be parsed as Sierra code
+        if is_casm_class(content) {
+            return Err(SierraAnalyzerError::CasmClass);
         }
+
+        ProgramParser::new()
+            .parse(content)
+            .map_err(|err| SierraAnalyzerError::Parse(err.to_string()))
     }
 
     /// Returns a reference to the parsed Sierra program
@@ -48,9 +152,17 @@ impl SierraProgram {
         &self.program
     }
 
-    /// Returns a reference to the program registry
-    pub fn registry(&self) -> &ProgramRegistry<CoreType, CoreLibfunc> {
-        &self.registry
+    /// Returns a reference to the program registry, if available
+    pub fn registry(&self) -> Option<&ProgramRegistry<CoreType, CoreLibfunc>> {
+        self.registry.as_ref()
+    }
+
+    /// Returns the kind of the program, a program with an ABI is a contract class
+    pub fn kind(&self) -> ProgramKind {
+        match self.abi {
+            Some(_) => ProgramKind::ContractClass,
+            None => ProgramKind::RawSierra,
+        }
     }
 
     /// Decompiles the Sierra program and returns a `Decompiler` instance
@@ -58,8 +170,48 @@ impl SierraProgram {
         Decompiler::new(self, verbose)
     }
 
+    /// Analyzes the Sierra program and returns a thread-safe `AnalysisSession` instance
+    pub fn session(&self) -> Result<AnalysisSession> {
+        AnalysisSession::new(self)
+    }
+
     /// Sets the ABI of the contract
     pub fn set_abi(&mut self, abi: Contract) {
         self.abi = Some(abi);
     }
+
+    /// Sets the Sierra version of the contract class
+    pub fn set_sierra_version(&mut self, sierra_version: SierraVersion) {
+        self.sierra_version = Some(sierra_version);
+    }
+}
+
+impl Clone for SierraProgram {
+    /// Clones the Sierra program, the program registry can't be cloned and is rebuilt
+    fn clone(&self) -> Self {
+        SierraProgram {
+            program: self.program.clone(),
+            registry: self
+                .registry
+                .as_ref()
+                .and_then(|_| ProgramRegistry::<CoreType, CoreLibfunc>::new(&self.program).ok()),
+            abi: self.abi.clone(),
+            sierra_version: self.sierra_version,
+        }
+    }
+}
+
+/// Checks if the content is a compiled contract class (CASM)
+/// These JSON files contain the CASM bytecode but not the Sierra program
+pub fn is_casm_class(content: &str) -> bool {
+    if !content.trim_start().starts_with('{') {
+        return false;
+    }
+
+    match serde_json::from_str::<Value>(content) {
+        Ok(Value::Object(fields)) => {
+            fields.contains_key("bytecode") && !fields.contains_key("sierra_program")
+        }
+        _ => false,
+    }
 }
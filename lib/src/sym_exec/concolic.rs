@@ -0,0 +1,364 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cairo_lang_runner::profiling::ProfilingInfoCollectionConfig;
+use cairo_lang_runner::{Arg, RunResultValue, SierraCasmRunner, StarknetState};
+use cairo_lang_sierra::program::{GenStatement, Program};
+use num_bigint::BigInt;
+use tracing::{debug, instrument};
+#[cfg(feature = "z3")]
+use z3::{Config, Context};
+
+use crate::config::SymbolicExecutionConfig;
+use crate::decompiler::function::Function;
+use crate::decompiler::libfuncs_patterns::PEDERSEN_REGEX;
+use crate::detectors::controlled_library_call_detector::BUILTINS;
+use crate::error::{Result, SierraAnalyzerError};
+use crate::sym_exec::formula::{ArrayTerm, Formula, Term};
+use crate::sym_exec::hashes::{bigint_to_felt, felt252_value, felt_to_bigint, pedersen, HashModel};
+use crate::sym_exec::prove::{arguments_ranges, integer_arguments};
+use crate::sym_exec::solver::{Solver, SolverResult};
+use crate::sym_exec::storage::INITIAL_STORAGE;
+use crate::sym_exec::sym_exec::trace_frames;
+use crate::sym_exec::unrolling::{unrolled_traces, UnrolledTrace};
+#[cfg(feature = "z3")]
+use crate::sym_exec::z3_solver::Z3Solver;
+use crate::{extract_parameters, parse_element_name, parse_element_name_with_fallback};
+
+/// Result of the concrete execution of a function
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConcreteRun {
+    /// Offsets of the executed statements that were compiled to CASM instructions
+    pub executed_offsets: HashSet<u32>,
+    /// True if the execution panicked
+    pub panicked: bool,
+    /// Returned values, or panic data
+    pub values: Vec<BigInt>,
+}
+
+/// Runs the functions of a Sierra program with cairo-vm
+/// The program is compiled to CASM once, the executed statements are collected by the profiler
+pub struct ConcreteRunner {
+    runner: SierraCasmRunner,
+}
+
+impl ConcreteRunner {
+    /// Creates a new `ConcreteRunner` instance, compiling the program to CASM
+    pub fn new(program: &Program) -> Result<Self> {
+        let runner = SierraCasmRunner::new(
+            program.clone(),
+            Some(Default::default()),
+            Default::default(),
+            Some(ProfilingInfoCollectionConfig::default()),
+        )
+        .map_err(|e| SierraAnalyzerError::Runner(e.to_string()))?;
+
+        Ok(Self { runner })
+    }
+
+    /// Runs a function with the given felt252 arguments & an empty contract storage
+    /// The builtins arguments are given by the runner
+    pub fn run(&self, function_name: &str, arguments: &[BigInt]) -> Result<ConcreteRun> {
+        let function = self
+            .runner
+            .find_function(function_name)
+            .map_err(|e| SierraAnalyzerError::Runner(e.to_string()))?;
+        let arguments = arguments
+            .iter()
+            .map(|argument| Arg::Value(bigint_to_felt(argument)))
+            .collect();
+
+        let result = self
+            .runner
+            .run_function_with_starknet_context(
+                function,
+                arguments,
+                Some(SymbolicExecutionConfig::CONCRETE_AVAILABLE_GAS),
+                StarknetState::default(),
+            )
+            .map_err(|e| SierraAnalyzerError::Runner(e.to_string()))?;
+
+        let executed_offsets = result
+            .profiling_info
+            .map(|profiling_info| {
+                profiling_info
+                    .sierra_statement_weights
+                    .iter_sorted()
+                    .filter_map(|(statement_idx, _)| u32::try_from(statement_idx.0).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (panicked, values) = match result.value {
+            RunResultValue::Success(values) => (false, values),
+            RunResultValue::Panic(values) => (true, values),
+        };
+
+        Ok(ConcreteRun {
+            executed_offsets,
+            panicked,
+            values: values.iter().map(felt_to_bigint).collect(),
+        })
+    }
+}
+
+/// Validation of a path by the concrete execution of its inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathValidation {
+    /// The concrete execution follows the path
+    Validated,
+    /// The concrete execution leaves the path, at the first statement of the function where they differ
+    Diverged(u32),
+    /// No input follows the path once its hashes results are replaced by their concrete values
+    Infeasible,
+    /// The solver could not decide the path feasibility
+    Unknown,
+}
+
+/// A path of a function executed with the inputs generated by the solver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcolicReport {
+    /// Values of the function arguments, empty if no input was generated
+    pub arguments: Vec<(String, BigInt)>,
+    /// Number of hashes results replaced by their concrete values
+    pub concretized_hashes: usize,
+    /// True if the concrete execution panicked
+    pub panicked: bool,
+    pub validation: PathValidation,
+}
+
+/// Executes the paths of a function of a program concolically using the Z3 solver
+/// See `concolic_execution_with_solver`
+#[cfg(feature = "z3")]
+pub fn concolic_execution(
+    program: &Program,
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    hash_model: HashModel,
+) -> Result<Vec<ConcolicReport>> {
+    let runner = ConcreteRunner::new(program)?;
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    let mut solver = Z3Solver::new(&context);
+
+    concolic_execution_with_solver(
+        &mut solver,
+        &runner,
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        SymbolicExecutionConfig::UNROLL_BOUND,
+        hash_model,
+    )
+}
+
+/// Executes the paths of a function of a program concolically with a given solver backend
+/// The inputs of each path are generated by the solver, then the function is run with cairo-vm to check
+/// that it follows the path. The solver can't invert the hash functions : the Pedersen hashes results
+/// are replaced by the hashes of the model values until the model is consistent, for up to
+/// `CONCRETIZATION_ROUNDS` rounds. The contract storage is empty before the concrete execution.
+/// Only the functions whose parameters are builtins, felt252 or integers can be run.
+#[instrument(skip_all, fields(function_index = function_index))]
+pub fn concolic_execution_with_solver<S: Solver>(
+    solver: &mut S,
+    runner: &ConcreteRunner,
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    unroll_bound: usize,
+    hash_model: HashModel,
+) -> Result<Vec<ConcolicReport>> {
+    let function = &functions[function_index];
+    let function_name = parse_element_name!(function.function.id);
+
+    // The values of the other parameters types (structs, arrays...) are not generated
+    let parameters: Vec<String> = integer_arguments(function).map(|(name, _)| name).collect();
+    if let Some((arg_name, arg_type)) = function
+        .arguments
+        .iter()
+        .filter(|(_, arg_type)| !BUILTINS.contains(&arg_type.as_str()))
+        .find(|(arg_name, _)| !parameters.contains(arg_name))
+    {
+        return Err(SierraAnalyzerError::Runner(format!(
+            "Unsupported parameter {}: {} of {}",
+            arg_name, arg_type, function_name
+        )));
+    }
+
+    let traces = unrolled_traces(
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        unroll_bound,
+    );
+    debug!("{} unrolled traces", traces.len());
+
+    let mut reports = Vec::new();
+    for trace in &traces {
+        solver.reset();
+
+        // The integer arguments are in their type range & the storage is empty
+        for constraint in arguments_ranges(function) {
+            solver.assert(&constraint);
+        }
+        solver.assert(&Formula::array_eq(
+            ArrayTerm::var(INITIAL_STORAGE),
+            ArrayTerm::constant(Term::constant(0)),
+        ));
+        for frame in trace_frames(trace, declared_libfuncs_names, hash_model) {
+            for constraint in &frame.constraints {
+                solver.assert(constraint);
+            }
+        }
+
+        let (result, concretized_hashes) =
+            check_with_concrete_hashes(solver, &pedersen_hashes(trace, declared_libfuncs_names));
+        let validation = match result {
+            SolverResult::Sat => None,
+            SolverResult::Unsat => Some(PathValidation::Infeasible),
+            SolverResult::Unknown => Some(PathValidation::Unknown),
+        };
+        if let Some(validation) = validation {
+            reports.push(ConcolicReport {
+                arguments: Vec::new(),
+                concretized_hashes,
+                panicked: false,
+                validation,
+            });
+            continue;
+        }
+
+        let arguments: Vec<(String, BigInt)> = parameters
+            .iter()
+            .map(|name| (name.clone(), solver.value(name).unwrap_or_default()))
+            .collect();
+        let values: Vec<BigInt> = arguments.iter().map(|(_, value)| value.clone()).collect();
+        let run = runner.run(&function_name, &values)?;
+
+        reports.push(ConcolicReport {
+            arguments,
+            concretized_hashes,
+            panicked: run.panicked,
+            validation: validate_path(function, trace, &run.executed_offsets),
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Checks the asserted constraints, replacing the inconsistent hashes results of the model by their
+/// concrete values (the hash inputs are fixed to their model values)
+/// Returns the result of the last check & the number of concretized hashes
+fn check_with_concrete_hashes<S: Solver>(
+    solver: &mut S,
+    hashes: &[(String, String, String)],
+) -> (SolverResult, usize) {
+    let mut concretized_hashes = 0;
+    let mut result = solver.check();
+
+    for _ in 0..SymbolicExecutionConfig::CONCRETIZATION_ROUNDS {
+        if result != SolverResult::Sat {
+            break;
+        }
+
+        let mut consistent = true;
+        for (lhs, rhs, digest) in hashes {
+            let (Some(lhs_value), Some(rhs_value), Some(digest_value)) =
+                (solver.value(lhs), solver.value(rhs), solver.value(digest))
+            else {
+                continue;
+            };
+
+            let hash = pedersen(&lhs_value, &rhs_value);
+            if felt252_value(&digest_value) == hash {
+                continue;
+            }
+            solver.assert(&Formula::eq(Term::var(lhs), Term::Const(lhs_value)));
+            solver.assert(&Formula::eq(Term::var(rhs), Term::Const(rhs_value)));
+            solver.assert(&Formula::eq(Term::var(digest), Term::Const(hash)));
+            concretized_hashes += 1;
+            consistent = false;
+        }
+
+        if consistent {
+            break;
+        }
+        result = solver.check();
+    }
+
+    (result, concretized_hashes)
+}
+
+/// Returns the operands & the result variables of the Pedersen hashes of a trace
+fn pedersen_hashes(
+    trace: &UnrolledTrace,
+    declared_libfuncs_names: &[Arc<str>],
+) -> Vec<(String, String, String)> {
+    trace
+        .blocks
+        .iter()
+        .flatten()
+        .filter_map(|trace_statement| {
+            let GenStatement::Invocation(invocation) = &trace_statement.statement.statement else {
+                return None;
+            };
+            let libfunc_name =
+                parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+            if !PEDERSEN_REGEX.is_match(&libfunc_name) {
+                return None;
+            }
+
+            // pedersen(builtin, lhs, rhs) -> (builtin, digest)
+            let parameters = extract_parameters!(invocation.args);
+            let results = extract_parameters!(&invocation.branches.first()?.results);
+            let suffix = &trace_statement.suffix;
+            Some((
+                format!("{}{}", parameters.get(1)?, suffix),
+                format!("{}{}", parameters.get(2)?, suffix),
+                format!("{}{}", results.get(1)?, suffix),
+            ))
+        })
+        .collect()
+}
+
+/// Compares the statements of the function executed concretely with the ones of a path
+/// The statements that are not compiled to CASM instructions (e.g. `branch_align`) are never
+/// executed, so only the branches & the returns of the path must have been executed
+fn validate_path(
+    function: &Function,
+    trace: &UnrolledTrace,
+    executed_offsets: &HashSet<u32>,
+) -> PathValidation {
+    // The statements of the inlined functions instances have a suffix
+    let path_statements: Vec<_> = trace
+        .blocks
+        .iter()
+        .flatten()
+        .filter(|trace_statement| trace_statement.suffix.is_empty())
+        .map(|trace_statement| trace_statement.statement)
+        .collect();
+    let path_offsets: HashSet<u32> = path_statements
+        .iter()
+        .map(|statement| statement.offset)
+        .collect();
+
+    // A statement of the function executed outside of the path
+    if let Some(offset) = function
+        .statements()
+        .iter()
+        .map(|statement| statement.offset)
+        .find(|offset| executed_offsets.contains(offset) && !path_offsets.contains(offset))
+    {
+        return PathValidation::Diverged(offset);
+    }
+
+    // A branch or a return of the path that was not executed
+    if let Some(statement) = path_statements.iter().find(|statement| {
+        (statement.is_conditional_branch || matches!(statement.statement, GenStatement::Return(_)))
+            && !executed_offsets.contains(&statement.offset)
+    }) {
+        return PathValidation::Diverged(statement.offset);
+    }
+
+    PathValidation::Validated
+}
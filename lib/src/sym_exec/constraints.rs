@@ -0,0 +1,769 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use cairo_lang_sierra::program::{GenStatement, Invocation};
+use num_bigint::BigInt;
+
+use crate::decompiler::function::SierraStatement;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::decompiler::libfuncs_patterns::{
+    ARRAY_TYPE_REGEX, BOOL_AND_REGEX, BOOL_INIT_REGEX, BOOL_MATCH_REGEX, BOOL_NOT_REGEX,
+    BOOL_OR_REGEX, BOOL_XOR_REGEX, BOUNDED_INT_CONSTRAIN_REGEX, CONST_REGEXES, ENUM_INIT_REGEX,
+    ENUM_MATCH_REGEX, OVERFLOWING_OPERATION_REGEX, SPAN_STRUCT_REGEX,
+    STORAGE_ADDRESS_FROM_BASE_AND_OFFSET_REGEX, STORAGE_ADDRESS_FROM_BASE_REGEX,
+    STORAGE_BASE_ADDRESS_FROM_FELT252_REGEX,
+};
+use crate::sym_exec::formula::{ArrayTerm, Formula, Term};
+use crate::{extract_parameters, parse_element_name_with_fallback};
+
+/// Converts a SierraStatement to a constraint, or returns None if not applicable
+#[inline]
+pub fn sierra_statement_to_constraint(
+    statement: &SierraStatement,
+    declared_libfuncs_names: &[Arc<str>],
+) -> Option<Formula> {
+    sierra_statement_to_constraint_with_suffix(statement, declared_libfuncs_names, "", None)
+}
+
+/// Converts a SierraStatement to a constraint, appending a suffix to the variables names
+/// The suffix is used to distinguish the variables of the inlined recursive calls.
+/// `branch` is the index of the branch taken by the statement, if known
+pub fn sierra_statement_to_constraint_with_suffix(
+    statement: &SierraStatement,
+    declared_libfuncs_names: &[Arc<str>],
+    suffix: &str,
+    branch: Option<usize>,
+) -> Option<Formula> {
+    let GenStatement::Invocation(invocation) = &statement.statement else {
+        return None;
+    };
+
+    let variables = InvocationVariables::new(invocation, suffix);
+    invocation_constraint(statement, declared_libfuncs_names, &variables, branch)
+}
+
+/// Names of the variables of an invocation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct InvocationVariables {
+    pub(crate) parameters: Vec<String>,
+    /// Results of each branch
+    pub(crate) results: Vec<Vec<String>>,
+}
+
+impl InvocationVariables {
+    /// Returns the variables of an invocation, appending a suffix to their names
+    pub(crate) fn new(invocation: &Invocation, suffix: &str) -> Self {
+        let add_suffix = |names: Vec<String>| -> Vec<String> {
+            names
+                .into_iter()
+                .map(|name| format!("{}{}", name, suffix))
+                .collect()
+        };
+
+        Self {
+            parameters: add_suffix(extract_parameters!(invocation.args)),
+            results: invocation
+                .branches
+                .iter()
+                .map(|branch| add_suffix(extract_parameters!(&branch.results)))
+                .collect(),
+        }
+    }
+
+    /// Returns the results of a branch, the single branch libfuncs assign the results of the first one
+    pub(crate) fn branch_results(&self, branch: usize) -> &[String] {
+        self.results.get(branch).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Converts an invocation to a constraint given the names of its variables
+pub(crate) fn invocation_constraint(
+    statement: &SierraStatement,
+    declared_libfuncs_names: &[Arc<str>],
+    variables: &InvocationVariables,
+    branch: Option<usize>,
+) -> Option<Formula> {
+    let GenStatement::Invocation(invocation) = &statement.statement else {
+        return None;
+    };
+    let libfunc_id_str =
+        parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+
+    // Branch-aware libfuncs constrain the variables of the taken branch
+    if let Some(constraint) = branch.and_then(|branch| {
+        handle_branch(
+            statement.libfunc_kind,
+            &libfunc_id_str,
+            &variables.parameters,
+            branch,
+            variables.branch_results(branch),
+        )
+    }) {
+        return Some(constraint);
+    }
+
+    handle_invocation(
+        statement.libfunc_kind,
+        &libfunc_id_str,
+        &variables.parameters,
+        variables.branch_results(0),
+    )
+}
+
+/// Handles an invocation by trying to match it to known patterns.
+fn handle_invocation(
+    libfunc_kind: LibfuncKind,
+    libfunc_id_str: &str,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    handle_array_operations(libfunc_kind, libfunc_id_str, parameters, assigned_variables)
+        .or_else(|| handle_duplication(libfunc_kind, parameters, assigned_variables))
+        .or_else(|| handle_assignment(libfunc_kind, libfunc_id_str, parameters, assigned_variables))
+        .or_else(|| handle_snapshot_and_box(libfunc_kind, parameters, assigned_variables))
+        .or_else(|| handle_constant_assignment(libfunc_kind, libfunc_id_str, assigned_variables))
+        .or_else(|| handle_is_zero(libfunc_kind, parameters))
+        .or_else(|| handle_bounded_int_div_rem(libfunc_kind, parameters, assigned_variables))
+        .or_else(|| handle_dict_operations(libfunc_kind, parameters, assigned_variables))
+        .or_else(|| handle_arithmetic_operations(libfunc_kind, parameters, assigned_variables))
+        .or_else(|| handle_boolean_operations(libfunc_id_str, parameters, assigned_variables))
+        .or_else(|| handle_enum_init(libfunc_id_str, assigned_variables))
+        .or_else(|| handle_storage_addresses(libfunc_id_str, parameters, assigned_variables))
+}
+
+/// Handles the libfuncs whose semantics depend on the taken branch
+fn handle_branch(
+    libfunc_kind: LibfuncKind,
+    libfunc_id_str: &str,
+    parameters: &[String],
+    branch: usize,
+    branch_variables: &[String],
+) -> Option<Formula> {
+    let taken = branch == 1;
+
+    if let Some(constraint) =
+        handle_array_accesses(libfunc_kind, parameters, taken, branch_variables)
+    {
+        return Some(constraint);
+    }
+
+    // Zero check : branch 0 is taken if the operand is zero,
+    // branch 1 returns the operand as a NonZero value
+    if libfunc_kind == LibfuncKind::IsZero {
+        let operand = Term::var(&parameters[0]);
+        let is_zero = Formula::eq(operand.clone(), Term::constant(0));
+        if !taken {
+            return Some(is_zero);
+        }
+
+        let mut constraints = vec![!is_zero];
+        if let Some(non_zero_variable) = branch_variables.first() {
+            constraints.push(Formula::eq(Term::var(non_zero_variable), operand));
+        }
+        return Some(Formula::and(constraints));
+    }
+
+    // Boolean & enum match : the matched value is the index of the branch
+    if BOOL_MATCH_REGEX.is_match(libfunc_id_str) || ENUM_MATCH_REGEX.is_match(libfunc_id_str) {
+        let operand = Term::var(&parameters[0]);
+        return Some(Formula::eq(operand, Term::constant(branch as u64)));
+    }
+
+    // Bounded integers constraints : branch 0 is taken if the value is lower than the boundary
+    // `bounded_int_constrain<T, boundary>(range_check, value) { fallthrough(range_check, lower) 42(range_check, higher) }`
+    if let Some(captures) = (libfunc_kind == LibfuncKind::BoundedIntConstrain)
+        .then(|| BOUNDED_INT_CONSTRAIN_REGEX.captures(libfunc_id_str))
+        .flatten()
+    {
+        let boundary = Term::constant(BigInt::from_str(&captures["boundary"]).ok()?);
+        let value = Term::var(parameters.last()?);
+        let is_lower = Formula::lt(value.clone(), boundary);
+
+        let mut constraints = vec![if taken { !is_lower } else { is_lower }];
+        if let Some(constrained_variable) = branch_variables.get(1) {
+            constraints.push(Formula::eq(Term::var(constrained_variable), value));
+        }
+        return Some(Formula::and(constraints));
+    }
+
+    // Unsigned integers overflowing operations
+    if let Some(captures) = matches!(
+        libfunc_kind,
+        LibfuncKind::Addition | LibfuncKind::Subtraction
+    )
+    .then(|| OVERFLOWING_OPERATION_REGEX.captures(libfunc_id_str))
+    .flatten()
+    {
+        return handle_overflowing_operation(
+            &captures["bits"],
+            &captures["operator"],
+            parameters,
+            taken,
+            branch_variables,
+        );
+    }
+
+    // Integers comparisons
+    // The operands are the last parameters, `lt` & `diff` libfuncs take a range check first
+    if parameters.len() < 2 {
+        return None;
+    }
+    let lhs = Term::var(&parameters[parameters.len() - 2]);
+    let rhs = Term::var(&parameters[parameters.len() - 1]);
+
+    let comparison = match libfunc_kind {
+        LibfuncKind::LessThan => Formula::lt(lhs, rhs),
+        LibfuncKind::LessThanOrEqual => Formula::le(lhs, rhs),
+        LibfuncKind::Equal => Formula::eq(lhs, rhs),
+        _ => return None,
+    };
+
+    // Branch 0 is taken when the comparison is false
+    Some(if taken { comparison } else { !comparison })
+}
+
+/// Handles the unsigned integers overflowing additions & substractions
+/// `u32_overflowing_add(range_check, lhs, rhs) { fallthrough(range_check, result) 374(range_check, wrapped_result) }`
+fn handle_overflowing_operation(
+    bits: &str,
+    operator: &str,
+    parameters: &[String],
+    overflow: bool,
+    branch_variables: &[String],
+) -> Option<Formula> {
+    let bits = usize::from_str(bits).ok()?;
+    let modulus = Term::constant(BigInt::from(1) << bits);
+
+    let lhs = Term::var(parameters.get(1)?);
+    let rhs = Term::var(parameters.get(2)?);
+    let result = Term::var(branch_variables.get(1)?);
+
+    // The operands are in the integer range
+    let mut constraints = vec![
+        Formula::ge(lhs.clone(), Term::constant(0)),
+        Formula::lt(lhs.clone(), modulus.clone()),
+        Formula::ge(rhs.clone(), Term::constant(0)),
+        Formula::lt(rhs.clone(), modulus.clone()),
+    ];
+
+    let value = match operator {
+        "add" => lhs + rhs,
+        _ => lhs - rhs,
+    };
+
+    match (overflow, operator) {
+        (false, _) => {
+            constraints.push(Formula::ge(value.clone(), Term::constant(0)));
+            constraints.push(Formula::lt(value.clone(), modulus));
+            constraints.push(Formula::eq(result, value));
+        }
+        (true, "add") => {
+            constraints.push(Formula::ge(value.clone(), modulus.clone()));
+            constraints.push(Formula::eq(result, value - modulus));
+        }
+        (true, _) => {
+            constraints.push(Formula::lt(value.clone(), Term::constant(0)));
+            constraints.push(Formula::eq(result, value + modulus));
+        }
+    }
+
+    Some(Formula::and(constraints))
+}
+
+/// Handles the bounded integers divisions
+/// `bounded_int_div_rem<Lhs, Rhs>(range_check, lhs, rhs) -> (range_check, quotient, remainder)`
+fn handle_bounded_int_div_rem(
+    libfunc_kind: LibfuncKind,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    if libfunc_kind != LibfuncKind::BoundedIntDivRem {
+        return None;
+    }
+
+    let lhs = Term::var(parameters.get(1)?);
+    let rhs = Term::var(parameters.get(2)?);
+    let quotient = Term::var(assigned_variables.get(1)?);
+    let remainder = Term::var(assigned_variables.get(2)?);
+
+    Some(Formula::and(vec![
+        Formula::eq(lhs, quotient * rhs.clone() + remainder.clone()),
+        Formula::ge(remainder.clone(), Term::constant(0)),
+        Formula::lt(remainder, rhs),
+    ]))
+}
+
+/// Handles the dictionaries (Felt252Dict) operations
+/// A dictionary is an array indexed by key, the missing keys have a zero value.
+/// An entry keeps the dictionary it was taken from & its key until it is finalized
+fn handle_dict_operations(
+    libfunc_kind: LibfuncKind,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    // felt252_dict_new<T>(segment_arena) -> (segment_arena, dict)
+    if libfunc_kind == LibfuncKind::DictNew {
+        return Some(Formula::array_eq(
+            dict_array(assigned_variables.last()?),
+            ArrayTerm::constant(Term::constant(0)),
+        ));
+    }
+
+    // felt252_dict_entry_get<T>(dict, key) -> (entry, previous_value)
+    if libfunc_kind == LibfuncKind::DictEntryGet {
+        let dict = dict_array(parameters.first()?);
+        let key = Term::var(parameters.get(1)?);
+        let entry = assigned_variables.first()?;
+
+        return Some(Formula::and(vec![
+            Formula::array_eq(dict_array(entry), dict.clone()),
+            Formula::eq(dict_entry_key(entry), key.clone()),
+            Formula::eq(
+                Term::var(assigned_variables.get(1)?),
+                Term::select(dict, key),
+            ),
+        ]));
+    }
+
+    // felt252_dict_entry_finalize<T>(entry, new_value) -> (dict)
+    if libfunc_kind == LibfuncKind::DictEntryFinalize {
+        let entry = parameters.first()?;
+        let value = Term::var(parameters.get(1)?);
+
+        return Some(Formula::array_eq(
+            dict_array(assigned_variables.first()?),
+            dict_array(entry).store(dict_entry_key(entry), value),
+        ));
+    }
+
+    // felt252_dict_squash<T>(range_check, gas, segment_arena, dict) -> (range_check, gas, segment_arena, squashed_dict)
+    if libfunc_kind == LibfuncKind::DictSquash {
+        return Some(Formula::array_eq(
+            dict_array(assigned_variables.last()?),
+            dict_array(parameters.last()?),
+        ));
+    }
+
+    None
+}
+
+/// Returns the array holding the values of a dictionary (or of the dictionary of an entry)
+fn dict_array(variable: &str) -> ArrayTerm {
+    ArrayTerm::var(&format!("dict_{}", variable))
+}
+
+/// Returns the key of a dictionary entry
+fn dict_entry_key(entry: &str) -> Term {
+    Term::var(&format!("key_{}", entry))
+}
+
+/// Handles the arrays creation, appends, lengths & copies
+/// An array (or a span, or a snapshot of them) is an unbounded array of values & the `[start, end)`
+/// range of its elements, so popping an element from the front doesn't shift the values.
+/// Spans are structs wrapping an array snapshot, they share the values & range of the array
+fn handle_array_operations(
+    libfunc_kind: LibfuncKind,
+    libfunc_id_str: &str,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    // array_new<T>() -> (array)
+    if libfunc_kind == LibfuncKind::ArrayNew {
+        let array = assigned_variables.first()?;
+        return Some(Formula::and(vec![
+            Formula::array_eq(array_values(array), ArrayTerm::constant(Term::constant(0))),
+            Formula::eq(array_start(array), Term::constant(0)),
+            Formula::eq(array_end(array), Term::constant(0)),
+        ]));
+    }
+
+    // array_append<T>(array, value) -> (array)
+    if libfunc_kind == LibfuncKind::ArrayAppend {
+        let array = parameters.first()?;
+        let value = Term::var(parameters.get(1)?);
+        let appended_array = assigned_variables.first()?;
+        return Some(Formula::and(vec![
+            Formula::array_eq(
+                array_values(appended_array),
+                array_values(array).store(array_end(array), value),
+            ),
+            Formula::eq(array_start(appended_array), array_start(array)),
+            Formula::eq(
+                array_end(appended_array),
+                array_end(array) + Term::constant(1),
+            ),
+        ]));
+    }
+
+    // array_len<T>(array) -> (length)
+    if libfunc_kind == LibfuncKind::ArrayLen {
+        return Some(Formula::eq(
+            Term::var(assigned_variables.first()?),
+            array_length(parameters.first()?),
+        ));
+    }
+
+    // Copies, snapshots & spans construction or deconstruction
+    // e.g. `store_temp<Snapshot<Array<felt252>>>(array) -> (array)`
+    let is_copy = libfunc_kind.is_assignment()
+        || matches!(libfunc_kind, LibfuncKind::Dup | LibfuncKind::SnapshotTake);
+    let copies_array = is_copy
+        && libfunc_id_str
+            .split_once('<')
+            .and_then(|(_, type_name)| type_name.strip_suffix('>'))
+            .is_some_and(|type_name| ARRAY_TYPE_REGEX.is_match(type_name));
+    if copies_array || SPAN_STRUCT_REGEX.is_match(libfunc_id_str) {
+        let array = parameters.first()?;
+        return Some(Formula::and(
+            assigned_variables
+                .iter()
+                .filter(|copy| *copy != array)
+                .flat_map(|copy| array_copy(copy, array))
+                .collect(),
+        ));
+    }
+
+    None
+}
+
+/// Handles the arrays accesses, branch 1 is taken if the array is empty or the access is out of bounds
+/// `array_snapshot_pop_front<T>(array) { fallthrough(array, element) 12(array) }`
+/// `array_get<T>(range_check, array, index) { fallthrough(range_check, element) 42(range_check) }`
+/// `array_slice<T>(range_check, array, start, length) { fallthrough(range_check, slice) 42(range_check) }`
+fn handle_array_accesses(
+    libfunc_kind: LibfuncKind,
+    parameters: &[String],
+    out_of_bounds: bool,
+    branch_variables: &[String],
+) -> Option<Formula> {
+    match libfunc_kind {
+        LibfuncKind::ArrayPopFront | LibfuncKind::ArrayPopBack => {
+            let array = parameters.first()?;
+            let is_empty = Formula::ge(array_start(array), array_end(array));
+            if out_of_bounds {
+                // The consuming pop doesn't return the empty array
+                let mut constraints = vec![is_empty];
+                if let Some(remaining_array) = branch_variables.first() {
+                    constraints.extend(array_copy(remaining_array, array));
+                }
+                return Some(Formula::and(constraints));
+            }
+
+            let remaining_array = branch_variables.first()?;
+            let element = Term::var(branch_variables.get(1)?);
+            let (start, end, index) = if libfunc_kind == LibfuncKind::ArrayPopFront {
+                (
+                    array_start(array) + Term::constant(1),
+                    array_end(array),
+                    array_start(array),
+                )
+            } else {
+                (
+                    array_start(array),
+                    array_end(array) - Term::constant(1),
+                    array_end(array) - Term::constant(1),
+                )
+            };
+            Some(Formula::and(vec![
+                !is_empty,
+                Formula::array_eq(array_values(remaining_array), array_values(array)),
+                Formula::eq(array_start(remaining_array), start),
+                Formula::eq(array_end(remaining_array), end),
+                Formula::eq(element, Term::select(array_values(array), index)),
+            ]))
+        }
+        LibfuncKind::ArrayGet => {
+            let array = parameters.get(1)?;
+            let index = Term::var(parameters.get(2)?);
+            let in_bounds = Formula::and(vec![
+                Formula::ge(index.clone(), Term::constant(0)),
+                Formula::lt(index.clone(), array_length(array)),
+            ]);
+            if out_of_bounds {
+                return Some(!in_bounds);
+            }
+
+            let element = Term::var(branch_variables.get(1)?);
+            Some(Formula::and(vec![
+                in_bounds,
+                Formula::eq(
+                    element,
+                    Term::select(array_values(array), array_start(array) + index),
+                ),
+            ]))
+        }
+        LibfuncKind::ArraySlice => {
+            let array = parameters.get(1)?;
+            let start = Term::var(parameters.get(2)?);
+            let length = Term::var(parameters.get(3)?);
+            let in_bounds = Formula::and(vec![
+                Formula::ge(start.clone(), Term::constant(0)),
+                Formula::ge(length.clone(), Term::constant(0)),
+                Formula::le(start.clone() + length.clone(), array_length(array)),
+            ]);
+            if out_of_bounds {
+                return Some(!in_bounds);
+            }
+
+            let slice = branch_variables.get(1)?;
+            let slice_start = array_start(array) + start;
+            Some(Formula::and(vec![
+                in_bounds,
+                Formula::array_eq(array_values(slice), array_values(array)),
+                Formula::eq(array_start(slice), slice_start.clone()),
+                Formula::eq(array_end(slice), slice_start + length),
+            ]))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the values of an array, indexed from the start of the array when it was created
+pub(crate) fn array_values(variable: &str) -> ArrayTerm {
+    ArrayTerm::var(&format!("array_{}", variable))
+}
+
+/// Returns the index of the first element of an array in its values
+pub(crate) fn array_start(variable: &str) -> Term {
+    Term::var(&format!("start_{}", variable))
+}
+
+/// Returns the index following the last element of an array in its values
+pub(crate) fn array_end(variable: &str) -> Term {
+    Term::var(&format!("end_{}", variable))
+}
+
+/// Returns the number of elements of an array
+fn array_length(variable: &str) -> Term {
+    array_end(variable) - array_start(variable)
+}
+
+/// Returns the constraints of the copy of an array
+fn array_copy(copy: &str, array: &str) -> Vec<Formula> {
+    vec![
+        Formula::array_eq(array_values(copy), array_values(array)),
+        Formula::eq(array_start(copy), array_start(array)),
+        Formula::eq(array_end(copy), array_end(array)),
+    ]
+}
+
+/// Handles boolean operations in Sierra statements
+/// Booleans are encoded as integers : 0 is false & 1 is true
+fn handle_boolean_operations(
+    libfunc_id_str: &str,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    let assigned_variable = Term::var(assigned_variables.first()?);
+
+    // Boolean initialization
+    if let Some(captures) = BOOL_INIT_REGEX.captures(libfunc_id_str) {
+        let variant = u64::from_str(&captures["variant"]).ok()?;
+        return Some(Formula::eq(assigned_variable, Term::constant(variant)));
+    }
+
+    if BOOL_NOT_REGEX.is_match(libfunc_id_str) {
+        let operand = Term::var(parameters.first()?);
+        return Some(Formula::eq(assigned_variable, Term::constant(1) - operand));
+    }
+
+    if parameters.len() < 2 {
+        return None;
+    }
+    let lhs = Term::var(&parameters[0]);
+    let rhs = Term::var(&parameters[1]);
+
+    let value = if BOOL_AND_REGEX.is_match(libfunc_id_str) {
+        lhs.clone() * rhs.clone()
+    } else if BOOL_OR_REGEX.is_match(libfunc_id_str) {
+        lhs.clone() + rhs.clone() - lhs.clone() * rhs.clone()
+    } else if BOOL_XOR_REGEX.is_match(libfunc_id_str) {
+        lhs.clone() + rhs.clone() - Term::constant(2) * lhs.clone() * rhs.clone()
+    } else {
+        return None;
+    };
+
+    // The operands are booleans
+    Some(Formula::and(vec![
+        Formula::eq(assigned_variable, value),
+        Formula::ge(lhs.clone(), Term::constant(0)),
+        Formula::le(lhs, Term::constant(1)),
+        Formula::ge(rhs.clone(), Term::constant(0)),
+        Formula::le(rhs, Term::constant(1)),
+    ]))
+}
+
+/// Handles the storage addresses computations in Sierra statements
+/// The storage addresses are used as indexes of the symbolic storage
+fn handle_storage_addresses(
+    libfunc_id_str: &str,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    if STORAGE_ADDRESS_FROM_BASE_REGEX.is_match(libfunc_id_str) {
+        return Some(Formula::eq(
+            Term::var(assigned_variables.first()?),
+            Term::var(parameters.first()?),
+        ));
+    }
+
+    if STORAGE_ADDRESS_FROM_BASE_AND_OFFSET_REGEX.is_match(libfunc_id_str) {
+        return Some(Formula::eq(
+            Term::var(assigned_variables.first()?),
+            Term::var(parameters.first()?) + Term::var(parameters.get(1)?),
+        ));
+    }
+
+    // storage_base_address_from_felt252(range_check, felt) -> (range_check, base)
+    // The address is reduced modulo 2**251 - 256, the reduction is not modeled
+    if STORAGE_BASE_ADDRESS_FROM_FELT252_REGEX.is_match(libfunc_id_str) {
+        return Some(Formula::eq(
+            Term::var(assigned_variables.get(1)?),
+            Term::var(parameters.get(1)?),
+        ));
+    }
+
+    None
+}
+
+/// Handles the enums initialization in Sierra statements
+/// Enums are encoded as the index of their variant
+fn handle_enum_init(libfunc_id_str: &str, assigned_variables: &[String]) -> Option<Formula> {
+    let captures = ENUM_INIT_REGEX.captures(libfunc_id_str)?;
+    let variant = u64::from_str(&captures["variant"]).ok()?;
+
+    Some(Formula::eq(
+        Term::var(assigned_variables.first()?),
+        Term::constant(variant),
+    ))
+}
+
+/// Handles variable assignment (renaming, temporary & local variables) in Sierra statements
+fn handle_assignment(
+    libfunc_kind: LibfuncKind,
+    libfunc_id_str: &str,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    if libfunc_kind.is_assignment() {
+        // The dictionaries & their entries are arrays
+        if libfunc_id_str.contains("Felt252Dict") {
+            let assigned_variable = assigned_variables.first()?;
+            let variable = parameters.first()?;
+            return Some(Formula::and(vec![
+                Formula::array_eq(dict_array(assigned_variable), dict_array(variable)),
+                Formula::eq(dict_entry_key(assigned_variable), dict_entry_key(variable)),
+            ]));
+        }
+
+        return Some(Formula::eq(
+            Term::var(assigned_variables.first()?),
+            Term::var(parameters.first()?),
+        ));
+    }
+    None
+}
+
+/// Handles the snapshots & boxes creation in Sierra statements, they don't change the values
+/// `snapshot_take<T>(value) -> (value, snapshot)` & `into_box<T>(value) -> (box)`
+fn handle_snapshot_and_box(
+    libfunc_kind: LibfuncKind,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    if !matches!(
+        libfunc_kind,
+        LibfuncKind::SnapshotTake | LibfuncKind::IntoBox
+    ) {
+        return None;
+    }
+
+    let value = Term::var(parameters.first()?);
+    Some(Formula::and(
+        assigned_variables
+            .iter()
+            .map(|variable| Formula::eq(Term::var(variable), value.clone()))
+            .collect(),
+    ))
+}
+
+/// Handles variable duplication in Sierra statements
+/// `dup<T>(value) -> (value, copy)`, both results are new versions of the value
+fn handle_duplication(
+    libfunc_kind: LibfuncKind,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    if libfunc_kind == LibfuncKind::Dup {
+        let value = Term::var(parameters.first()?);
+        return Some(Formula::and(
+            assigned_variables
+                .iter()
+                .map(|variable| Formula::eq(Term::var(variable), value.clone()))
+                .collect(),
+        ));
+    }
+    None
+}
+
+/// Handles constant assignment in Sierra statements
+fn handle_constant_assignment(
+    libfunc_kind: LibfuncKind,
+    libfunc_id_str: &str,
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    if libfunc_kind != LibfuncKind::Const {
+        return None;
+    }
+
+    for regex in CONST_REGEXES.iter() {
+        if let Some(captures) = regex.captures(libfunc_id_str) {
+            if let Some(const_value) = captures.name("const") {
+                let const_value_str = const_value.as_str();
+                if let Ok(const_value) = BigInt::from_str(const_value_str) {
+                    if !assigned_variables.is_empty() {
+                        let assigned_var = Term::var(&assigned_variables[0]);
+                        return Some(Formula::eq(assigned_var, Term::Const(const_value)));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Handles zero check in Sierra statements
+fn handle_is_zero(libfunc_kind: LibfuncKind, parameters: &[String]) -> Option<Formula> {
+    if libfunc_kind == LibfuncKind::IsZero {
+        let operand = Term::var(&parameters[0]);
+        return Some(Formula::eq(operand, Term::constant(0)));
+    }
+    None
+}
+
+/// Handles arithmetic operations in Sierra statements
+fn handle_arithmetic_operations(
+    libfunc_kind: LibfuncKind,
+    parameters: &[String],
+    assigned_variables: &[String],
+) -> Option<Formula> {
+    let operator = match libfunc_kind {
+        LibfuncKind::Addition => "+",
+        LibfuncKind::Subtraction => "-",
+        LibfuncKind::Multiplication => "*",
+        _ => return None,
+    };
+
+    let assigned_variable = Term::var(&assigned_variables[0]);
+    let first_operand = Term::var(&parameters[0]);
+    let second_operand = Term::var(&parameters[1]);
+
+    let constraint = match operator {
+        "+" => Formula::eq(assigned_variable, first_operand + second_operand),
+        "-" => Formula::eq(assigned_variable, first_operand - second_operand),
+        "*" => Formula::eq(assigned_variable, first_operand * second_operand),
+        _ => return None,
+    };
+
+    Some(constraint)
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use tracing::{debug, instrument};
+#[cfg(feature = "z3")]
+use z3::{Config, Context};
+
+#[cfg(feature = "z3")]
+use crate::config::SymbolicExecutionConfig;
+use crate::decompiler::function::Function;
+use crate::sym_exec::formula::Formula;
+use crate::sym_exec::hashes::HashModel;
+use crate::sym_exec::solver::{Solver, SolverResult};
+use crate::sym_exec::sym_exec::{trace_frames, Frame};
+use crate::sym_exec::unrolling::unrolled_traces;
+#[cfg(feature = "z3")]
+use crate::sym_exec::z3_solver::Z3Solver;
+
+/// Coverage of a basic block by the generated inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockCoverage {
+    /// A generated input reaches the block
+    Covered,
+    /// The explored paths reaching the block are unsatisfiable, with the constraints of the first one
+    Unsatisfiable(Vec<Formula>),
+    /// The solver could not decide if a path reaching the block is satisfiable
+    Unknown,
+    /// No explored path reaches the block (paths limit or unroll bound)
+    Unexplored,
+}
+
+/// A basic block of a function & its coverage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockReport {
+    /// Offset of the first statement of the block
+    pub offset: u32,
+    pub coverage: BlockCoverage,
+}
+
+/// Returns the coverage of the basic blocks of a function of a program using the Z3 solver
+/// See `coverage_map_with_solver`
+#[cfg(feature = "z3")]
+pub fn coverage_map(
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    hash_model: HashModel,
+) -> Vec<BlockReport> {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    let mut solver = Z3Solver::new(&context);
+
+    coverage_map_with_solver(
+        &mut solver,
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        SymbolicExecutionConfig::UNROLL_BOUND,
+        hash_model,
+    )
+}
+
+/// Returns the coverage of the basic blocks of a function of a program with a given solver backend
+/// A block is covered if the constraints of an explored path prefix reaching it are satisfiable, i.e. if
+/// the tests generator creates an input reaching it. The paths are the ones explored by the tests generator,
+/// with the loops unrolled up to `unroll_bound` times.
+#[instrument(skip_all, fields(function_index = function_index))]
+pub fn coverage_map_with_solver<S: Solver>(
+    solver: &mut S,
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    unroll_bound: usize,
+    hash_model: HashModel,
+) -> Vec<BlockReport> {
+    let cfg = functions[function_index].cfg();
+
+    let traces = unrolled_traces(
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        unroll_bound,
+    );
+    debug!("{} unrolled traces", traces.len());
+
+    // Offsets of the function statements reached by a satisfiable path prefix
+    let mut reached_offsets = HashSet::new();
+    // Offsets of the function statements reached by a path prefix of unknown satisfiability
+    let mut unknown_offsets = HashSet::new();
+    // Offsets of the function statements reached by the unsatisfiable path prefixes & their constraints
+    let mut unsatisfiable_prefixes: Vec<(Vec<u32>, Vec<Formula>)> = Vec::new();
+
+    // Frames currently pushed on the solver & their satisfiability
+    let mut stack: Vec<(Frame, SolverResult)> = Vec::new();
+    solver.reset();
+
+    for trace in &traces {
+        let frames = trace_frames(trace, declared_libfuncs_names, hash_model);
+
+        // Pop the frames of the previous trace that are not shared with this one
+        let shared_frames = stack
+            .iter()
+            .zip(&frames)
+            .take_while(|((pushed_frame, _), frame)| pushed_frame == *frame)
+            .count();
+        while stack.len() > shared_frames {
+            solver.pop();
+            stack.pop();
+        }
+
+        // A block is reached if the frames before it are satisfiable, each block frame contains the
+        // condition of the branch to the next block
+        for (block_index, (block, frame)) in trace.blocks.iter().zip(frames).enumerate() {
+            if stack.len() <= block_index {
+                solver.push();
+                for constraint in &frame.constraints {
+                    solver.assert(constraint);
+                }
+                let result = solver.check();
+                stack.push((frame, result));
+            }
+
+            // The statements of the inlined functions instances have a suffix
+            let offsets = block
+                .iter()
+                .filter(|trace_statement| trace_statement.suffix.is_empty())
+                .map(|trace_statement| trace_statement.statement.offset);
+
+            match stack[block_index].1 {
+                SolverResult::Sat => reached_offsets.extend(offsets),
+                SolverResult::Unknown => {
+                    unknown_offsets.extend(offsets);
+                    break;
+                }
+                SolverResult::Unsat => {
+                    let constraints: Vec<Formula> = stack
+                        .iter()
+                        .flat_map(|(frame, _)| frame.constraints.iter().cloned())
+                        .collect();
+                    unsatisfiable_prefixes.push((offsets.collect(), constraints));
+                    break;
+                }
+            }
+        }
+    }
+
+    cfg.basic_blocks
+        .iter()
+        .map(|basic_block| {
+            let block_offsets: Vec<u32> = basic_block
+                .statements
+                .iter()
+                .map(|statement| statement.offset)
+                .collect();
+
+            let coverage = if block_offsets
+                .iter()
+                .any(|offset| reached_offsets.contains(offset))
+            {
+                BlockCoverage::Covered
+            } else if block_offsets
+                .iter()
+                .any(|offset| unknown_offsets.contains(offset))
+            {
+                BlockCoverage::Unknown
+            } else if let Some((_, constraints)) = unsatisfiable_prefixes
+                .iter()
+                .find(|(offsets, _)| offsets.iter().any(|offset| block_offsets.contains(offset)))
+            {
+                BlockCoverage::Unsatisfiable(constraints.clone())
+            } else {
+                BlockCoverage::Unexplored
+            };
+
+            BlockReport {
+                offset: basic_block.start_offset,
+                coverage,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::ops;
+
+use num_bigint::BigInt;
+
+/// An integer term of a constraint
+/// The Sierra variables are encoded as unbounded integers
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term {
+    /// Symbolic variable, identified by its name
+    Var(String),
+    /// Integer constant
+    Const(BigInt),
+    /// Addition of two terms
+    Add(Box<Term>, Box<Term>),
+    /// Substraction of two terms
+    Sub(Box<Term>, Box<Term>),
+    /// Multiplication of two terms
+    Mul(Box<Term>, Box<Term>),
+    /// Value of an array at an index
+    Select(Box<ArrayTerm>, Box<Term>),
+    /// Application of an uninterpreted function, identified by its name, to integer terms
+    App(String, Vec<Term>),
+}
+
+impl Term {
+    /// Creates a symbolic variable
+    #[inline]
+    pub fn var(name: &str) -> Self {
+        Term::Var(name.to_string())
+    }
+
+    /// Creates an integer constant
+    #[inline]
+    pub fn constant<T: Into<BigInt>>(value: T) -> Self {
+        Term::Const(value.into())
+    }
+
+    /// Creates the value of an array at an index
+    #[inline]
+    pub fn select(array: ArrayTerm, index: Term) -> Self {
+        Term::Select(Box::new(array), Box::new(index))
+    }
+
+    /// Creates the application of an uninterpreted function
+    #[inline]
+    pub fn app(function: &str, arguments: Vec<Term>) -> Self {
+        Term::App(function.to_string(), arguments)
+    }
+}
+
+/// An array of integers indexed by integers
+/// Used to model the contract storage, indexed by storage address, and the dictionaries
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayTerm {
+    /// Symbolic array, identified by its name
+    Var(String),
+    /// Array with the same value at every index
+    Const(Box<Term>),
+    /// Array where the value at an index is replaced
+    Store(Box<ArrayTerm>, Box<Term>, Box<Term>),
+}
+
+impl ArrayTerm {
+    /// Creates a symbolic array
+    #[inline]
+    pub fn var(name: &str) -> Self {
+        ArrayTerm::Var(name.to_string())
+    }
+
+    /// Creates an array with the same value at every index
+    #[inline]
+    pub fn constant(value: Term) -> Self {
+        ArrayTerm::Const(Box::new(value))
+    }
+
+    /// Creates the array where the value at `index` is replaced by `value`
+    #[inline]
+    pub fn store(self, index: Term, value: Term) -> Self {
+        ArrayTerm::Store(Box::new(self), Box::new(index), Box::new(value))
+    }
+}
+
+impl ops::Add for Term {
+    type Output = Term;
+
+    fn add(self, rhs: Term) -> Term {
+        Term::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl ops::Sub for Term {
+    type Output = Term;
+
+    fn sub(self, rhs: Term) -> Term {
+        Term::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl ops::Mul for Term {
+    type Output = Term;
+
+    fn mul(self, rhs: Term) -> Term {
+        Term::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+/// A boolean constraint over integer terms
+/// This is the solver-independent representation of the Sierra statements semantics,
+/// each solver backend translates it to its own expressions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Formula {
+    /// `lhs == rhs`
+    Eq(Term, Term),
+    /// `lhs == rhs` for arrays
+    ArrayEq(ArrayTerm, ArrayTerm),
+    /// `lhs < rhs`
+    Lt(Term, Term),
+    /// `lhs <= rhs`
+    Le(Term, Term),
+    /// Negation of a formula
+    Not(Box<Formula>),
+    /// Conjunction of formulas
+    And(Vec<Formula>),
+}
+
+impl Formula {
+    /// Creates the `lhs == rhs` formula
+    #[inline]
+    pub fn eq(lhs: Term, rhs: Term) -> Self {
+        Formula::Eq(lhs, rhs)
+    }
+
+    /// Creates the `lhs == rhs` formula for arrays
+    #[inline]
+    pub fn array_eq(lhs: ArrayTerm, rhs: ArrayTerm) -> Self {
+        Formula::ArrayEq(lhs, rhs)
+    }
+
+    /// Creates the `lhs < rhs` formula
+    #[inline]
+    pub fn lt(lhs: Term, rhs: Term) -> Self {
+        Formula::Lt(lhs, rhs)
+    }
+
+    /// Creates the `lhs <= rhs` formula
+    #[inline]
+    pub fn le(lhs: Term, rhs: Term) -> Self {
+        Formula::Le(lhs, rhs)
+    }
+
+    /// Creates the `lhs >= rhs` formula
+    #[inline]
+    pub fn ge(lhs: Term, rhs: Term) -> Self {
+        Formula::Le(rhs, lhs)
+    }
+
+    /// Creates the conjunction of formulas
+    #[inline]
+    pub fn and(formulas: Vec<Formula>) -> Self {
+        Formula::And(formulas)
+    }
+}
+
+impl ops::Not for Formula {
+    type Output = Formula;
+
+    fn not(self) -> Formula {
+        Formula::Not(Box::new(self))
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(name) => write!(f, "{}", name),
+            Term::Const(value) => write!(f, "{}", value),
+            Term::Add(lhs, rhs) => write!(f, "({} + {})", lhs, rhs),
+            Term::Sub(lhs, rhs) => write!(f, "({} - {})", lhs, rhs),
+            Term::Mul(lhs, rhs) => write!(f, "({} * {})", lhs, rhs),
+            Term::Select(array, index) => write!(f, "{}[{}]", array, index),
+            Term::App(function, arguments) => {
+                let arguments: Vec<String> = arguments
+                    .iter()
+                    .map(|argument| argument.to_string())
+                    .collect();
+                write!(f, "{}({})", function, arguments.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ArrayTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayTerm::Var(name) => write!(f, "{}", name),
+            ArrayTerm::Const(value) => write!(f, "const({})", value),
+            ArrayTerm::Store(array, index, value) => {
+                write!(f, "store({}, {}, {})", array, index, value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Formula::Eq(lhs, rhs) => write!(f, "{} == {}", lhs, rhs),
+            Formula::ArrayEq(lhs, rhs) => write!(f, "{} == {}", lhs, rhs),
+            Formula::Lt(lhs, rhs) => write!(f, "{} < {}", lhs, rhs),
+            Formula::Le(lhs, rhs) => write!(f, "{} <= {}", lhs, rhs),
+            Formula::Not(formula) => write!(f, "!({})", formula),
+            // The empty conjunction is true
+            Formula::And(formulas) if formulas.is_empty() => write!(f, "true"),
+            Formula::And(formulas) => {
+                let formulas: Vec<String> = formulas
+                    .iter()
+                    .map(|formula| format!("({})", formula))
+                    .collect();
+                write!(f, "{}", formulas.join(" && "))
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use num_bigint::{BigInt, Sign};
+use starknet_core::crypto::pedersen_hash;
+use starknet_core::types::Felt;
+use starknet_crypto::poseidon_permute_comp;
+
+use crate::decompiler::libfuncs_patterns::{HADES_PERMUTATION_REGEX, PEDERSEN_REGEX};
+use crate::decompiler::utils::felt252_prime;
+use crate::sym_exec::formula::{Formula, Term};
+
+/// Modeling of the hash functions results by the solver
+/// The Pedersen hashes & the Poseidon permutations are modeled, the keccak & sha256 syscalls hash
+/// arrays that are not part of the constraints and their results are always unconstrained
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashModel {
+    /// The results are unconstrained variables
+    #[default]
+    Unconstrained,
+    /// The results are uninterpreted functions of the inputs, equal digests have equal inputs
+    Uninterpreted,
+    /// The hashes of constant inputs are evaluated, the other ones are uninterpreted functions
+    Concrete,
+}
+
+/// A hash function applied in a trace
+#[derive(Debug, Clone)]
+struct HashApplication {
+    function: &'static str,
+    inputs: Vec<Term>,
+    digest: Term,
+}
+
+/// Generates the constraints of the hash functions of a trace
+#[derive(Debug, Clone)]
+pub(crate) struct HashModeler {
+    model: HashModel,
+    /// Constant values of the variables, propagated through the assignments
+    constants: HashMap<String, BigInt>,
+    /// Hash functions applied before in the trace, used by the injectivity axioms
+    applications: Vec<HashApplication>,
+}
+
+impl HashModeler {
+    /// Creates a new `HashModeler` instance
+    pub(crate) fn new(model: HashModel) -> Self {
+        Self {
+            model,
+            constants: HashMap::new(),
+            applications: Vec::new(),
+        }
+    }
+
+    /// Returns true if the hash functions are constrained
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.model != HashModel::Unconstrained
+    }
+
+    /// Records the constant values of the variables assigned by a constraint
+    pub(crate) fn propagate_constants(&mut self, constraint: &Formula) {
+        if self.model != HashModel::Concrete {
+            return;
+        }
+
+        match constraint {
+            Formula::Eq(Term::Var(variable), Term::Const(value))
+            | Formula::Eq(Term::Const(value), Term::Var(variable)) => {
+                self.constants.insert(variable.clone(), value.clone());
+            }
+            Formula::Eq(Term::Var(lhs), Term::Var(rhs)) => {
+                if let Some(value) = self.constants.get(rhs).cloned() {
+                    self.constants.insert(lhs.clone(), value);
+                } else if let Some(value) = self.constants.get(lhs).cloned() {
+                    self.constants.insert(rhs.clone(), value);
+                }
+            }
+            Formula::And(constraints) => {
+                for constraint in constraints {
+                    self.propagate_constants(constraint);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the constraint of a hash libfunc invocation, None if the libfunc is not a modeled hash
+    /// `pedersen(builtin, lhs, rhs) -> (builtin, digest)`
+    /// `hades_permutation(builtin, s0, s1, s2) -> (builtin, s0, s1, s2)`
+    pub(crate) fn constraint(
+        &mut self,
+        libfunc_name: &str,
+        parameters: &[String],
+        results: &[String],
+    ) -> Option<Formula> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let (function, inputs, outputs) = if PEDERSEN_REGEX.is_match(libfunc_name) {
+            ("pedersen", parameters.get(1..3)?, results.get(1..2)?)
+        } else if HADES_PERMUTATION_REGEX.is_match(libfunc_name) {
+            (
+                "hades_permutation",
+                parameters.get(1..4)?,
+                results.get(1..4)?,
+            )
+        } else {
+            return None;
+        };
+
+        let input_terms: Vec<Term> = inputs.iter().map(|input| Term::var(input)).collect();
+        let mut constraints = Vec::new();
+
+        // Each output is an uninterpreted function of the inputs
+        for (index, output) in outputs.iter().enumerate() {
+            let function_name = match outputs.len() {
+                1 => function.to_string(),
+                _ => format!("{}_{}", function, index),
+            };
+            constraints.push(Formula::eq(
+                Term::var(output),
+                Term::app(&function_name, input_terms.clone()),
+            ));
+        }
+
+        // The hashes of constant inputs are computed
+        let input_values: Option<Vec<BigInt>> = inputs
+            .iter()
+            .map(|input| self.constants.get(input).cloned())
+            .collect();
+        if let Some(input_values) = input_values.filter(|_| self.model == HashModel::Concrete) {
+            let digests = match function {
+                "pedersen" => vec![pedersen(&input_values[0], &input_values[1])],
+                _ => hades_permutation(&input_values),
+            };
+            for (output, digest) in outputs.iter().zip(digests) {
+                constraints.push(Formula::eq(Term::var(output), Term::Const(digest.clone())));
+                self.constants.insert(output.clone(), digest);
+            }
+        }
+
+        // Injectivity : the applications with equal digests have equal inputs
+        // The digest of the Poseidon permutation is its first output, as in the Poseidon hash
+        let digest = Term::var(&outputs[0]);
+        for application in &self.applications {
+            if application.function != function {
+                continue;
+            }
+            let equal_inputs = Formula::and(
+                application
+                    .inputs
+                    .iter()
+                    .zip(&input_terms)
+                    .map(|(previous_input, input)| {
+                        Formula::eq(previous_input.clone(), input.clone())
+                    })
+                    .collect(),
+            );
+            constraints.push(!Formula::and(vec![
+                Formula::eq(application.digest.clone(), digest.clone()),
+                !equal_inputs,
+            ]));
+        }
+        self.applications.push(HashApplication {
+            function,
+            inputs: input_terms,
+            digest,
+        });
+
+        Some(Formula::and(constraints))
+    }
+}
+
+/// Returns the Pedersen hash of two felt252 values
+pub(crate) fn pedersen(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    felt_to_bigint(&pedersen_hash(&bigint_to_felt(lhs), &bigint_to_felt(rhs)))
+}
+
+/// Returns the Poseidon permutation of a state of three felt252 values
+pub(crate) fn hades_permutation(state: &[BigInt]) -> Vec<BigInt> {
+    let mut felts = [Felt::ZERO; 3];
+    for (felt, value) in felts.iter_mut().zip(state) {
+        *felt = bigint_to_felt(value);
+    }
+    poseidon_permute_comp(&mut felts);
+    felts.iter().map(felt_to_bigint).collect()
+}
+
+/// Returns the value of an integer in the felt252 field
+pub(crate) fn felt252_value(value: &BigInt) -> BigInt {
+    let prime = felt252_prime();
+    ((value % &prime) + &prime) % &prime
+}
+
+/// Converts an integer to a felt, the negative values are reduced modulo the prime
+pub(crate) fn bigint_to_felt(value: &BigInt) -> Felt {
+    let (_, bytes) = felt252_value(value).to_bytes_be();
+    let mut padded_bytes = [0u8; 32];
+    padded_bytes[32 - bytes.len()..].copy_from_slice(&bytes);
+    Felt::from_bytes_be(&padded_bytes)
+}
+
+/// Converts a felt to an integer
+pub(crate) fn felt_to_bigint(felt: &Felt) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, &felt.to_bytes_be())
+}
@@ -1 +1,15 @@
+#[cfg(feature = "concolic")]
+pub mod concolic;
+pub mod constraints;
+pub mod coverage;
+pub mod formula;
+pub mod hashes;
+pub mod prove;
+pub mod smtlib;
+pub mod snforge;
+pub mod solver;
+pub mod storage;
 pub mod sym_exec;
+pub mod unrolling;
+#[cfg(feature = "z3")]
+pub mod z3_solver;
@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use regex::Regex;
+use tracing::{debug, instrument};
+#[cfg(feature = "z3")]
+use z3::{Config, Context};
+
+#[cfg(feature = "z3")]
+use crate::config::SymbolicExecutionConfig;
+use crate::decompiler::function::Function;
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::sym_exec::formula::{Formula, Term};
+use crate::sym_exec::hashes::HashModel;
+use crate::sym_exec::solver::{Solver, SolverResult};
+use crate::sym_exec::storage::storage_pre_state;
+use crate::sym_exec::sym_exec::trace_frames;
+use crate::sym_exec::unrolling::{inlined_traces, UnrolledTrace};
+#[cfg(feature = "z3")]
+use crate::sym_exec::z3_solver::Z3Solver;
+
+lazy_static! {
+    /// Integer types of the arguments that are part of a witness
+    static ref INTEGER_TYPE_REGEX: Regex =
+        Regex::new(r"^(core::integer::)?(?P<signedness>[ui])(?P<bits>8|16|32|64|128)$").unwrap();
+}
+
+/// Reachability of a panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PanicReachability {
+    /// The panic is reachable with the given inputs
+    Reachable(Witness),
+    /// No explored path reaches the panic
+    Unreachable,
+    /// The solver could not decide the reachability of the panic
+    Unknown,
+}
+
+/// Concrete inputs reaching a panic
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Witness {
+    /// Values of the function integer arguments
+    pub arguments: Vec<(String, BigInt)>,
+    /// Values of the storage slots required before the call, indexed by address
+    pub storage: Vec<(BigInt, BigInt)>,
+}
+
+/// A panic (assert-like pattern) of a function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    /// Offset of the statement constructing the panic
+    pub offset: u32,
+    /// Reachability of the panic
+    pub reachability: PanicReachability,
+}
+
+/// Checks the reachability of the panics of a function of a program using the Z3 solver
+/// See `prove_panics_with_solver`
+#[cfg(feature = "z3")]
+pub fn prove_panics(
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    hash_model: HashModel,
+) -> Vec<PanicReport> {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    let mut solver = Z3Solver::new(&context);
+
+    prove_panics_with_solver(
+        &mut solver,
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        SymbolicExecutionConfig::INLINE_DEPTH,
+        hash_model,
+    )
+}
+
+/// Checks the reachability of the panics of a function of a program with a given solver backend
+/// Instead of covering the paths, each panic is reported with a witness if a path reaching it is satisfiable.
+/// The user-defined functions calls are inlined up to `inline_depth` nested calls.
+#[instrument(skip_all, fields(function_index = function_index))]
+pub fn prove_panics_with_solver<S: Solver>(
+    solver: &mut S,
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    inline_depth: usize,
+    hash_model: HashModel,
+) -> Vec<PanicReport> {
+    let function = &functions[function_index];
+    // The panics of the callees (e.g. overflow checks of the core library) are reachable from the function
+    let traces = inlined_traces(
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        inline_depth,
+    );
+    debug!("{} inlined traces", traces.len());
+
+    // Traces reaching each panic, indexed by the panic offset
+    let mut panics: BTreeMap<u32, Vec<&UnrolledTrace>> = BTreeMap::new();
+    for trace in &traces {
+        if let Some(offset) = panic_offset(trace) {
+            panics.entry(offset).or_default().push(trace);
+        }
+    }
+
+    panics
+        .into_iter()
+        .map(|(offset, panic_traces)| {
+            let mut reachability = PanicReachability::Unreachable;
+
+            for trace in panic_traces {
+                solver.reset();
+
+                // The integer arguments are in their type range
+                for constraint in arguments_ranges(function) {
+                    solver.assert(&constraint);
+                }
+
+                let frames = trace_frames(trace, declared_libfuncs_names, hash_model);
+                for frame in &frames {
+                    for constraint in &frame.constraints {
+                        solver.assert(constraint);
+                    }
+                }
+
+                match solver.check() {
+                    SolverResult::Sat => {
+                        let storage_reads: Vec<_> = frames
+                            .iter()
+                            .flat_map(|frame| frame.storage_reads.iter().cloned())
+                            .collect();
+                        let witness = Witness {
+                            arguments: integer_arguments(function)
+                                .filter_map(|(name, _)| {
+                                    solver.value(&name).map(|value| (name, value))
+                                })
+                                .collect(),
+                            storage: storage_pre_state(solver, &storage_reads),
+                        };
+                        reachability = PanicReachability::Reachable(witness);
+                        break;
+                    }
+                    SolverResult::Unknown => reachability = PanicReachability::Unknown,
+                    SolverResult::Unsat => {}
+                }
+            }
+
+            PanicReport {
+                offset,
+                reachability,
+            }
+        })
+        .collect()
+}
+
+/// Returns the offset of the first panic constructed in a trace
+fn panic_offset(trace: &UnrolledTrace) -> Option<u32> {
+    trace
+        .blocks
+        .iter()
+        .flatten()
+        .find(|trace_statement| trace_statement.statement.libfunc_kind == LibfuncKind::Panic)
+        .map(|trace_statement| trace_statement.statement.offset)
+}
+
+/// Returns the felt252 & integer arguments of a function with their type
+pub(crate) fn integer_arguments<'f>(
+    function: &'f Function,
+) -> impl Iterator<Item = (String, &'f str)> + 'f {
+    function
+        .arguments
+        .iter()
+        .filter(|(_, arg_type)| arg_type == "felt252" || INTEGER_TYPE_REGEX.is_match(arg_type))
+        .map(|(arg_name, arg_type)| (arg_name.clone(), arg_type.as_str()))
+}
+
+/// Returns the range constraints of the integer arguments of a function
+pub(crate) fn arguments_ranges(function: &Function) -> Vec<Formula> {
+    let mut constraints = Vec::new();
+
+    for (arg_name, arg_type) in integer_arguments(function) {
+        let Some(captures) = INTEGER_TYPE_REGEX.captures(arg_type) else {
+            continue;
+        };
+        let Ok(bits) = usize::from_str(&captures["bits"]) else {
+            continue;
+        };
+
+        let (min, max) = match &captures["signedness"] {
+            "u" => (BigInt::from(0), (BigInt::from(1) << bits) - 1),
+            _ => (
+                -(BigInt::from(1) << (bits - 1)),
+                (BigInt::from(1) << (bits - 1)) - 1,
+            ),
+        };
+
+        let argument = Term::var(&arg_name);
+        constraints.push(Formula::ge(argument.clone(), Term::Const(min)));
+        constraints.push(Formula::le(argument, Term::Const(max)));
+    }
+
+    constraints
+}
@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use num_bigint::Sign;
+use tracing::{debug, instrument};
+#[cfg(feature = "z3")]
+use z3::{Config, Context};
+
+#[cfg(feature = "z3")]
+use crate::config::SymbolicExecutionConfig;
+use crate::decompiler::function::Function;
+use crate::sym_exec::formula::{ArrayTerm, Formula, Term};
+use crate::sym_exec::hashes::HashModel;
+use crate::sym_exec::solver::{Solver, SolverResult};
+use crate::sym_exec::sym_exec::{arguments_constraints, symbolic_arguments, trace_frames};
+use crate::sym_exec::unrolling::unrolled_traces;
+#[cfg(feature = "z3")]
+use crate::sym_exec::z3_solver::Z3Solver;
+
+/// Constraints of an enumerated path & the verdict of the solver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathConstraints {
+    pub constraints: Vec<Formula>,
+    pub result: SolverResult,
+    /// Time spent by the solver to check the constraints
+    pub solving_time: Duration,
+}
+
+impl PathConstraints {
+    /// Returns the SMT-LIB2 script asserting the constraints of the path
+    pub fn to_smtlib2(&self) -> String {
+        smtlib2_script(&self.constraints)
+    }
+}
+
+/// Returns the constraints of the paths of a function of a program & their satisfiability using the Z3 solver
+/// See `path_constraints_with_solver`
+#[cfg(feature = "z3")]
+pub fn path_constraints(
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    hash_model: HashModel,
+) -> Vec<PathConstraints> {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    let mut solver = Z3Solver::new(&context);
+
+    path_constraints_with_solver(
+        &mut solver,
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        SymbolicExecutionConfig::UNROLL_BOUND,
+        hash_model,
+    )
+}
+
+/// Returns the constraints of the paths of a function of a program & their satisfiability with a given
+/// solver backend. The paths are the ones explored by the tests generator, with the loops unrolled up to
+/// `unroll_bound` times, and their constraints include the ones of the generated arguments.
+#[instrument(skip_all, fields(function_index = function_index))]
+pub fn path_constraints_with_solver<S: Solver>(
+    solver: &mut S,
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    unroll_bound: usize,
+    hash_model: HashModel,
+) -> Vec<PathConstraints> {
+    let arguments_constraints =
+        arguments_constraints(&symbolic_arguments(&functions[function_index]));
+
+    let traces = unrolled_traces(
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        unroll_bound,
+    );
+    debug!("{} unrolled traces", traces.len());
+
+    traces
+        .iter()
+        .map(|trace| {
+            let constraints: Vec<Formula> = arguments_constraints
+                .iter()
+                .cloned()
+                .chain(
+                    trace_frames(trace, declared_libfuncs_names, hash_model)
+                        .into_iter()
+                        .flat_map(|frame| frame.constraints),
+                )
+                .collect();
+
+            let start = Instant::now();
+            solver.reset();
+            for constraint in &constraints {
+                solver.assert(constraint);
+            }
+            let result = solver.check();
+
+            PathConstraints {
+                constraints,
+                result,
+                solving_time: start.elapsed(),
+            }
+        })
+        .collect()
+}
+
+/// Returns an SMT-LIB2 script declaring the symbols of constraints, asserting them & checking
+/// their satisfiability. The variables are integers & the arrays map integers to integers
+pub fn smtlib2_script(constraints: &[Formula]) -> String {
+    let mut symbols = Symbols::default();
+    for constraint in constraints {
+        symbols.collect_formula(constraint);
+    }
+
+    let mut script = String::new();
+    for variable in &symbols.variables {
+        script += &format!("(declare-const {} Int)\n", symbol(variable));
+    }
+    for array in &symbols.arrays {
+        script += &format!("(declare-const {} (Array Int Int))\n", symbol(array));
+    }
+    for (function, arity) in &symbols.functions {
+        script += &format!(
+            "(declare-fun {} ({}) Int)\n",
+            symbol(function),
+            vec!["Int"; *arity].join(" ")
+        );
+    }
+    for constraint in constraints {
+        script += &format!("(assert {})\n", formula_to_smtlib2(constraint));
+    }
+    script += "(check-sat)\n";
+
+    script
+}
+
+/// Symbols used by constraints, sorted by name so the scripts are deterministic
+#[derive(Debug, Default)]
+struct Symbols {
+    variables: BTreeSet<String>,
+    arrays: BTreeSet<String>,
+    /// Uninterpreted functions & their number of arguments
+    functions: BTreeMap<String, usize>,
+}
+
+impl Symbols {
+    fn collect_formula(&mut self, formula: &Formula) {
+        match formula {
+            Formula::Eq(lhs, rhs) | Formula::Lt(lhs, rhs) | Formula::Le(lhs, rhs) => {
+                self.collect_term(lhs);
+                self.collect_term(rhs);
+            }
+            Formula::ArrayEq(lhs, rhs) => {
+                self.collect_array(lhs);
+                self.collect_array(rhs);
+            }
+            Formula::Not(formula) => self.collect_formula(formula),
+            Formula::And(formulas) => {
+                for formula in formulas {
+                    self.collect_formula(formula);
+                }
+            }
+        }
+    }
+
+    fn collect_term(&mut self, term: &Term) {
+        match term {
+            Term::Var(name) => {
+                self.variables.insert(name.clone());
+            }
+            Term::Const(_) => {}
+            Term::Add(lhs, rhs) | Term::Sub(lhs, rhs) | Term::Mul(lhs, rhs) => {
+                self.collect_term(lhs);
+                self.collect_term(rhs);
+            }
+            Term::Select(array, index) => {
+                self.collect_array(array);
+                self.collect_term(index);
+            }
+            Term::App(function, arguments) => {
+                self.functions.insert(function.clone(), arguments.len());
+                for argument in arguments {
+                    self.collect_term(argument);
+                }
+            }
+        }
+    }
+
+    fn collect_array(&mut self, array: &ArrayTerm) {
+        match array {
+            ArrayTerm::Var(name) => {
+                self.arrays.insert(name.clone());
+            }
+            ArrayTerm::Const(value) => self.collect_term(value),
+            ArrayTerm::Store(array, index, value) => {
+                self.collect_array(array);
+                self.collect_term(index);
+                self.collect_term(value);
+            }
+        }
+    }
+}
+
+/// Returns the SMT-LIB2 expression of a formula
+pub fn formula_to_smtlib2(formula: &Formula) -> String {
+    match formula {
+        Formula::Eq(lhs, rhs) => format!("(= {} {})", term_to_smtlib2(lhs), term_to_smtlib2(rhs)),
+        Formula::ArrayEq(lhs, rhs) => {
+            format!("(= {} {})", array_to_smtlib2(lhs), array_to_smtlib2(rhs))
+        }
+        Formula::Lt(lhs, rhs) => format!("(< {} {})", term_to_smtlib2(lhs), term_to_smtlib2(rhs)),
+        Formula::Le(lhs, rhs) => format!("(<= {} {})", term_to_smtlib2(lhs), term_to_smtlib2(rhs)),
+        Formula::Not(formula) => format!("(not {})", formula_to_smtlib2(formula)),
+        // The empty conjunction is true
+        Formula::And(formulas) if formulas.is_empty() => "true".to_string(),
+        Formula::And(formulas) => {
+            let formulas: Vec<String> = formulas.iter().map(formula_to_smtlib2).collect();
+            format!("(and {})", formulas.join(" "))
+        }
+    }
+}
+
+/// Returns the SMT-LIB2 expression of an integer term
+fn term_to_smtlib2(term: &Term) -> String {
+    match term {
+        Term::Var(name) => symbol(name),
+        // The negative numerals are not part of the SMT-LIB2 syntax
+        Term::Const(value) if value.sign() == Sign::Minus => format!("(- {})", -value),
+        Term::Const(value) => value.to_string(),
+        Term::Add(lhs, rhs) => format!("(+ {} {})", term_to_smtlib2(lhs), term_to_smtlib2(rhs)),
+        Term::Sub(lhs, rhs) => format!("(- {} {})", term_to_smtlib2(lhs), term_to_smtlib2(rhs)),
+        Term::Mul(lhs, rhs) => format!("(* {} {})", term_to_smtlib2(lhs), term_to_smtlib2(rhs)),
+        Term::Select(array, index) => format!(
+            "(select {} {})",
+            array_to_smtlib2(array),
+            term_to_smtlib2(index)
+        ),
+        Term::App(function, arguments) => {
+            let arguments: Vec<String> = arguments.iter().map(term_to_smtlib2).collect();
+            format!("({} {})", symbol(function), arguments.join(" "))
+        }
+    }
+}
+
+/// Returns the SMT-LIB2 expression of an array
+fn array_to_smtlib2(array: &ArrayTerm) -> String {
+    match array {
+        ArrayTerm::Var(name) => symbol(name),
+        ArrayTerm::Const(value) => {
+            format!("((as const (Array Int Int)) {})", term_to_smtlib2(value))
+        }
+        ArrayTerm::Store(array, index, value) => format!(
+            "(store {} {} {})",
+            array_to_smtlib2(array),
+            term_to_smtlib2(index),
+            term_to_smtlib2(value)
+        ),
+    }
+}
+
+/// Returns an SMT-LIB2 symbol, quoted if the name is not a simple symbol (e.g. `|v0[1]|`)
+fn symbol(name: &str) -> String {
+    let is_simple = name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_simple {
+        name.to_string()
+    } else {
+        format!("|{}|", name)
+    }
+}
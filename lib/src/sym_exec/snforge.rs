@@ -0,0 +1,287 @@
+use std::collections::BTreeSet;
+
+use cairo_lang_starknet_classes::abi::{Contract, Item};
+
+/// ABI types of the parameters solved by the symbolic execution
+const FELT252_TYPE: &str = "core::felt252";
+const FELT252_ARRAY_TYPE: &str = "core::array::Array::<core::felt252>";
+const FELT252_SPAN_TYPE: &str = "core::array::Span::<core::felt252>";
+
+/// A test case generated by the symbolic execution, e.g. `v0: 1, v1: [0, 2] | storage[0x1234]: 5`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    /// Values of the felt252 & arrays arguments, in the order of the parameters (e.g. `1` or `[0, 2]`)
+    pub arguments: Vec<String>,
+    /// Initial storage values required by the path (storage address, value)
+    pub storage: Vec<(String, String)>,
+}
+
+impl TestCase {
+    /// Parses a line of the test cases, None if it is malformed
+    pub fn parse(line: &str) -> Option<Self> {
+        let (arguments, storage) = match line.split_once(" | ") {
+            Some((arguments, storage)) => (arguments, Some(storage)),
+            None => (line, None),
+        };
+
+        let arguments = split_arguments(arguments)
+            .into_iter()
+            .map(|argument| {
+                argument
+                    .split_once(": ")
+                    .map(|(_, value)| value.to_string())
+            })
+            .collect::<Option<Vec<String>>>()?;
+        let storage = storage
+            .map(|storage| {
+                storage
+                    .split(", ")
+                    .map(|slot| {
+                        let (address, value) = slot.split_once(": ")?;
+                        let address = address.strip_prefix("storage[")?.strip_suffix(']')?;
+                        Some((address.to_string(), value.to_string()))
+                    })
+                    .collect::<Option<Vec<(String, String)>>>()
+            })
+            .unwrap_or(Some(Vec::new()))?;
+
+        Some(Self { arguments, storage })
+    }
+
+    /// Parses the test cases of a function, the malformed lines are skipped
+    pub fn parse_all(test_cases: &str) -> Vec<Self> {
+        test_cases.lines().filter_map(Self::parse).collect()
+    }
+}
+
+/// Splits the arguments of a test case on the commas that are not in an array value
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut part_start = 0;
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(arguments[part_start..index].trim());
+                part_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(arguments[part_start..].trim());
+    parts
+}
+
+/// An entry point declared by an interface & its test cases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPointTestCases {
+    /// Full name of the interface (e.g. `hello::IHelloStarknet`)
+    pub interface: String,
+    pub entry_point: String,
+    /// ABI types of the parameters
+    pub parameters: Vec<String>,
+    pub test_cases: Vec<TestCase>,
+}
+
+/// A contract of a Scarb package & the test cases of its entry points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnforgeContract {
+    /// Contract name, declared by the tests
+    pub name: String,
+    /// ABI types of the constructor parameters
+    pub constructor_parameters: Vec<String>,
+    pub entry_points: Vec<EntryPointTestCases>,
+}
+
+/// Returns the functions declared by the interfaces of an ABI
+/// (interface name, function name, parameters types)
+pub fn abi_interface_functions(abi: &Contract) -> Vec<(String, String, Vec<String>)> {
+    abi.clone()
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Interface(interface) => Some(interface),
+            _ => None,
+        })
+        .flat_map(|interface| {
+            interface
+                .items
+                .into_iter()
+                .filter_map(move |item| match item {
+                    Item::Function(function) => Some((
+                        interface.name.clone(),
+                        function.name,
+                        function.inputs.into_iter().map(|input| input.ty).collect(),
+                    )),
+                    _ => None,
+                })
+        })
+        .collect()
+}
+
+/// Returns the types of the constructor parameters of an ABI, empty without constructor
+pub fn abi_constructor_parameters(abi: &Contract) -> Vec<String> {
+    abi.clone()
+        .into_iter()
+        .find_map(|item| match item {
+            Item::Constructor(constructor) => Some(
+                constructor
+                    .inputs
+                    .into_iter()
+                    .map(|input| input.ty)
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Returns a snforge tests file calling the entry points with the inputs of their test cases
+/// The contracts are deployed with a zero constructor calldata, the storage values required by the
+/// paths are written with the `store` cheatcode. The calls use the safe dispatchers so the test cases
+/// reaching a panic don't fail. The entry points with a parameter whose type has no default value
+/// (e.g. a struct) are skipped.
+pub fn snforge_tests(contracts: &[SnforgeContract]) -> String {
+    let mut interfaces = BTreeSet::new();
+    let mut tests = String::new();
+
+    for contract in contracts {
+        let calldata: Vec<&str> = contract
+            .constructor_parameters
+            .iter()
+            .flat_map(|parameter| vec!["0"; serialized_length(parameter)])
+            .collect();
+
+        for entry_point in &contract.entry_points {
+            let Some((module, interface)) = entry_point.interface.rsplit_once("::") else {
+                continue;
+            };
+
+            for (index, test_case) in entry_point.test_cases.iter().enumerate() {
+                let Some(arguments) = call_arguments(&entry_point.parameters, test_case) else {
+                    break;
+                };
+                interfaces.insert((module, interface));
+
+                tests += &format!(
+                    "\n#[test]\n#[feature(\"safe_dispatcher\")]\nfn test_{}_{}_{}() {{\n",
+                    snake_case(&contract.name),
+                    entry_point.entry_point,
+                    index
+                );
+                tests += &format!(
+                    "    let contract_address = deploy(\"{}\", array![{}]);\n",
+                    contract.name,
+                    calldata.join(", ")
+                );
+                for (address, value) in &test_case.storage {
+                    tests += &format!(
+                        "    store(contract_address, {}, array![{}].span());\n",
+                        address, value
+                    );
+                }
+                tests += &format!(
+                    "    let dispatcher = {}SafeDispatcher {{ contract_address }};\n",
+                    interface
+                );
+                tests += &format!(
+                    "    let _ = dispatcher.{}({});\n}}\n",
+                    entry_point.entry_point,
+                    arguments.join(", ")
+                );
+            }
+        }
+    }
+
+    let mut file =
+        "// Generated by the sierra-analyzer tests generator from the symbolic execution of the contracts\n"
+            .to_string();
+    file += "use snforge_std::{declare, store, ContractClassTrait, DeclareResultTrait};\n";
+    file += "use starknet::ContractAddress;\n";
+    for (module, interface) in &interfaces {
+        file += &format!(
+            "use {}::{{{}SafeDispatcher, {}SafeDispatcherTrait}};\n",
+            module, interface, interface
+        );
+    }
+    file += "\nfn deploy(name: ByteArray, calldata: Array<felt252>) -> ContractAddress {\n";
+    file += "    let contract = declare(name).unwrap().contract_class();\n";
+    file += "    let (contract_address, _) = contract.deploy(@calldata).unwrap();\n";
+    file += "    contract_address\n}\n";
+    file += &tests;
+
+    file
+}
+
+/// Returns the arguments of a call, the felt252 & arrays values of the test case & the default values
+/// of the other parameters, None if a parameter has no default value or a value is missing
+fn call_arguments(parameters: &[String], test_case: &TestCase) -> Option<Vec<String>> {
+    let mut values = test_case.arguments.iter();
+    parameters
+        .iter()
+        .map(|parameter| match parameter.as_str() {
+            FELT252_TYPE => values.next().cloned(),
+            FELT252_ARRAY_TYPE => values.next().and_then(|value| array_literal(value)),
+            FELT252_SPAN_TYPE => values
+                .next()
+                .and_then(|value| array_literal(value))
+                .map(|array| format!("{}.span()", array)),
+            _ => default_value(parameter).map(str::to_string),
+        })
+        .collect()
+}
+
+/// Converts the value of an array argument to a Cairo array, e.g. `[1, 2]` becomes `array![1, 2]`
+fn array_literal(value: &str) -> Option<String> {
+    let elements = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(format!("array![{}]", elements))
+}
+
+/// Returns the Cairo expression of the default value of an ABI type, None for the other types
+fn default_value(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "core::bool" => Some("false"),
+        "core::byte_array::ByteArray" => Some("\"\""),
+        "core::starknet::contract_address::ContractAddress"
+        | "core::starknet::class_hash::ClassHash"
+        | "core::starknet::eth_address::EthAddress" => Some("0.try_into().unwrap()"),
+        _ if type_name.starts_with("core::integer::") => Some("0"),
+        _ if type_name.starts_with("core::array::Array::<") => Some("array![]"),
+        _ if type_name.starts_with("core::array::Span::<") => Some("array![].span()"),
+        _ => None,
+    }
+}
+
+/// Returns the number of felts of a zero value of an ABI type
+/// The types that are not known are assumed to be serialized as a single felt
+fn serialized_length(type_name: &str) -> usize {
+    match type_name {
+        "core::integer::u256" => 2,
+        // Empty data, pending word & pending word length
+        "core::byte_array::ByteArray" => 3,
+        _ => 1,
+    }
+}
+
+/// Converts a contract name to snake case, e.g. `HelloStarknet` becomes `hello_starknet`
+/// The acronyms are kept in a single word, e.g. `ERC20Token` becomes `erc20_token`
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake_case = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let word_start = c.is_ascii_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_ascii_lowercase()
+                    || ((previous.is_ascii_uppercase() || previous.is_ascii_digit())
+                        && next.is_some_and(|next| next.is_ascii_lowercase()))
+            });
+        if word_start {
+            snake_case.push('_');
+        }
+        snake_case.push(c.to_ascii_lowercase());
+    }
+    snake_case
+}
@@ -0,0 +1,39 @@
+use num_bigint::BigInt;
+
+use crate::sym_exec::formula::{Formula, Term};
+
+/// Result of a satisfiability check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverResult {
+    Sat,
+    Unsat,
+    Unknown,
+}
+
+/// An SMT solver backend used by the symbolic execution
+/// Implement this trait to use another solver than Z3 (e.g. cvc5 or a pure-Rust solver)
+pub trait Solver {
+    /// Asserts a constraint
+    fn assert(&mut self, formula: &Formula);
+
+    /// Checks if the asserted constraints are satisfiable
+    fn check(&mut self) -> SolverResult;
+
+    /// Returns the value of a term in the model of the last satisfiable check
+    /// Variables that are not constrained are given a default value
+    fn eval(&self, term: &Term) -> Option<BigInt>;
+
+    /// Returns the value of a variable in the model of the last satisfiable check
+    fn value(&self, variable: &str) -> Option<BigInt> {
+        self.eval(&Term::var(variable))
+    }
+
+    /// Creates a new scope, the constraints asserted in it are removed by the next `pop`
+    fn push(&mut self);
+
+    /// Removes the last scope & its constraints
+    fn pop(&mut self);
+
+    /// Removes all the asserted constraints & scopes
+    fn reset(&mut self);
+}
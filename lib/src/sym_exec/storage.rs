@@ -0,0 +1,135 @@
+use num_bigint::BigInt;
+
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::sym_exec::formula::{ArrayTerm, Formula, Term};
+use crate::sym_exec::solver::Solver;
+
+/// Name of the array holding the storage before the execution of the path
+pub const INITIAL_STORAGE: &str = "storage";
+
+/// A storage read of a path
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageRead {
+    /// Address of the read storage slot
+    pub address: Term,
+    /// Addresses of the slots written before the read in the path
+    pub previous_writes: Vec<Term>,
+}
+
+/// Symbolic model of the contract storage along a path
+/// The storage is an array indexed by storage address, each write creates a new version of the array
+#[derive(Debug, Clone, Default)]
+pub struct SymbolicStorage {
+    /// Number of writes in the path
+    version: usize,
+    /// Addresses of the slots written in the path
+    writes: Vec<Term>,
+    /// Reads of the path
+    reads: Vec<StorageRead>,
+}
+
+impl SymbolicStorage {
+    /// Creates a new `SymbolicStorage` instance, the storage is unconstrained
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current version of the storage array
+    fn current(&self) -> ArrayTerm {
+        storage_version(self.version)
+    }
+
+    /// Returns the constraint of a storage syscall, or None if the libfunc is not a storage syscall
+    /// `branch` is the index of the branch taken by the syscall, if known (branch 1 is a failure)
+    pub fn handle_syscall(
+        &mut self,
+        libfunc_kind: LibfuncKind,
+        parameters: &[String],
+        results: &[String],
+        branch: Option<usize>,
+    ) -> Option<Formula> {
+        // The syscall failed, the storage is not accessed
+        if branch.map_or(false, |branch| branch != 0) {
+            return None;
+        }
+
+        // storage_read_syscall(gas, system, address_domain, address) -> (gas, system, value)
+        if libfunc_kind == LibfuncKind::StorageRead {
+            let address = Term::var(parameters.get(3)?);
+            let value = Term::var(results.get(2)?);
+
+            self.reads.push(StorageRead {
+                address: address.clone(),
+                previous_writes: self.writes.clone(),
+            });
+            return Some(Formula::eq(value, Term::select(self.current(), address)));
+        }
+
+        // storage_write_syscall(gas, system, address_domain, address, value) -> (gas, system)
+        if libfunc_kind == LibfuncKind::StorageWrite {
+            let address = Term::var(parameters.get(3)?);
+            let value = Term::var(parameters.get(4)?);
+
+            let previous = self.current();
+            self.version += 1;
+            self.writes.push(address.clone());
+            return Some(Formula::array_eq(
+                self.current(),
+                previous.store(address, value),
+            ));
+        }
+
+        None
+    }
+
+    /// Returns the reads of the path
+    #[inline]
+    pub fn reads(&self) -> &[StorageRead] {
+        &self.reads
+    }
+}
+
+/// Returns the storage array after `version` writes
+fn storage_version(version: usize) -> ArrayTerm {
+    match version {
+        0 => ArrayTerm::var(INITIAL_STORAGE),
+        _ => ArrayTerm::var(&format!("{}_{}", INITIAL_STORAGE, version)),
+    }
+}
+
+/// Returns the storage slots values required before the execution of the path
+/// in the model of the last satisfiable check, ordered by address.
+/// The slots written before being read do not depend on the initial storage and are skipped
+pub fn storage_pre_state<S: Solver>(solver: &S, reads: &[StorageRead]) -> Vec<(BigInt, BigInt)> {
+    let mut pre_state: Vec<(BigInt, BigInt)> = Vec::new();
+
+    for read in reads {
+        let Some(address) = solver.eval(&read.address) else {
+            continue;
+        };
+
+        // The read value comes from a previous write
+        if read
+            .previous_writes
+            .iter()
+            .any(|write| solver.eval(write).as_ref() == Some(&address))
+        {
+            continue;
+        }
+
+        if pre_state.iter().any(|(slot, _)| *slot == address) {
+            continue;
+        }
+
+        let initial_value = Term::select(
+            ArrayTerm::var(INITIAL_STORAGE),
+            Term::Const(address.clone()),
+        );
+        if let Some(value) = solver.eval(&initial_value) {
+            pre_state.push((address, value));
+        }
+    }
+
+    pre_state.sort();
+    pre_state
+}
@@ -1,271 +1,552 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-
-use z3::{
-    ast::{Ast, Bool, Int},
-    Config, Context, SatResult, Solver,
-};
+use std::sync::Arc;
+use std::time::Instant;
 
 use cairo_lang_sierra::program::GenStatement;
+use tracing::{debug, info, instrument};
+#[cfg(feature = "z3")]
+use z3::{ast::Bool, Config, Context, Solver as Z3RawSolver};
 
+use crate::config::SymbolicExecutionConfig;
 use crate::decompiler::function::{Function, SierraStatement};
-use crate::decompiler::libfuncs_patterns::{
-    ADDITION_REGEX, CONST_REGEXES, DUP_REGEX, IS_ZERO_REGEX, MULTIPLICATION_REGEX,
-    SUBSTRACTION_REGEX,
+use crate::decompiler::libfunc_kind::LibfuncKind;
+use crate::parse_element_name_with_fallback;
+use crate::sym_exec::constraints::{
+    array_end, array_start, array_values, invocation_constraint, InvocationVariables,
 };
-use crate::{extract_parameters, parse_element_name_with_fallback};
+pub use crate::sym_exec::constraints::{
+    sierra_statement_to_constraint, sierra_statement_to_constraint_with_suffix,
+};
+use crate::sym_exec::formula::{Formula, Term};
+use crate::sym_exec::hashes::{HashModel, HashModeler};
+use crate::sym_exec::solver::{Solver, SolverResult};
+use crate::sym_exec::storage::{storage_pre_state, StorageRead, SymbolicStorage};
+use crate::sym_exec::unrolling::unrolled_traces;
+use crate::sym_exec::unrolling::TraceStatement;
+use crate::sym_exec::unrolling::UnrolledTrace;
+#[cfg(feature = "z3")]
+use crate::sym_exec::z3_solver::Z3Solver;
+
+/// Versions of the variables of a trace, each definition of a variable creates a new version so that
+/// the reassigned variables (e.g. `v1 = store_temp(v1)`) are distinct solver variables.
+/// The first version of a variable keeps its name (e.g. the arguments), the next ones are `v1#1`, `v1#2`...
+#[derive(Debug, Default)]
+struct SsaVersions {
+    versions: HashMap<String, usize>,
+}
 
-/// Converts a SierraStatement to a Z3 constraint, or returns None if not applicable
-pub fn sierra_statement_to_constraint<'ctx>(
-    statement: &SierraStatement,
-    context: &'ctx Context,
-    declared_libfuncs_names: Vec<String>,
-) -> Option<Bool<'ctx>> {
-    match &statement.statement {
-        GenStatement::Invocation(invocation) => {
-            let libfunc_id_str =
-                parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
-            let parameters = extract_parameters!(invocation.args);
-            let assigned_variables = invocation
-                .branches
-                .first()
-                .map(|branch| extract_parameters!(&branch.results))
-                .unwrap_or_else(Vec::new);
-
-            handle_invocation(context, &libfunc_id_str, &parameters, &assigned_variables)
+impl SsaVersions {
+    /// Returns the name of the current version of a variable
+    fn current(&mut self, name: &str) -> String {
+        let version = *self.versions.entry(name.to_string()).or_insert(0);
+        versioned_name(name, version)
+    }
+
+    /// Creates a new version of a variable & returns its name
+    fn define(&mut self, name: &str) -> String {
+        let version = match self.versions.get_mut(name) {
+            Some(version) => {
+                *version += 1;
+                *version
+            }
+            None => {
+                self.versions.insert(name.to_string(), 0);
+                0
+            }
+        };
+        versioned_name(name, version)
+    }
+
+    /// Renames the variables of an invocation, the parameters are used before the results are defined
+    fn rename(&mut self, variables: &InvocationVariables) -> InvocationVariables {
+        let parameters = variables
+            .parameters
+            .iter()
+            .map(|parameter| self.current(parameter))
+            .collect();
+
+        // A variable assigned by several branches is defined once
+        let mut definitions: HashMap<&str, String> = HashMap::new();
+        let mut results = Vec::with_capacity(variables.results.len());
+        for branch_results in &variables.results {
+            let mut renamed_results = Vec::with_capacity(branch_results.len());
+            for result in branch_results {
+                if !definitions.contains_key(result.as_str()) {
+                    definitions.insert(result, self.define(result));
+                }
+                renamed_results.push(definitions[result.as_str()].clone());
+            }
+            results.push(renamed_results);
+        }
+
+        InvocationVariables {
+            parameters,
+            results,
         }
-        _ => None,
     }
 }
 
-/// Handles an invocation by trying to match it to known patterns.
-fn handle_invocation<'ctx>(
-    context: &'ctx Context,
-    libfunc_id_str: &str,
-    parameters: &[String],
-    assigned_variables: &[String],
-) -> Option<Bool<'ctx>> {
-    handle_duplication(context, libfunc_id_str, assigned_variables)
-        .or_else(|| handle_constant_assignment(context, libfunc_id_str, assigned_variables))
-        .or_else(|| handle_is_zero(context, libfunc_id_str, parameters))
-        .or_else(|| {
-            handle_arithmetic_operations(context, libfunc_id_str, parameters, assigned_variables)
-        })
+/// Returns the name of a version of a variable
+fn versioned_name(name: &str, version: usize) -> String {
+    match version {
+        0 => name.to_string(),
+        _ => format!("{}#{}", name, version),
+    }
 }
 
-/// Handles variable duplication in Sierra statements
-fn handle_duplication<'ctx>(
-    context: &'ctx Context,
-    libfunc_id_str: &str,
-    assigned_variables: &[String],
-) -> Option<Bool<'ctx>> {
-    if DUP_REGEX.is_match(libfunc_id_str) {
-        let first_var_z3 = Int::new_const(context, assigned_variables[0].clone());
-        let second_var_z3 = Int::new_const(context, assigned_variables[1].clone());
-        return Some(second_var_z3._eq(&first_var_z3).into());
-    }
-    None
+/// Generates test cases for a single function using the Z3 solver
+/// The recursive calls of the function to itself are unrolled
+#[cfg(feature = "z3")]
+pub fn generate_test_cases_for_function(
+    function: &mut Function,
+    declared_libfuncs_names: &[Arc<str>],
+) -> String {
+    // Generate the function CFG
+    function.create_cfg();
+
+    generate_test_cases_in_program(
+        std::slice::from_ref(&*function),
+        0,
+        declared_libfuncs_names,
+        SymbolicExecutionConfig::UNROLL_BOUND,
+        SymbolicExecutionConfig::HASH_MODEL,
+    )
 }
 
-/// Handles constant assignment in Sierra statements
-fn handle_constant_assignment<'ctx>(
-    context: &'ctx Context,
-    libfunc_id_str: &str,
-    assigned_variables: &[String],
-) -> Option<Bool<'ctx>> {
-    for regex in CONST_REGEXES.iter() {
-        if let Some(captures) = regex.captures(libfunc_id_str) {
-            if let Some(const_value) = captures.name("const") {
-                let const_value_str = const_value.as_str();
-                if let Ok(const_value_u64) = u64::from_str(const_value_str) {
-                    if !assigned_variables.is_empty() {
-                        let assigned_var_z3 = Int::new_const(context, &*assigned_variables[0]);
-                        let const_value_z3 = Int::from_u64(context, const_value_u64);
-                        return Some(assigned_var_z3._eq(&const_value_z3).into());
-                    }
-                }
+/// Generates test cases for a function of a program using the Z3 solver
+/// See `generate_test_cases_with_solver`
+#[cfg(feature = "z3")]
+pub fn generate_test_cases_in_program(
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    unroll_bound: usize,
+    hash_model: HashModel,
+) -> String {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    let mut solver = Z3Solver::new(&context);
+
+    generate_test_cases_with_solver(
+        &mut solver,
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        unroll_bound,
+        hash_model,
+    )
+}
+
+/// Constraints asserted in a single solver scope
+/// A trace is split into frames (the constraints of the arguments, then one frame per basic block)
+/// so that consecutive traces sharing a prefix only solve it once
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Frame {
+    /// Constraints of the frame
+    pub(crate) constraints: Vec<Formula>,
+    /// Operands of the zero checks whose branch is unknown
+    pub(crate) zero_operands: Vec<Term>,
+    /// Constraints of the frame, except the zero checks equalities
+    pub(crate) other_constraints: Vec<Formula>,
+    /// Storage reads of the frame
+    pub(crate) storage_reads: Vec<StorageRead>,
+}
+
+/// Converts a trace to solver frames, the hash functions are modeled according to `hash_model`
+pub(crate) fn trace_frames(
+    trace: &UnrolledTrace,
+    declared_libfuncs_names: &[Arc<str>],
+    hash_model: HashModel,
+) -> Vec<Frame> {
+    let mut frames = vec![Frame::default()];
+
+    // The storage & the hashes applications are shared by all the function instances of the trace
+    let mut storage = SymbolicStorage::new();
+    let mut hashes = HashModeler::new(hash_model);
+    let mut versions = SsaVersions::default();
+
+    // Convert Sierra statements to constraints
+    for (block_index, basic_block) in trace.blocks.iter().enumerate() {
+        let mut frame = Frame::default();
+        let previous_reads_count = storage.reads().len();
+
+        // Bind the parameters & results of the calls inlined before the block
+        let bindings = bindings_constraints(trace, block_index, &mut versions);
+        frame.constraints.extend(bindings.iter().cloned());
+        frame.other_constraints.extend(bindings);
+
+        for TraceStatement {
+            statement,
+            suffix,
+            branch,
+        } in basic_block
+        {
+            let GenStatement::Invocation(invocation) = &statement.statement else {
+                continue;
+            };
+
+            // The results are defined even if the invocation is not constrained
+            let variables = versions.rename(&InvocationVariables::new(invocation, suffix));
+            let Some(constraint) =
+                storage_syscall_constraint(&mut storage, statement, &variables, *branch)
+                    .or_else(|| {
+                        invocation_constraint(
+                            statement,
+                            declared_libfuncs_names,
+                            &variables,
+                            *branch,
+                        )
+                    })
+                    .or_else(|| {
+                        hash_constraint(&mut hashes, statement, declared_libfuncs_names, &variables)
+                    })
+            else {
+                continue;
+            };
+            hashes.propagate_constants(&constraint);
+            frame.constraints.push(constraint.clone());
+
+            // Zero checks with a known branch are already constrained,
+            // the operands of the other ones are stored for non-zero testing
+            if statement.libfunc_kind == LibfuncKind::IsZero && branch.is_none() {
+                frame
+                    .zero_operands
+                    .push(Term::var(&variables.parameters[0]));
+            } else {
+                // Store other constraints for reuse
+                frame.other_constraints.push(constraint);
             }
         }
+
+        frame.storage_reads = storage.reads()[previous_reads_count..].to_vec();
+        frames.push(frame);
     }
-    None
-}
 
-/// Handles zero check in Sierra statements
-fn handle_is_zero<'ctx>(
-    context: &'ctx Context,
-    libfunc_id_str: &str,
-    parameters: &[String],
-) -> Option<Bool<'ctx>> {
-    if IS_ZERO_REGEX.is_match(libfunc_id_str) {
-        let operand = Int::new_const(context, parameters[0].clone());
-        let constraint = operand._eq(&Int::from_i64(context, 0));
-        return Some(constraint);
+    // Bindings of the results of a call ending the trace
+    let bindings = bindings_constraints(trace, trace.blocks.len(), &mut versions);
+    if let Some(frame) = frames.last_mut() {
+        frame.constraints.extend(bindings.iter().cloned());
+        frame.other_constraints.extend(bindings);
     }
-    None
+
+    frames
 }
 
-/// Handles arithmetic operations in Sierra statements
-fn handle_arithmetic_operations<'ctx>(
-    context: &'ctx Context,
-    libfunc_id_str: &str,
-    parameters: &[String],
-    assigned_variables: &[String],
-) -> Option<Bool<'ctx>> {
-    let operator = if ADDITION_REGEX
-        .iter()
-        .any(|regex| regex.is_match(libfunc_id_str))
-    {
-        "+"
-    } else if SUBSTRACTION_REGEX
+/// Returns the constraints of the bindings of a trace preceding a block
+/// The bound variables are used before the variables of the callee parameters or of the call
+/// results are defined
+fn bindings_constraints(
+    trace: &UnrolledTrace,
+    block_index: usize,
+    versions: &mut SsaVersions,
+) -> Vec<Formula> {
+    trace
+        .bindings
         .iter()
-        .any(|regex| regex.is_match(libfunc_id_str))
-    {
-        "-"
-    } else if MULTIPLICATION_REGEX
-        .iter()
-        .any(|regex| regex.is_match(libfunc_id_str))
-    {
-        "*"
-    } else {
-        return None;
-    };
+        .zip(&trace.binding_blocks)
+        .filter(|(_, binding_block)| **binding_block == block_index)
+        .map(|((defined_variable, used_variable), _)| {
+            let used_variable = versions.current(used_variable);
+            Formula::eq(
+                Term::var(&versions.define(defined_variable)),
+                Term::var(&used_variable),
+            )
+        })
+        .collect()
+}
 
-    let assigned_variable = Int::new_const(context, assigned_variables[0].clone());
-    let first_operand = Int::new_const(context, parameters[0].clone());
-    let second_operand = Int::new_const(context, parameters[1].clone());
+/// Returns the constraint of a storage syscall & updates the symbolic storage
+fn storage_syscall_constraint(
+    storage: &mut SymbolicStorage,
+    statement: &SierraStatement,
+    variables: &InvocationVariables,
+    branch: Option<usize>,
+) -> Option<Formula> {
+    storage.handle_syscall(
+        statement.libfunc_kind,
+        &variables.parameters,
+        variables.branch_results(0),
+        branch,
+    )
+}
 
-    let constraint = match operator {
-        "+" => assigned_variable._eq(&(first_operand + second_operand)),
-        "-" => assigned_variable._eq(&(first_operand - second_operand)),
-        "*" => assigned_variable._eq(&(first_operand * second_operand)),
-        _ => return None,
+/// Returns the constraint of a hash function & records its application
+fn hash_constraint(
+    hashes: &mut HashModeler,
+    statement: &SierraStatement,
+    declared_libfuncs_names: &[Arc<str>],
+    variables: &InvocationVariables,
+) -> Option<Formula> {
+    if !hashes.is_enabled() {
+        return None;
+    }
+    let GenStatement::Invocation(invocation) = &statement.statement else {
+        return None;
     };
 
-    Some(constraint)
+    let libfunc_id_str =
+        parse_element_name_with_fallback!(invocation.libfunc_id, declared_libfuncs_names);
+    hashes.constraint(
+        &libfunc_id_str,
+        &variables.parameters,
+        variables.branch_results(0),
+    )
 }
 
-/// Generates test cases for a single function
-pub fn generate_test_cases_for_function(
-    function: &mut Function,
-    declared_libfuncs_names: Vec<String>,
+/// Generates test cases for a function of a program with a given solver backend
+/// The loops (recursive functions) called by the function are unrolled up to `unroll_bound` times,
+/// generating the inputs reaching 0, 1 and `unroll_bound` iterations.
+#[instrument(skip_all, fields(function_index = function_index))]
+pub fn generate_test_cases_with_solver<S: Solver>(
+    solver: &mut S,
+    functions: &[Function],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    unroll_bound: usize,
+    hash_model: HashModel,
 ) -> String {
     let mut result = String::new();
     let mut unique_results = HashSet::new();
 
-    let felt252_arguments: Vec<(String, String)> = function
-        .arguments
-        .iter()
-        .filter(|(_, arg_type)| arg_type == "felt252")
-        .map(|(arg_name, arg_type)| (arg_name.clone(), arg_type.clone()))
-        .collect();
+    let function = &functions[function_index];
+    let arguments = symbolic_arguments(function);
 
-    // Skip the function if there are no felt252 arguments
-    if felt252_arguments.is_empty() {
+    // Skip the function if there are no felt252 or arrays arguments
+    if arguments.is_empty() {
         return result;
     }
-
-    // Generate the function CFG
-    function.create_cfg();
-
-    let function_paths = function.cfg.as_ref().unwrap().paths();
-
-    for path in &function_paths {
-        // Create a new symbolic execution engine for the function
-        let cfg = Config::new();
-        let context = Context::new(&cfg);
-
-        // Create Z3 variables for each felt252 argument
-        let z3_variables: Vec<Int> = felt252_arguments
+    let arguments_constraints = arguments_constraints(&arguments);
+
+    // Paths of the function with the loops unrolled
+    let traces = unrolled_traces(
+        functions,
+        function_index,
+        declared_libfuncs_names,
+        unroll_bound,
+    );
+    debug!("{} unrolled traces", traces.len());
+
+    // Frames currently pushed on the solver, shared by consecutive traces with a common prefix
+    let mut stack: Vec<Frame> = Vec::new();
+    solver.reset();
+
+    for (trace_index, trace) in traces.iter().enumerate() {
+        let start = Instant::now();
+        let mut frames = trace_frames(trace, declared_libfuncs_names, hash_model);
+        frames[0]
+            .constraints
+            .extend(arguments_constraints.iter().cloned());
+        frames[0]
+            .other_constraints
+            .extend(arguments_constraints.iter().cloned());
+
+        // Pop the frames of the previous trace that are not shared with this one
+        let shared_frames = stack
             .iter()
-            .map(|(arg_name, _)| Int::new_const(&context, &**arg_name))
-            .collect();
+            .zip(&frames)
+            .take_while(|(pushed_frame, frame)| pushed_frame == frame)
+            .count();
+        while stack.len() > shared_frames {
+            solver.pop();
+            stack.pop();
+        }
 
-        // Create a solver
-        let mut symbolic_execution = SymbolicExecution::new(&context);
-
-        let mut zero_constraints = Vec::new();
-        let mut other_constraints = Vec::new();
-
-        // Convert Sierra statements to z3 constraints
-        for basic_block in path {
-            for statement in &basic_block.statements {
-                // Convert SierraStatement to a Z3 constraint and add to solver
-                if let Some(constraint) = sierra_statement_to_constraint(
-                    &statement,
-                    &context,
-                    declared_libfuncs_names.clone(),
-                ) {
-                    symbolic_execution.add_constraint(&constraint);
-
-                    // Identify if it's a zero check and store the variable for non-zero testing
-                    if let GenStatement::Invocation(invocation) = &statement.statement {
-                        let libfunc_id_str = parse_element_name_with_fallback!(
-                            invocation.libfunc_id,
-                            declared_libfuncs_names
-                        );
-
-                        if IS_ZERO_REGEX.is_match(&libfunc_id_str) {
-                            let operand_name = format!("v{}", invocation.args[0].id.to_string());
-                            let operand = Int::new_const(&context, operand_name.clone());
-                            zero_constraints.push((operand, constraint));
-                        } else {
-                            // Store other constraints for reuse
-                            other_constraints.push(constraint);
-                        }
-                    }
-                }
+        // The shared frames were already solved, only the new ones are checked
+        for (frame_index, frame) in frames.into_iter().enumerate().skip(shared_frames) {
+            solver.push();
+            for constraint in &frame.constraints {
+                solver.assert(constraint);
+            }
+            stack.push(frame);
+
+            // The first frame only contains the constraints of the arguments
+            if frame_index == 0 {
+                continue;
             }
 
+            // Storage slots read along the path
+            let storage_reads: Vec<StorageRead> = stack
+                .iter()
+                .flat_map(|frame| frame.storage_reads.iter().cloned())
+                .collect();
+
             // Check if the constraints are satisfiable (value == 0)
             generate_zero_test_cases(
                 &mut result,
                 &mut unique_results,
-                &symbolic_execution,
-                &felt252_arguments,
-                &z3_variables,
+                solver,
+                &arguments,
+                &storage_reads,
             );
 
             // Now generate test cases where the value is not equal to 0
-            generate_non_zero_test_cases(
-                &mut result,
-                &mut unique_results,
-                &context,
-                &felt252_arguments,
-                &z3_variables,
-                &zero_constraints,
-                &other_constraints,
-            );
+            let zero_operands: Vec<Term> = stack
+                .iter()
+                .flat_map(|frame| frame.zero_operands.iter().cloned())
+                .collect();
+            if !zero_operands.is_empty() {
+                let other_constraints: Vec<Formula> = stack
+                    .iter()
+                    .flat_map(|frame| frame.other_constraints.iter().cloned())
+                    .collect();
+                generate_non_zero_test_cases(
+                    &mut result,
+                    &mut unique_results,
+                    solver,
+                    &arguments,
+                    &storage_reads,
+                    &zero_operands,
+                    &other_constraints,
+                );
+
+                // Restore the frames of the path
+                solver.reset();
+                for frame in &stack {
+                    solver.push();
+                    for constraint in &frame.constraints {
+                        solver.assert(constraint);
+                    }
+                }
+            }
         }
+
+        info!(
+            "Path {}/{} solved in {:.2?}",
+            trace_index + 1,
+            traces.len(),
+            start.elapsed()
+        );
     }
 
     result.trim_end().to_string()
 }
 
+/// Returns the arguments of a function whose values are generated by the tests generator
+pub(crate) fn symbolic_arguments(function: &Function) -> Vec<SymbolicArgument> {
+    function
+        .arguments
+        .iter()
+        .filter_map(|(arg_name, arg_type)| SymbolicArgument::new(arg_name, arg_type))
+        .collect()
+}
+
+/// Returns the constraints of the arguments, the arrays arguments have a bounded symbolic length
+pub(crate) fn arguments_constraints(arguments: &[SymbolicArgument]) -> Vec<Formula> {
+    arguments
+        .iter()
+        .flat_map(SymbolicArgument::constraints)
+        .collect()
+}
+
+/// An argument of a function whose values are generated by the tests generator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SymbolicArgument {
+    Felt252(String),
+    /// An array or a span of felt252, its length is bounded by `SymbolicExecutionConfig::MAX_ARRAY_LENGTH`
+    Array(String),
+}
+
+impl SymbolicArgument {
+    /// Returns the symbolic argument of a function parameter, None if its type is not supported
+    fn new(name: &str, type_name: &str) -> Option<Self> {
+        match type_name {
+            "felt252" => Some(Self::Felt252(name.to_string())),
+            "Array<felt252>"
+            | "Snapshot<Array<felt252>>"
+            | "core::array::Span::<core::felt252>" => Some(Self::Array(name.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Returns the constraints of the argument
+    /// The elements of an array are variables named after their index (e.g. `v0[1]`)
+    fn constraints(&self) -> Vec<Formula> {
+        let Self::Array(name) = self else {
+            return Vec::new();
+        };
+
+        let mut constraints = vec![
+            Formula::eq(array_start(name), Term::constant(0)),
+            Formula::ge(array_end(name), Term::constant(0)),
+            Formula::le(
+                array_end(name),
+                Term::constant(SymbolicExecutionConfig::MAX_ARRAY_LENGTH),
+            ),
+        ];
+        constraints.extend((0..SymbolicExecutionConfig::MAX_ARRAY_LENGTH).map(|index| {
+            Formula::eq(
+                Term::select(array_values(name), Term::constant(index)),
+                Term::var(&array_element(name, index)),
+            )
+        }));
+        constraints
+    }
+
+    /// Formats the value of the argument in the model of the last satisfiable check
+    /// e.g. `v0: 1` or `v1: [2, 3]`
+    fn format<S: Solver>(&self, solver: &S) -> Option<String> {
+        match self {
+            Self::Felt252(name) => solver
+                .value(name)
+                .map(|value| format!("{}: {}", name, value)),
+            Self::Array(name) => {
+                let length = usize::try_from(solver.eval(&array_end(name))?).ok()?;
+                let elements = (0..length)
+                    .map(|index| {
+                        solver
+                            .value(&array_element(name, index))
+                            .map(|value| value.to_string())
+                    })
+                    .collect::<Option<Vec<String>>>()?;
+                Some(format!("{}: [{}]", name, elements.join(", ")))
+            }
+        }
+    }
+}
+
+/// Returns the name of the variable of an element of an array argument
+fn array_element(name: &str, index: usize) -> String {
+    format!("{}[{}]", name, index)
+}
+
+/// Formats the values of the arguments in the model of the last satisfiable check
+/// The initial storage values required by the path are appended after a `|` separator
+/// e.g. `v0: 1, v1: [0, 2] | storage[0x1234]: 5`
+fn format_test_case<S: Solver>(
+    solver: &S,
+    arguments: &[SymbolicArgument],
+    storage_reads: &[StorageRead],
+) -> Option<String> {
+    let values = arguments
+        .iter()
+        .map(|argument| argument.format(solver))
+        .collect::<Option<Vec<String>>>()?;
+
+    let pre_state: Vec<String> = storage_pre_state(solver, storage_reads)
+        .iter()
+        .map(|(address, value)| format!("storage[0x{:x}]: {}", address, value))
+        .collect();
+
+    if pre_state.is_empty() {
+        Some(values.join(", "))
+    } else {
+        Some(format!("{} | {}", values.join(", "), pre_state.join(", ")))
+    }
+}
+
 /// Generates test cases where the constraints are satisfiable (value == 0).
-fn generate_zero_test_cases(
+fn generate_zero_test_cases<S: Solver>(
     result: &mut String,
     unique_results: &mut HashSet<String>,
-    symbolic_execution: &SymbolicExecution,
-    felt252_arguments: &[(String, String)],
-    z3_variables: &[Int],
+    solver: &mut S,
+    arguments: &[SymbolicArgument],
+    storage_reads: &[StorageRead],
 ) {
     // Check if the constraints are satisfiable
-    if symbolic_execution.check() == SatResult::Sat {
-        // Get the model from the solver
-        if let Some(model) = symbolic_execution.solver.get_model() {
-            // Evaluate the variables and format the results
-            let values: Vec<String> = felt252_arguments
-                .iter()
-                .zip(z3_variables.iter())
-                .map(|((arg_name, _), var)| {
-                    format!(
-                        "{}: {}",
-                        arg_name,
-                        model.eval(var, true).unwrap().to_string()
-                    )
-                })
-                .collect();
-            let values_str = format!("{}", values.join(", "));
+    if solver.check() == SolverResult::Sat {
+        // Evaluate the variables and format the results
+        if let Some(values_str) = format_test_case(solver, arguments, storage_reads) {
             // Add the result to the unique results set and the result string
             if unique_results.insert(values_str.clone()) {
                 result.push_str(&format!("{}\n", values_str));
@@ -275,44 +556,31 @@ fn generate_zero_test_cases(
 }
 
 /// Generates test cases where the value is not equal to 0.
-fn generate_non_zero_test_cases(
+fn generate_non_zero_test_cases<S: Solver>(
     result: &mut String,
     unique_results: &mut HashSet<String>,
-    context: &Context,
-    felt252_arguments: &[(String, String)],
-    z3_variables: &[Int],
-    zero_constraints: &[(Int, Bool)],
-    other_constraints: &[Bool],
+    solver: &mut S,
+    arguments: &[SymbolicArgument],
+    storage_reads: &[StorageRead],
+    zero_operands: &[Term],
+    other_constraints: &[Formula],
 ) {
-    for (operand, _) in zero_constraints {
-        // Create a fresh solver for the non-zero case
-        let non_zero_solver = Solver::new(context);
+    for operand in zero_operands {
+        // Start from a fresh set of constraints for the non-zero case
+        solver.reset();
 
         // Re-apply all other constraints except the zero-equality one
         for constraint in other_constraints {
-            non_zero_solver.assert(constraint);
+            solver.assert(constraint);
         }
 
         // Add a constraint to force the operand to be not equal to 0
-        non_zero_solver.assert(&operand._eq(&Int::from_i64(context, 0)).not());
+        solver.assert(&!Formula::eq(operand.clone(), Term::constant(0)));
 
         // Check if the constraints are satisfiable
-        if non_zero_solver.check() == SatResult::Sat {
-            // Get the model from the solver
-            if let Some(model) = non_zero_solver.get_model() {
-                // Evaluate the variables and format the results
-                let values: Vec<String> = felt252_arguments
-                    .iter()
-                    .zip(z3_variables.iter())
-                    .map(|((arg_name, _), var)| {
-                        format!(
-                            "{}: {}",
-                            arg_name,
-                            model.eval(var, true).unwrap().to_string()
-                        )
-                    })
-                    .collect();
-                let values_str = format!("{}", values.join(", "));
+        if solver.check() == SolverResult::Sat {
+            // Evaluate the variables and format the results
+            if let Some(values_str) = format_test_case(solver, arguments, storage_reads) {
                 // Add the result to the unique results set and the result string
                 if unique_results.insert(values_str.clone()) {
                     result.push_str(&format!("{}\n", values_str));
@@ -326,11 +594,17 @@ fn generate_non_zero_test_cases(
 /// TODO : Reverse the logic and generate formatted testcases from the integers vectors
 pub fn get_integers_inputs(test_cases: &str) -> Vec<Vec<i64>> {
     let mut result = Vec::new();
-    let unique_results: HashSet<String> = test_cases.lines().map(|line| line.to_string()).collect();
+    // The duplicated lines are skipped, the test cases order is kept
+    let mut unique_results = HashSet::new();
+    let lines = test_cases
+        .lines()
+        .filter(|line| unique_results.insert(*line));
 
-    for line in unique_results {
+    for line in lines {
         let mut line_inputs = Vec::new();
-        let parts: Vec<&str> = line.split(", ").collect();
+        // The storage pre-state is not part of the inputs
+        let inputs = line.split(" | ").next().unwrap_or_default();
+        let parts: Vec<&str> = inputs.split(", ").collect();
         for part in parts {
             let key_value: Vec<&str> = part.split(": ").collect();
             if key_value.len() == 2 {
@@ -345,16 +619,18 @@ pub fn get_integers_inputs(test_cases: &str) -> Vec<Vec<i64>> {
     result
 }
 
-/// A struct that represents a symbolic execution solver
+/// A struct that represents a symbolic execution Z3 solver
+#[cfg(feature = "z3")]
 #[derive(Debug)]
 pub struct SymbolicExecution<'a> {
-    pub solver: Solver<'a>,
+    pub solver: Z3RawSolver<'a>,
 }
 
+#[cfg(feature = "z3")]
 impl<'a> SymbolicExecution<'a> {
     /// Creates a new instance of `SymbolicExecution`
     pub fn new(context: &'a Context) -> Self {
-        let solver = Solver::new(context);
+        let solver = Z3RawSolver::new(context);
 
         SymbolicExecution { solver }
     }
@@ -0,0 +1,356 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cairo_lang_sierra::program::BranchTarget;
+use cairo_lang_sierra::program::GenStatement;
+
+use crate::config::SymbolicExecutionConfig;
+use crate::decompiler::function::{Function, SierraStatement};
+use crate::decompiler::libfuncs_patterns::USER_DEFINED_FUNCTION_REGEX;
+use crate::graph::callgraph::CallGraph;
+use crate::{
+    extract_parameters, parse_element_name, parse_element_name_with_fallback, var_id_to_name,
+};
+
+/// A statement of an unrolled trace
+/// The suffix is appended to the statement variables names to distinguish the inlined calls
+#[derive(Debug, Clone)]
+pub struct TraceStatement<'f> {
+    /// The Sierra statement
+    pub statement: &'f SierraStatement,
+    /// Suffix of the variables of the function instance the statement belongs to
+    pub suffix: String,
+    /// Index of the branch taken by the statement in the trace, if known
+    pub branch: Option<usize>,
+}
+
+/// A path through a function where the calls to recursive functions (Sierra loops) are inlined
+#[derive(Debug, Clone, Default)]
+pub struct UnrolledTrace<'f> {
+    /// Statements of the trace grouped by basic blocks
+    pub blocks: Vec<Vec<TraceStatement<'f>>>,
+    /// Variables that are equal because of the inlined calls (arguments & returned values)
+    /// The first variable is defined by the binding, the second one is used
+    pub bindings: Vec<(String, String)>,
+    /// Index of the block before which each binding holds, the bindings of the parameters precede
+    /// the first block of the callee & the ones of the results the block following the call
+    pub binding_blocks: Vec<usize>,
+    /// Number of inlined recursive calls (loop iterations) in the trace
+    pub iterations: usize,
+    /// Variables returned by the function instance
+    pub returned_variables: Vec<String>,
+}
+
+/// Returns the names of the functions that are part of a recursion cycle
+/// Sierra loops are compiled to recursive functions
+pub fn recursive_functions(functions: &[Function]) -> HashSet<String> {
+    let callgraph = CallGraph::new(functions);
+
+    callgraph
+        .functions_indexes()
+        .filter_map(|index| {
+            let function_name = &callgraph.graph[index].name;
+            let callees: Vec<String> = callgraph
+                .graph
+                .neighbors(index)
+                .map(|callee_index| callgraph.graph[callee_index].name.clone())
+                .collect();
+
+            callgraph
+                .reachable_from(&callees)
+                .contains(function_name)
+                .then(|| function_name.clone())
+        })
+        .collect()
+}
+
+/// Returns the traces of a function where the recursive calls are unrolled up to `bound` times
+///
+/// Only the traces reaching 0, 1 or `bound` loop iterations are returned.
+pub fn unrolled_traces<'f>(
+    functions: &'f [Function<'f>],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    bound: usize,
+) -> Vec<UnrolledTrace<'f>> {
+    let mut unroller = Unroller {
+        functions,
+        declared_libfuncs_names,
+        recursive_functions: recursive_functions(functions),
+        inline_all_calls: false,
+        bound,
+        next_instance: 0,
+    };
+
+    // The top-level function variables keep their names
+    unroller
+        .expand(function_index, "", 0)
+        .into_iter()
+        .filter(|trace| trace.iterations <= 1 || trace.iterations == bound)
+        .map(remove_empty_blocks)
+        .collect()
+}
+
+/// Returns the traces of a function where all the user-defined functions calls are inlined,
+/// up to `depth` nested calls (the recursive calls are unrolled up to `depth` times)
+pub fn inlined_traces<'f>(
+    functions: &'f [Function<'f>],
+    function_index: usize,
+    declared_libfuncs_names: &[Arc<str>],
+    depth: usize,
+) -> Vec<UnrolledTrace<'f>> {
+    let mut unroller = Unroller {
+        functions,
+        declared_libfuncs_names,
+        recursive_functions: HashSet::new(),
+        inline_all_calls: true,
+        bound: depth,
+        next_instance: 0,
+    };
+
+    unroller
+        .expand(function_index, "", 0)
+        .into_iter()
+        .map(remove_empty_blocks)
+        .collect()
+}
+
+/// Removes the empty blocks of a trace, the bindings preceding an empty block precede the next one
+fn remove_empty_blocks(mut trace: UnrolledTrace) -> UnrolledTrace {
+    // Index of each block once the empty blocks are removed
+    let mut new_indexes = Vec::with_capacity(trace.blocks.len() + 1);
+    let mut non_empty_blocks = 0;
+    for block in &trace.blocks {
+        new_indexes.push(non_empty_blocks);
+        if !block.is_empty() {
+            non_empty_blocks += 1;
+        }
+    }
+    new_indexes.push(non_empty_blocks);
+
+    for block_index in &mut trace.binding_blocks {
+        *block_index = new_indexes[*block_index];
+    }
+    trace.blocks.retain(|block| !block.is_empty());
+    trace
+}
+
+/// Returns the index of the branch taken by a statement
+/// The branch is known if the statement has a single branch or if the next executed offset is given
+fn taken_branch(statement: &SierraStatement, next_offset: Option<u32>) -> Option<usize> {
+    let GenStatement::Invocation(invocation) = &statement.statement else {
+        return None;
+    };
+
+    if invocation.branches.len() == 1 {
+        return Some(0);
+    }
+
+    let next_offset = next_offset?;
+    invocation
+        .branches
+        .iter()
+        .position(|branch| match &branch.target {
+            BranchTarget::Fallthrough => statement.offset + 1 == next_offset,
+            BranchTarget::Statement(statement_idx) => statement_idx.0 == next_offset as usize,
+        })
+}
+
+/// Inlines the recursive calls of a function paths
+struct Unroller<'f, 'n> {
+    functions: &'f [Function<'f>],
+    declared_libfuncs_names: &'n [Arc<str>],
+    recursive_functions: HashSet<String>,
+    /// Inline all the user-defined functions calls instead of the recursive calls only
+    inline_all_calls: bool,
+    bound: usize,
+    /// Counter used to generate the suffixes of the inlined calls
+    next_instance: usize,
+}
+
+impl<'f, 'n> Unroller<'f, 'n> {
+    /// Returns the traces of an instance of a function
+    fn expand(
+        &mut self,
+        function_index: usize,
+        suffix: &str,
+        depth: usize,
+    ) -> Vec<UnrolledTrace<'f>> {
+        let functions = self.functions;
+        let mut traces = Vec::new();
+
+        let cfg = functions[function_index].cfg();
+
+        for path in cfg.paths_iter(
+            SymbolicExecutionConfig::MAX_PATHS,
+            SymbolicExecutionConfig::MAX_PATH_DEPTH,
+        ) {
+            let mut partials = vec![UnrolledTrace::default()];
+
+            for (block_index, basic_block) in path.iter().enumerate() {
+                for partial in &mut partials {
+                    partial.blocks.push(Vec::new());
+                }
+
+                // The taken branch of the block last statement is given by the next block
+                let next_block_offset = path.get(block_index + 1).map(|block| block.start_offset);
+
+                for (statement_index, statement) in basic_block.statements.iter().enumerate() {
+                    let branch = if statement_index == basic_block.statements.len() - 1 {
+                        taken_branch(statement, next_block_offset)
+                    } else {
+                        taken_branch(statement, None)
+                    };
+
+                    // Inline the call if it is a recursive call & the bound is not reached
+                    if let Some((callee_index, arguments, results)) =
+                        self.inlined_call(statement, depth)
+                    {
+                        self.next_instance += 1;
+                        let callee_suffix = format!("_{}", self.next_instance);
+                        let callee_traces = self.expand(callee_index, &callee_suffix, depth + 1);
+
+                        if !callee_traces.is_empty() {
+                            partials = self.inline(
+                                &partials,
+                                &callee_traces,
+                                callee_index,
+                                (arguments.as_slice(), results.as_slice()),
+                                (suffix, callee_suffix.as_str()),
+                            );
+                            continue;
+                        }
+                    }
+
+                    for partial in &mut partials {
+                        if let GenStatement::Return(returned_variables) = &statement.statement {
+                            partial.returned_variables = extract_parameters!(returned_variables)
+                                .into_iter()
+                                .map(|name| format!("{}{}", name, suffix))
+                                .collect();
+                        }
+
+                        partial.blocks.last_mut().unwrap().push(TraceStatement {
+                            statement,
+                            suffix: suffix.to_string(),
+                            branch,
+                        });
+                    }
+                }
+            }
+
+            traces.extend(partials);
+            if traces.len() >= SymbolicExecutionConfig::MAX_PATHS {
+                traces.truncate(SymbolicExecutionConfig::MAX_PATHS);
+                break;
+            }
+        }
+
+        traces
+    }
+
+    /// Returns the cross product of the caller partial traces & the callee traces
+    fn inline(
+        &self,
+        partials: &[UnrolledTrace<'f>],
+        callee_traces: &[UnrolledTrace<'f>],
+        callee_index: usize,
+        (arguments, results): (&[String], &[String]),
+        (suffix, callee_suffix): (&str, &str),
+    ) -> Vec<UnrolledTrace<'f>> {
+        let mut new_partials = Vec::new();
+        let callee = &self.functions[callee_index];
+
+        for partial in partials {
+            for callee_trace in callee_traces {
+                if new_partials.len() >= SymbolicExecutionConfig::MAX_PATHS {
+                    return new_partials;
+                }
+
+                let mut new_partial = partial.clone();
+                let callee_start = partial.blocks.len();
+                let callee_end = callee_start + callee_trace.blocks.len();
+
+                // The callee parameters are the call arguments
+                for (param, argument) in callee.function.params.iter().zip(arguments) {
+                    let param_name: String = var_id_to_name!(param.id);
+                    new_partial.bindings.push((
+                        format!("{}{}", param_name, callee_suffix),
+                        format!("{}{}", argument, suffix),
+                    ));
+                    new_partial.binding_blocks.push(callee_start);
+                }
+
+                // The call results are the callee returned variables
+                for (result, returned_variable) in
+                    results.iter().zip(&callee_trace.returned_variables)
+                {
+                    new_partial
+                        .bindings
+                        .push((format!("{}{}", result, suffix), returned_variable.clone()));
+                    new_partial.binding_blocks.push(callee_end);
+                }
+
+                new_partial
+                    .bindings
+                    .extend(callee_trace.bindings.iter().cloned());
+                new_partial.binding_blocks.extend(
+                    callee_trace
+                        .binding_blocks
+                        .iter()
+                        .map(|block_index| callee_start + block_index),
+                );
+                new_partial
+                    .blocks
+                    .extend(callee_trace.blocks.iter().cloned());
+                // The caller basic block continues after the call
+                new_partial.blocks.push(Vec::new());
+                new_partial.iterations += callee_trace.iterations + 1;
+
+                new_partials.push(new_partial);
+            }
+        }
+
+        new_partials
+    }
+
+    /// Returns the callee index, the arguments & the results of a call to inline
+    fn inlined_call(
+        &self,
+        statement: &SierraStatement,
+        depth: usize,
+    ) -> Option<(usize, Vec<String>, Vec<String>)> {
+        if depth >= self.bound {
+            return None;
+        }
+
+        let GenStatement::Invocation(invocation) = &statement.statement else {
+            return None;
+        };
+
+        let libfunc_name =
+            parse_element_name_with_fallback!(invocation.libfunc_id, self.declared_libfuncs_names);
+        let callee_name = USER_DEFINED_FUNCTION_REGEX
+            .captures(&libfunc_name)?
+            .name("function_id")?
+            .as_str()
+            .to_string();
+
+        // Only the loops are unrolled
+        if !self.inline_all_calls && !self.recursive_functions.contains(&callee_name) {
+            return None;
+        }
+
+        let callee_index = self
+            .functions
+            .iter()
+            .position(|function| parse_element_name!(function.function.id) == callee_name)?;
+        let results = invocation
+            .branches
+            .first()
+            .map(|branch| extract_parameters!(&branch.results))
+            .unwrap_or_default();
+
+        Some((callee_index, extract_parameters!(invocation.args), results))
+    }
+}
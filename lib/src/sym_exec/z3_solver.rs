@@ -0,0 +1,231 @@
+use std::ffi::CStr;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use tracing::warn;
+use z3::ast::{Array, Ast, Bool, Int};
+use z3::{Config, Context, FuncDecl, Model, Params, SatResult, Sort, Tactic};
+
+use crate::config::SymbolicExecutionConfig;
+use crate::sym_exec::formula::{ArrayTerm, Formula, Term};
+use crate::sym_exec::solver::{Solver, SolverResult};
+
+/// Tuning options of the Z3 solver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Z3Options {
+    /// Maximum time of a satisfiability check in milliseconds, the checks reaching it are unknown
+    pub timeout: Option<u32>,
+    /// Seed of the solver heuristics, changing it changes the generated models
+    pub random_seed: Option<u32>,
+    /// Tactic the solver is created from (e.g. `qfnia`), see `z3_tactics`
+    pub tactic: Option<String>,
+}
+
+impl Default for Z3Options {
+    fn default() -> Self {
+        Self {
+            timeout: SymbolicExecutionConfig::SOLVER_TIMEOUT,
+            random_seed: SymbolicExecutionConfig::SOLVER_RANDOM_SEED,
+            tactic: None,
+        }
+    }
+}
+
+lazy_static! {
+    /// Options of the solvers created by `Z3Solver::new`
+    static ref DEFAULT_OPTIONS: RwLock<Z3Options> = RwLock::new(Z3Options::default());
+}
+
+/// Sets the options of the solvers created by `Z3Solver::new`, i.e. of the symbolic execution analyses
+pub fn set_default_options(options: Z3Options) {
+    *DEFAULT_OPTIONS.write().unwrap() = options;
+}
+
+/// Z3 solver backend
+#[derive(Debug)]
+pub struct Z3Solver<'ctx> {
+    context: &'ctx Context,
+    solver: z3::Solver<'ctx>,
+    /// Model of the last satisfiable check
+    model: Option<Model<'ctx>>,
+}
+
+impl<'ctx> Z3Solver<'ctx> {
+    /// Creates a new `Z3Solver` instance with the default options, see `set_default_options`
+    pub fn new(context: &'ctx Context) -> Self {
+        let options = DEFAULT_OPTIONS.read().unwrap().clone();
+        Self::with_options(context, &options)
+    }
+
+    /// Creates a new `Z3Solver` instance with the given options
+    /// The unknown tactics are ignored & the default solver is used
+    pub fn with_options(context: &'ctx Context, options: &Z3Options) -> Self {
+        let tactic = options.tactic.as_deref().filter(|tactic| {
+            let is_known = Tactic::list_all(context).any(|name| name.ok() == Some(*tactic));
+            if !is_known {
+                warn!("Unknown Z3 tactic '{}', using the default solver", tactic);
+            }
+            is_known
+        });
+        let solver = match tactic {
+            Some(tactic) => Tactic::new(context, tactic).solver(),
+            None => z3::Solver::new(context),
+        };
+
+        let mut params = Params::new(context);
+        if let Some(timeout) = options.timeout {
+            params.set_u32("timeout", timeout);
+        }
+        if let Some(random_seed) = options.random_seed {
+            params.set_u32("random_seed", random_seed);
+        }
+        solver.set_params(&params);
+
+        Self {
+            context,
+            solver,
+            model: None,
+        }
+    }
+}
+
+/// Returns the names of the tactics the solver can be created from
+pub fn z3_tactics() -> Vec<String> {
+    let context = Context::new(&Config::new());
+    let tactics = Tactic::list_all(&context)
+        .filter_map(|name| name.ok().map(str::to_string))
+        .collect();
+    tactics
+}
+
+/// Returns the full version of the Z3 library the solver is linked with
+#[inline]
+pub fn z3_version() -> &'static str {
+    // The version is a static null-terminated string owned by the Z3 library
+    unsafe { CStr::from_ptr(z3_sys::Z3_get_full_version()) }
+        .to_str()
+        .unwrap_or("unknown")
+}
+
+impl<'ctx> Solver for Z3Solver<'ctx> {
+    fn assert(&mut self, formula: &Formula) {
+        self.solver.assert(&formula_to_z3(self.context, formula));
+    }
+
+    fn check(&mut self) -> SolverResult {
+        let result = self.solver.check();
+        self.model = match result {
+            SatResult::Sat => self.solver.get_model(),
+            _ => None,
+        };
+
+        match result {
+            SatResult::Sat => SolverResult::Sat,
+            SatResult::Unsat => SolverResult::Unsat,
+            SatResult::Unknown => SolverResult::Unknown,
+        }
+    }
+
+    fn eval(&self, term: &Term) -> Option<BigInt> {
+        let value = self
+            .model
+            .as_ref()?
+            .eval(&term_to_z3(self.context, term), true)?;
+
+        // Large values do not fit in a i64
+        value
+            .as_i64()
+            .map(BigInt::from)
+            .or_else(|| value.to_string().parse().ok())
+    }
+
+    fn push(&mut self) {
+        self.solver.push();
+    }
+
+    fn pop(&mut self) {
+        self.solver.pop(1);
+        self.model = None;
+    }
+
+    fn reset(&mut self) {
+        self.solver.reset();
+        self.model = None;
+    }
+}
+
+/// Converts a term to a Z3 integer expression
+pub fn term_to_z3<'ctx>(context: &'ctx Context, term: &Term) -> Int<'ctx> {
+    match term {
+        Term::Var(name) => Int::new_const(context, name.as_str()),
+        Term::Const(value) => match i64::try_from(value) {
+            Ok(value) => Int::from_i64(context, value),
+            Err(_) => Int::from_str(context, &value.to_string()).unwrap(),
+        },
+        Term::Add(lhs, rhs) => term_to_z3(context, lhs) + term_to_z3(context, rhs),
+        Term::Sub(lhs, rhs) => term_to_z3(context, lhs) - term_to_z3(context, rhs),
+        Term::Mul(lhs, rhs) => term_to_z3(context, lhs) * term_to_z3(context, rhs),
+        Term::Select(array, index) => array_to_z3(context, array)
+            .select(&term_to_z3(context, index))
+            .as_int()
+            .unwrap(),
+        Term::App(function, arguments) => {
+            let arguments: Vec<Int> = arguments
+                .iter()
+                .map(|argument| term_to_z3(context, argument))
+                .collect();
+            let domain: Vec<Sort> = arguments.iter().map(|_| Sort::int(context)).collect();
+            let function = FuncDecl::new(
+                context,
+                function.as_str(),
+                &domain.iter().collect::<Vec<_>>(),
+                &Sort::int(context),
+            );
+            function
+                .apply(
+                    &arguments
+                        .iter()
+                        .map(|argument| argument as &dyn Ast)
+                        .collect::<Vec<_>>(),
+                )
+                .as_int()
+                .unwrap()
+        }
+    }
+}
+
+/// Converts an array term to a Z3 array of integers indexed by integers
+pub fn array_to_z3<'ctx>(context: &'ctx Context, array: &ArrayTerm) -> Array<'ctx> {
+    match array {
+        ArrayTerm::Var(name) => Array::new_const(
+            context,
+            name.as_str(),
+            &Sort::int(context),
+            &Sort::int(context),
+        ),
+        ArrayTerm::Const(value) => {
+            Array::const_array(context, &Sort::int(context), &term_to_z3(context, value))
+        }
+        ArrayTerm::Store(array, index, value) => array_to_z3(context, array)
+            .store(&term_to_z3(context, index), &term_to_z3(context, value)),
+    }
+}
+
+/// Converts a formula to a Z3 boolean expression
+pub fn formula_to_z3<'ctx>(context: &'ctx Context, formula: &Formula) -> Bool<'ctx> {
+    match formula {
+        Formula::Eq(lhs, rhs) => term_to_z3(context, lhs)._eq(&term_to_z3(context, rhs)),
+        Formula::ArrayEq(lhs, rhs) => array_to_z3(context, lhs)._eq(&array_to_z3(context, rhs)),
+        Formula::Lt(lhs, rhs) => term_to_z3(context, lhs).lt(&term_to_z3(context, rhs)),
+        Formula::Le(lhs, rhs) => term_to_z3(context, lhs).le(&term_to_z3(context, rhs)),
+        Formula::Not(formula) => formula_to_z3(context, formula).not(),
+        Formula::And(formulas) => {
+            let formulas: Vec<Bool> = formulas
+                .iter()
+                .map(|formula| formula_to_z3(context, formula))
+                .collect();
+            Bool::and(context, &formulas.iter().collect::<Vec<_>>())
+        }
+    }
+}
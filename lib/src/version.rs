@@ -0,0 +1,78 @@
+use std::fmt;
+
+use serde_json::Value;
+
+/// Latest Sierra version supported by the program registry
+pub const SUPPORTED_SIERRA_VERSION: SierraVersion = SierraVersion::new(1, 6, 0);
+
+/// Version of the analyzer, the cached results computed by another version are not reused
+pub const ANALYZER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the Sierra language or of the compiler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SierraVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl SierraVersion {
+    /// Creates a new `SierraVersion` instance
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Returns true if the version is older than the latest supported version
+    pub fn is_legacy(&self) -> bool {
+        *self < SUPPORTED_SIERRA_VERSION
+    }
+}
+
+impl fmt::Display for SierraVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Versions of a contract class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassVersion {
+    /// Version of the Sierra program
+    pub sierra_version: SierraVersion,
+    /// Version of the compiler used to build the class
+    pub compiler_version: SierraVersion,
+    /// Version of the contract class format (e.g. "0.1.0")
+    pub contract_class_version: Option<String>,
+}
+
+/// Detects the versions of a contract class from its JSON content
+/// The Sierra & compiler versions are the first 6 felts of the serialized Sierra program
+pub fn detect_class_version(content: &str) -> Option<ClassVersion> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    let felts = value["sierra_program"].as_array()?;
+
+    // Parses the version at the given index of the serialized program
+    let version_at = |index: usize| -> Option<SierraVersion> {
+        let mut parts = felts.get(index..index + 3)?.iter().map(|felt| {
+            let felt = felt.as_str()?;
+            u64::from_str_radix(felt.trim_start_matches("0x"), 16).ok()
+        });
+        Some(SierraVersion::new(
+            parts.next()??,
+            parts.next()??,
+            parts.next()??,
+        ))
+    };
+
+    Some(ClassVersion {
+        sierra_version: version_at(0)?,
+        compiler_version: version_at(3)?,
+        contract_class_version: value["contract_class_version"]
+            .as_str()
+            .map(|version| version.to_string()),
+    })
+}
@@ -0,0 +1,53 @@
+use sierra_analyzer_lib::analysis::access_control::{AccessCheck, AccessControlMatrix};
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_access_control_matrix() {
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/with_ownable__ownable_balance.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The checks are found in the SSA form of the functions
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+    let matrix = AccessControlMatrix::new(&decompiler.functions);
+
+    // The caller address is compared with the owner read from the storage by validate_ownership
+    let checks: Vec<(&str, Vec<AccessCheck>)> = matrix
+        .entries
+        .iter()
+        .map(|entry| (entry.entry_point.as_str(), entry.access_checks()))
+        .collect();
+    assert_eq!(
+        checks,
+        vec![
+            ("get_balance", vec![AccessCheck::None]),
+            ("set_balance", vec![AccessCheck::Owner]),
+            ("owner", vec![AccessCheck::None]),
+            ("transfer_ownership", vec![AccessCheck::Owner]),
+            ("constructor", vec![AccessCheck::None]),
+        ]
+    );
+    assert!(matrix.entries[1].owner_checks[0].ends_with("::validate_ownership"));
+    assert!(matrix.entries[1].role_checks.is_empty());
+
+    // Text, Markdown & JSON outputs
+    let text = matrix.to_text();
+    assert!(text.starts_with("Entry point         Owner  Role  None\n"));
+    assert!(text.contains("\nset_balance         x\n"));
+    assert!(text.contains("\nget_balance                      x\n"));
+    let markdown = matrix.to_markdown();
+    assert!(markdown.starts_with("| Entry point | Owner | Role | None |\n"));
+    assert!(markdown.contains("| `transfer_ownership` | ✓ |  |  |\n"));
+    let json = matrix.to_json();
+    assert!(json.contains("\"entry_point\": \"set_balance\""));
+    assert!(json.contains("\"role_checks\": []"));
+}
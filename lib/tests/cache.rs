@@ -0,0 +1,64 @@
+use std::fs;
+
+use sierra_analyzer_lib::analysis::context::AnalysisContext;
+use sierra_analyzer_lib::cache::{cache_key, program_hash, AnalysisCache, DetectorResults};
+use sierra_analyzer_lib::detectors::detector::{Detector, FindingReport};
+use sierra_analyzer_lib::detectors::truncation_detector::TruncationDetector;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_analysis_cache_reused_between_runs() {
+    let directory = std::env::temp_dir().join("sierra_analyzer_analysis_cache");
+    let _ = fs::remove_dir_all(&directory);
+
+    let content = include_str!("../../examples/vulnerable/truncation.sierra").to_string();
+    let program = SierraProgram::new(content.clone());
+
+    // First run, the results are computed & saved
+    let context = AnalysisContext::new(&program).unwrap();
+    let mut detector = TruncationDetector::new();
+    let results = DetectorResults {
+        report: detector.detect(&context),
+        findings: detector
+            .findings(&context)
+            .into_iter()
+            .map(|finding| FindingReport::new(detector.id(), finding, &context))
+            .collect(),
+    };
+    let mut cache = AnalysisCache::open(&directory, &program, "options").unwrap();
+    assert!(cache.detector_results("truncation", None).is_none());
+    cache.set_decompiled("options", "decompiled".to_string());
+    cache.set_detector_results("truncation", None, results.clone());
+    cache.save().unwrap();
+
+    // Second run on the same program, the results are read from the cache file
+    let program = SierraProgram::new(content);
+    let cache = AnalysisCache::open(&directory, &program, "options").unwrap();
+    assert_eq!(
+        cache.path(),
+        directory.join(format!("{}.json", cache_key(&program, "options")))
+    );
+    assert_eq!(cache.decompiled("options"), Some("decompiled"));
+    assert!(cache.decompiled("other options").is_none());
+    assert_eq!(cache.detector_results("truncation", None), Some(&results));
+
+    // The results depend on the function filter
+    assert!(cache
+        .detector_results("truncation", Some("to_u128"))
+        .is_none());
+
+    // The results depend on the analysis options
+    let other_options = AnalysisCache::open(&directory, &program, "other options").unwrap();
+    assert_ne!(other_options.path(), cache.path());
+    assert!(other_options.detector_results("truncation", None).is_none());
+
+    // Another program has its own cache file
+    let other_program = SierraProgram::new(
+        include_str!("../../examples/vulnerable/truncation_patched.sierra").to_string(),
+    );
+    assert_ne!(program_hash(&program), program_hash(&other_program));
+    let other_cache = AnalysisCache::open(&directory, &other_program, "options").unwrap();
+    assert!(other_cache.detector_results("truncation", None).is_none());
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,32 @@
+use sierra_analyzer_lib::class_hash::{compute_class_hash, verify_class_hash};
+use sierra_analyzer_lib::error::SierraAnalyzerError;
+
+#[test]
+fn test_verify_class_hash() {
+    let content =
+        include_str!("../../examples/starknet/hello_starknet__hello_starknet.contract_class.json");
+
+    // The class hash is computed from the contract class
+    let class_hash = compute_class_hash(content).unwrap();
+    assert_eq!(
+        format!("{:#x}", class_hash),
+        "0x36068dd3c4b7bb0907105c2bf3a9aa85e0a5911442f963cb1651fff36dbbd03"
+    );
+    let other_content = include_str!("../../examples/starknet/erc20__erc_20.contract_class.json");
+    assert_ne!(compute_class_hash(other_content).unwrap(), class_hash);
+
+    // Same class hash
+    let verification = verify_class_hash(content, &format!("{:#x}", class_hash)).unwrap();
+    assert!(verification.is_match());
+
+    // Different class hash
+    let verification = verify_class_hash(content, "0x1").unwrap();
+    assert!(!verification.is_match());
+
+    // Invalid class hash & contract class
+    assert!(matches!(
+        verify_class_hash(content, "class hash"),
+        Err(SierraAnalyzerError::ClassHash(_))
+    ));
+    assert!(compute_class_hash(include_str!("../../examples/sierra/fib.sierra")).is_err());
+}
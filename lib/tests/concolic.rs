@@ -0,0 +1,55 @@
+#![cfg(all(feature = "concolic", feature = "z3"))]
+
+use num_bigint::BigInt;
+
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::sym_exec::concolic::{concolic_execution, ConcreteRunner, PathValidation};
+use sierra_analyzer_lib::sym_exec::hashes::HashModel;
+
+#[test]
+fn test_concrete_run() {
+    let content = include_str!("../../examples/sierra/symbolic_execution_test.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let runner = ConcreteRunner::new(program.program()).unwrap();
+
+    let arguments: Vec<BigInt> = [102, 117, 122, 122].into_iter().map(BigInt::from).collect();
+    let run = runner
+        .run("symbolic::symbolic::symbolic_execution_test", &arguments)
+        .unwrap();
+    assert!(!run.panicked);
+    assert_eq!(run.values, vec![BigInt::from(0)]);
+
+    // The jump of the zero branch of the first check is executed
+    assert!(run.executed_offsets.contains(&5));
+
+    let arguments = vec![BigInt::from(0); 4];
+    let run = runner
+        .run("symbolic::symbolic::symbolic_execution_test", &arguments)
+        .unwrap();
+    assert!(!run.executed_offsets.contains(&5));
+}
+
+#[test]
+fn test_concolic_execution() {
+    let content = include_str!("../../examples/sierra/symbolic_execution_test.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    decompiler.functions[0].create_cfg();
+
+    let reports = concolic_execution(
+        program.program(),
+        &decompiler.functions,
+        0,
+        &decompiler.declared_libfuncs_names,
+        HashModel::Unconstrained,
+    )
+    .unwrap();
+
+    // Each combination of the four checks is followed by the concrete execution of its inputs
+    assert_eq!(reports.len(), 16);
+    assert!(reports
+        .iter()
+        .all(|report| report.validation == PathValidation::Validated && !report.panicked));
+}
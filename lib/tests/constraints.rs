@@ -0,0 +1,143 @@
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::sym_exec::constraints::{
+    sierra_statement_to_constraint, sierra_statement_to_constraint_with_suffix,
+};
+use sierra_analyzer_lib::sym_exec::formula::{Formula, Term};
+
+const PROGRAM: &str = r#"type felt252 = felt252;
+type NonZero<felt252> = NonZero<felt252>;
+
+libfunc felt252_const<5> = felt252_const<5>;
+libfunc felt252_add = felt252_add;
+libfunc dup<felt252> = dup<felt252>;
+libfunc store_temp<felt252> = store_temp<felt252>;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+
+felt252_const<5>() -> ([1]);
+felt252_add([0], [1]) -> ([2]);
+dup<felt252>([2]) -> ([2], [3]);
+store_temp<felt252>([3]) -> ([5]);
+felt252_is_zero([2]) { fallthrough() 7([4]) };
+branch_align() -> ();
+return([5]);
+branch_align() -> ();
+drop<NonZero<felt252>>([4]) -> ();
+return([5]);
+
+test::constraints@0([0]: felt252) -> (felt252);"#;
+
+#[test]
+fn test_arithmetic_constraints() {
+    let program = SierraProgram::new(PROGRAM.to_string());
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let statements = decompiler.functions[0].statements();
+
+    // v1 = 5
+    assert_eq!(
+        sierra_statement_to_constraint(&statements[0], &decompiler.declared_libfuncs_names),
+        Some(Formula::eq(Term::var("v1"), Term::constant(5)))
+    );
+
+    // v2 = v0 + v1
+    assert_eq!(
+        sierra_statement_to_constraint(&statements[1], &decompiler.declared_libfuncs_names),
+        Some(Formula::eq(
+            Term::var("v2"),
+            Term::var("v0") + Term::var("v1")
+        ))
+    );
+
+    // The variables of an inlined call have the suffix of its instance
+    assert_eq!(
+        sierra_statement_to_constraint_with_suffix(
+            &statements[1],
+            &decompiler.declared_libfuncs_names,
+            "_1",
+            None
+        ),
+        Some(Formula::eq(
+            Term::var("v2_1"),
+            Term::var("v0_1") + Term::var("v1_1")
+        ))
+    );
+}
+
+#[test]
+fn test_assignment_constraints() {
+    let program = SierraProgram::new(PROGRAM.to_string());
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let statements = decompiler.functions[0].statements();
+
+    // Both results of a duplication are equal to the duplicated value
+    assert_eq!(
+        sierra_statement_to_constraint(&statements[2], &decompiler.declared_libfuncs_names),
+        Some(Formula::and(vec![
+            Formula::eq(Term::var("v2"), Term::var("v2")),
+            Formula::eq(Term::var("v3"), Term::var("v2")),
+        ]))
+    );
+
+    // v5 = v3
+    assert_eq!(
+        sierra_statement_to_constraint(&statements[3], &decompiler.declared_libfuncs_names),
+        Some(Formula::eq(Term::var("v5"), Term::var("v3")))
+    );
+}
+
+#[test]
+fn test_zero_check_constraints() {
+    let program = SierraProgram::new(PROGRAM.to_string());
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let statements = decompiler.functions[0].statements();
+    let is_zero = Formula::eq(Term::var("v2"), Term::constant(0));
+
+    // The constraint of a zero check with an unknown branch is the one of the zero branch
+    assert_eq!(
+        sierra_statement_to_constraint(&statements[4], &decompiler.declared_libfuncs_names),
+        Some(is_zero.clone())
+    );
+    assert_eq!(
+        sierra_statement_to_constraint_with_suffix(
+            &statements[4],
+            &decompiler.declared_libfuncs_names,
+            "",
+            Some(0)
+        ),
+        Some(is_zero.clone())
+    );
+
+    // The non-zero branch returns the operand as a NonZero value
+    assert_eq!(
+        sierra_statement_to_constraint_with_suffix(
+            &statements[4],
+            &decompiler.declared_libfuncs_names,
+            "",
+            Some(1)
+        ),
+        Some(Formula::and(vec![
+            !is_zero,
+            Formula::eq(Term::var("v4"), Term::var("v2")),
+        ]))
+    );
+}
+
+#[test]
+fn test_unconstrained_statements() {
+    let program = SierraProgram::new(PROGRAM.to_string());
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let statements = decompiler.functions[0].statements();
+
+    // The branch alignments & the returns don't constrain the variables
+    for statement in [&statements[5], &statements[6]] {
+        assert_eq!(
+            sierra_statement_to_constraint(statement, &decompiler.declared_libfuncs_names),
+            None
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use sierra_analyzer_lib::corpus::{corpus_entries, diff_output};
+
+#[test]
+fn test_corpus_snapshots() {
+    // Decompile all the examples & compare the outputs with the snapshots
+    // The snapshots are updated using `cargo insta review`
+    let entries = corpus_entries(Path::new("../examples")).unwrap();
+    assert!(!entries.is_empty());
+
+    for entry in entries {
+        insta::assert_snapshot!(entry.name.clone(), entry.snapshot_output());
+    }
+}
+
+#[test]
+fn test_corpus_diff() {
+    assert!(diff_output("func f () {\n}\n", "func f () {\n}").is_none());
+
+    // Broken indentation
+    let diff = diff_output("func f () {\n\treturn ()\n}", "func f () {\nreturn ()\n}").unwrap();
+    assert!(diff.contains("-\treturn ()"));
+    assert!(diff.contains("+return ()"));
+}
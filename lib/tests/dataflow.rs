@@ -0,0 +1,284 @@
+use num_bigint::BigInt;
+use sierra_analyzer_lib::analysis::dataflow::Definition;
+use sierra_analyzer_lib::analysis::intervals::{Interval, IntervalAnalysis};
+use sierra_analyzer_lib::analysis::ssa::SsaVariable;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_def_use_fib() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let dataflow = decompiler.functions[0].def_use();
+
+    // [0] is a parameter redefined by store_temp then returned
+    assert_eq!(
+        dataflow.definitions(0),
+        &[Definition::Parameter, Definition::Statement(6)]
+    );
+    assert_eq!(dataflow.uses(0), &[6, 7, 11]);
+
+    // [3] is the dup of [2] checked by felt252_is_zero
+    assert_eq!(dataflow.definitions(3), &[Definition::Statement(1)]);
+    assert_eq!(dataflow.uses(3), &[2]);
+
+    // [4] is only defined in the non-zero branch of felt252_is_zero
+    assert_eq!(dataflow.definitions(4), &[Definition::Statement(2)]);
+    assert_eq!(dataflow.uses(4), &[9]);
+
+    // [9] is the result of the recursive call
+    assert_eq!(dataflow.definitions(9), &[Definition::Statement(17)]);
+    assert_eq!(dataflow.uses(9), &[18]);
+
+    // Every variable of fib is used
+    assert!(dataflow.unused_variables().is_empty());
+    assert_eq!(dataflow.variables().count(), 10);
+}
+
+#[test]
+fn test_def_use_fib_array() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // Each used variable has at least one definition site
+    for function in &decompiler.functions {
+        let dataflow = function.def_use();
+        for (var_id, def_use) in dataflow.variables() {
+            assert!(
+                !def_use.definitions.is_empty(),
+                "Variable [{}] is used but never defined",
+                var_id
+            );
+        }
+    }
+}
+
+#[test]
+fn test_ssa_fib() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let ssa = decompiler.functions[0].ssa().unwrap();
+    let variable = |id, version| SsaVariable { id, version };
+
+    // The if/else blocks are dominated by the entry block, there is no join
+    assert_eq!(ssa.immediate_dominator(0), None);
+    assert_eq!(ssa.immediate_dominator(3), Some(0));
+    assert_eq!(ssa.immediate_dominator(8), Some(0));
+    assert!(ssa.dominance_frontier(3).is_empty());
+    assert_eq!(ssa.all_phis().count(), 0);
+
+    // [0] is redefined by store_temp in the zero branch only
+    assert_eq!(ssa.parameters()[0], variable(0, 0));
+    let store_temp = ssa.statement(6).unwrap();
+    assert_eq!(store_temp.uses, vec![variable(0, 0)]);
+    assert_eq!(store_temp.definitions, vec![vec![variable(0, 1)]]);
+    assert_eq!(ssa.statement(7).unwrap().uses, vec![variable(0, 1)]);
+    assert_eq!(ssa.statement(11).unwrap().uses[0], variable(0, 0));
+
+    // [4] is defined by the non-zero branch of felt252_is_zero
+    assert_eq!(
+        ssa.statement(2).unwrap().definitions,
+        vec![vec![], vec![variable(4, 1)]]
+    );
+    assert_eq!(ssa.statement(9).unwrap().uses, vec![variable(4, 1)]);
+}
+
+#[test]
+fn test_ssa_phi_nodes() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/match_or.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let ssa = decompiler.functions[0].ssa().unwrap();
+    let variable = |id, version| SsaVariable { id, version };
+
+    // The or-pattern branches join at the u8_overflowing_add of statement 41
+    assert_eq!(ssa.immediate_dominator(41), Some(0));
+    assert_eq!(ssa.dominance_frontier(30), vec![41]);
+    assert_eq!(ssa.dominance_frontier(36), vec![41]);
+
+    // [27] & [28] are merged, the other variables of the branches are dead at the join
+    let phis = ssa.phis(41);
+    assert_eq!(phis.len(), 2);
+    assert_eq!(phis[0].result, variable(27, 3));
+    assert_eq!(
+        phis[0].operands.iter().collect::<Vec<_>>(),
+        vec![(&30, &Some(variable(27, 1))), (&36, &Some(variable(27, 2)))]
+    );
+    assert_eq!(phis[1].result.id, 28);
+    assert_eq!(
+        ssa.statement(41).unwrap().uses,
+        vec![variable(0, 0), variable(27, 3), variable(28, 3)]
+    );
+
+    // The range check [23] is merged after the first match
+    let phis = ssa.phis(46);
+    assert_eq!(phis.len(), 1);
+    assert_eq!(phis[0].result, variable(23, 3));
+    assert_eq!(ssa.statement(52).unwrap().uses, vec![variable(23, 3)]);
+}
+
+#[test]
+fn test_intervals_comparison() {
+    // The u128 parameter is lower than 100 in the branch where the comparison is true
+    let content = r#"type RangeCheck = RangeCheck [storable: true, drop: false, dup: false, zero_sized: false];
+type u128 = u128 [storable: true, drop: true, dup: true, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type Const<u128, 100> = Const<u128, 100> [storable: false, drop: false, dup: false, zero_sized: false];
+
+libfunc const_as_immediate<Const<u128, 100>> = const_as_immediate<Const<u128, 100>>;
+libfunc store_temp<u128> = store_temp<u128>;
+libfunc u128_lt = u128_lt;
+libfunc branch_align = branch_align;
+libfunc drop<u128> = drop<u128>;
+libfunc u128_to_felt252 = u128_to_felt252;
+libfunc dup<felt252> = dup<felt252>;
+libfunc felt252_mul = felt252_mul;
+libfunc drop<felt252> = drop<felt252>;
+
+const_as_immediate<Const<u128, 100>>() -> ([2]);
+store_temp<u128>([2]) -> ([2]);
+u128_lt([0], [1], [2]) { fallthrough([3]) 6([4]) };
+branch_align() -> ();
+drop<u128>([1]) -> ();
+return([3]);
+branch_align() -> ();
+u128_to_felt252([1]) -> ([5]);
+dup<felt252>([5]) -> ([5], [6]);
+felt252_mul([5], [6]) -> ([7]);
+drop<felt252>([7]) -> ();
+return([4]);
+
+test::square@0([0]: RangeCheck, [1]: u128) -> (RangeCheck);"#
+        .to_string();
+
+    // u128_lt is not a core libfunc anymore, the program is loaded without a registry
+    let program = SierraProgram::try_new_legacy(content).unwrap();
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let analysis = IntervalAnalysis::new(
+        &decompiler.functions[0],
+        &decompiler.declared_libfuncs_names,
+    )
+    .unwrap();
+    let interval = |min: u64, max: u64| Interval::new(BigInt::from(min), BigInt::from(max));
+
+    // The constant & the parameter type bound the operands
+    assert_eq!(
+        analysis.interval(SsaVariable { id: 2, version: 2 }),
+        Some(&interval(100, 100))
+    );
+    assert_eq!(analysis.argument_interval(2, 1), Interval::of_type("u128"));
+
+    // The parameter is refined by the true branch of u128_lt
+    assert_eq!(analysis.argument_interval(7, 0), Some(interval(0, 99)));
+    assert_eq!(analysis.argument_interval(9, 0), Some(interval(0, 99)));
+    assert!(analysis.proves_no_overflow(9, "felt252_mul"));
+
+    // The false branch implies the opposite bound
+    assert_eq!(
+        analysis.argument_interval(4, 0),
+        Some(Interval::new(
+            BigInt::from(100),
+            (BigInt::from(1) << 128) - 1
+        ))
+    );
+}
+
+#[test]
+fn test_intervals_overflowing_sub() {
+    // The u64 parameter is lower than 100 in the branch where the subtraction overflows
+    let content = r#"type RangeCheck = RangeCheck [storable: true, drop: false, dup: false, zero_sized: false];
+type u64 = u64 [storable: true, drop: true, dup: true, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type Const<u64, 100> = Const<u64, 100> [storable: false, drop: false, dup: false, zero_sized: false];
+
+libfunc const_as_immediate<Const<u64, 100>> = const_as_immediate<Const<u64, 100>>;
+libfunc store_temp<u64> = store_temp<u64>;
+libfunc u64_overflowing_sub = u64_overflowing_sub;
+libfunc branch_align = branch_align;
+libfunc drop<u64> = drop<u64>;
+libfunc u64_to_felt252 = u64_to_felt252;
+libfunc dup<felt252> = dup<felt252>;
+libfunc felt252_mul = felt252_mul;
+libfunc drop<felt252> = drop<felt252>;
+
+const_as_immediate<Const<u64, 100>>() -> ([2]);
+store_temp<u64>([2]) -> ([2]);
+u64_overflowing_sub([0], [1], [2]) { fallthrough([3], [4]) 7([5], [6]) };
+branch_align() -> ();
+drop<u64>([4]) -> ();
+drop<u64>([1]) -> ();
+return([3]);
+branch_align() -> ();
+drop<u64>([6]) -> ();
+u64_to_felt252([1]) -> ([7]);
+dup<felt252>([7]) -> ([7], [8]);
+felt252_mul([7], [8]) -> ([9]);
+drop<felt252>([9]) -> ();
+return([5]);
+
+test::square@0([0]: RangeCheck, [1]: u64) -> (RangeCheck);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let analysis = IntervalAnalysis::new(
+        &decompiler.functions[0],
+        &decompiler.declared_libfuncs_names,
+    )
+    .unwrap();
+    let interval = |min: u64, max: u64| Interval::new(BigInt::from(min), BigInt::from(max));
+
+    // The constant & the parameter type bound the operands
+    assert_eq!(
+        analysis.interval(SsaVariable { id: 2, version: 2 }),
+        Some(&interval(100, 100))
+    );
+    assert_eq!(analysis.argument_interval(2, 1), Interval::of_type("u64"));
+
+    // The parameter is refined by the overflow branch of u64_overflowing_sub
+    assert_eq!(analysis.argument_interval(9, 0), Some(interval(0, 99)));
+    assert_eq!(analysis.argument_interval(11, 0), Some(interval(0, 99)));
+    assert!(analysis.proves_no_overflow(11, "felt252_mul"));
+
+    // The branch without overflow implies the opposite bound
+    assert_eq!(
+        analysis.argument_interval(5, 0),
+        Some(Interval::new(
+            BigInt::from(100),
+            (BigInt::from(1) << 64) - 1
+        ))
+    );
+}
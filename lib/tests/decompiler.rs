@@ -1,10 +1,25 @@
 use cairo_lang_starknet_classes::contract_class::ContractClass;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use num_bigint::BigInt;
+use sierra_analyzer_lib::decompiler::formatting::{FormattingOptions, IndentStyle};
+use sierra_analyzer_lib::decompiler::libfunc_kind::LibfuncKind;
+use sierra_analyzer_lib::decompiler::libfuncs_patterns::{
+    ADDITION_REGEX, EQUAL_REGEX, LESS_THAN_REGEX, MULTIPLICATION_REGEX, SUBSTRACTION_REGEX,
+};
+use sierra_analyzer_lib::decompiler::utils::{felt252_prime, format_constant};
+use sierra_analyzer_lib::decompiler::validation::validate_program;
+use sierra_analyzer_lib::error::SierraAnalyzerError;
+use sierra_analyzer_lib::provider::RpcResponse;
+use sierra_analyzer_lib::scarb;
+use sierra_analyzer_lib::sierra_program::{is_casm_class, ProgramKind, SierraProgram};
+use sierra_analyzer_lib::version::{detect_class_version, SierraVersion};
 use std::fs;
+use std::path::Path;
 use test_case::test_case;
 
 #[test_case("account__account.sierra")]
+#[test_case("bounded_int.sierra")]
 #[test_case("corelib_usage.sierra")]
+#[test_case("dict.sierra")]
 #[test_case("enum_flow.sierra")]
 #[test_case("erc20__erc_20.sierra")]
 #[test_case("fib_array.sierra")]
@@ -159,6 +174,251 @@ func examples::fib::fib (v0: felt252, v1: felt252, v2: felt252) -> (felt252) {
     assert_eq!(decompiler_output, expected_output);
 }
 
+#[test]
+fn test_decompile_single_function() {
+    let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let name = "test::hello_starknet::HelloStarknetImpl::get_balance";
+
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    // Only the function is loaded, its output is the same as in the whole program decompilation
+    let mut function_decompiler = program.decompiler(false);
+    let function_output = function_decompiler.decompile_function(name).unwrap();
+    assert!(function_decompiler.functions.is_empty());
+    assert!(function_output.contains(&format!("func {} (", name)));
+    assert!(decompiler_output.contains(&function_output));
+
+    // The functions already loaded are reused
+    assert_eq!(
+        decompiler.decompile_function(name).unwrap(),
+        function_output
+    );
+
+    assert!(matches!(
+        function_decompiler.decompile_function("test::hello_starknet::unknown"),
+        Err(SierraAnalyzerError::FunctionNotFound(_))
+    ));
+}
+
+#[test]
+fn test_decompiler_formatting_options() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Indent with 2 spaces, wrap the lines longer than 30 characters & remove the function comments
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        indent_style: IndentStyle::Spaces,
+        indent_width: 2,
+        max_line_length: Some(30),
+        function_comments: false,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(!decompiler_output.contains("// Function"));
+    assert!(!decompiler_output.contains('\t'));
+    assert!(decompiler_output.contains("\n  v3 = v2\n"));
+    assert!(decompiler_output
+        .contains("\n    v9 = user@examples::fib::fib(\n      v1,\n      v6,\n      v8\n    )\n"));
+
+    // The nested arguments & the strings are not split
+    let formatting = FormattingOptions {
+        max_line_length: Some(30),
+        ..FormattingOptions::default()
+    };
+    assert_eq!(
+        formatting.format_line(r#"v1 = call(v2, Array<felt252, u8>(v3, v4), "a, b")"#, 0),
+        "v1 = call(\n\tv2,\n\tArray<felt252, u8>(v3, v4),\n\t\"a, b\"\n)\n"
+    );
+
+    // The missing fields of a config file keep their default values
+    let formatting: FormattingOptions =
+        serde_json::from_str(r#"{"indent_style": "spaces", "function_comments": false}"#).unwrap();
+    assert_eq!(formatting.indent_style, IndentStyle::Spaces);
+    assert_eq!(formatting.indent_width, 4);
+    assert_eq!(formatting.max_line_length, None);
+    assert!(!formatting.function_comments);
+}
+
+#[test]
+fn test_decompiler_inline_wrappers() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // The wrappers are merged with the implementation they call
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        inline_wrappers: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    // The implementations are only printed in their wrappers
+    assert!(!decompiler_output.contains("\nfunc test::hello_starknet::HelloStarknetImpl::"));
+    assert!(decompiler_output.contains(
+        "// Inlined implementation test::hello_starknet::HelloStarknetImpl::increase_balance\nfunc test::hello_starknet::__wrapper__HelloStarknetImpl__increase_balance ("
+    ));
+    assert!(decompiler_output.contains(
+        "// Inlined implementation test::hello_starknet::HelloStarknetImpl::get_balance\nfunc test::hello_starknet::__wrapper__HelloStarknetImpl__get_balance ("
+    ));
+
+    // The body of the implementation is printed in place of its call
+    assert!(decompiler_output.contains(
+        "\t\t\t\t\t// Inlined func test::hello_starknet::HelloStarknetImpl::increase_balance (v0: RangeCheck, v1: GasBuiltin, v2: System, v3: test::hello_starknet::ContractState, v4: u32)"
+    ));
+    assert!(decompiler_output.contains(
+        "\t\t\t\t\tv54, v55, v56, v57 = user@test::hello_starknet::HelloStarknetImpl::increase_balance(v58, v59, v60, v61, v62) {\n\t\t\t\t\t\tv5 = struct_deconstruct<test::hello_starknet::ContractState>(v3)\n"
+    ));
+}
+
+#[test]
+fn test_decompiler_summarize_calldata() {
+    // Contract class with its ABI
+    let content =
+        include_str!("../../examples/starknet/hello_starknet__hello_starknet.contract_class.json");
+    let contract_class: ContractClass = serde_json::from_str(content).unwrap();
+    let program_string = contract_class.extract_sierra_program().unwrap().to_string();
+    let mut program = SierraProgram::new(program_string);
+    program.set_abi(contract_class.abi.unwrap());
+
+    // The calldata deserialization of the wrappers is summarized using the ABI inputs types
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        summarize_calldata: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(decompiler_output.contains("\n\tlet args = deserialize::<(u32)>(v3) else panic(\"Input too long for arguments\" | \"Out of gas\" | \"Failed to deserialize param #1\")\n"));
+
+    // The implementation is called with the deserialized arguments & the builtins of the wrapper
+    assert!(decompiler_output
+        .contains("HelloStarknetImpl::increase_balance(v0, v1, v2, v38, args.0)\n"));
+
+    // The get_balance implementation is inlined in its wrapper, the deserialization is kept
+    assert!(!decompiler_output.contains("deserialize::<()>"));
+    assert!(decompiler_output.contains("panic(\"Input too long for arguments\")"));
+}
+
+#[test]
+fn test_decompiler_abi_types() {
+    // Contract class with its ABI
+    let content =
+        include_str!("../../examples/starknet/hello_starknet__hello_starknet.contract_class.json");
+    let contract_class: ContractClass = serde_json::from_str(content).unwrap();
+    let program_string = contract_class.extract_sierra_program().unwrap().to_string();
+    let mut program = SierraProgram::new(program_string);
+    program.set_abi(contract_class.abi.unwrap());
+
+    let prototype = |output: &str, function_name: &str| {
+        output
+            .lines()
+            .find(|line| {
+                line.starts_with("func ") && line.contains(&format!("{} (", function_name))
+            })
+            .unwrap()
+            .to_string()
+    };
+
+    // The prototypes contain the Sierra types by default, get_balance is inlined in its wrapper
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(
+        prototype(&decompiler_output, "HelloStarknetImpl::increase_balance")
+            .contains("PanicResult")
+    );
+
+    // The returned PanicResult is replaced by the ABI outputs
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        abi_types: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    let increase_balance = prototype(&decompiler_output, "HelloStarknetImpl::increase_balance");
+    assert!(!increase_balance.contains("PanicResult"));
+    assert!(increase_balance.contains(": u32) -> ("));
+    assert!(increase_balance.ends_with(", ()) {"));
+
+    // The wrappers keep their Sierra prototype
+    assert!(prototype(
+        &decompiler_output,
+        "__wrapper__HelloStarknetImpl__get_balance"
+    )
+    .contains("PanicResult"));
+}
+
+#[test]
+fn test_decompiler_panic_annotations() {
+    // fib doesn't return a PanicResult
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        panic_annotations: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+    assert!(decompiler_output.contains("-> (felt252) nopanic {"));
+    assert!(!decompiler_output.contains("// Can panic"));
+
+    let panic_safety = decompiler.functions[0].panic_safety();
+    assert!(panic_safety.nopanic);
+    assert!(!panic_safety.can_panic);
+
+    // The entry points of the contract return a PanicResult
+    let content =
+        include_str!("../../examples/starknet/hello_starknet__hello_starknet.contract_class.json");
+    let contract_class: ContractClass = serde_json::from_str(content).unwrap();
+    let program_string = contract_class.extract_sierra_program().unwrap().to_string();
+    let program = SierraProgram::new(program_string);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        panic_annotations: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    let lines: Vec<&str> = decompiler_output.lines().collect();
+    let prototype_index = lines
+        .iter()
+        .position(|line| {
+            line.starts_with("func ")
+                && line.contains("__wrapper__HelloStarknetImpl__get_balance (")
+        })
+        .unwrap();
+    assert!(lines[prototype_index - 1].starts_with("// Can panic ("));
+    assert!(lines[prototype_index - 1].contains("returns PanicResult"));
+    assert!(!lines[prototype_index].contains("nopanic"));
+}
+
+#[test]
+fn test_decompiler_round_trip_validation() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // The verbose output contains all the statements of the program, with the same branches
+    let report = validate_program(&program).unwrap();
+    assert_eq!(report.functions, 1);
+    assert!(report.statements > 0);
+    assert!(report.is_valid(), "{:?}", report.mismatches);
+}
+
 #[test]
 fn test_decompiler_verbose_output() {
     // Read file content
@@ -241,12 +501,7 @@ fn test_decompiler_array_output() {
     let expected_output = r#"// Function 1
 func examples::fib::fib (v0: RangeCheck, v1: GasBuiltin, v2: felt252, v3: felt252, v4: felt252) -> (RangeCheck, GasBuiltin, core::panics::PanicResult::<(core::felt252)>) {
 	if (withdraw_gas(v0, v1) == 0) {		
-		v20 = Array<felt252>::new()
-		v21 = 375233589013918064796019 // "Out of gas"
-		v22 = v20.append(v21)
-		v23 = struct_construct<core::panics::Panic>()
-		v24 = struct_construct<Tuple<core::panics::Panic, Array<felt252>>>(v23, v22)
-		v25 = enum_init<core::panics::PanicResult::<(core::felt252)>, 1>(v24)
+		v25 = panic("Out of gas")
 		return (v7, v8, v25)
 	} else {	
 		v9 = v4
@@ -266,3 +521,510 @@ func examples::fib::fib (v0: RangeCheck, v1: GasBuiltin, v2: felt252, v3: felt25
 }"#;
     assert_eq!(decompiler_output, expected_output);
 }
+
+#[test]
+fn test_malformed_programs_return_errors() {
+    // Invalid Sierra code
+    let result = SierraProgram::try_new("type felt252 = felt252;\nnot sierra".to_string());
+    assert!(matches!(result, Err(SierraAnalyzerError::Parse(_))));
+
+    // Valid Sierra code
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+    let program = SierraProgram::try_new(content).unwrap();
+    let mut decompiler = program.decompiler(false);
+    assert!(decompiler.try_decompile(false).is_ok());
+
+    // Remote class without debug names whose return type is not declared
+    let content =
+        "type [0] = felt252 [storable: true, drop: true, dup: true, zero_sized: false];\n\n\
+                   return([0]);\n\n\
+                   [0]@0([0]: [0]) -> ([1]);"
+            .to_string();
+    let program = SierraProgram::try_new_legacy(content).unwrap();
+    let mut decompiler = program.decompiler(false);
+    assert!(matches!(
+        decompiler.try_decompile(false),
+        Err(SierraAnalyzerError::UnknownType(1))
+    ));
+
+    // Contract class without ABI
+    let response = RpcResponse {
+        result: serde_json::json!({ "sierra_program": [] }),
+        error: None,
+    };
+    assert!(matches!(
+        response.to_json(),
+        Err(SierraAnalyzerError::MissingField("ABI"))
+    ));
+}
+
+#[test]
+fn test_decompiler_large_program_offsets() {
+    // Synthetic program with more than 65535 statements
+    let duplications = 35_000;
+    let mut content = String::from(
+        "type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];\n\n\
+         libfunc dup<felt252> = dup<felt252>;\n\
+         libfunc drop<felt252> = drop<felt252>;\n\n",
+    );
+    for _ in 0..duplications {
+        content.push_str("dup<felt252>([0]) -> ([0], [1]);\ndrop<felt252>([1]) -> ();\n");
+    }
+    content.push_str("return([0]);\nreturn([0]);\n\n");
+    let last_offset = 2 * duplications + 1;
+    content.push_str("test::large@0([0]: felt252) -> (felt252);\n");
+    content.push_str(&format!(
+        "test::last@{}([0]: felt252) -> (felt252);\n",
+        last_offset
+    ));
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.analyze().unwrap();
+
+    // The offsets after 65535 are not truncated
+    let large = &decompiler.functions[0];
+    assert_eq!(large.statements().len(), 2 * duplications as usize + 1);
+    assert_eq!(large.end_offset, Some(last_offset - 1));
+
+    let last = &decompiler.functions[1];
+    assert_eq!(last.start_offset, Some(last_offset));
+    assert_eq!(last.end_offset, Some(last_offset));
+    assert_eq!(last.statements().len(), 1);
+    assert_eq!(last.statements()[0].offset, last_offset);
+}
+
+#[test]
+fn test_casm_class_diagnostic() {
+    // Compiled contract class, without the Sierra program
+    let content = r#"{
+        "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+        "compiler_version": "2.9.2",
+        "bytecode": ["0xa0680017fff8000", "0x7"],
+        "hints": [],
+        "entry_points_by_type": {"EXTERNAL": [], "L1_HANDLER": [], "CONSTRUCTOR": []}
+    }"#;
+    assert!(is_casm_class(content));
+    assert!(matches!(
+        SierraProgram::try_new(content.to_string()),
+        Err(SierraAnalyzerError::CasmClass)
+    ));
+
+    // Contract classes & Sierra programs are not CASM classes
+    let contract_class = include_str!("../../examples/starknet/with_ownable.contract_class.json");
+    assert!(!is_casm_class(contract_class));
+    assert!(!is_casm_class(include_str!(
+        "../../examples/sierra/fib.sierra"
+    )));
+}
+
+#[test]
+fn test_legacy_program_without_registry() {
+    // Program using a libfunc that is not supported anymore
+    let content = r#"type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc felt_add = felt_add;
+
+felt_add([0], [1]) -> ([2]);
+return([2]);
+
+test::legacy@0([0]: felt252, [1]: felt252) -> (felt252);"#;
+
+    // The program registry can't be created
+    assert!(matches!(
+        SierraProgram::try_new(content.to_string()),
+        Err(SierraAnalyzerError::Registry(_))
+    ));
+
+    // The program can still be decompiled without its registry
+    let program = SierraProgram::try_new_legacy(content.to_string()).unwrap();
+    assert!(program.registry().is_none());
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.try_decompile(false).unwrap();
+    assert!(decompiler_output.contains("felt_add(v0, v1)"));
+}
+
+#[test]
+fn test_detect_class_version() {
+    let content = include_str!("../../examples/starknet/erc20.contract_class.json");
+    let class_version = detect_class_version(content).unwrap();
+    assert_eq!(class_version.sierra_version, SierraVersion::new(1, 3, 0));
+    assert_eq!(class_version.compiler_version, SierraVersion::new(2, 3, 1));
+    assert_eq!(
+        class_version.contract_class_version.as_deref(),
+        Some("0.1.0")
+    );
+    assert_eq!(class_version.sierra_version.to_string(), "1.3.0");
+    assert!(class_version.sierra_version.is_legacy());
+
+    // Sierra programs don't have a version
+    assert!(detect_class_version(include_str!("../../examples/sierra/fib.sierra")).is_none());
+}
+
+#[test]
+fn test_format_constant() {
+    let prime = felt252_prime();
+
+    // Values close to the prime are negative numbers
+    assert_eq!(format_constant(&(&prime - 1)).as_deref(), Some("-1"));
+    assert_eq!(format_constant(&(&prime - 1000)).as_deref(), Some("-1000"));
+
+    // Powers of two
+    assert_eq!(
+        format_constant(&(BigInt::from(1) << 128)).as_deref(),
+        Some("2**128")
+    );
+
+    // Strings
+    assert_eq!(
+        format_constant(&BigInt::from(375233589013918064796019u128)).as_deref(),
+        Some(r#""Out of gas""#)
+    );
+
+    // Addresses & class hashes are padded
+    let class_hash = BigInt::parse_bytes(
+        b"2ef74f5cffde4e8543a1b7e0b7b0b6c9c4d1e81c1a7bd2e6ee8a4da0f9dd8e1",
+        16,
+    )
+    .unwrap();
+    assert_eq!(
+        format_constant(&class_hash).as_deref(),
+        Some("0x02ef74f5cffde4e8543a1b7e0b7b0b6c9c4d1e81c1a7bd2e6ee8a4da0f9dd8e1")
+    );
+
+    // Small integers don't need a comment
+    assert_eq!(format_constant(&BigInt::from(123456789)), None);
+}
+
+#[test]
+fn test_decompiler_signed_integers() {
+    let content = r#"type i8 = i8 [storable: true, drop: true, dup: true, zero_sized: false];
+type NonZero<i8> = NonZero<i8> [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc i8_const<-5> = i8_const<-5>;
+libfunc dup<i8> = dup<i8>;
+libfunc i8_is_zero = i8_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<i8>> = drop<NonZero<i8>>;
+libfunc store_temp<i8> = store_temp<i8>;
+
+i8_const<-5>() -> ([0]);
+dup<i8>([0]) -> ([0], [1]);
+i8_is_zero([1]) { fallthrough() 6([2]) };
+branch_align() -> ();
+store_temp<i8>([0]) -> ([0]);
+return([0]);
+branch_align() -> ();
+drop<NonZero<i8>>([2]) -> ();
+store_temp<i8>([0]) -> ([0]);
+return([0]);
+
+test::signed_integers@0() -> (i8);"#;
+
+    let program = SierraProgram::new(content.to_string());
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    // Signed integers constants & zero checks
+    assert!(decompiler_output.contains("v0 = -5"));
+    assert!(decompiler_output.contains("if (v1 == 0)"));
+
+    // Signed integers arithmetic & comparisons
+    assert!(ADDITION_REGEX
+        .iter()
+        .any(|regex| regex.is_match("i8_overflowing_add_impl")));
+    assert!(SUBSTRACTION_REGEX
+        .iter()
+        .any(|regex| regex.is_match("i128_overflowing_sub_impl")));
+    assert!(MULTIPLICATION_REGEX
+        .iter()
+        .any(|regex| regex.is_match("i64_wide_mul")));
+    assert!(LESS_THAN_REGEX.is_match("i32_diff"));
+    assert!(EQUAL_REGEX.is_match("i16_eq"));
+}
+
+#[test]
+fn test_decompiler_functions_order() {
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+    let program = SierraProgram::new(content.clone());
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    // The functions are sorted by entry offset
+    let offsets: Vec<Option<u32>> = decompiler
+        .functions
+        .iter()
+        .map(|function| function.start_offset)
+        .collect();
+    assert_eq!(offsets, vec![Some(0), Some(69)]);
+
+    // The output doesn't depend on the functions declarations order
+    let fib = content
+        .lines()
+        .find(|line| line.starts_with("examples::fib_array::fib@"))
+        .unwrap();
+    let fib_inner = content
+        .lines()
+        .find(|line| line.starts_with("examples::fib_array::fib_inner@"))
+        .unwrap();
+    let swapped_content = content
+        .replace(fib, "<fib>")
+        .replace(fib_inner, fib)
+        .replace("<fib>", fib_inner);
+    let swapped_program = SierraProgram::new(swapped_content);
+    let mut swapped_decompiler = swapped_program.decompiler(false);
+    assert_eq!(swapped_decompiler.decompile(false), decompiler_output);
+
+    // Decompiling again gives the same output
+    let mut decompiler = program.decompiler(false);
+    assert_eq!(decompiler.decompile(false), decompiler_output);
+}
+
+#[test]
+fn test_decompiler_libfuncs_kinds() {
+    // The corelib arithmetic functions are operations, not user function calls
+    assert_eq!(
+        LibfuncKind::classify("u8_overflowing_add"),
+        LibfuncKind::Addition
+    );
+    assert_eq!(
+        LibfuncKind::classify("function_call<user@core::Felt252Add::add>"),
+        LibfuncKind::Addition
+    );
+    assert_eq!(
+        LibfuncKind::classify("function_call<user@examples::fib::fib>"),
+        LibfuncKind::FunctionCall
+    );
+    assert_eq!(
+        LibfuncKind::classify("storage_write_syscall"),
+        LibfuncKind::StorageWrite
+    );
+    assert_eq!(LibfuncKind::classify("dup<felt252>"), LibfuncKind::Dup);
+    assert_eq!(
+        LibfuncKind::classify("struct_construct<Unit>"),
+        LibfuncKind::Other
+    );
+
+    // The statements carry the kind of their libfunc
+    let content = include_str!("../../examples/sierra/fib_gas.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let statements = decompiler
+        .functions
+        .iter()
+        .flat_map(|function| function.statements().iter());
+    assert!(statements
+        .clone()
+        .any(|statement| statement.libfunc_kind == LibfuncKind::GasCheck));
+    assert!(statements
+        .clone()
+        .any(|statement| statement.libfunc_kind == LibfuncKind::FunctionCall));
+}
+
+#[test]
+fn test_decompiler_u256_operations() {
+    let content = include_str!("../../examples/sierra/with_erc20.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    // function_call<user@core::integer::U256Add::add>([42], [43], [44]) -> ([40], [41])
+    assert!(decompiler_output.contains("v41 = v43 + v44 (u256)"));
+
+    // The verbose output keeps the corelib calls
+    let mut decompiler = program.decompiler(true);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(decompiler_output.contains("core::integer::U256Add::add(v42, v43, v44)"));
+}
+
+#[test]
+fn test_decompiler_bounded_int() {
+    let content = include_str!("../../examples/sierra/bounded_int.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(decompiler_output.contains("v3 = v1 + v2"));
+    assert!(decompiler_output.contains("if (v3 < 10)"));
+}
+
+#[test]
+fn test_decompiler_dict_operations() {
+    let content = include_str!("../../examples/sierra/dict.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(decompiler_output.contains("v6 = Felt252Dict<felt252>::new()"));
+    assert!(decompiler_output.contains("v7, v8 = v6[v3]"));
+    assert!(decompiler_output.contains("v9 = v7.write(v4)"));
+    assert!(decompiler_output.contains("v13 = v9.squash()"));
+}
+
+#[test]
+fn test_decompiler_snapshots_and_boxes() {
+    let content = include_str!("../../examples/sierra/erc20__erc_20.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    // The snapshots are rendered using the Cairo syntax
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(!decompiler_output.contains("snapshot_take"));
+    assert!(decompiler_output.contains("v34, v35 = v33, @v33"));
+
+    // The verbose output keeps the libfuncs
+    let mut decompiler = program.decompiler(true);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(decompiler_output.contains("snapshot_take<Array<felt252>>(v33)"));
+
+    // The boxes are casts
+    let content = include_str!("../../examples/sierra/fib_box.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(decompiler_output.contains("v10 = v9 as Box<felt252>"));
+}
+
+#[test]
+fn test_decompiler_options() {
+    let content = include_str!("../../examples/sierra/erc20__erc_20.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+
+    // enum_init<core::option::Option::<core::felt252>, 0>([14]) -> ([15])
+    assert!(decompiler_output.contains("v15 = Some(v14)"));
+    // enum_init<core::option::Option::<core::felt252>, 1>([18]) -> ([19])
+    assert!(decompiler_output.contains("v19 = None"));
+    // enum_match<core::option::Option::<core::felt252>>([17]) { fallthrough([20]) 625([21]) }
+    assert!(decompiler_output.contains("if let Some(v20) = v17 {"));
+}
+
+#[test]
+fn test_decompiler_panic_summaries() {
+    let content = include_str!("../../examples/sierra/fib_gas.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    // The panics are summarized in the regular output
+    let mut decompiler = program.decompiler(false);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(decompiler_output.contains(r#"v25 = panic("Out of gas")"#));
+    assert!(!decompiler_output.contains("struct_construct<core::panics::Panic>"));
+
+    // The panic data construction is kept in the verbose output
+    let mut decompiler = program.decompiler(true);
+    let decompiler_output = decompiler.decompile(false);
+    assert!(!decompiler_output.contains("panic("));
+    assert!(decompiler_output.contains("struct_construct<core::panics::Panic>"));
+}
+
+#[test]
+fn test_decompiler_summarize_gas_checks() {
+    let content = include_str!("../../examples/sierra/fib_gas.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    // The gas check is replaced by a comment & the main path is not nested
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        summarize_gas_checks: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(
+        decompiler_output.contains("\n\t// out-of-gas guard: withdraw_gas(v0, v1)\n\tv9 = v4\n")
+    );
+    assert!(decompiler_output.contains("\n\tif (v9 == 0) {"));
+
+    // The out-of-gas branch & its panic are not printed
+    assert!(!decompiler_output.contains("Out of gas"));
+    assert!(!decompiler_output.contains("return (v7, v8, v25)"));
+    assert_eq!(decompiler_output.matches("return (").count(), 2);
+
+    // The out-of-gas branch is kept in the verbose output
+    let mut decompiler = program.decompiler(true);
+    decompiler.set_formatting(FormattingOptions {
+        summarize_gas_checks: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(!decompiler_output.contains("out-of-gas guard"));
+    assert!(decompiler_output.contains("withdraw_gas(v0, v1)"));
+}
+
+#[test]
+fn test_decompiler_early_returns() {
+    let content = include_str!("../../examples/sierra/fib_gas.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    // The if blocks ending with a return are guard clauses, the else blocks are not nested
+    let mut decompiler = program.decompiler(false);
+    decompiler.set_formatting(FormattingOptions {
+        early_returns: true,
+        ..FormattingOptions::default()
+    });
+    let decompiler_output = decompiler.decompile(false);
+
+    assert!(decompiler_output.contains("\t\treturn (v7, v8, v25)\n\t}\n\tv9 = v4\n"));
+    assert!(decompiler_output.contains("\t\treturn (v17, v18, v19)\n\t}\n\tv11 = "));
+    assert!(decompiler_output.ends_with("\treturn (v5, v6, v12)\n}"));
+    assert!(!decompiler_output.contains("else"));
+}
+
+#[test]
+fn test_program_from_file() {
+    // The Sierra program, the ABI & the Sierra version of a contract class are loaded
+    let program = SierraProgram::from_file(Path::new(
+        "../examples/starknet/minimal_contract__minimal_contract.contract_class.json",
+    ))
+    .unwrap();
+    assert_eq!(program.kind(), ProgramKind::ContractClass);
+    assert!(program.sierra_version.is_some());
+
+    // A Sierra program has no ABI
+    let program = SierraProgram::from_file(Path::new("../examples/sierra/fib.sierra")).unwrap();
+    assert_eq!(program.kind(), ProgramKind::RawSierra);
+    assert!(program.sierra_version.is_none());
+
+    // The functions are named by their prototype
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    assert_eq!(
+        decompiler.functions[0].prototype_name(),
+        Some("examples::fib::fib")
+    );
+}
+
+#[test]
+fn test_scarb_contract_classes() {
+    let directory = std::env::temp_dir().join("sierra_analyzer_scarb_target");
+    let dev_directory = directory.join("dev");
+    fs::create_dir_all(&dev_directory).unwrap();
+    for file_name in [
+        "package_Counter.contract_class.json",
+        "package_Counter.compiled_contract_class.json",
+        "package.starknet_artifacts.json",
+        "package_Account.contract_class.json",
+    ] {
+        fs::write(dev_directory.join(file_name), "{}").unwrap();
+    }
+
+    // The compiled (CASM) contract classes & the artifacts files are skipped
+    let contract_classes = scarb::contract_classes(&directory).unwrap();
+    assert_eq!(
+        contract_classes,
+        vec![
+            dev_directory.join("package_Account.contract_class.json"),
+            dev_directory.join("package_Counter.contract_class.json"),
+        ]
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
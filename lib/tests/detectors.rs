@@ -1,9 +1,29 @@
-use sierra_analyzer_lib::detectors::detector::Detector;
+use std::thread;
+use std::time::Duration;
+
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use sierra_analyzer_lib::analysis::context::AnalysisContext;
+use sierra_analyzer_lib::decompiler::function::FunctionType;
+use sierra_analyzer_lib::detectors::constructor_detector::ConstructorDetector;
+use sierra_analyzer_lib::detectors::controlled_library_call_detector::ControlledLibraryCallDetector;
+use sierra_analyzer_lib::detectors::deploy_detector::DeployDetector;
+use sierra_analyzer_lib::detectors::detector::{
+    findings_to_json, Detector, DetectorType, FindingReport,
+};
+use sierra_analyzer_lib::detectors::effects_timeline_detector::EffectsTimelineDetector;
+use sierra_analyzer_lib::detectors::felt_comparison_detector::FeltComparisonDetector;
 use sierra_analyzer_lib::detectors::felt_overflow_detector::FeltOverflowDetector;
 use sierra_analyzer_lib::detectors::functions_detector::FunctionsDetector;
+use sierra_analyzer_lib::detectors::get_detectors;
+use sierra_analyzer_lib::detectors::missing_events_detector::MissingEventsDetector;
+use sierra_analyzer_lib::detectors::recursion_detector::RecursionDetector;
+use sierra_analyzer_lib::detectors::reinitialization_detector::ReinitializationDetector;
+use sierra_analyzer_lib::detectors::runner::{run_detectors, DetectorOutcome, RunOptions};
 use sierra_analyzer_lib::detectors::statistics_detector::StatisticsDetector;
 use sierra_analyzer_lib::detectors::strings_detector::StringsDetector;
-use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::detectors::truncation_detector::TruncationDetector;
+use sierra_analyzer_lib::detectors::tx_origin_detector::TxOriginDetector;
+use sierra_analyzer_lib::sierra_program::{ProgramKind, SierraProgram};
 
 #[test]
 fn test_string_detector() {
@@ -25,7 +45,7 @@ fn test_string_detector() {
     let mut detector = StringsDetector::new();
 
     // Detected strings
-    let detected_strings = detector.detect(&mut decompiler);
+    let detected_strings = detector.detect(&decompiler.analysis_context());
 
     let expected_output = r#"Index out of bounds
 u32_sub Overflow"#;
@@ -33,6 +53,55 @@ u32_sub Overflow"#;
     assert_eq!(detected_strings, expected_output);
 }
 
+#[test]
+fn test_string_detector_byte_array() {
+    // ByteArray literal longer than 31 bytes: one data word & a pending word of 28 bytes
+    let content = r#"type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type u32 = u32 [storable: true, drop: true, dup: true, zero_sized: false];
+type bytes31 = bytes31 [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<bytes31> = Array<bytes31> [storable: true, drop: true, dup: false, zero_sized: false];
+type core::byte_array::ByteArray = Struct<ut@core::byte_array::ByteArray, Array<bytes31>, felt252, u32> [storable: true, drop: true, dup: false, zero_sized: false];
+type Const<bytes31, WORD> = Const<bytes31, WORD> [storable: false, drop: false, dup: false, zero_sized: false];
+type Const<felt252, PENDING> = Const<felt252, PENDING> [storable: false, drop: false, dup: false, zero_sized: false];
+type Const<u32, 28> = Const<u32, 28> [storable: false, drop: false, dup: false, zero_sized: false];
+
+libfunc array_new<bytes31> = array_new<bytes31>;
+libfunc const_as_immediate<Const<bytes31, WORD>> = const_as_immediate<Const<bytes31, WORD>>;
+libfunc store_temp<bytes31> = store_temp<bytes31>;
+libfunc array_append<bytes31> = array_append<bytes31>;
+libfunc const_as_immediate<Const<felt252, PENDING>> = const_as_immediate<Const<felt252, PENDING>>;
+libfunc const_as_immediate<Const<u32, 28>> = const_as_immediate<Const<u32, 28>>;
+libfunc struct_construct<core::byte_array::ByteArray> = struct_construct<core::byte_array::ByteArray>;
+libfunc store_temp<core::byte_array::ByteArray> = store_temp<core::byte_array::ByteArray>;
+
+array_new<bytes31>() -> ([0]);
+const_as_immediate<Const<bytes31, WORD>>() -> ([1]);
+store_temp<bytes31>([1]) -> ([1]);
+array_append<bytes31>([0], [1]) -> ([2]);
+const_as_immediate<Const<felt252, PENDING>>() -> ([3]);
+const_as_immediate<Const<u32, 28>>() -> ([4]);
+struct_construct<core::byte_array::ByteArray>([2], [3], [4]) -> ([5]);
+store_temp<core::byte_array::ByteArray>([5]) -> ([5]);
+return([5]);
+
+test::byte_array_literal@0() -> (core::byte_array::ByteArray);"#
+        .replace("WORD", "149135661433117010263176858446383873659754571480323961747206482530034541938")
+        .replace("PENDING", "10259905916029454935217676813835057902556359393677658803976491263347");
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    let decompiled_code = decompiler.decompile(false);
+
+    // The words are reassembled into a single string
+    let expected_string = "The reassembled ByteArray literals are longer than 31 bytes";
+    assert!(decompiled_code.contains(&format!(r#"// "{}""#, expected_string)));
+
+    // The partial words are not reported
+    let mut detector = StringsDetector::new();
+    let detected_strings = detector.detect(&decompiler.analysis_context());
+    assert_eq!(detected_strings, expected_string);
+}
+
 #[test]
 fn test_functions_detector() {
     // Read file content
@@ -53,7 +122,7 @@ fn test_functions_detector() {
     let mut detector = FunctionsDetector::new();
 
     // functions names
-    let functions_names = detector.detect(&mut decompiler);
+    let functions_names = detector.detect(&decompiler.analysis_context());
 
     let expected_output =
         "Private : examples::fib_array::fib\n\u{1b}[0mPrivate : examples::fib_array::fib_inner\u{1b}[0m";
@@ -81,7 +150,7 @@ fn test_statistics_detector() {
     let mut detector = StatisticsDetector::new();
 
     // Program statistics
-    let statistics = detector.detect(&mut decompiler);
+    let statistics = detector.detect(&decompiler.analysis_context());
 
     let expected_output = r#"Libfuncs: 42
 Types: 19
@@ -111,7 +180,7 @@ fn test_integer_overflow_detector() {
     let mut detector = FeltOverflowDetector::new();
 
     // Detected felt overflows
-    let statistics = detector.detect(&mut decompiler);
+    let statistics = detector.detect(&decompiler.analysis_context());
 
     let expected_output = r#"cairo_level_tests::contracts::test_contract::test_contract::__wrapper__Impl__test: method felt252_add could be used to trigger a felt overflow/underflow (Confidence: Low)
 cairo_level_tests::contracts::test_contract::test_contract::__wrapper__Impl__test: method felt252_add could be used to trigger a felt overflow/underflow (Confidence: Low)
@@ -119,3 +188,1015 @@ cairo_level_tests::contracts::test_contract::test_contract::__wrapper__Impl__tes
 
     assert_eq!(statistics, expected_output);
 }
+
+#[test]
+fn test_integer_overflow_detector_intervals() {
+    // The sum of two u8 can't overflow, adding the felt252 parameter can
+    let content = r#"type u8 = u8 [storable: true, drop: true, dup: true, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc u8_to_felt252 = u8_to_felt252;
+libfunc felt252_add = felt252_add;
+libfunc store_temp<felt252> = store_temp<felt252>;
+
+u8_to_felt252([0]) -> ([3]);
+u8_to_felt252([1]) -> ([4]);
+felt252_add([3], [4]) -> ([5]);
+felt252_add([5], [2]) -> ([6]);
+store_temp<felt252>([6]) -> ([6]);
+return([6]);
+
+test::add@0([0]: u8, [1]: u8, [2]: felt252) -> (felt252);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // Only the addition of the felt252 parameter is reported
+    let mut detector = FeltOverflowDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].offset, Some(3));
+}
+
+#[test]
+fn test_integer_overflow_detector_safe_patterns() {
+    // The first sum is only hashed, the second one is returned
+    let content =
+        r#"type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type Pedersen = Pedersen [storable: true, drop: false, dup: false, zero_sized: false];
+
+libfunc dup<felt252> = dup<felt252>;
+libfunc felt252_add = felt252_add;
+libfunc pedersen = pedersen;
+libfunc store_temp<Pedersen> = store_temp<Pedersen>;
+libfunc store_temp<felt252> = store_temp<felt252>;
+
+dup<felt252>([1]) -> ([1], [3]);
+dup<felt252>([2]) -> ([2], [4]);
+felt252_add([1], [2]) -> ([5]);
+pedersen([0], [5], [3]) -> ([6], [7]);
+felt252_add([7], [4]) -> ([8]);
+store_temp<Pedersen>([6]) -> ([6]);
+store_temp<felt252>([8]) -> ([8]);
+return([6], [8]);
+
+test::hash@0([0]: Pedersen, [1]: felt252, [2]: felt252) -> (Pedersen, felt252);"#
+            .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let mut detector = FeltOverflowDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].offset, Some(4));
+
+    // The parameter is range checked before the addition
+    let content = r#"type RangeCheck = RangeCheck [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type u8 = u8 [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc dup<felt252> = dup<felt252>;
+libfunc u8_try_from_felt252 = u8_try_from_felt252;
+libfunc branch_align = branch_align;
+libfunc drop<u8> = drop<u8>;
+libfunc drop<felt252> = drop<felt252>;
+libfunc felt252_add = felt252_add;
+libfunc store_temp<RangeCheck> = store_temp<RangeCheck>;
+libfunc store_temp<felt252> = store_temp<felt252>;
+
+dup<felt252>([1]) -> ([1], [3]);
+u8_try_from_felt252([0], [3]) { fallthrough([4], [5]) 8([6]) };
+branch_align() -> ();
+drop<u8>([5]) -> ();
+felt252_add([1], [2]) -> ([7]);
+store_temp<RangeCheck>([4]) -> ([4]);
+store_temp<felt252>([7]) -> ([7]);
+return([4], [7]);
+branch_align() -> ();
+drop<felt252>([2]) -> ();
+store_temp<RangeCheck>([6]) -> ([6]);
+store_temp<felt252>([1]) -> ([1]);
+return([6], [1]);
+
+test::guarded@0([0]: RangeCheck, [1]: felt252, [2]: felt252) -> (RangeCheck, felt252);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The addition of the parameters is downgraded
+    let findings = detector.findings(&decompiler.analysis_context());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].offset, Some(4));
+    assert!(findings[0].message.ends_with("(Confidence: Low)"));
+}
+
+#[test]
+fn test_felt_comparison_detector() {
+    // The first substraction is an equality check, the second one is ordered as a u256
+    let content = r#"type RangeCheck = RangeCheck [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type NonZero<felt252> = NonZero<felt252> [storable: true, drop: true, dup: true, zero_sized: false];
+type u128 = u128 [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc dup<felt252> = dup<felt252>;
+libfunc felt252_sub = felt252_sub;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc jump = jump;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+libfunc store_temp<felt252> = store_temp<felt252>;
+libfunc u128s_from_felt252 = u128s_from_felt252;
+libfunc drop<u128> = drop<u128>;
+
+dup<felt252>([1]) -> ([1], [3]);
+dup<felt252>([2]) -> ([2], [4]);
+felt252_sub([3], [4]) -> ([5]);
+felt252_is_zero([5]) { fallthrough() 6([6]) };
+branch_align() -> ();
+jump() { 8() };
+branch_align() -> ();
+drop<NonZero<felt252>>([6]) -> ();
+felt252_sub([1], [2]) -> ([7]);
+store_temp<felt252>([7]) -> ([7]);
+u128s_from_felt252([0], [7]) { fallthrough([8], [9]) 13([10], [11], [12]) };
+branch_align() -> ();
+return([8], [9]);
+branch_align() -> ();
+drop<u128>([11]) -> ();
+return([10], [12]);
+
+test::compare@0([0]: RangeCheck, [1]: felt252, [2]: felt252) -> (RangeCheck, u128);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let mut detector = FeltComparisonDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].offset, Some(10));
+    assert_eq!(
+        findings[0].message,
+        "felt252_sub result (statement 8) is compared by u128s_from_felt252, the felt252 arithmetic wraps around instead of failing"
+    );
+}
+
+#[test]
+fn test_effects_timeline_detector() {
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/new_syntax_test_contract__counter_contract.sierra")
+            .to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The entry points are expanded through the functions they call
+    let mut detector = EffectsTimelineDetector::new();
+    let timelines = detector.detect(&decompiler.analysis_context());
+    let module = "cairo_level_tests::contracts::new_syntax_test_contract::counter_contract";
+    let decrease_counter = format!("{}::CounterContract::decrease_counter", module);
+    let expected_timeline = format!(
+        r#"decrease_counter ({module}::__wrapper__CounterContract__decrease_counter):
+    storage read in {function} (statement 657)
+    external call in {function} (statement 671)
+    storage read in {function} (statement 715)
+    storage write in {function} (statement 732) after the external call of statement 671
+"#,
+        module = module,
+        function = decrease_counter
+    );
+    assert!(timelines.contains(&expected_timeline));
+    assert!(timelines.contains(&format!(
+        "increase_counter ({}::__wrapper__CounterContract__increase_counter):\n",
+        module
+    )));
+
+    // Only the storage write following the external call is reported
+    let findings = detector.findings(&decompiler.analysis_context());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].detector_type, DetectorType::INFORMATIONAL);
+    assert_eq!(findings[0].function, decrease_counter);
+    assert_eq!(findings[0].offset, Some(732));
+    assert_eq!(
+        findings[0].message,
+        "storage write after the external call of statement 671 (entry point decrease_counter)"
+    );
+}
+
+#[test]
+fn test_missing_events_detector() {
+    // The entry points of the ownable balance contract don't emit any event
+    let content =
+        include_str!("../../examples/sierra/with_ownable__ownable_balance.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The getters & the constructor are not reported
+    let mut detector = MissingEventsDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    let messages: Vec<&str> = findings
+        .iter()
+        .map(|finding| finding.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "entry point set_balance writes the storage without emitting any event",
+            "entry point transfer_ownership writes the storage without emitting any event",
+        ]
+    );
+    assert_eq!(
+        findings[0].function,
+        "cairo_level_tests::contracts::with_ownable::ownable_balance::__wrapper__OwnableBalanceImpl__set_balance"
+    );
+    assert_eq!(findings[0].offset, None);
+
+    // The entry points of the counter contract emit an event when they write the counter
+    let content =
+        include_str!("../../examples/sierra/new_syntax_test_contract__counter_contract.sierra")
+            .to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    assert!(detector.findings(&decompiler.analysis_context()).is_empty());
+}
+
+#[test]
+fn test_constructor_detector() {
+    // The owner & the initial balance are written by the constructor without any check
+    let content =
+        include_str!("../../examples/sierra/with_ownable__ownable_balance.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The owner compared by validate_ownership is initialized by the constructor
+    let mut detector = ConstructorDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    let constructor =
+        "cairo_level_tests::contracts::with_ownable::ownable_balance::__wrapper__OwnableBalanceImpl__constructor";
+    let locations: Vec<(&str, Option<u32>)> = findings
+        .iter()
+        .map(|finding| (finding.function.as_str(), finding.offset))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![(constructor, Some(588)), (constructor, Some(597))]
+    );
+    assert_eq!(
+        findings[0].message,
+        "constructor parameter written to the storage without validation"
+    );
+}
+
+#[test]
+fn test_reinitialization_detector() {
+    // The initializer writes the storage without any check
+    let content = r#"type GasBuiltin = GasBuiltin [storable: true, drop: false, dup: false, zero_sized: false];
+type System = System [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type u32 = u32 [storable: true, drop: true, dup: true, zero_sized: false];
+type StorageBaseAddress = StorageBaseAddress [storable: true, drop: true, dup: true, zero_sized: false];
+type StorageAddress = StorageAddress [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<felt252> = Array<felt252> [storable: true, drop: true, dup: false, zero_sized: false];
+
+libfunc storage_base_address_const<1> = storage_base_address_const<1>;
+libfunc storage_address_from_base = storage_address_from_base;
+libfunc u32_const<0> = u32_const<0>;
+libfunc felt252_const<1> = felt252_const<1>;
+libfunc store_temp<u32> = store_temp<u32>;
+libfunc store_temp<StorageAddress> = store_temp<StorageAddress>;
+libfunc store_temp<felt252> = store_temp<felt252>;
+libfunc storage_write_syscall = storage_write_syscall;
+libfunc branch_align = branch_align;
+libfunc drop<Array<felt252>> = drop<Array<felt252>>;
+
+storage_base_address_const<1>() -> ([2]);
+storage_address_from_base([2]) -> ([3]);
+u32_const<0>() -> ([4]);
+felt252_const<1>() -> ([5]);
+store_temp<u32>([4]) -> ([4]);
+store_temp<StorageAddress>([3]) -> ([3]);
+store_temp<felt252>([5]) -> ([5]);
+storage_write_syscall([0], [1], [4], [3], [5]) { fallthrough([6], [7]) 10([8], [9], [10]) };
+branch_align() -> ();
+return([6], [7]);
+branch_align() -> ();
+drop<Array<felt252>>([10]) -> ();
+return([8], [9]);
+
+test::__wrapper__Impl__initialize@0([0]: GasBuiltin, [1]: System) -> (GasBuiltin, System);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let mut detector = ReinitializationDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].function, "test::__wrapper__Impl__initialize");
+    assert_eq!(findings[0].offset, None);
+    assert_eq!(
+        findings[0].message,
+        "the initializer initialize writes the storage without checking an already initialized flag, it can be called again"
+    );
+
+    // The initializer only writes the flag when it is not set
+    let content = r#"type GasBuiltin = GasBuiltin [storable: true, drop: false, dup: false, zero_sized: false];
+type System = System [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type NonZero<felt252> = NonZero<felt252> [storable: true, drop: true, dup: true, zero_sized: false];
+type u32 = u32 [storable: true, drop: true, dup: true, zero_sized: false];
+type StorageBaseAddress = StorageBaseAddress [storable: true, drop: true, dup: true, zero_sized: false];
+type StorageAddress = StorageAddress [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<felt252> = Array<felt252> [storable: true, drop: true, dup: false, zero_sized: false];
+
+libfunc storage_base_address_const<1> = storage_base_address_const<1>;
+libfunc storage_address_from_base = storage_address_from_base;
+libfunc u32_const<0> = u32_const<0>;
+libfunc felt252_const<1> = felt252_const<1>;
+libfunc storage_read_syscall = storage_read_syscall;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc storage_write_syscall = storage_write_syscall;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+libfunc drop<Array<felt252>> = drop<Array<felt252>>;
+
+storage_base_address_const<1>() -> ([2]);
+storage_address_from_base([2]) -> ([3]);
+u32_const<0>() -> ([4]);
+storage_read_syscall([0], [1], [4], [3]) { fallthrough([5], [6], [7]) 17([8], [9], [10]) };
+branch_align() -> ();
+felt252_is_zero([7]) { fallthrough() 14([11]) };
+branch_align() -> ();
+storage_base_address_const<1>() -> ([12]);
+storage_address_from_base([12]) -> ([13]);
+u32_const<0>() -> ([14]);
+felt252_const<1>() -> ([15]);
+storage_write_syscall([5], [6], [14], [13], [15]) { fallthrough([16], [17]) 20([18], [19], [20]) };
+branch_align() -> ();
+return([16], [17]);
+branch_align() -> ();
+drop<NonZero<felt252>>([11]) -> ();
+return([5], [6]);
+branch_align() -> ();
+drop<Array<felt252>>([10]) -> ();
+return([8], [9]);
+branch_align() -> ();
+drop<Array<felt252>>([20]) -> ();
+return([18], [19]);
+
+test::__wrapper__Impl__initialize@0([0]: GasBuiltin, [1]: System) -> (GasBuiltin, System);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    assert!(detector.findings(&decompiler.analysis_context()).is_empty());
+}
+
+#[test]
+fn test_tx_origin_detector() {
+    // check_caller compares the caller with the account address, only_account checks the account
+    let content = r#"type GasBuiltin = GasBuiltin [storable: true, drop: false, dup: false, zero_sized: false];
+type System = System [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type NonZero<felt252> = NonZero<felt252> [storable: true, drop: true, dup: true, zero_sized: false];
+type ContractAddress = ContractAddress [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<felt252> = Array<felt252> [storable: true, drop: true, dup: false, zero_sized: false];
+type u32 = u32 [storable: true, drop: true, dup: true, zero_sized: false];
+type u64 = u64 [storable: true, drop: true, dup: true, zero_sized: false];
+type u128 = u128 [storable: true, drop: true, dup: true, zero_sized: false];
+type Snapshot<Array<felt252>> = Snapshot<Array<felt252>> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::array::Span::<core::felt252> = Struct<ut@core::array::Span::<core::felt252>, Snapshot<Array<felt252>>> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::starknet::info::v2::ResourceBounds = Struct<ut@core::starknet::info::v2::ResourceBounds, felt252, u64, u128> [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<core::starknet::info::v2::ResourceBounds> = Array<core::starknet::info::v2::ResourceBounds> [storable: true, drop: true, dup: false, zero_sized: false];
+type Snapshot<Array<core::starknet::info::v2::ResourceBounds>> = Snapshot<Array<core::starknet::info::v2::ResourceBounds>> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::array::Span::<core::starknet::info::v2::ResourceBounds> = Struct<ut@core::array::Span::<core::starknet::info::v2::ResourceBounds>, Snapshot<Array<core::starknet::info::v2::ResourceBounds>>> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::starknet::info::v2::TxInfo = Struct<ut@core::starknet::info::v2::TxInfo, felt252, ContractAddress, u128, core::array::Span::<core::felt252>, felt252, felt252, felt252, core::array::Span::<core::starknet::info::v2::ResourceBounds>, u128, core::array::Span::<core::felt252>, u32, u32, core::array::Span::<core::felt252>> [storable: true, drop: true, dup: true, zero_sized: false];
+type Box<core::starknet::info::v2::TxInfo> = Box<core::starknet::info::v2::TxInfo> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::starknet::info::BlockInfo = Struct<ut@core::starknet::info::BlockInfo, u64, u64, ContractAddress> [storable: true, drop: true, dup: true, zero_sized: false];
+type Box<core::starknet::info::BlockInfo> = Box<core::starknet::info::BlockInfo> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::starknet::info::v2::ExecutionInfo = Struct<ut@core::starknet::info::v2::ExecutionInfo, Box<core::starknet::info::BlockInfo>, Box<core::starknet::info::v2::TxInfo>, ContractAddress, ContractAddress, felt252> [storable: true, drop: true, dup: true, zero_sized: false];
+type Box<core::starknet::info::v2::ExecutionInfo> = Box<core::starknet::info::v2::ExecutionInfo> [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc get_execution_info_v2_syscall = get_execution_info_v2_syscall;
+libfunc unbox<core::starknet::info::v2::ExecutionInfo> = unbox<core::starknet::info::v2::ExecutionInfo>;
+libfunc struct_deconstruct<core::starknet::info::v2::ExecutionInfo> = struct_deconstruct<core::starknet::info::v2::ExecutionInfo>;
+libfunc unbox<core::starknet::info::v2::TxInfo> = unbox<core::starknet::info::v2::TxInfo>;
+libfunc struct_deconstruct<core::starknet::info::v2::TxInfo> = struct_deconstruct<core::starknet::info::v2::TxInfo>;
+libfunc contract_address_to_felt252 = contract_address_to_felt252;
+libfunc felt252_sub = felt252_sub;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+libfunc drop<Array<felt252>> = drop<Array<felt252>>;
+
+get_execution_info_v2_syscall([0], [1]) { fallthrough([2], [3], [4]) 15([5], [6], [7]) };
+branch_align() -> ();
+unbox<core::starknet::info::v2::ExecutionInfo>([4]) -> ([8]);
+struct_deconstruct<core::starknet::info::v2::ExecutionInfo>([8]) -> ([9], [10], [11], [12], [13]);
+unbox<core::starknet::info::v2::TxInfo>([10]) -> ([14]);
+struct_deconstruct<core::starknet::info::v2::TxInfo>([14]) -> ([15], [16], [17], [18], [19], [20], [21], [22], [23], [24], [25], [26], [27]);
+contract_address_to_felt252([11]) -> ([28]);
+contract_address_to_felt252([16]) -> ([29]);
+felt252_sub([28], [29]) -> ([30]);
+felt252_is_zero([30]) { fallthrough() 12([31]) };
+branch_align() -> ();
+return([2], [3]);
+branch_align() -> ();
+drop<NonZero<felt252>>([31]) -> ();
+return([2], [3]);
+branch_align() -> ();
+drop<Array<felt252>>([7]) -> ();
+return([5], [6]);
+get_execution_info_v2_syscall([0], [1]) { fallthrough([2], [3], [4]) 31([5], [6], [7]) };
+branch_align() -> ();
+unbox<core::starknet::info::v2::ExecutionInfo>([4]) -> ([8]);
+struct_deconstruct<core::starknet::info::v2::ExecutionInfo>([8]) -> ([9], [10], [11], [12], [13]);
+unbox<core::starknet::info::v2::TxInfo>([10]) -> ([14]);
+struct_deconstruct<core::starknet::info::v2::TxInfo>([14]) -> ([15], [16], [17], [18], [19], [20], [21], [22], [23], [24], [25], [26], [27]);
+contract_address_to_felt252([16]) -> ([28]);
+felt252_is_zero([28]) { fallthrough() 28([29]) };
+branch_align() -> ();
+return([2], [3]);
+branch_align() -> ();
+drop<NonZero<felt252>>([29]) -> ();
+return([2], [3]);
+branch_align() -> ();
+drop<Array<felt252>>([7]) -> ();
+return([5], [6]);
+
+test::check_caller@0([0]: GasBuiltin, [1]: System) -> (GasBuiltin, System);
+test::only_account@18([0]: GasBuiltin, [1]: System) -> (GasBuiltin, System);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let mut detector = TxOriginDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    let locations: Vec<(&str, Option<u32>)> = findings
+        .iter()
+        .map(|finding| (finding.function.as_str(), finding.offset))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            ("test::check_caller", Some(9)),
+            ("test::only_account", Some(25))
+        ]
+    );
+    assert_eq!(
+        findings[0].message,
+        "authorization decision mixing the caller address with the transaction account address"
+    );
+    assert_eq!(
+        findings[1].message,
+        "authorization decision on the transaction account address while test::check_caller uses the caller address"
+    );
+
+    // The account contract checks the caller address & the transaction version, not the account
+    let content = include_str!("../../examples/sierra/account__account.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    assert!(detector.findings(&decompiler.analysis_context()).is_empty());
+}
+
+#[test]
+fn test_truncation_detector() {
+    // set_decimals unwraps the conversion of its parameter, low drops the high word of its parameter
+    let content = r#"type RangeCheck = RangeCheck [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type u8 = u8 [storable: true, drop: true, dup: true, zero_sized: false];
+type u128 = u128 [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<felt252> = Array<felt252> [storable: true, drop: true, dup: false, zero_sized: false];
+type Tuple<u8> = Struct<ut@Tuple, u8> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::panics::Panic = Struct<ut@core::panics::Panic> [storable: true, drop: true, dup: true, zero_sized: true];
+type Tuple<core::panics::Panic, Array<felt252>> = Struct<ut@Tuple, core::panics::Panic, Array<felt252>> [storable: true, drop: true, dup: false, zero_sized: false];
+type core::panics::PanicResult::<(u8,)> = Enum<ut@core::panics::PanicResult::<(u8,)>, Tuple<u8>, Tuple<core::panics::Panic, Array<felt252>>> [storable: true, drop: true, dup: false, zero_sized: false];
+
+libfunc u8_try_from_felt252 = u8_try_from_felt252;
+libfunc u128s_from_felt252 = u128s_from_felt252;
+libfunc branch_align = branch_align;
+libfunc struct_construct<Tuple<u8>> = struct_construct<Tuple<u8>>;
+libfunc enum_init<core::panics::PanicResult::<(u8,)>, 0> = enum_init<core::panics::PanicResult::<(u8,)>, 0>;
+libfunc enum_init<core::panics::PanicResult::<(u8,)>, 1> = enum_init<core::panics::PanicResult::<(u8,)>, 1>;
+libfunc array_new<felt252> = array_new<felt252>;
+libfunc struct_construct<core::panics::Panic> = struct_construct<core::panics::Panic>;
+libfunc struct_construct<Tuple<core::panics::Panic, Array<felt252>>> = struct_construct<Tuple<core::panics::Panic, Array<felt252>>>;
+libfunc drop<u128> = drop<u128>;
+
+u8_try_from_felt252([0], [1]) { fallthrough([2], [3]) 5([4]) };
+branch_align() -> ();
+struct_construct<Tuple<u8>>([3]) -> ([5]);
+enum_init<core::panics::PanicResult::<(u8,)>, 0>([5]) -> ([6]);
+return([2], [6]);
+branch_align() -> ();
+array_new<felt252>() -> ([7]);
+struct_construct<core::panics::Panic>() -> ([8]);
+struct_construct<Tuple<core::panics::Panic, Array<felt252>>>([8], [7]) -> ([9]);
+enum_init<core::panics::PanicResult::<(u8,)>, 1>([9]) -> ([10]);
+return([4], [10]);
+u128s_from_felt252([0], [1]) { fallthrough([2], [3]) 14([4], [5], [6]) };
+branch_align() -> ();
+return([2], [3]);
+branch_align() -> ();
+drop<u128>([5]) -> ();
+return([4], [6]);
+
+test::set_decimals@0([0]: RangeCheck, [1]: felt252) -> (RangeCheck, core::panics::PanicResult::<(u8,)>);
+test::low@11([0]: RangeCheck, [1]: felt252) -> (RangeCheck, u128);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let mut detector = TruncationDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    let locations: Vec<(&str, Option<u32>)> = findings
+        .iter()
+        .map(|finding| (finding.function.as_str(), finding.offset))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![("test::set_decimals", Some(0)), ("test::low", Some(11))]
+    );
+    assert_eq!(
+        findings[0].message,
+        "conversion of a parameter to u8 panics when the value is out of range"
+    );
+    assert_eq!(
+        findings[1].message,
+        "truncation of a felt252 parameter to u128, its high 128 bits are dropped"
+    );
+}
+
+#[test]
+fn test_deploy_detector() {
+    // deploy_user deploys its parameters from zero, deploy_const deploys a constant class
+    let content = r#"type GasBuiltin = GasBuiltin [storable: true, drop: false, dup: false, zero_sized: false];
+type System = System [storable: true, drop: false, dup: false, zero_sized: false];
+type felt252 = felt252 [storable: true, drop: true, dup: true, zero_sized: false];
+type ClassHash = ClassHash [storable: true, drop: true, dup: true, zero_sized: false];
+type ContractAddress = ContractAddress [storable: true, drop: true, dup: true, zero_sized: false];
+type Array<felt252> = Array<felt252> [storable: true, drop: true, dup: false, zero_sized: false];
+type Snapshot<Array<felt252>> = Snapshot<Array<felt252>> [storable: true, drop: true, dup: true, zero_sized: false];
+type core::array::Span::<core::felt252> = Struct<ut@core::array::Span::<core::felt252>, Snapshot<Array<felt252>>> [storable: true, drop: true, dup: true, zero_sized: false];
+type Unit = Struct<ut@Tuple> [storable: true, drop: true, dup: true, zero_sized: true];
+type core::bool = Enum<ut@core::bool, Unit, Unit> [storable: true, drop: true, dup: true, zero_sized: false];
+
+libfunc array_new<felt252> = array_new<felt252>;
+libfunc array_append<felt252> = array_append<felt252>;
+libfunc snapshot_take<Array<felt252>> = snapshot_take<Array<felt252>>;
+libfunc drop<Array<felt252>> = drop<Array<felt252>>;
+libfunc struct_construct<core::array::Span::<core::felt252>> = struct_construct<core::array::Span::<core::felt252>>;
+libfunc felt252_const<0> = felt252_const<0>;
+libfunc class_hash_const<123> = class_hash_const<123>;
+libfunc struct_construct<Unit> = struct_construct<Unit>;
+libfunc enum_init<core::bool, 0> = enum_init<core::bool, 0>;
+libfunc enum_init<core::bool, 1> = enum_init<core::bool, 1>;
+libfunc store_temp<core::bool> = store_temp<core::bool>;
+libfunc deploy_syscall = deploy_syscall;
+libfunc branch_align = branch_align;
+libfunc drop<ContractAddress> = drop<ContractAddress>;
+libfunc drop<core::array::Span::<core::felt252>> = drop<core::array::Span::<core::felt252>>;
+
+array_new<felt252>() -> ([4]);
+array_append<felt252>([4], [3]) -> ([5]);
+snapshot_take<Array<felt252>>([5]) -> ([6], [7]);
+drop<Array<felt252>>([6]) -> ();
+struct_construct<core::array::Span::<core::felt252>>([7]) -> ([8]);
+felt252_const<0>() -> ([9]);
+struct_construct<Unit>() -> ([10]);
+enum_init<core::bool, 1>([10]) -> ([11]);
+store_temp<core::bool>([11]) -> ([12]);
+deploy_syscall([0], [1], [2], [9], [8], [12]) { fallthrough([13], [14], [15], [16]) 14([17], [18], [19]) };
+branch_align() -> ();
+drop<ContractAddress>([15]) -> ();
+drop<core::array::Span::<core::felt252>>([16]) -> ();
+return([13], [14]);
+branch_align() -> ();
+drop<Array<felt252>>([19]) -> ();
+return([17], [18]);
+class_hash_const<123>() -> ([2]);
+array_new<felt252>() -> ([3]);
+snapshot_take<Array<felt252>>([3]) -> ([4], [5]);
+drop<Array<felt252>>([4]) -> ();
+struct_construct<core::array::Span::<core::felt252>>([5]) -> ([6]);
+felt252_const<0>() -> ([7]);
+struct_construct<Unit>() -> ([8]);
+enum_init<core::bool, 0>([8]) -> ([9]);
+deploy_syscall([0], [1], [2], [7], [6], [9]) { fallthrough([10], [11], [12], [13]) 30([14], [15], [16]) };
+branch_align() -> ();
+drop<ContractAddress>([12]) -> ();
+drop<core::array::Span::<core::felt252>>([13]) -> ();
+return([10], [11]);
+branch_align() -> ();
+drop<Array<felt252>>([16]) -> ();
+return([14], [15]);
+
+test::deploy_user@0([0]: GasBuiltin, [1]: System, [2]: ClassHash, [3]: felt252) -> (GasBuiltin, System);
+test::deploy_const@17([0]: GasBuiltin, [1]: System) -> (GasBuiltin, System);"#
+        .to_string();
+
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let mut detector = DeployDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    let locations: Vec<(&str, Option<u32>)> = findings
+        .iter()
+        .map(|finding| (finding.function.as_str(), finding.offset))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            ("test::deploy_user", Some(9)),
+            ("test::deploy_const", Some(25))
+        ]
+    );
+    assert_eq!(
+        findings[0].message,
+        "contract deployment with user-controlled class hash, user-controlled constructor calldata, deployed from zero (class hash: input, calldata: input, deploy_from_zero: true)"
+    );
+    assert_eq!(
+        findings[1].message,
+        "contract deployment (class hash: constant, calldata: constant, deploy_from_zero: false)"
+    );
+
+    // The contract doesn't deploy any contract
+    let content = include_str!("../../examples/sierra/account__account.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    assert!(detector.findings(&decompiler.analysis_context()).is_empty());
+}
+
+#[test]
+fn test_recursion_detector() {
+    // fib calls itself, the program has no entry point
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+
+    let mut detector = RecursionDetector::new();
+    let findings = detector.findings(&context);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].function, "examples::fib::fib");
+    assert_eq!(findings[0].offset, None);
+    assert_eq!(
+        findings[0].message,
+        "directly recursive function examples::fib::fib -> examples::fib::fib"
+    );
+    assert!(detector
+        .detect(&context)
+        .contains("Max call depth of examples::fib::fib: unbounded"));
+
+    // The mutual recursion reachable from an entry point is a potential unbounded gas consumption
+    let content = include_str!("../../examples/vulnerable/recursion.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+    assert_eq!(
+        detector.findings(&context)[0].message,
+        "mutually recursive functions vulnerable::recursion::is_even -> vulnerable::recursion::is_odd -> vulnerable::recursion::is_even reachable from the entry points is_even, potential unbounded gas consumption"
+    );
+
+    // The loops generated by the compiler are not reported
+    let content = include_str!("../../examples/sierra/fib_loop.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+    assert!(detector.findings(&context).is_empty());
+
+    // The calls depth of the patched entry point is bounded
+    let content = include_str!("../../examples/vulnerable/recursion_patched.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+    assert_eq!(
+        detector.detect(&context),
+        "Max call depth of vulnerable::recursion::__wrapper__Impl__is_even: 1\n"
+    );
+}
+
+#[test]
+fn test_detectors_function_filter() {
+    // Read file content
+    let content = include_str!("../../examples/vulnerable/truncation.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+
+    // Only the findings of the functions whose prototype contains the name are reported
+    let mut detector = TruncationDetector::new();
+    assert_eq!(detector.findings(&context).len(), 2);
+    let scoped_context = context.with_function_filter("to_u128");
+    let findings = detector.findings(&scoped_context);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].function, "vulnerable::truncation::to_u128");
+    assert!(scoped_context.scoped_functions().all(|function| function
+        .prototype
+        .as_ref()
+        .unwrap()
+        .contains("to_u128")));
+
+    // The whole program is still analyzed
+    assert_eq!(
+        scoped_context.functions().len(),
+        AnalysisContext::new(&program).unwrap().functions().len()
+    );
+}
+
+#[test]
+fn test_findings_reports() {
+    // Read file content
+    let content = include_str!("../../examples/vulnerable/truncation.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+
+    // The reports are located by the function entry offset & the statements offsets
+    let mut detector = TruncationDetector::new();
+    let reports: Vec<FindingReport> = detector
+        .findings(&context)
+        .into_iter()
+        .map(|finding| FindingReport::new(detector.id(), finding, &context))
+        .collect();
+    let locations: Vec<(&str, Option<u32>, Vec<u32>)> = reports
+        .iter()
+        .map(|report| {
+            (
+                report.function.as_str(),
+                report.function_offset,
+                report.statements.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            ("vulnerable::truncation::set_decimals", Some(0), vec![0]),
+            ("vulnerable::truncation::to_u128", Some(11), vec![11]),
+        ]
+    );
+    assert!(reports
+        .iter()
+        .all(|report| report.detector == "truncation" && report.detector_type == "security"));
+
+    let json: serde_json::Value = serde_json::from_str(&findings_to_json(&reports)).unwrap();
+    assert_eq!(json[1]["function_offset"], 11);
+    assert_eq!(json[1]["statements"][0], 11);
+
+    // The JSON findings are sorted by function entry offset, whatever the reports order
+    let reversed_reports: Vec<FindingReport> = reports.iter().rev().cloned().collect();
+    assert_eq!(
+        findings_to_json(&reversed_reports),
+        findings_to_json(&reports)
+    );
+}
+
+#[test]
+fn test_detectors_share_the_analysis_context() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // The context is created without decompiling the program
+    let context = AnalysisContext::new(&program).unwrap();
+
+    // The functions are loaded once with their CFGs
+    assert_eq!(context.functions().len(), 2);
+    assert!(context
+        .functions()
+        .iter()
+        .all(|function| function.has_cfg()));
+
+    // Several detectors run against the same read-only context
+    let mut strings_detector = StringsDetector::new();
+    let mut statistics_detector = StatisticsDetector::new();
+    assert_eq!(
+        strings_detector.detect(&context),
+        "Index out of bounds\nu32_sub Overflow"
+    );
+    assert_eq!(
+        statistics_detector.detect(&context),
+        "Libfuncs: 42\nTypes: 19\nFunctions: 2"
+    );
+}
+
+#[test]
+fn test_detectors_program_kind() {
+    // Raw Sierra program
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let context = AnalysisContext::new(&program).unwrap();
+    assert_eq!(context.kind(), ProgramKind::RawSierra);
+
+    // The detectors relying on the ABI are known
+    assert!(ControlledLibraryCallDetector::new().requires_abi());
+    assert!(!StringsDetector::new().requires_abi());
+
+    // Contract class with its ABI
+    let content = include_str!("../../examples/starknet/with_ownable.contract_class.json");
+    let contract_class: ContractClass = serde_json::from_str(content).unwrap();
+    let program_string = contract_class.extract_sierra_program().unwrap().to_string();
+    let mut program = SierraProgram::new(program_string);
+    program.set_abi(contract_class.abi.unwrap());
+    let context = AnalysisContext::new(&program).unwrap();
+    assert_eq!(context.kind(), ProgramKind::ContractClass);
+
+    // The External & View functions are typed using the ABI
+    assert!(context
+        .functions()
+        .iter()
+        .any(|function| matches!(function.function_type, Some(FunctionType::External))));
+    assert!(context
+        .functions()
+        .iter()
+        .any(|function| matches!(function.function_type, Some(FunctionType::View))));
+}
+
+#[test]
+fn test_detectors_tags() {
+    // The tags are the detector type & its speed
+    assert_eq!(FeltOverflowDetector::new().tags(), vec!["security", "fast"]);
+    assert_eq!(
+        StatisticsDetector::new().tags(),
+        vec!["informational", "fast"]
+    );
+    assert_eq!(
+        MissingEventsDetector::new().tags(),
+        vec!["correctness", "fast"]
+    );
+
+    // Only the tests generator uses the symbolic execution
+    assert!(get_detectors()
+        .iter()
+        .filter(|detector| detector.detector_type() != DetectorType::TESTING)
+        .all(|detector| detector.tags().contains(&"fast")));
+}
+
+#[test]
+fn test_findings_highlighted_in_graphs() {
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/test_contract__test_contract.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The findings are located at the felt252_add statements
+    let mut detector = FeltOverflowDetector::new();
+    let findings = detector.findings(&decompiler.analysis_context());
+    let function_name =
+        "cairo_level_tests::contracts::test_contract::test_contract::__wrapper__Impl__test";
+    let message =
+        "method felt252_add could be used to trigger a felt overflow/underflow (Confidence: Low)";
+    assert_eq!(findings.len(), 2);
+    for finding in &findings {
+        assert_eq!(finding.detector_type, DetectorType::SECURITY);
+        assert_eq!(finding.function, function_name);
+        assert!(finding.offset.is_some());
+        assert_eq!(finding.message, message);
+    }
+
+    // The blocks & functions containing a finding are highlighted with a tooltip
+    decompiler.set_findings(findings);
+    let cfg_dotgraph = decompiler.generate_cfg();
+    assert!(cfg_dotgraph.contains(&format!(
+        "[fillcolor=\"#EF5350\", style=\"filled\", tooltip=\"{}",
+        message
+    )));
+    let callgraph_dotgraph = decompiler.generate_callgraph();
+    assert!(callgraph_dotgraph.contains(&format!(
+        "   \"{}\" [fillcolor=\"#EF5350\", style=\"filled\", tooltip=\"{}",
+        function_name, message
+    )));
+}
+
+/// Detector panicking while it runs
+#[derive(Debug)]
+struct PanickingDetector;
+
+impl Detector for PanickingDetector {
+    fn id(&self) -> &'static str {
+        "panicking"
+    }
+
+    fn name(&self) -> &'static str {
+        "Panicking detector"
+    }
+
+    fn description(&self) -> &'static str {
+        "Panics while it runs"
+    }
+
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::INFORMATIONAL
+    }
+
+    fn detect(&mut self, _context: &AnalysisContext) -> String {
+        panic!("Detector failure")
+    }
+}
+
+/// Detector running longer than the timeout of the tests
+#[derive(Debug)]
+struct SleepingDetector;
+
+impl Detector for SleepingDetector {
+    fn id(&self) -> &'static str {
+        "sleeping"
+    }
+
+    fn name(&self) -> &'static str {
+        "Sleeping detector"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sleeps for a minute"
+    }
+
+    fn detector_type(&self) -> DetectorType {
+        DetectorType::INFORMATIONAL
+    }
+
+    fn detect(&mut self, _context: &AnalysisContext) -> String {
+        thread::sleep(Duration::from_secs(60));
+        String::new()
+    }
+}
+
+#[test]
+fn test_run_detectors_outcomes() {
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let options = RunOptions {
+        with_report: true,
+        timeout: Some(Duration::from_millis(500)),
+        ..RunOptions::default()
+    };
+
+    // The panicking detector fails & the sleeping one times out, the other one finishes
+    let mut reported = Vec::new();
+    let outcomes = run_detectors(
+        &program,
+        vec![
+            Box::new(PanickingDetector),
+            Box::new(SleepingDetector),
+            Box::new(StatisticsDetector::new()),
+        ],
+        options,
+        |index, _| reported.push(index),
+    )
+    .unwrap();
+
+    assert_eq!(outcomes[0], DetectorOutcome::Failed);
+    assert_eq!(outcomes[1], DetectorOutcome::TimedOut);
+    assert!(matches!(
+        &outcomes[2],
+        DetectorOutcome::Finished(results) if results.report.starts_with("Libfuncs: ")
+    ));
+
+    // Each outcome is reported once
+    reported.sort();
+    assert_eq!(reported, vec![0, 1, 2]);
+
+    // Without a timeout, a panicking detector doesn't block the run
+    let outcomes = run_detectors(
+        &program,
+        vec![Box::new(PanickingDetector)],
+        RunOptions {
+            with_report: true,
+            ..RunOptions::default()
+        },
+        |_, _| {},
+    )
+    .unwrap();
+    assert_eq!(outcomes, vec![DetectorOutcome::Failed]);
+}
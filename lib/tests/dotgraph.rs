@@ -1,3 +1,14 @@
+use sierra_analyzer_lib::decompiler::cfg::CfgNodeDetail;
+use sierra_analyzer_lib::decompiler::cfg::EdgeType;
+use sierra_analyzer_lib::graph::callgraph::module_path;
+use sierra_analyzer_lib::graph::callgraph::CallDepth;
+use sierra_analyzer_lib::graph::callgraph::CallKind;
+use sierra_analyzer_lib::graph::cross_contract::cross_contract_edges;
+use sierra_analyzer_lib::graph::cross_contract::process_cross_contract_callgraph;
+use sierra_analyzer_lib::graph::cross_contract::process_cross_contract_callgraph_json;
+use sierra_analyzer_lib::graph::cross_contract::CrossContract;
+use sierra_analyzer_lib::graph::cross_contract::CrossContractCallKind;
+use sierra_analyzer_lib::graph::cross_contract::CrossContractEdge;
 use sierra_analyzer_lib::sierra_program::SierraProgram;
 
 #[test]
@@ -53,3 +64,438 @@ fn test_dogtgraph_callgraph_output() {
 
     assert_eq!(callgraph_dotgraph, expected_output);
 }
+
+#[test]
+fn test_callgraph_edges() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program with a colorless output
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // Get the callgraph edges
+    let edges = decompiler.callgraph_edges();
+
+    let expected_edges = vec![
+        (
+            "examples::fib::fib".to_string(),
+            "const_as_immediate<Const<felt252, 1>>".to_string(),
+            CallKind::Libfunc,
+        ),
+        (
+            "examples::fib::fib".to_string(),
+            "examples::fib::fib".to_string(),
+            CallKind::UserDefined,
+        ),
+    ];
+
+    assert_eq!(edges, expected_edges);
+
+    // The JSON export contains the same edges
+    let callgraph_json: serde_json::Value =
+        serde_json::from_str(&decompiler.generate_callgraph_json()).unwrap();
+    assert_eq!(callgraph_json["edges"].as_array().unwrap().len(), 2);
+    assert_eq!(callgraph_json["edges"][1]["kind"], "user_defined");
+    assert_eq!(callgraph_json["nodes"][0]["name"], "examples::fib::fib");
+}
+
+#[test]
+fn test_callgraph_nodes_order() {
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The program functions come first by entry offset, then the callees by first call
+    let callgraph_json: serde_json::Value =
+        serde_json::from_str(&decompiler.generate_callgraph_json()).unwrap();
+    let nodes: Vec<&str> = callgraph_json["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| node["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        nodes[..2],
+        ["examples::fib_array::fib", "examples::fib_array::fib_inner"]
+    );
+
+    // The graphs are the same across runs
+    let mut other_decompiler = program.decompiler(false);
+    other_decompiler.decompile(false);
+    assert_eq!(
+        other_decompiler.generate_callgraph_json(),
+        decompiler.generate_callgraph_json()
+    );
+    assert_eq!(
+        other_decompiler.generate_callgraph(),
+        decompiler.generate_callgraph()
+    );
+}
+
+#[test]
+fn test_callgraph_reachability() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program with a colorless output
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let callgraph = decompiler.callgraph();
+
+    // fib calls fib_inner so fib_inner is reachable from fib
+    let reachable = callgraph.reachable_from(&["examples::fib_array::fib".to_string()]);
+    assert!(reachable.contains("examples::fib_array::fib"));
+    assert!(reachable.contains("examples::fib_array::fib_inner"));
+
+    // fib is a caller of fib_inner
+    let callers = callgraph.callers("examples::fib_array::fib_inner");
+    assert!(callers.contains("examples::fib_array::fib"));
+    assert!(callers.contains("examples::fib_array::fib_inner"));
+
+    // Unknown functions have no callers
+    assert!(callgraph.callers("unknown::function").is_empty());
+
+    // The filtered callgraph only contains the retained nodes
+    let filtered_callgraph = decompiler.generate_filtered_callgraph(&callers);
+    assert!(filtered_callgraph
+        .contains("\"examples::fib_array::fib\" -> \"examples::fib_array::fib_inner\""));
+    assert!(!filtered_callgraph.contains("\t\t\""));
+}
+
+#[test]
+fn test_cross_contract_callgraph() {
+    // Read the contracts files content
+    let caller_content =
+        include_str!("../../examples/sierra/new_syntax_test_contract.sierra").to_string();
+    let callee_content = include_str!("../../examples/sierra/other_contract.sierra").to_string();
+
+    // Init the SierraPrograms with the .sierra files content
+    let caller_program = SierraProgram::new(caller_content);
+    let callee_program = SierraProgram::new(callee_content);
+
+    // Decompile both programs to set the functions types
+    let mut caller_decompiler = caller_program.decompiler(false);
+    caller_decompiler.decompile(false);
+    let mut callee_decompiler = callee_program.decompiler(false);
+    callee_decompiler.decompile(false);
+
+    let contracts = vec![
+        CrossContract::new("caller", None, &caller_decompiler.functions),
+        CrossContract::new("callee", None, &callee_decompiler.functions),
+    ];
+
+    // The dispatcher call is linked to the wrapper of the other contract entry point
+    let edges = cross_contract_edges(&contracts);
+    assert_eq!(
+        edges,
+        vec![CrossContractEdge {
+            caller_contract: "caller".to_string(),
+            caller: "test::IOtherContractDispatcherImpl::decrease_allowed".to_string(),
+            callee_contract: "callee".to_string(),
+            callee: "test::other_contract::__wrapper__OtherContractImpl__decrease_allowed"
+                .to_string(),
+            kind: CrossContractCallKind::ContractCall,
+        }]
+    );
+
+    // DOT output contains one cluster per contract & the inter-contract edge
+    let dotgraph = process_cross_contract_callgraph(&contracts);
+    assert!(dotgraph.contains("subgraph \"cluster_0\" {\n        label=\"caller\";\n"));
+    assert!(dotgraph.contains("subgraph \"cluster_1\" {\n        label=\"callee\";\n"));
+    assert!(dotgraph.contains("    \"caller::test::IOtherContractDispatcherImpl::decrease_allowed\" -> \"callee::test::other_contract::__wrapper__OtherContractImpl__decrease_allowed\" [color=\"#1F77B4\", style=\"dashed\"];\n"));
+
+    // JSON output
+    let json = process_cross_contract_callgraph_json(&contracts);
+    assert!(json.contains("\"kind\": \"contract_call\""));
+    assert!(json.contains("\"callee_contract\": \"callee\""));
+}
+
+#[test]
+fn test_petgraph_representations() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The callgraph contains one edge per call site
+    let callgraph = decompiler.callgraph();
+    assert_eq!(callgraph.graph.node_count(), 2);
+    assert_eq!(callgraph.graph.edge_count(), 2);
+
+    // The CFG contains the 3 basic blocks & the 2 conditional edges of fib
+    let cfg_graph = decompiler.functions[0].cfg().graph();
+    let blocks_names: Vec<&str> = cfg_graph.node_weights().map(|block| block.name()).collect();
+    assert_eq!(blocks_names, vec!["bb_0", "bb_3", "bb_8"]);
+    assert_eq!(cfg_graph.edge_count(), 2);
+
+    // JSON export of the CFG
+    let cfg_json = decompiler.generate_cfg_json();
+    assert!(cfg_json.contains("\"function\": \"examples::fib::fib\""));
+    assert!(cfg_json.contains("\"kind\": \"conditional_true\""));
+    assert!(cfg_json.contains("\"kind\": \"conditional_false\""));
+
+    // fib doesn't return a PanicResult
+    assert!(cfg_json.contains("\"panic_safety\""));
+    assert!(cfg_json.contains("\"nopanic\": true"));
+}
+
+#[test]
+fn test_cfg_paths_iter_limits() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let cfg = decompiler.functions[0].cfg();
+
+    // fib has 2 paths : bb_0 -> bb_8 & bb_0 -> bb_3
+    let paths = cfg.paths();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(cfg.paths_iter(usize::MAX, usize::MAX).count(), 2);
+
+    // The number of paths is bounded
+    assert_eq!(cfg.paths_iter(1, usize::MAX).count(), 1);
+
+    // Paths are truncated at the maximum depth
+    let truncated_paths: Vec<_> = cfg.paths_iter(usize::MAX, 1).collect();
+    assert_eq!(truncated_paths.len(), 1);
+    assert_eq!(truncated_paths[0].len(), 1);
+    assert_eq!(truncated_paths[0][0].name(), "bb_0");
+}
+
+#[test]
+fn test_cfg_cache() {
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.analyze().unwrap();
+
+    // The CFG is built on its first access, then reused
+    let function = &mut decompiler.functions[0];
+    assert!(!function.has_cfg());
+    let cfg: *const _ = function.cfg();
+    assert!(function.has_cfg());
+    function.create_cfg();
+    assert!(std::ptr::eq(cfg, function.cfg()));
+    assert_eq!(function.cfg().basic_blocks.len(), 3);
+
+    // Changing the statements invalidates it
+    let statements = function.statements()[..1].to_vec();
+    function.set_statements(statements);
+    assert!(!function.has_cfg());
+    assert_eq!(function.cfg().basic_blocks.len(), 1);
+}
+
+#[test]
+fn test_callgraph_modules_clusters() {
+    // Module paths, without the generic arguments & the impls names
+    assert_eq!(module_path("examples::fib::fib"), Some("examples::fib"));
+    assert_eq!(
+        module_path("erc20::erc20::ERC20Impl::<erc20::erc20::ContractState>::transfer"),
+        Some("erc20::erc20")
+    );
+    assert_eq!(
+        module_path("core::array::array_new::<core::felt252>"),
+        Some("core::array")
+    );
+    assert_eq!(module_path("fib"), None);
+
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/multi_component__contract_with_4_components.sierra")
+            .to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program with a colorless output
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The functions are not grouped by default
+    assert!(!decompiler.generate_callgraph().contains("subgraph"));
+
+    // The functions are grouped by module
+    decompiler.set_callgraph_clusters(true);
+    let callgraph_dotgraph = decompiler.generate_callgraph();
+    assert!(callgraph_dotgraph
+        .contains("subgraph \"cluster_cairo_level_tests::components::erc20::erc20\" {"));
+    assert!(callgraph_dotgraph
+        .contains("subgraph \"cluster_cairo_level_tests::components::ownable::ownable\" {"));
+    assert_eq!(
+        callgraph_dotgraph
+            .matches("subgraph \"cluster_cairo_level_tests::components::erc20::erc20\"")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_dotgraph_cfg_detail() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the sierra program with a colorless output
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The raw statements are the default content
+    assert_eq!(
+        decompiler.generate_cfg_with_detail(CfgNodeDetail::Raw),
+        decompiler.generate_cfg()
+    );
+
+    // Decompiled statements, the hidden statements are skipped
+    let cfg_dotgraph = decompiler.generate_cfg_with_detail(CfgNodeDetail::Decompiled);
+    assert!(cfg_dotgraph.contains("2 : if (felt252_is_zero(v3))\t\t\\l"));
+    assert!(cfg_dotgraph.contains("11 : v6 = v0 + v5\t\t\\l"));
+    assert!(!cfg_dotgraph.contains("branch_align"));
+
+    // Block names & statements counts
+    let cfg_dotgraph = decompiler.generate_cfg_with_detail(CfgNodeDetail::Summary);
+    assert!(cfg_dotgraph.contains("\"bb_0\" [label=\"bb_0 : 3 statements\\l\""));
+    assert!(cfg_dotgraph.contains("\"bb_8\" [label=\"bb_8 : 11 statements\\l\""));
+}
+
+#[test]
+fn test_graphml_output() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the sierra program with a colorless output
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // CFG basic blocks, prefixed by their function name
+    let cfg_graphml = decompiler.generate_cfg_graphml();
+    assert!(cfg_graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml"));
+    assert!(cfg_graphml.contains("<graph id=\"cfg\" edgedefault=\"directed\">"));
+    assert!(cfg_graphml.contains("<node id=\"examples::fib::fib::bb_0\">"));
+    assert!(cfg_graphml.contains(
+        "<edge source=\"examples::fib::fib::bb_0\" target=\"examples::fib::fib::bb_8\">\n      <data key=\"kind\">conditional_true</data>"
+    ));
+    assert!(cfg_graphml
+        .contains("12 : const_as_immediate&lt;Const&lt;felt252, 1&gt;&gt;() -&gt; ([7])"));
+
+    // Callgraph functions & libfuncs
+    let callgraph_graphml = decompiler.generate_callgraph_graphml();
+    assert!(callgraph_graphml.contains(
+        "<node id=\"examples::fib::fib\">\n      <data key=\"label\">examples::fib::fib</data>\n      <data key=\"kind\">user_defined</data>"
+    ));
+    assert!(callgraph_graphml.contains(
+        "<edge source=\"examples::fib::fib\" target=\"examples::fib::fib\">\n      <data key=\"kind\">user_defined</data>"
+    ));
+    assert_eq!(callgraph_graphml.matches("<node ").count(), 2);
+}
+
+#[test]
+fn test_callgraph_recursion_cycles() {
+    // fib_inner calls itself
+    let content = include_str!("../../examples/sierra/fib_array.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.analyze().unwrap();
+
+    let callgraph = decompiler.callgraph();
+    let cycles = callgraph.recursion_cycles();
+    assert_eq!(cycles.len(), 1);
+    assert!(cycles[0].is_direct());
+    assert_eq!(
+        cycles[0].path(),
+        "examples::fib_array::fib_inner -> examples::fib_array::fib_inner"
+    );
+
+    // The calls depth of the recursive functions & of their callers is unbounded
+    assert_eq!(
+        callgraph.max_call_depth("examples::fib_array::fib"),
+        Some(CallDepth::Unbounded)
+    );
+    assert_eq!(callgraph.max_call_depth("unknown::function"), None);
+
+    // is_even & is_odd call each other
+    let content = include_str!("../../examples/vulnerable/recursion.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let cycles = decompiler.callgraph().recursion_cycles();
+    assert_eq!(cycles.len(), 1);
+    assert!(!cycles[0].is_direct());
+    assert_eq!(
+        cycles[0].functions,
+        vec![
+            "vulnerable::recursion::is_even".to_string(),
+            "vulnerable::recursion::is_odd".to_string(),
+        ]
+    );
+
+    // Without recursion, the depth is the number of nested calls
+    let content = include_str!("../../examples/vulnerable/recursion_patched.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let callgraph = decompiler.callgraph();
+    assert!(callgraph.recursion_cycles().is_empty());
+    assert_eq!(
+        callgraph.max_call_depth("vulnerable::recursion::__wrapper__Impl__is_even"),
+        Some(CallDepth::Bounded(1))
+    );
+    assert_eq!(
+        callgraph.max_call_depth("vulnerable::recursion::is_even"),
+        Some(CallDepth::Bounded(0))
+    );
+}
+
+#[test]
+fn test_cfg_fallthrough_blocks() {
+    let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
+    let program = SierraProgram::new(content);
+    let mut decompiler = program.decompiler(false);
+    decompiler.analyze().unwrap();
+
+    let function = decompiler
+        .functions
+        .iter()
+        .find(|function| function.prototype.as_ref().unwrap().contains("U32Add::add"))
+        .unwrap();
+
+    // The overflow branch of `u32_overflowing_add` falls through into the jump target
+    // of the other branch, it is kept as a basic block
+    let overflow_block = function
+        .cfg()
+        .basic_blocks
+        .iter()
+        .find(|block| block.start_offset == 374)
+        .unwrap();
+    assert_eq!(overflow_block.statements.len(), 4);
+    assert!(overflow_block
+        .edges
+        .iter()
+        .any(|edge| edge.destination == 378 && edge.edge_type == EdgeType::Fallthrough));
+}
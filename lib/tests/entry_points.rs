@@ -0,0 +1,46 @@
+use sierra_analyzer_lib::analysis::entry_points::{EntryPointKind, EntryPointsTable};
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+use starknet_core::utils::starknet_keccak;
+
+#[test]
+fn test_entry_points_table() {
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/with_ownable__ownable_balance.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // Without ABI, the external & view functions are told apart by their storage writes
+    let table = EntryPointsTable::new(&decompiler.functions, None);
+    let entry_points: Vec<(&str, EntryPointKind, &str)> = table
+        .entries
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry.kind, entry.mutability()))
+        .collect();
+    assert_eq!(
+        entry_points,
+        vec![
+            ("get_balance", EntryPointKind::View, "read-only"),
+            ("set_balance", EntryPointKind::External, "mutable"),
+            ("owner", EntryPointKind::View, "read-only"),
+            ("transfer_ownership", EntryPointKind::External, "mutable"),
+            ("constructor", EntryPointKind::Constructor, "mutable"),
+        ]
+    );
+
+    // The selector is the starknet keccak of the entry point name
+    assert_eq!(
+        table.entries[1].selector,
+        format!("{:#x}", starknet_keccak(b"set_balance"))
+    );
+
+    // The text table has a header & a row per entry point
+    let text = table.to_text();
+    assert_eq!(text.lines().count(), 6);
+    assert!(text.lines().next().unwrap().starts_with("Entry point"));
+}
@@ -0,0 +1,78 @@
+use std::fs;
+
+use sierra_analyzer_lib::detectors::detector::{findings_to_json, FindingReport};
+use sierra_analyzer_lib::detectors::findings_diff::{read_findings, FindingsDiff};
+
+/// Returns a security finding of a function
+fn report(detector: &str, function: &str, statement: u32, message: &str) -> FindingReport {
+    FindingReport {
+        detector: detector.to_string(),
+        detector_type: "security".to_string(),
+        function: function.to_string(),
+        function_offset: Some(0),
+        statements: vec![statement],
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn test_findings_diff() {
+    let old = vec![
+        report("truncation", "test::to_u8", 4, "unchecked conversion"),
+        report("tx_origin", "test::only_account", 25, "account check"),
+        report("felt_overflow", "test::add", 2, "felt252_add overflow"),
+        report("felt_overflow", "test::add", 7, "felt252_add overflow"),
+    ];
+    // The truncation is fixed, a statement was added before the overflows, one of them is fixed
+    let new = vec![
+        report("tx_origin", "test::only_account", 26, "account check"),
+        report("felt_overflow", "test::add", 3, "felt252_add overflow"),
+        report("deploy", "test::factory", 9, "contract deployment"),
+    ];
+
+    let diff = FindingsDiff::new(&old, &new);
+    assert_eq!(diff.fixed, vec![old[0].clone(), old[2].clone()]);
+    assert_eq!(diff.new, vec![new[2].clone()]);
+    // The unchanged findings are located in the new report
+    assert_eq!(diff.unchanged, vec![new[0].clone(), new[1].clone()]);
+    assert!(diff.has_new());
+
+    assert_eq!(
+        diff.to_text(),
+        "Fixed (2)
+\t- [security] truncation in test::to_u8: unchecked conversion
+\t- [security] felt_overflow in test::add: felt252_add overflow
+New (1)
+\t- [security] deploy in test::factory: contract deployment
+Unchanged (2)
+\t- [security] tx_origin in test::only_account: account check
+\t- [security] felt_overflow in test::add: felt252_add overflow
+
+2 fixed, 1 new, 2 unchanged
+"
+    );
+
+    // Identical reports have no new nor fixed finding
+    let diff = FindingsDiff::new(&new, &new);
+    assert!(diff.fixed.is_empty() && !diff.has_new());
+    assert_eq!(diff.unchanged.len(), 3);
+}
+
+#[test]
+fn test_read_findings() {
+    let path = std::env::temp_dir().join("sierra_analyzer_findings_diff.json");
+    let findings = vec![report(
+        "truncation",
+        "test::to_u8",
+        4,
+        "unchecked conversion",
+    )];
+    fs::write(&path, findings_to_json(&findings)).unwrap();
+
+    assert_eq!(read_findings(&path).unwrap(), findings);
+
+    // A file that isn't a JSON findings array is an error
+    fs::write(&path, "{}").unwrap();
+    assert!(read_findings(&path).is_err());
+    let _ = fs::remove_file(&path);
+}
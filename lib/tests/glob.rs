@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use sierra_analyzer_lib::glob::glob_files;
+
+#[test]
+fn test_glob_files() {
+    // `*` doesn't match the directories separators
+    let files = glob_files("../examples/*/fib_a*.sierra").unwrap();
+    assert_eq!(
+        files,
+        vec![PathBuf::from("../examples/sierra/fib_array.sierra")]
+    );
+
+    // `**` matches any number of directories
+    let files = glob_files("../examples/**/*_patched.sierra").unwrap();
+    assert!(!files.is_empty());
+    assert!(files
+        .iter()
+        .all(|file| file.starts_with("../examples/vulnerable")));
+    let files = glob_files("../examples/**/erc20__erc_20.*").unwrap();
+    assert_eq!(
+        files,
+        vec![
+            PathBuf::from("../examples/sierra/erc20__erc_20.sierra"),
+            PathBuf::from("../examples/starknet/erc20__erc_20.contract_class.json"),
+        ]
+    );
+
+    // The files are sorted by path
+    let files = glob_files("../examples/sierra/fib_?.sierra").unwrap();
+    assert!(files.is_empty());
+    let files = glob_files("../examples/sierra/fib_*.sierra").unwrap();
+    assert!(files.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_glob_files_directory() {
+    // The Sierra programs & contract classes of a directory are collected recursively
+    let files = glob_files("../examples").unwrap();
+    assert!(files.contains(&PathBuf::from("../examples/sierra/fib.sierra")));
+    assert!(files.contains(&PathBuf::from(
+        "../examples/starknet/erc20__erc_20.contract_class.json"
+    )));
+    assert!(files.iter().all(|file| file
+        .extension()
+        .map_or(false, |extension| extension != "md")));
+
+    // A pattern without wildcards can be a file
+    let files = glob_files("../examples/sierra/fib.sierra").unwrap();
+    assert_eq!(files, vec![PathBuf::from("../examples/sierra/fib.sierra")]);
+}
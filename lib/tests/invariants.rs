@@ -0,0 +1,114 @@
+use sierra_analyzer_lib::analysis::invariants::{check_invariants, load_invariants, Invariant};
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_load_invariants() {
+    let invariants = load_invariants(
+        r#"[
+            {"kind": "never_writes_storage", "function": "test::f", "slot": "0x2a"},
+            {"kind": "checks_caller", "function": "test::g"}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        invariants,
+        vec![
+            Invariant::NeverWritesStorage {
+                function: "test::f".to_string(),
+                slot: "0x2a".to_string(),
+            },
+            Invariant::ChecksCaller {
+                function: "test::g".to_string(),
+            },
+        ]
+    );
+
+    // The storage slots must be numbers
+    assert!(load_invariants(
+        r#"[{"kind": "never_writes_storage", "function": "test::f", "slot": "balance"}]"#
+    )
+    .is_err());
+}
+
+#[test]
+fn test_never_writes_storage_invariant() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // Storage slot of the `balance` variable
+    let invariants = load_invariants(
+        r#"[
+            {
+                "kind": "never_writes_storage",
+                "function": "test::hello_starknet::HelloStarknetImpl::increase_balance",
+                "slot": "916907772491729262376534102982219947830828984996257231353398618781993312401"
+            },
+            {
+                "kind": "never_writes_storage",
+                "function": "test::hello_starknet::HelloStarknetImpl::get_balance",
+                "slot": "916907772491729262376534102982219947830828984996257231353398618781993312401"
+            },
+            {
+                "kind": "never_writes_storage",
+                "function": "test::hello_starknet::HelloStarknetImpl::increase_balance",
+                "slot": "0x1"
+            }
+        ]"#,
+    )
+    .unwrap();
+
+    // Only increase_balance writes the balance, through the storage member write function
+    let violations = check_invariants(&decompiler.functions, &invariants);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].invariant, invariants[0]);
+    assert_eq!(
+        violations[0].reason,
+        "test::hello_starknet::balance::InternalContractMemberStateImpl::write writes the slot at statement 403"
+    );
+}
+
+#[test]
+fn test_checks_caller_invariant() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/ownable_erc20.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The paths are explored over the CFGs
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    // The generic arguments can be omitted from the functions names
+    let invariants = load_invariants(
+        r#"[
+            {"kind": "checks_caller", "function": "test::ownable::Transfer::transfer_ownership"},
+            {"kind": "checks_caller", "function": "test::erc20::IERC20::get_name"},
+            {"kind": "checks_caller", "function": "test::unknown"}
+        ]"#,
+    )
+    .unwrap();
+
+    // The ownership transfer validates the caller, the name getter doesn't
+    let violations = check_invariants(&decompiler.functions, &invariants);
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0].invariant, invariants[1]);
+    assert!(violations[0]
+        .reason
+        .ends_with("does not read the caller address"));
+    assert_eq!(violations[1].invariant, invariants[2]);
+    assert_eq!(violations[1].reason, "function test::unknown not found");
+}
@@ -0,0 +1,39 @@
+use std::fs;
+
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use sierra_analyzer_lib::error::SierraAnalyzerError;
+use sierra_analyzer_lib::local_provider::LocalProvider;
+
+#[test]
+fn test_directory_provider() {
+    // Directory dump with a single class
+    let directory = std::env::temp_dir().join(format!(
+        "sierra_analyzer_local_provider_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(
+        directory.join("0x1234.json"),
+        include_str!("../../examples/starknet/hello_starknet__hello_starknet.contract_class.json"),
+    )
+    .unwrap();
+
+    let provider = LocalProvider::from_path(&directory).unwrap();
+
+    // The class hash can be padded with zeros
+    let response = provider
+        .get_class("0x0000000000000000000000000000000000000000000000000000000000001234")
+        .unwrap();
+    let content = response.to_json().unwrap();
+    let contract_class: ContractClass = serde_json::from_str(&content).unwrap();
+    assert!(contract_class.abi.is_some());
+    assert!(contract_class.extract_sierra_program().is_ok());
+
+    // Unknown class
+    assert!(matches!(
+        provider.get_class("0x5678"),
+        Err(SierraAnalyzerError::ClassNotFound(_))
+    ));
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,48 @@
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+
+use sierra_analyzer_lib::analysis::messaging::MessagingReport;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_messaging_report() {
+    // Contract class with its ABI, the L1 handlers are only known from the ABI
+    let content =
+        include_str!("../../examples/starknet/token_bridge__token_bridge.contract_class.json");
+    let contract_class: ContractClass = serde_json::from_str(content).unwrap();
+    let program_string = contract_class.extract_sierra_program().unwrap().to_string();
+    let mut program = SierraProgram::new(program_string);
+    program.set_abi(contract_class.abi.unwrap());
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The origins are tracked in the SSA form of the functions
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+    let report = MessagingReport::new(&decompiler.functions, decompiler.abi.as_ref());
+
+    // The deposits of L1 are handled by handle_deposit
+    let handlers: Vec<&str> = report
+        .l1_handlers
+        .iter()
+        .map(|handler| handler.name.as_str())
+        .collect();
+    assert_eq!(handlers, vec!["handle_deposit"]);
+
+    // initiate_withdraw sends the withdraw message to the L1 bridge read from the storage
+    assert_eq!(report.messages.len(), 1);
+    let message = &report.messages[0];
+    assert!(message
+        .function
+        .ends_with("::TokenBridgeImpl::initiate_withdraw"));
+    assert_eq!(message.entry_points, vec!["initiate_withdraw".to_string()]);
+    assert!(message.to_address.storage);
+    assert!(!message.to_address.is_user_controlled());
+
+    // The payload is the withdraw message type followed by the calldata recipient & amount
+    assert_eq!(message.payload_fields.len(), 4);
+    assert_eq!(message.payload_fields[0].as_str(), "constant");
+    assert_eq!(message.user_controlled_fields(), vec![1, 2, 3]);
+}
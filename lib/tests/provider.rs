@@ -0,0 +1,195 @@
+#![cfg(feature = "remote")]
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use sierra_analyzer_lib::error::SierraAnalyzerError;
+use sierra_analyzer_lib::provider::{
+    ApiKey, ApiKeyLocation, RpcClient, RpcProvider, RpcResponse, API_KEY_PLACEHOLDER,
+};
+
+/// Returns a temporary directory unique to the test & the test process
+fn temp_directory(test_name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "sierra_analyzer_{}_{}",
+        test_name,
+        std::process::id()
+    ))
+}
+
+/// Serves the canned JSON-RPC responses to the next requests & returns the URL of the server
+fn serve_responses(responses: Vec<&'static str>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for body in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_request(&mut stream);
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+/// Reads an HTTP request, until the end of its body
+fn read_request(stream: &mut TcpStream) {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    loop {
+        let read = stream.read(&mut buffer).unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some(headers_end) = text.find("\r\n\r\n") {
+            let content_length = text[..headers_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if request.len() >= headers_end + 4 + content_length {
+                return;
+            }
+        }
+        if read == 0 {
+            return;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_classes_resumes_from_cache() {
+    // Cache of an interrupted batch, the class has already been fetched
+    let cache = temp_directory("fetch_cache");
+    fs::create_dir_all(&cache).unwrap();
+    fs::write(
+        cache.join("0x0000000000000000000000000000000000000000000000000000000000001234.json"),
+        include_str!("../../examples/starknet/hello_starknet__hello_starknet.contract_class.json"),
+    )
+    .unwrap();
+
+    // The node is unreachable, the cached class is not fetched again
+    let client = RpcClient::new("http://127.0.0.1:1")
+        .with_concurrency(2)
+        .with_request_interval(Duration::from_millis(10))
+        .with_max_retries(0);
+    let responses = client
+        .get_classes(&["0x1234".to_string(), "0x5678".to_string()], Some(&cache))
+        .await;
+
+    assert_eq!(responses.len(), 2);
+    assert!(responses[0]
+        .as_ref()
+        .unwrap()
+        .to_json()
+        .unwrap()
+        .contains("sierra_program"));
+    assert!(responses[1].is_err());
+
+    fs::remove_dir_all(&cache).unwrap();
+}
+
+#[tokio::test]
+async fn test_rpc_errors_hide_api_key() {
+    // The API key is a part of the URL path, the node is unreachable
+    let client = RpcClient::new("http://127.0.0.1:1/{api_key}/rpc")
+        .with_api_key(ApiKey::new(
+            "secret-key".to_string(),
+            ApiKeyLocation::UrlPath,
+        ))
+        .with_max_retries(0);
+    let error = client.get_class("0x1234").await.unwrap_err();
+
+    assert!(!error.to_string().contains("secret-key"));
+    assert!(!format!("{:?}", error).contains("secret-key"));
+}
+
+#[test]
+fn test_rpc_provider_urls() {
+    let provider = RpcProvider::from_name("Infura").unwrap();
+    assert_eq!(provider, RpcProvider::Infura);
+    assert!(RpcProvider::from_name("unknown").is_none());
+
+    // The API key is a part of the URL path of each provider
+    for provider in [
+        RpcProvider::Blast,
+        RpcProvider::Infura,
+        RpcProvider::Alchemy,
+    ] {
+        for network in ["mainnet", "sepolia"] {
+            let url = provider.url(network).unwrap();
+            assert!(url.contains(network));
+            assert!(url.contains(API_KEY_PLACEHOLDER));
+        }
+        assert!(provider.url("goerli").is_none());
+    }
+}
+
+#[test]
+fn test_declared_class_hash_responses() {
+    let response = |content: &str| serde_json::from_str::<RpcResponse>(content).unwrap();
+
+    // DECLARE transaction
+    let declare = response(
+        r#"{"jsonrpc":"2.0","id":1,"result":{"type":"DECLARE","version":"0x2","class_hash":"0x1234"}}"#,
+    );
+    assert_eq!(
+        declare.declared_class_hash().unwrap(),
+        Some("0x1234".to_string())
+    );
+
+    // Other transaction types don't declare a class
+    let invoke = response(r#"{"jsonrpc":"2.0","id":1,"result":{"type":"INVOKE","version":"0x1"}}"#);
+    assert_eq!(invoke.declared_class_hash().unwrap(), None);
+
+    // Malformed transactions are errors
+    let missing_class_hash = response(r#"{"jsonrpc":"2.0","id":1,"result":{"type":"DECLARE"}}"#);
+    assert!(matches!(
+        missing_class_hash.declared_class_hash(),
+        Err(SierraAnalyzerError::MissingField("class hash"))
+    ));
+    let missing_type = response(r#"{"jsonrpc":"2.0","id":1,"result":{"class_hash":"0x1234"}}"#);
+    assert!(matches!(
+        missing_type.declared_class_hash(),
+        Err(SierraAnalyzerError::MissingField("transaction type"))
+    ));
+
+    // The JSON-RPC errors have no result
+    let error = response(
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":29,"message":"Transaction hash not found"}}"#,
+    );
+    assert!(error.error.is_some());
+    assert!(error.result.is_null());
+}
+
+#[tokio::test]
+async fn test_rpc_node_errors() {
+    let url = serve_responses(vec![
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":29,"message":"Transaction hash not found"}}"#,
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":20,"message":"Contract not found"}}"#,
+        r#"{"jsonrpc":"2.0","id":1,"result":"0x1234"}"#,
+    ]);
+    let client = RpcClient::new(&url).with_max_retries(0);
+
+    // The errors returned by the node are not reported as missing fields
+    let error = client.get_declared_class_hash("0x1").await.unwrap_err();
+    assert!(
+        matches!(&error, SierraAnalyzerError::RpcNode(message) if message.contains("Transaction hash not found"))
+    );
+    let error = client.get_class_hash_at("0x2").await.unwrap_err();
+    assert!(
+        matches!(&error, SierraAnalyzerError::RpcNode(message) if message.contains("Contract not found"))
+    );
+    assert_eq!(client.get_class_hash_at("0x2").await.unwrap(), "0x1234");
+}
@@ -0,0 +1,55 @@
+use sierra_analyzer_lib::analysis::search::find_libfunc_calls;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_find_libfunc_calls() {
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/with_ownable__ownable_balance.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // The storage is written by set_balance, transfer_ownership & the constructor
+    let function_calls = find_libfunc_calls(
+        &decompiler.functions,
+        "^storage_write_syscall$",
+        &decompiler.declared_libfuncs_names,
+    )
+    .unwrap();
+    let offsets: Vec<Vec<u32>> = function_calls
+        .iter()
+        .map(|function_calls| {
+            function_calls
+                .calls
+                .iter()
+                .map(|call| call.offset)
+                .collect()
+        })
+        .collect();
+    assert_eq!(offsets, vec![vec![173], vec![429], vec![588, 597]]);
+    assert!(function_calls[0]
+        .function
+        .ends_with("::__wrapper__OwnableBalanceImpl__set_balance"));
+
+    // No function deploys a contract
+    let function_calls = find_libfunc_calls(
+        &decompiler.functions,
+        "deploy_syscall",
+        &decompiler.declared_libfuncs_names,
+    )
+    .unwrap();
+    assert!(function_calls.is_empty());
+
+    // Invalid regexes are reported
+    assert!(find_libfunc_calls(
+        &decompiler.functions,
+        "(",
+        &decompiler.declared_libfuncs_names
+    )
+    .is_err());
+}
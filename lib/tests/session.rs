@@ -0,0 +1,84 @@
+use std::thread;
+
+use sierra_analyzer_lib::analysis::context::AnalysisContext;
+use sierra_analyzer_lib::analysis::invariants::{check_invariants, load_invariants};
+use sierra_analyzer_lib::analysis::session::AnalysisSession;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_session_is_thread_safe() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<AnalysisSession>();
+    assert_send_sync::<AnalysisContext>();
+}
+
+#[test]
+fn test_session_concurrent_analyses() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // The functions, CFGs & callgraph are computed once
+    let session = program.session().unwrap();
+    assert!(session
+        .functions()
+        .iter()
+        .all(|function| function.has_cfg()));
+    assert!(session
+        .function("test::hello_starknet::HelloStarknetImpl::increase_balance")
+        .is_some());
+
+    let invariants = load_invariants(
+        r#"[{
+            "kind": "never_writes_storage",
+            "function": "test::hello_starknet::HelloStarknetImpl::get_balance",
+            "slot": "916907772491729262376534102982219947830828984996257231353398618781993312401"
+        }]"#,
+    )
+    .unwrap();
+
+    // Several analyses run concurrently against the same session
+    let (violations, callers, unused_variables) = thread::scope(|scope| {
+        let violations = scope.spawn(|| check_invariants(session.functions(), &invariants));
+        let callers = scope.spawn(|| {
+            session.callers("test::hello_starknet::balance::InternalContractMemberStateImpl::write")
+        });
+        let unused_variables = scope.spawn(|| {
+            session
+                .functions()
+                .iter()
+                .map(|function| function.def_use().unused_variables().len())
+                .sum::<usize>()
+        });
+
+        (
+            violations.join().unwrap(),
+            callers.join().unwrap(),
+            unused_variables.join().unwrap(),
+        )
+    });
+
+    assert!(violations.is_empty());
+    assert!(callers.contains("test::hello_starknet::HelloStarknetImpl::increase_balance"));
+
+    // The results are the same as the sequential analysis
+    let sequential_unused_variables: usize = session
+        .functions()
+        .iter()
+        .map(|function| function.def_use().unused_variables().len())
+        .sum();
+    assert_eq!(unused_variables, sequential_unused_variables);
+
+    // Cloning a session shares its analysis results
+    let cloned_session = session.clone();
+    assert!(std::ptr::eq(
+        cloned_session.functions(),
+        session.functions()
+    ));
+    assert!(std::ptr::eq(
+        cloned_session.declared_libfuncs_names(),
+        session.declared_libfuncs_names()
+    ));
+}
@@ -0,0 +1,140 @@
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+
+use sierra_analyzer_lib::sym_exec::snforge::{
+    abi_constructor_parameters, abi_interface_functions, snforge_tests, EntryPointTestCases,
+    SnforgeContract, TestCase,
+};
+
+#[test]
+fn test_parse_test_cases() {
+    assert_eq!(
+        TestCase::parse("v1: 5, v2: 0 | storage[0x1234]: 3"),
+        Some(TestCase {
+            arguments: vec!["5".to_string(), "0".to_string()],
+            storage: vec![("0x1234".to_string(), "3".to_string())],
+        })
+    );
+    assert_eq!(
+        TestCase::parse("v1: [1, 2], v2: []"),
+        Some(TestCase {
+            arguments: vec!["[1, 2]".to_string(), "[]".to_string()],
+            storage: Vec::new(),
+        })
+    );
+    assert_eq!(
+        TestCase::parse_all("v1: 1\nmalformed\nv1: 2"),
+        vec![
+            TestCase {
+                arguments: vec!["1".to_string()],
+                storage: Vec::new(),
+            },
+            TestCase {
+                arguments: vec!["2".to_string()],
+                storage: Vec::new(),
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_abi_interface_functions() {
+    let content =
+        include_str!("../../examples/starknet/unimpaired_cairo_Overflow.contract_class.json");
+    let contract_class: ContractClass = serde_json::from_str(content).unwrap();
+    let abi = contract_class.abi.unwrap();
+
+    let interface = "unimpaired_cairo::overflow::overflow::IOverflow".to_string();
+    assert_eq!(
+        abi_interface_functions(&abi),
+        vec![
+            (
+                interface.clone(),
+                "increase_balance".to_string(),
+                vec!["core::felt252".to_string()]
+            ),
+            (interface, "get_balance".to_string(), Vec::new()),
+        ]
+    );
+    assert!(abi_constructor_parameters(&abi).is_empty());
+}
+
+#[test]
+fn test_snforge_array_arguments() {
+    let contract = SnforgeContract {
+        name: "Registry".to_string(),
+        constructor_parameters: Vec::new(),
+        entry_points: vec![EntryPointTestCases {
+            interface: "registry::IRegistry".to_string(),
+            entry_point: "register".to_string(),
+            parameters: vec![
+                "core::array::Span::<core::felt252>".to_string(),
+                "core::array::Array::<core::felt252>".to_string(),
+            ],
+            test_cases: TestCase::parse_all("v1: [1, 2], v2: []"),
+        }],
+    };
+
+    assert!(snforge_tests(&[contract])
+        .contains("    let _ = dispatcher.register(array![1, 2].span(), array![]);\n"));
+}
+
+#[test]
+fn test_snforge_tests() {
+    let contract = SnforgeContract {
+        name: "ERC20Token".to_string(),
+        constructor_parameters: vec![
+            "core::felt252".to_string(),
+            "core::integer::u256".to_string(),
+        ],
+        entry_points: vec![
+            EntryPointTestCases {
+                interface: "token::IToken".to_string(),
+                entry_point: "transfer".to_string(),
+                parameters: vec![
+                    "core::starknet::contract_address::ContractAddress".to_string(),
+                    "core::felt252".to_string(),
+                ],
+                test_cases: TestCase::parse_all("v2: 0\nv2: 7 | storage[0x1234]: 3"),
+            },
+            // The struct parameters have no default value
+            EntryPointTestCases {
+                interface: "token::IToken".to_string(),
+                entry_point: "configure".to_string(),
+                parameters: vec!["token::Config".to_string(), "core::felt252".to_string()],
+                test_cases: TestCase::parse_all("v1: 1"),
+            },
+        ],
+    };
+
+    assert_eq!(
+        snforge_tests(&[contract]),
+        r#"// Generated by the sierra-analyzer tests generator from the symbolic execution of the contracts
+use snforge_std::{declare, store, ContractClassTrait, DeclareResultTrait};
+use starknet::ContractAddress;
+use token::{ITokenSafeDispatcher, ITokenSafeDispatcherTrait};
+
+fn deploy(name: ByteArray, calldata: Array<felt252>) -> ContractAddress {
+    let contract = declare(name).unwrap().contract_class();
+    let (contract_address, _) = contract.deploy(@calldata).unwrap();
+    contract_address
+}
+
+#[test]
+#[feature("safe_dispatcher")]
+fn test_erc20_token_transfer_0() {
+    let contract_address = deploy("ERC20Token", array![0, 0, 0]);
+    let dispatcher = ITokenSafeDispatcher { contract_address };
+    let _ = dispatcher.transfer(0.try_into().unwrap(), 0);
+}
+
+#[test]
+#[feature("safe_dispatcher")]
+fn test_erc20_token_transfer_1() {
+    let contract_address = deploy("ERC20Token", array![0, 0, 0]);
+    store(contract_address, 0x1234, array![3].span());
+    let dispatcher = ITokenSafeDispatcher { contract_address };
+    let _ = dispatcher.transfer(0.try_into().unwrap(), 7);
+}
+"#
+    );
+}
@@ -0,0 +1,73 @@
+use sierra_analyzer_lib::analysis::storage_access::{StorageAccess, StorageAccessMatrix};
+use sierra_analyzer_lib::analysis::storage_layout::{storage_base_address, StorageLayout};
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_storage_access_matrix() {
+    // Read file content
+    let content =
+        include_str!("../../examples/sierra/with_ownable__ownable_balance.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let layout = StorageLayout::new(
+        &decompiler.functions,
+        &decompiler.declared_libfuncs_names,
+        &decompiler.declared_types_names,
+    );
+    let matrix = StorageAccessMatrix::new(&decompiler.functions, &layout);
+
+    // The owner is declared by the ownable component, the balance is only a base address constant
+    let balance = format!("{:#x}", storage_base_address("balance"));
+    assert_eq!(matrix.variables, vec!["owner".to_string(), balance.clone()]);
+
+    // The owner is read by validate_ownership, called by the entry points of the owner
+    let accesses: Vec<(&str, Vec<StorageAccess>)> = matrix
+        .entries
+        .iter()
+        .map(|entry| (entry.entry_point.as_str(), entry.accesses.clone()))
+        .collect();
+    assert_eq!(
+        accesses,
+        vec![
+            (
+                "get_balance",
+                vec![StorageAccess::None, StorageAccess::Read]
+            ),
+            (
+                "set_balance",
+                vec![StorageAccess::Read, StorageAccess::Write]
+            ),
+            ("owner", vec![StorageAccess::Read, StorageAccess::None]),
+            (
+                "transfer_ownership",
+                vec![StorageAccess::ReadWrite, StorageAccess::None]
+            ),
+            (
+                "constructor",
+                vec![StorageAccess::Write, StorageAccess::Write]
+            ),
+        ]
+    );
+    assert_eq!(
+        matrix.writers("owner"),
+        vec!["transfer_ownership", "constructor"]
+    );
+    assert_eq!(
+        matrix.access("set_balance", &balance),
+        Some(StorageAccess::Write)
+    );
+
+    // The CSV output has a header & a row per entry point
+    let csv = matrix.to_csv();
+    assert_eq!(
+        csv.lines().next(),
+        Some(format!("entry_point,owner,{}", balance).as_str())
+    );
+    assert_eq!(csv.lines().nth(4), Some("transfer_ownership,RW,"));
+}
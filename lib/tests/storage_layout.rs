@@ -0,0 +1,75 @@
+use num_bigint::BigUint;
+
+use sierra_analyzer_lib::analysis::storage_layout::{
+    storage_base_address, StorageKind, StorageLayout,
+};
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+#[test]
+fn test_storage_base_address() {
+    // The base address is the starknet keccak of the variable name
+    assert_eq!(
+        storage_base_address("balance"),
+        "916907772491729262376534102982219947830828984996257231353398618781993312401"
+            .parse::<BigUint>()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_storage_layout() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/with_erc20.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let layout = StorageLayout::new(
+        &decompiler.functions,
+        &decompiler.declared_libfuncs_names,
+        &decompiler.declared_types_names,
+    );
+
+    // The variables & their types are declared by the member states & their accessors
+    let variables: Vec<(&str, StorageKind, String)> = layout
+        .variables
+        .iter()
+        .map(|variable| (variable.display_name(), variable.kind, variable.type_name()))
+        .collect();
+    assert_eq!(
+        variables,
+        vec![
+            (
+                "allowances",
+                StorageKind::Map,
+                "Map<(ContractAddress, ContractAddress), u256>".to_string()
+            ),
+            (
+                "balances",
+                StorageKind::Map,
+                "Map<ContractAddress, u256>".to_string()
+            ),
+            ("decimals", StorageKind::Value, "u8".to_string()),
+            ("name", StorageKind::Value, "felt252".to_string()),
+            ("symbol", StorageKind::Value, "felt252".to_string()),
+            ("total_supply", StorageKind::Value, "u256".to_string()),
+        ]
+    );
+    assert!(layout
+        .variables
+        .iter()
+        .all(|variable| variable.module.as_deref() == Some("test::erc20")));
+
+    // The name is read by its getter & written by the initializer
+    let name = &layout.variables[3];
+    assert_eq!(name.base_address, storage_base_address("name"));
+    assert!(!name.readers.is_empty());
+    assert!(!name.writers.is_empty());
+
+    // The variables have distinct base addresses
+    assert!(layout.collisions.is_empty());
+}
@@ -1,9 +1,28 @@
+#![cfg(feature = "z3")]
+
 use z3::{
     ast::{Ast, Int},
     Config, Context, SatResult,
 };
 
+use num_bigint::BigInt;
+use sierra_analyzer_lib::decompiler::libfunc_kind::LibfuncKind;
+use sierra_analyzer_lib::parse_element_name;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+use sierra_analyzer_lib::sym_exec::coverage::{coverage_map, BlockCoverage};
+use sierra_analyzer_lib::sym_exec::formula::{ArrayTerm, Formula, Term};
+use sierra_analyzer_lib::sym_exec::hashes::HashModel;
+use sierra_analyzer_lib::sym_exec::prove::{prove_panics, PanicReachability};
+use sierra_analyzer_lib::sym_exec::smtlib::{path_constraints, smtlib2_script};
+use sierra_analyzer_lib::sym_exec::solver::{Solver, SolverResult};
+use sierra_analyzer_lib::sym_exec::storage::{storage_pre_state, SymbolicStorage};
+use sierra_analyzer_lib::sym_exec::sym_exec::generate_test_cases_for_function;
+use sierra_analyzer_lib::sym_exec::sym_exec::generate_test_cases_in_program;
+use sierra_analyzer_lib::sym_exec::sym_exec::sierra_statement_to_constraint_with_suffix;
 use sierra_analyzer_lib::sym_exec::sym_exec::SymbolicExecution;
+use sierra_analyzer_lib::sym_exec::unrolling::recursive_functions;
+use sierra_analyzer_lib::sym_exec::unrolling::unrolled_traces;
+use sierra_analyzer_lib::sym_exec::z3_solver::{z3_tactics, Z3Options, Z3Solver};
 
 #[test]
 fn test_constraints() {
@@ -70,3 +89,618 @@ fn test_add_constraint() {
         SatResult::Unknown => panic!("Satisfiability of constraints is unknown"),
     }
 }
+
+#[test]
+fn test_loop_unrolling() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/fib.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // fib is recursive
+    let recursive_functions = recursive_functions(&decompiler.functions);
+    assert!(recursive_functions.contains("examples::fib::fib"));
+
+    // Unroll the recursive calls 3 times
+    decompiler.functions[0].create_cfg();
+    let traces = unrolled_traces(
+        &decompiler.functions,
+        0,
+        &decompiler.declared_libfuncs_names,
+        3,
+    );
+
+    // Only the traces reaching 0, 1 and 3 iterations are kept
+    let iterations: Vec<usize> = traces.iter().map(|trace| trace.iterations).collect();
+    assert_eq!(iterations, vec![3, 3, 1, 0]);
+
+    // The first inlined call parameters are bound to the call arguments
+    let one_iteration_trace = &traces[2];
+    assert!(one_iteration_trace
+        .bindings
+        .contains(&("v2_1".to_string(), "v8".to_string())));
+    assert!(one_iteration_trace
+        .bindings
+        .contains(&("v9".to_string(), "v0_1".to_string())));
+    assert_eq!(
+        one_iteration_trace.binding_blocks.len(),
+        one_iteration_trace.bindings.len()
+    );
+
+    // Test cases are generated for the unrolled paths
+    let test_cases = generate_test_cases_for_function(
+        &mut decompiler.functions[0],
+        &decompiler.declared_libfuncs_names,
+    );
+    assert!(!test_cases.is_empty());
+
+    // The zero check branches are constrained : n reaches 0, 1 and 3 iterations
+    assert!(test_cases.contains("v2: 0"));
+    assert!(test_cases.contains("v2: 1"));
+    assert!(test_cases.contains("v2: 3"));
+}
+
+#[test]
+fn test_z3_solver_backend() {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+
+    let mut solver = Z3Solver::new(&context);
+
+    // v1 == v0 & v1 + 2 == 0
+    solver.assert(&Formula::eq(Term::var("v1"), Term::var("v0")));
+    solver.assert(&Formula::eq(
+        Term::var("v1") + Term::constant(2),
+        Term::constant(0),
+    ));
+
+    assert_eq!(solver.check(), SolverResult::Sat);
+    assert_eq!(solver.value("v0"), Some(BigInt::from(-2)));
+
+    // v0 can't be both -2 and positive
+    solver.assert(&!Formula::le(Term::var("v0"), Term::constant(0)));
+    assert_eq!(solver.check(), SolverResult::Unsat);
+
+    // The constraints are removed by a reset
+    solver.reset();
+    assert_eq!(solver.check(), SolverResult::Sat);
+}
+
+#[test]
+fn test_z3_solver_scopes() {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+
+    let mut solver = Z3Solver::new(&context);
+    solver.assert(&Formula::eq(Term::var("v0"), Term::constant(1)));
+
+    // The constraints of a scope are removed when it is popped
+    solver.push();
+    solver.assert(&Formula::eq(Term::var("v0"), Term::constant(2)));
+    assert_eq!(solver.check(), SolverResult::Unsat);
+    solver.pop();
+
+    assert_eq!(solver.check(), SolverResult::Sat);
+    assert_eq!(solver.value("v0"), Some(BigInt::from(1)));
+}
+
+#[test]
+fn test_z3_solver_options() {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    assert!(z3_tactics().contains(&"smt".to_string()));
+
+    let options = Z3Options {
+        timeout: Some(10_000),
+        random_seed: Some(42),
+        tactic: Some("smt".to_string()),
+    };
+    let mut solver = Z3Solver::with_options(&context, &options);
+    solver.assert(&Formula::eq(
+        Term::var("v0") + Term::constant(1),
+        Term::constant(8),
+    ));
+    assert_eq!(solver.check(), SolverResult::Sat);
+    assert_eq!(solver.value("v0"), Some(BigInt::from(7)));
+
+    // An unknown tactic falls back to the default solver
+    let options = Z3Options {
+        tactic: Some("unknown_tactic".to_string()),
+        ..Z3Options::default()
+    };
+    let mut solver = Z3Solver::with_options(&context, &options);
+    solver.assert(&Formula::eq(Term::var("v0"), Term::constant(1)));
+    assert_eq!(solver.check(), SolverResult::Sat);
+}
+
+#[test]
+fn test_symbolic_storage() {
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+
+    let mut solver = Z3Solver::new(&context);
+    let mut storage = SymbolicStorage::new();
+    let names =
+        |names: &[&str]| -> Vec<String> { names.iter().map(|name| name.to_string()).collect() };
+
+    // v5 = storage[v3] ; storage[v3] = v5 + 1 ; v9 = storage[v3]
+    let first_read = storage
+        .handle_syscall(
+            LibfuncKind::StorageRead,
+            &names(&["v0", "v1", "v2", "v3"]),
+            &names(&["v4", "v4", "v5"]),
+            Some(0),
+        )
+        .unwrap();
+    let write = storage
+        .handle_syscall(
+            LibfuncKind::StorageWrite,
+            &names(&["v0", "v1", "v2", "v3", "v6"]),
+            &names(&["v7", "v8"]),
+            Some(0),
+        )
+        .unwrap();
+    let second_read = storage
+        .handle_syscall(
+            LibfuncKind::StorageRead,
+            &names(&["v0", "v1", "v2", "v3"]),
+            &names(&["v10", "v10", "v9"]),
+            None,
+        )
+        .unwrap();
+
+    // A failed syscall doesn't access the storage
+    assert!(storage
+        .handle_syscall(
+            LibfuncKind::StorageRead,
+            &names(&["v0", "v1", "v2", "v3"]),
+            &names(&["v11", "v11", "v12"]),
+            Some(1),
+        )
+        .is_none());
+
+    solver.assert(&first_read);
+    solver.assert(&write);
+    solver.assert(&second_read);
+    solver.assert(&Formula::eq(
+        Term::var("v6"),
+        Term::var("v5") + Term::constant(1),
+    ));
+    solver.assert(&Formula::eq(Term::var("v3"), Term::constant(0x1234)));
+    solver.assert(&Formula::eq(Term::var("v9"), Term::constant(8)));
+
+    assert_eq!(solver.check(), SolverResult::Sat);
+    assert_eq!(solver.value("v5"), Some(BigInt::from(7)));
+
+    // The slot must contain 7 before the execution, the second read comes from the write
+    assert_eq!(
+        storage_pre_state(&solver, storage.reads()),
+        vec![(BigInt::from(0x1234), BigInt::from(7))]
+    );
+}
+
+#[test]
+fn test_prove_overflow_panic() {
+    // Read file content
+    let content = include_str!("../../examples/sierra/hello_starknet.sierra").to_string();
+
+    // Init a new SierraProgram with the .sierra file content
+    let program = SierraProgram::new(content);
+
+    // Decompile the Sierra program
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    // Generate all the CFGs so the called functions can be inlined
+    for function in &mut decompiler.functions {
+        function.create_cfg();
+    }
+
+    let function_index = decompiler
+        .functions
+        .iter()
+        .position(|function| {
+            parse_element_name!(function.function.id) == "core::integer::U32Add::add"
+        })
+        .unwrap();
+    let reports = prove_panics(
+        &decompiler.functions,
+        function_index,
+        &decompiler.declared_libfuncs_names,
+        HashModel::Unconstrained,
+    );
+
+    // The panic of `expect` (statement 498) is reachable when the addition overflows
+    let report = reports.iter().find(|report| report.offset == 498).unwrap();
+    let PanicReachability::Reachable(witness) = &report.reachability else {
+        panic!("The overflow panic should be reachable");
+    };
+    let sum: BigInt = witness
+        .arguments
+        .iter()
+        .map(|(_, value)| value.clone())
+        .sum();
+    assert!(sum >= BigInt::from(1u64 << 32));
+}
+
+#[test]
+fn test_coverage_map() {
+    // The zero branch of the check of a non-zero constant can't be covered
+    let content = r#"type felt252 = felt252;
+type NonZero<felt252> = NonZero<felt252>;
+
+libfunc felt252_const<5> = felt252_const<5>;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+
+felt252_const<5>() -> ([1]);
+felt252_is_zero([1]) { fallthrough() 4([2]) };
+branch_align() -> ();
+return([0]);
+branch_align() -> ();
+drop<NonZero<felt252>>([2]) -> ();
+return([0]);
+
+test::check_constant@0([0]: felt252) -> (felt252);"#
+        .to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    decompiler.functions[0].create_cfg();
+
+    let reports = coverage_map(
+        &decompiler.functions,
+        0,
+        &decompiler.declared_libfuncs_names,
+        HashModel::Unconstrained,
+    );
+    let offsets: Vec<u32> = reports.iter().map(|report| report.offset).collect();
+    assert_eq!(offsets, vec![0, 2, 4]);
+    assert_eq!(reports[0].coverage, BlockCoverage::Covered);
+    assert_eq!(reports[2].coverage, BlockCoverage::Covered);
+
+    // The uncovered block is reported with the constraints of its path
+    let BlockCoverage::Unsatisfiable(constraints) = &reports[1].coverage else {
+        panic!("The zero branch should be unsatisfiable");
+    };
+    let constraints: Vec<String> = constraints
+        .iter()
+        .map(|constraint| constraint.to_string())
+        .collect();
+    assert_eq!(constraints, vec!["v1 == 5", "v1 == 0"]);
+}
+
+#[test]
+fn test_smtlib2_script() {
+    let constraints = vec![
+        Formula::eq(Term::var("v1"), Term::constant(-5)),
+        Formula::array_eq(
+            ArrayTerm::var("storage"),
+            ArrayTerm::constant(Term::constant(0)),
+        ),
+        !Formula::lt(
+            Term::select(ArrayTerm::var("storage"), Term::var("v0[1]")),
+            Term::app("pedersen", vec![Term::var("v0"), Term::var("v1")]),
+        ),
+    ];
+
+    assert_eq!(
+        smtlib2_script(&constraints),
+        r#"(declare-const v0 Int)
+(declare-const |v0[1]| Int)
+(declare-const v1 Int)
+(declare-const storage (Array Int Int))
+(declare-fun pedersen (Int Int) Int)
+(assert (= v1 (- 5)))
+(assert (= storage ((as const (Array Int Int)) 0)))
+(assert (not (< (select storage |v0[1]|) (pedersen v0 v1))))
+(check-sat)
+"#
+    );
+}
+
+#[test]
+fn test_path_constraints() {
+    // The zero branch of the check of a non-zero constant is unsatisfiable
+    let content = r#"type felt252 = felt252;
+type NonZero<felt252> = NonZero<felt252>;
+
+libfunc felt252_const<5> = felt252_const<5>;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+
+felt252_const<5>() -> ([1]);
+felt252_is_zero([1]) { fallthrough() 4([2]) };
+branch_align() -> ();
+return([0]);
+branch_align() -> ();
+drop<NonZero<felt252>>([2]) -> ();
+return([0]);
+
+test::check_constant@0([0]: felt252) -> (felt252);"#
+        .to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    decompiler.functions[0].create_cfg();
+
+    let paths = path_constraints(
+        &decompiler.functions,
+        0,
+        &decompiler.declared_libfuncs_names,
+        HashModel::Unconstrained,
+    );
+    assert_eq!(paths.len(), 2);
+
+    let unsatisfiable_paths: Vec<_> = paths
+        .iter()
+        .filter(|path| path.result == SolverResult::Unsat)
+        .collect();
+    assert_eq!(unsatisfiable_paths.len(), 1);
+    assert!(unsatisfiable_paths[0]
+        .to_smtlib2()
+        .contains("(assert (= v1 5))\n(assert (= v1 0))\n(check-sat)"));
+}
+
+#[test]
+fn test_hash_models() {
+    // The argument is compared to the Pedersen hash of two constants
+    let content = r#"type Pedersen = Pedersen;
+type felt252 = felt252;
+type NonZero<felt252> = NonZero<felt252>;
+
+libfunc felt252_const<1> = felt252_const<1>;
+libfunc felt252_const<2> = felt252_const<2>;
+libfunc pedersen = pedersen;
+libfunc felt252_sub = felt252_sub;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+
+felt252_const<1>() -> ([2]);
+felt252_const<2>() -> ([3]);
+pedersen([0], [2], [3]) -> ([4], [5]);
+felt252_sub([5], [1]) -> ([6]);
+felt252_is_zero([6]) { fallthrough() 7([7]) };
+branch_align() -> ();
+return([4]);
+branch_align() -> ();
+drop<NonZero<felt252>>([7]) -> ();
+return([4]);
+
+test::hash_check@0([0]: Pedersen, [1]: felt252) -> (Pedersen);"#
+        .to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    decompiler.functions[0].create_cfg();
+
+    // pedersen(1, 2)
+    let digest = "v1: 2592987851775965742543459319508348457290966253241455514226127639100457844774";
+    let test_cases = |hash_model| {
+        generate_test_cases_in_program(
+            &decompiler.functions,
+            0,
+            &decompiler.declared_libfuncs_names,
+            1,
+            hash_model,
+        )
+    };
+
+    // The digest is only known when the hashes of constants are evaluated
+    assert!(test_cases(HashModel::Concrete).contains(digest));
+    assert!(!test_cases(HashModel::Unconstrained).contains(digest));
+    assert!(!test_cases(HashModel::Uninterpreted).contains(digest));
+}
+
+#[test]
+fn test_array_arguments() {
+    // The first element of the span argument is compared to a constant
+    let content = r#"type felt252 = felt252;
+type Array<felt252> = Array<felt252>;
+type Snapshot<Array<felt252>> = Snapshot<Array<felt252>>;
+type core::array::Span::<core::felt252> = Struct<ut@[782572820229152792105145177694740816763001980856532159945905090893343825762], Snapshot<Array<felt252>>>;
+type Box<felt252> = Box<felt252>;
+type NonZero<felt252> = NonZero<felt252>;
+
+libfunc struct_deconstruct<core::array::Span::<core::felt252>> = struct_deconstruct<core::array::Span::<core::felt252>>;
+libfunc array_snapshot_pop_front<felt252> = array_snapshot_pop_front<felt252>;
+libfunc branch_align = branch_align;
+libfunc drop<Snapshot<Array<felt252>>> = drop<Snapshot<Array<felt252>>>;
+libfunc unbox<felt252> = unbox<felt252>;
+libfunc felt252_const<7> = felt252_const<7>;
+libfunc felt252_const<0> = felt252_const<0>;
+libfunc felt252_sub = felt252_sub;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+
+struct_deconstruct<core::array::Span::<core::felt252>>([0]) -> ([1]);
+array_snapshot_pop_front<felt252>([1]) { fallthrough([2], [3]) 13([4]) };
+branch_align() -> ();
+drop<Snapshot<Array<felt252>>>([2]) -> ();
+unbox<felt252>([3]) -> ([5]);
+felt252_const<7>() -> ([6]);
+felt252_sub([5], [6]) -> ([7]);
+felt252_is_zero([7]) { fallthrough() 10([8]) };
+branch_align() -> ();
+return([5]);
+branch_align() -> ();
+drop<NonZero<felt252>>([8]) -> ();
+return([5]);
+branch_align() -> ();
+drop<Snapshot<Array<felt252>>>([4]) -> ();
+felt252_const<0>() -> ([9]);
+return([9]);
+
+test::first_element@0([0]: core::array::Span::<core::felt252>) -> (felt252);"#
+        .to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let test_cases = generate_test_cases_for_function(
+        &mut decompiler.functions[0],
+        &decompiler.declared_libfuncs_names,
+    );
+    let test_cases: Vec<&str> = test_cases.lines().collect();
+
+    // The empty span, a span starting with the constant & a span starting with another value
+    assert!(test_cases.contains(&"v0: []"));
+    assert!(test_cases
+        .iter()
+        .any(|test_case| test_case.starts_with("v0: [7")));
+    assert!(test_cases.iter().any(|test_case| {
+        test_case.starts_with("v0: [") && !test_case.starts_with("v0: [7") && *test_case != "v0: []"
+    }));
+}
+
+#[test]
+fn test_reassigned_variables() {
+    // The argument is reassigned before the zero check
+    let content = r#"type felt252 = felt252;
+type NonZero<felt252> = NonZero<felt252>;
+
+libfunc felt252_const<5> = felt252_const<5>;
+libfunc felt252_sub = felt252_sub;
+libfunc store_temp<felt252> = store_temp<felt252>;
+libfunc dup<felt252> = dup<felt252>;
+libfunc felt252_is_zero = felt252_is_zero;
+libfunc branch_align = branch_align;
+libfunc drop<NonZero<felt252>> = drop<NonZero<felt252>>;
+
+felt252_const<5>() -> ([1]);
+felt252_sub([0], [1]) -> ([0]);
+store_temp<felt252>([0]) -> ([0]);
+dup<felt252>([0]) -> ([0], [3]);
+felt252_is_zero([0]) { fallthrough() 7([2]) };
+branch_align() -> ();
+return([3]);
+branch_align() -> ();
+drop<NonZero<felt252>>([2]) -> ();
+return([3]);
+
+test::reassign@0([0]: felt252) -> (felt252);"#
+        .to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    decompiler.functions[0].create_cfg();
+
+    // Each definition of v0 is a distinct variable, so the paths are satisfiable
+    let paths = path_constraints(
+        &decompiler.functions,
+        0,
+        &decompiler.declared_libfuncs_names,
+        HashModel::Unconstrained,
+    );
+    assert_eq!(paths.len(), 2);
+    assert!(paths.iter().all(|path| path.result == SolverResult::Sat));
+    assert!(paths[0].constraints.contains(&Formula::eq(
+        Term::var("v0#1"),
+        Term::var("v0") - Term::var("v1")
+    )));
+    assert!(paths[0]
+        .constraints
+        .contains(&Formula::eq(Term::var("v0#2"), Term::var("v0#1"))));
+
+    // Both results of a duplication are equal to the duplicated value
+    assert!(paths[0].constraints.contains(&Formula::and(vec![
+        Formula::eq(Term::var("v0#3"), Term::var("v0#2")),
+        Formula::eq(Term::var("v3"), Term::var("v0#2")),
+    ])));
+
+    // The zero branch is reached by the argument equal to the subtracted constant
+    let test_cases = generate_test_cases_for_function(
+        &mut decompiler.functions[0],
+        &decompiler.declared_libfuncs_names,
+    );
+    assert!(test_cases.lines().any(|test_case| test_case == "v0: 5"));
+    assert!(test_cases.lines().any(|test_case| test_case != "v0: 5"));
+}
+
+#[test]
+fn test_bounded_int_constraints() {
+    let content = include_str!("../../examples/sierra/bounded_int.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+    let statements = decompiler.functions[0].statements();
+
+    // v3 = v1 + v2
+    assert_eq!(
+        sierra_statement_to_constraint_with_suffix(
+            &statements[0],
+            &decompiler.declared_libfuncs_names,
+            "",
+            None
+        ),
+        Some(Formula::eq(
+            Term::var("v3"),
+            Term::var("v1") + Term::var("v2")
+        ))
+    );
+
+    // The branch 1 of bounded_int_constrain is taken when the value is not lower than the boundary
+    assert_eq!(
+        sierra_statement_to_constraint_with_suffix(
+            &statements[1],
+            &decompiler.declared_libfuncs_names,
+            "",
+            Some(1)
+        ),
+        Some(Formula::and(vec![
+            !Formula::lt(Term::var("v3"), Term::constant(10)),
+            Formula::eq(Term::var("v7"), Term::var("v3")),
+        ]))
+    );
+}
+
+#[test]
+fn test_dict_constraints() {
+    let content = include_str!("../../examples/sierra/dict.sierra").to_string();
+    let program = SierraProgram::new(content);
+
+    let mut decompiler = program.decompiler(false);
+    decompiler.decompile(false);
+
+    let cfg = Config::new();
+    let context = Context::new(&cfg);
+    let mut solver = Z3Solver::new(&context);
+
+    // New dictionary, entry read, write & squash
+    for statement in &decompiler.functions[0].statements()[..4] {
+        let constraint = sierra_statement_to_constraint_with_suffix(
+            statement,
+            &decompiler.declared_libfuncs_names,
+            "",
+            None,
+        )
+        .unwrap();
+        solver.assert(&constraint);
+    }
+
+    // The value read from a new dictionary is zero
+    solver.push();
+    solver.assert(&!Formula::eq(Term::var("v8"), Term::constant(0)));
+    assert_eq!(solver.check(), SolverResult::Unsat);
+    solver.pop();
+
+    // The squashed dictionary holds the written value
+    solver.assert(&!Formula::eq(
+        Term::select(ArrayTerm::var("dict_v13"), Term::var("v3")),
+        Term::var("v4"),
+    ));
+    assert_eq!(solver.check(), SolverResult::Unsat);
+}
@@ -0,0 +1,162 @@
+use sierra_analyzer_lib::analysis::context::AnalysisContext;
+use sierra_analyzer_lib::detectors::constructor_detector::ConstructorDetector;
+use sierra_analyzer_lib::detectors::detector::Detector;
+use sierra_analyzer_lib::detectors::effects_timeline_detector::EffectsTimelineDetector;
+use sierra_analyzer_lib::detectors::felt_comparison_detector::FeltComparisonDetector;
+use sierra_analyzer_lib::detectors::felt_overflow_detector::FeltOverflowDetector;
+use sierra_analyzer_lib::detectors::missing_events_detector::MissingEventsDetector;
+use sierra_analyzer_lib::detectors::recursion_detector::RecursionDetector;
+use sierra_analyzer_lib::detectors::reinitialization_detector::ReinitializationDetector;
+use sierra_analyzer_lib::detectors::truncation_detector::TruncationDetector;
+use sierra_analyzer_lib::detectors::tx_origin_detector::TxOriginDetector;
+use sierra_analyzer_lib::sierra_program::SierraProgram;
+
+/// Returns the locations of the findings of a detector on a Sierra program
+fn findings_locations(detector: &mut dyn Detector, content: &str) -> Vec<(String, Option<u32>)> {
+    let program = SierraProgram::new(content.to_string());
+    let context = AnalysisContext::new(&program).unwrap();
+    detector
+        .findings(&context)
+        .into_iter()
+        .map(|finding| (finding.function, finding.offset))
+        .collect()
+}
+
+/// Checks that a detector reports the vulnerable contract at the expected locations
+/// and doesn't report its patched counterpart
+fn assert_fixed_by_patch(
+    detector: &mut dyn Detector,
+    vulnerable: &str,
+    patched: &str,
+    expected: &[(&str, Option<u32>)],
+) {
+    let expected: Vec<(String, Option<u32>)> = expected
+        .iter()
+        .map(|(function, offset)| (function.to_string(), *offset))
+        .collect();
+    assert_eq!(
+        findings_locations(detector, vulnerable),
+        expected,
+        "{} findings on the vulnerable contract",
+        detector.id()
+    );
+    assert!(
+        findings_locations(detector, patched).is_empty(),
+        "{} findings on the patched contract",
+        detector.id()
+    );
+}
+
+#[test]
+fn test_vulnerable_felt_overflow() {
+    // The felt252 parameters are added, the patched contract adds u64 values converted to felt252
+    assert_fixed_by_patch(
+        &mut FeltOverflowDetector::new(),
+        include_str!("../../examples/vulnerable/felt_overflow.sierra"),
+        include_str!("../../examples/vulnerable/felt_overflow_patched.sierra"),
+        &[("vulnerable::felt_overflow::deposit", Some(0))],
+    );
+}
+
+#[test]
+fn test_vulnerable_felt_comparison() {
+    // The felt252 difference is ordered as a u256, the patched contract only checks the equality
+    assert_fixed_by_patch(
+        &mut FeltComparisonDetector::new(),
+        include_str!("../../examples/vulnerable/felt_comparison.sierra"),
+        include_str!("../../examples/vulnerable/felt_comparison_patched.sierra"),
+        &[("vulnerable::felt_comparison::remaining", Some(2))],
+    );
+}
+
+#[test]
+fn test_vulnerable_effects_timeline() {
+    // The storage is written after the external call, the patched contract writes it before
+    assert_fixed_by_patch(
+        &mut EffectsTimelineDetector::new(),
+        include_str!("../../examples/vulnerable/effects_timeline.sierra"),
+        include_str!("../../examples/vulnerable/effects_timeline_patched.sierra"),
+        &[(
+            "vulnerable::effects_timeline::__wrapper__Impl__withdraw",
+            Some(12),
+        )],
+    );
+}
+
+#[test]
+fn test_vulnerable_missing_events() {
+    // The value is written without any event, the patched contract emits ValueSet
+    assert_fixed_by_patch(
+        &mut MissingEventsDetector::new(),
+        include_str!("../../examples/vulnerable/missing_events.sierra"),
+        include_str!("../../examples/vulnerable/missing_events_patched.sierra"),
+        &[(
+            "vulnerable::missing_events::__wrapper__Impl__set_value",
+            None,
+        )],
+    );
+}
+
+#[test]
+fn test_vulnerable_constructor() {
+    // The owner parameter is written as is, the patched contract checks that it isn't zero
+    assert_fixed_by_patch(
+        &mut ConstructorDetector::new(),
+        include_str!("../../examples/vulnerable/constructor.sierra"),
+        include_str!("../../examples/vulnerable/constructor_patched.sierra"),
+        &[(
+            "vulnerable::constructor::__wrapper__Impl__constructor",
+            Some(4),
+        )],
+    );
+}
+
+#[test]
+fn test_vulnerable_reinitialization() {
+    // The initializer can be called again, the patched contract checks the initialized flag
+    assert_fixed_by_patch(
+        &mut ReinitializationDetector::new(),
+        include_str!("../../examples/vulnerable/reinitialization.sierra"),
+        include_str!("../../examples/vulnerable/reinitialization_patched.sierra"),
+        &[(
+            "vulnerable::reinitialization::__wrapper__Impl__initialize",
+            None,
+        )],
+    );
+}
+
+#[test]
+fn test_vulnerable_tx_origin() {
+    // The owner is compared with the transaction account, the patched contract uses the caller
+    assert_fixed_by_patch(
+        &mut TxOriginDetector::new(),
+        include_str!("../../examples/vulnerable/tx_origin.sierra"),
+        include_str!("../../examples/vulnerable/tx_origin_patched.sierra"),
+        &[("vulnerable::tx_origin::assert_only_owner", Some(9))],
+    );
+}
+
+#[test]
+fn test_vulnerable_truncation() {
+    // The conversions panic or drop the high word, the patched contract returns an Option & a u256
+    assert_fixed_by_patch(
+        &mut TruncationDetector::new(),
+        include_str!("../../examples/vulnerable/truncation.sierra"),
+        include_str!("../../examples/vulnerable/truncation_patched.sierra"),
+        &[
+            ("vulnerable::truncation::set_decimals", Some(0)),
+            ("vulnerable::truncation::to_u128", Some(11)),
+        ],
+    );
+}
+
+#[test]
+fn test_vulnerable_recursion() {
+    // The entry point reaches mutually recursive functions, the patched contract has no recursion
+    assert_fixed_by_patch(
+        &mut RecursionDetector::new(),
+        include_str!("../../examples/vulnerable/recursion.sierra"),
+        include_str!("../../examples/vulnerable/recursion_patched.sierra"),
+        &[("vulnerable::recursion::is_even", None)],
+    );
+}